@@ -0,0 +1,114 @@
+//! Prints captured TWS protocol traffic as named messages with labeled fields
+//!
+//! Usage: ibtws-dump [--incoming | --outgoing] [--server-version N] [--hex | --raw] [FILE]
+//!
+//! Reads FILE, or stdin when no file is given. The input may be raw length prefixed frames as
+//! read from the socket, or a textual dump such as the expected byte arrays in the client tests.
+
+use std::io::Read;
+use std::{env, fs, io, process};
+
+use ibtwsapi::core::dump::{dump_bytes, parse_hex_dump, Direction};
+use ibtwsapi::core::server_versions::MAX_CLIENT_VER;
+
+const USAGE: &str =
+    "Usage: ibtws-dump [--incoming | --outgoing] [--server-version N] [--hex | --raw] [FILE]";
+
+#[derive(PartialEq)]
+enum InputFormat {
+    Detect,
+    Hex,
+    Raw,
+}
+
+struct Options {
+    direction: Direction,
+    server_version: i32,
+    format: InputFormat,
+    path: Option<String>,
+}
+
+//==================================================================================================
+fn parse_args() -> Result<Options, String> {
+    let mut options = Options {
+        direction: Direction::Incoming,
+        server_version: MAX_CLIENT_VER,
+        format: InputFormat::Detect,
+        path: None,
+    };
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-i" | "--incoming" => options.direction = Direction::Incoming,
+            "-o" | "--outgoing" => options.direction = Direction::Outgoing,
+            "--hex" => options.format = InputFormat::Hex,
+            "--raw" => options.format = InputFormat::Raw,
+            "-s" | "--server-version" => {
+                let version = args.next().ok_or("--server-version needs a value")?;
+                options.server_version = version
+                    .parse()
+                    .map_err(|_| format!("invalid server version '{}'", version))?;
+            }
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                process::exit(0);
+            }
+            _ if arg.starts_with('-') => return Err(format!("unknown option '{}'", arg)),
+            _ => options.path = Some(arg),
+        }
+    }
+    Ok(options)
+}
+
+//==================================================================================================
+// Text dumps only ever contain printable ASCII, framed captures start with length bytes
+fn looks_like_text(input: &[u8]) -> bool {
+    !input.is_empty()
+        && input
+            .iter()
+            .all(|b| b.is_ascii_graphic() || b.is_ascii_whitespace())
+}
+
+//==================================================================================================
+fn run(options: Options) -> Result<(), String> {
+    let mut input = Vec::new();
+    match &options.path {
+        Some(path) => {
+            input = fs::read(path).map_err(|err| format!("{}: {}", path, err))?;
+        }
+        None => {
+            io::stdin()
+                .read_to_end(&mut input)
+                .map_err(|err| err.to_string())?;
+        }
+    }
+
+    let is_text = match options.format {
+        InputFormat::Hex => true,
+        InputFormat::Raw => false,
+        InputFormat::Detect => looks_like_text(&input),
+    };
+    let bytes = if is_text {
+        parse_hex_dump(&String::from_utf8_lossy(&input)).map_err(|err| err.to_string())?
+    } else {
+        input
+    };
+
+    let messages = dump_bytes(&bytes, options.direction, options.server_version)
+        .map_err(|err| err.to_string())?;
+    for message in messages {
+        println!("{}", message);
+    }
+    Ok(())
+}
+
+//==================================================================================================
+pub fn main() {
+    let result = parse_args().and_then(run);
+    if let Err(err) = result {
+        eprintln!("ibtws-dump: {}", err);
+        eprintln!("{}", USAGE);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,270 @@
+//! Account balances and portfolio maintained from the events of `req_account_updates` and
+//! `req_account_updates_multi`
+use std::collections::BTreeMap;
+use std::sync::mpsc::{channel, Receiver, Sender};
+
+use chrono::NaiveTime;
+use rust_decimal::Decimal;
+
+use crate::core::account_summary::AccountValues;
+use crate::core::contract::Contract;
+use crate::core::messages::ServerRspMsg;
+
+//==================================================================================================
+/// One position of the portfolio as reported by a `PortfolioValue` event
+#[derive(Clone, Debug)]
+pub struct PortfolioItem {
+    pub contract: Contract,
+    pub position: f64,
+    pub market_price: f64,
+    pub market_value: f64,
+    pub average_cost: f64,
+    pub unrealized_pnl: f64,
+    pub realized_pnl: f64,
+}
+
+impl PortfolioItem {
+    fn same_values(&self, other: &PortfolioItem) -> bool {
+        self.position == other.position
+            && self.market_price == other.market_price
+            && self.market_value == other.market_value
+            && self.average_cost == other.average_cost
+            && self.unrealized_pnl == other.unrealized_pnl
+            && self.realized_pnl == other.realized_pnl
+    }
+}
+
+//==================================================================================================
+/// A change applied to an `AccountState`
+#[derive(Clone, Debug, PartialEq)]
+pub enum AccountChange {
+    /// A balance changed. `model_code` is empty for values from `req_account_updates`.
+    Value {
+        account: String,
+        model_code: String,
+        key: String,
+        currency: String,
+    },
+    Portfolio {
+        account: String,
+        con_id: i32,
+    },
+    UpdateTime(String),
+    /// The initial download of an account's values and portfolio completed
+    DownloadEnd {
+        account: String,
+    },
+    /// The initial values of a `req_account_updates_multi` request were delivered
+    MultiEnd {
+        req_id: i32,
+    },
+}
+
+//==================================================================================================
+/// Balances and portfolio of the subscribed accounts.
+///
+/// Balances are kept per account and model code, values from `req_account_updates` use an empty
+/// model code. Feed every event read from `EClient::get_event` to `update`, events that do not
+/// concern account updates are ignored.
+#[derive(Debug, Default)]
+pub struct AccountState {
+    values: BTreeMap<(String, String), AccountValues>,
+    portfolio: BTreeMap<String, BTreeMap<i32, PortfolioItem>>,
+    update_time: Option<String>,
+    downloaded: Vec<String>,
+    observers: Vec<Sender<AccountChange>>,
+}
+
+impl AccountState {
+    pub fn new() -> Self {
+        AccountState::default()
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Applies an event, returning the change it made, if any
+    pub fn update(&mut self, msg: &ServerRspMsg) -> Option<AccountChange> {
+        let change = match msg {
+            ServerRspMsg::AcctValue {
+                key,
+                val,
+                currency,
+                account_name,
+            } => self.set_value(account_name, "", key, val, currency),
+            ServerRspMsg::AccountUpdateMulti {
+                account,
+                model_code,
+                key,
+                value,
+                currency,
+                ..
+            } => self.set_value(account, model_code, key, value, currency),
+            ServerRspMsg::PortfolioValue {
+                contract,
+                position,
+                market_price,
+                market_value,
+                average_cost,
+                unrealized_pnl,
+                realized_pnl,
+                account_name,
+            } => {
+                let item = PortfolioItem {
+                    contract: contract.clone(),
+                    position: *position,
+                    market_price: *market_price,
+                    market_value: *market_value,
+                    average_cost: *average_cost,
+                    unrealized_pnl: *unrealized_pnl,
+                    realized_pnl: *realized_pnl,
+                };
+                let positions = self.portfolio.entry(account_name.clone()).or_default();
+                match positions.insert(contract.con_id, item.clone()) {
+                    Some(previous) if previous.same_values(&item) => None,
+                    _ => Some(AccountChange::Portfolio {
+                        account: account_name.clone(),
+                        con_id: contract.con_id,
+                    }),
+                }
+            }
+            ServerRspMsg::AcctUpdateTime { time_stamp } => {
+                if self.update_time.as_ref() == Some(time_stamp) {
+                    None
+                } else {
+                    self.update_time = Some(time_stamp.clone());
+                    Some(AccountChange::UpdateTime(time_stamp.clone()))
+                }
+            }
+            ServerRspMsg::AcctDownloadEnd { account_name } => {
+                if !self.downloaded.contains(account_name) {
+                    self.downloaded.push(account_name.clone());
+                }
+                Some(AccountChange::DownloadEnd {
+                    account: account_name.clone(),
+                })
+            }
+            ServerRspMsg::AccountUpdateMultiEnd { req_id } => {
+                Some(AccountChange::MultiEnd { req_id: *req_id })
+            }
+            _ => None,
+        };
+
+        if let Some(change) = change.as_ref() {
+            self.observers
+                .retain(|observer| observer.send(change.clone()).is_ok());
+        }
+        change
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Receives every change applied from now on
+    pub fn subscribe(&mut self) -> Receiver<AccountChange> {
+        let (sender, receiver) = channel();
+        self.observers.push(sender);
+        receiver
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Accounts with balances or positions
+    pub fn accounts(&self) -> Vec<&str> {
+        let mut accounts: Vec<&str> = self
+            .values
+            .keys()
+            .map(|(account, _)| account.as_str())
+            .chain(self.portfolio.keys().map(|account| account.as_str()))
+            .collect();
+        accounts.sort_unstable();
+        accounts.dedup();
+        accounts
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Balances of an account from `req_account_updates`
+    pub fn values(&self, account: &str) -> Option<&AccountValues> {
+        self.model_values(account, "")
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Balances of an account and model code from `req_account_updates_multi`
+    pub fn model_values(&self, account: &str, model_code: &str) -> Option<&AccountValues> {
+        self.values
+            .get(&(account.to_string(), model_code.to_string()))
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// A balance of an account from `req_account_updates`, e.g. `value("DU123", "NetLiquidation",
+    /// "USD")`
+    pub fn value(&self, account: &str, key: &str, currency: &str) -> Option<Decimal> {
+        self.values(account)?.value(key, currency)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Positions of an account by con_id
+    pub fn portfolio(&self, account: &str) -> Option<&BTreeMap<i32, PortfolioItem>> {
+        self.portfolio.get(account)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn position(&self, account: &str, con_id: i32) -> Option<&PortfolioItem> {
+        self.portfolio.get(account)?.get(&con_id)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Total unrealized and realized PnL of an account's positions
+    pub fn pnl(&self, account: &str) -> Option<(f64, f64)> {
+        let positions = self.portfolio.get(account)?;
+        Some(
+            positions
+                .values()
+                .fold((0.0, 0.0), |(unrealized, realized), item| {
+                    (
+                        unrealized + item.unrealized_pnl,
+                        realized + item.realized_pnl,
+                    )
+                }),
+        )
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Time of the last account update as sent by TWS, formatted `HH:MM`
+    pub fn update_time(&self) -> Option<&str> {
+        self.update_time.as_deref()
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn last_update(&self) -> Option<NaiveTime> {
+        NaiveTime::parse_from_str(self.update_time.as_deref()?, "%H:%M").ok()
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// True once TWS delivered the initial values and portfolio of the account
+    pub fn is_downloaded(&self, account: &str) -> bool {
+        self.downloaded
+            .iter()
+            .any(|downloaded| downloaded == account)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn set_value(
+        &mut self,
+        account: &str,
+        model_code: &str,
+        key: &str,
+        value: &str,
+        currency: &str,
+    ) -> Option<AccountChange> {
+        let changed = self
+            .values
+            .entry((account.to_string(), model_code.to_string()))
+            .or_default()
+            .insert(key, value, currency);
+        if !changed {
+            return None;
+        }
+        Some(AccountChange::Value {
+            account: account.to_string(),
+            model_code: model_code.to_string(),
+            key: key.to_string(),
+            currency: currency.to_string(),
+        })
+    }
+}
@@ -0,0 +1,144 @@
+//! Account summary values collected from the events of a `req_account_summary` request
+use std::collections::BTreeMap;
+
+use rust_decimal::Decimal;
+
+use crate::core::account_summary_tags::AccountSummaryTags;
+use crate::core::common::parse_decimal;
+use crate::core::messages::ServerRspMsg;
+
+//==================================================================================================
+/// Summary values of one account
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AccountValues {
+    /// Numeric values by currency, then by tag
+    values: BTreeMap<String, BTreeMap<String, Decimal>>,
+    /// Values that are not numbers, such as `AccountType`, by tag
+    text: BTreeMap<String, String>,
+}
+
+impl AccountValues {
+    /// Value of a tag in a currency. The `$LEDGER:ALL` tags report base currency totals under
+    /// the currency `BASE`.
+    pub fn value(&self, tag: &str, currency: &str) -> Option<Decimal> {
+        self.values.get(currency)?.get(tag).copied()
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Value of a tag in whichever currency it was reported, for tags reported only once such as
+    /// `NetLiquidation`
+    pub fn get(&self, tag: &AccountSummaryTags) -> Option<Decimal> {
+        let tag = tag.to_string();
+        self.values
+            .values()
+            .find_map(|values| values.get(&tag))
+            .copied()
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// All numeric values reported in a currency, by tag
+    pub fn currency(&self, currency: &str) -> Option<&BTreeMap<String, Decimal>> {
+        self.values.get(currency)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn currencies(&self) -> impl Iterator<Item = &str> {
+        self.values.keys().map(|currency| currency.as_str())
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// A value that is not a number, such as `AccountType` or `LookAheadNextChange`
+    pub fn text(&self, tag: &str) -> Option<&str> {
+        self.text.get(tag).map(|value| value.as_str())
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Stores a value, returning true if it differs from the one stored before
+    pub(crate) fn insert(&mut self, tag: &str, value: &str, currency: &str) -> bool {
+        match parse_decimal(value) {
+            Some(decimal) => {
+                let previous = self
+                    .values
+                    .entry(currency.to_string())
+                    .or_default()
+                    .insert(tag.to_string(), decimal);
+                previous != Some(decimal)
+            }
+            None => {
+                let previous = self.text.insert(tag.to_string(), value.to_string());
+                previous.as_deref() != Some(value)
+            }
+        }
+    }
+}
+
+//==================================================================================================
+/// Collects the `AccountSummary` events of one request until `AccountSummaryEnd`. Updates that
+/// arrive afterwards while the subscription is active are applied as well.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccountSummary {
+    req_id: i32,
+    accounts: BTreeMap<String, AccountValues>,
+    complete: bool,
+}
+
+impl AccountSummary {
+    pub fn new(req_id: i32) -> Self {
+        AccountSummary {
+            req_id,
+            accounts: BTreeMap::new(),
+            complete: false,
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn req_id(&self) -> i32 {
+        self.req_id
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Folds an event into the summary. Returns true if the event belonged to this request.
+    pub fn update(&mut self, msg: &ServerRspMsg) -> bool {
+        match msg {
+            ServerRspMsg::AccountSummary {
+                req_id,
+                account,
+                tag,
+                value,
+                currency,
+            } if *req_id == self.req_id => {
+                self.accounts
+                    .entry(account.clone())
+                    .or_default()
+                    .insert(tag, value, currency);
+                true
+            }
+            ServerRspMsg::AccountSummaryEnd { req_id } if *req_id == self.req_id => {
+                self.complete = true;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// True once `AccountSummaryEnd` was received
+    pub fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn accounts(&self) -> impl Iterator<Item = &str> {
+        self.accounts.keys().map(|account| account.as_str())
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn account(&self, account: &str) -> Option<&AccountValues> {
+        self.accounts.get(account)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn value(&self, account: &str, tag: &str, currency: &str) -> Option<Decimal> {
+        self.account(account)?.value(tag, currency)
+    }
+}
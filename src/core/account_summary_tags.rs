@@ -37,6 +37,7 @@ use crate::core::account_summary_tags::AccountSummaryTags::*;
 /// $LEDGER:CURRENCY — Single flag to relay all cash balance tags*, only in the specified currency.
 /// $LEDGER:ALL — Single flag to relay all cash balance tags* in all currencies.
 
+#[derive(Clone, Debug, PartialEq)]
 pub enum AccountSummaryTags {
     AccountType,
     NetLiquidation,
@@ -47,8 +48,8 @@ pub enum AccountSummaryTags {
     EquityWithLoanValue,
     PreviousEquityWithLoanValue,
     GrossPositionValue,
-    ReqTEquity,
-    ReqTMargin,
+    RegTEquity,
+    RegTMargin,
     SMA,
     InitMarginReq,
     MaintMarginReq,
@@ -68,12 +69,57 @@ pub enum AccountSummaryTags {
     DayTradesRemaining,
     Leverage,
     Ledger,
-    LedgerCurrency,
+    /// Cash balances in the given currency, e.g. `$LEDGER:EUR`
+    LedgerCurrency(String),
     LedgerAll,
+    /// Every tag of `AccountSummaryTags::ALL`
     AllTags,
 }
 
 impl AccountSummaryTags {
+    /// All tags other than the `$LEDGER` ones
+    pub const ALL: [AccountSummaryTags; 29] = [
+        AccountType,
+        NetLiquidation,
+        TotalCashValue,
+        SettledCash,
+        AccruedCash,
+        BuyingPower,
+        EquityWithLoanValue,
+        PreviousEquityWithLoanValue,
+        GrossPositionValue,
+        RegTEquity,
+        RegTMargin,
+        SMA,
+        InitMarginReq,
+        MaintMarginReq,
+        AvailableFunds,
+        ExcessLiquidity,
+        Cushion,
+        FullInitMarginReq,
+        FullMaintMarginReq,
+        FullAvailableFunds,
+        FullExcessLiquidity,
+        LookAheadNextChange,
+        LookAheadInitMarginReq,
+        LookAheadMaintMarginReq,
+        LookAheadAvailableFunds,
+        LookAheadExcessLiquidity,
+        HighestSeverity,
+        DayTradesRemaining,
+        Leverage,
+    ];
+
+    //----------------------------------------------------------------------------------------------
+    /// The comma-separated tag list sent by `EClient::req_account_summary`
+    pub fn join(tags: &[AccountSummaryTags]) -> String {
+        tags.iter()
+            .map(|tag| tag.to_string())
+            .collect::<Vec<String>>()
+            .join(",")
+    }
+
+    //----------------------------------------------------------------------------------------------
     fn display(&self) -> &str {
         match self {
             AccountType => "AccountType",
@@ -85,8 +131,8 @@ impl AccountSummaryTags {
             EquityWithLoanValue => "EquityWithLoanValue",
             PreviousEquityWithLoanValue => "PreviousEquityWithLoanValue",
             GrossPositionValue => "GrossPositionValue",
-            ReqTEquity => "ReqTEquity",
-            ReqTMargin => "ReqTMargin",
+            RegTEquity => "RegTEquity",
+            RegTMargin => "RegTMargin",
             SMA => "SMA",
             InitMarginReq => "InitMarginReq",
             MaintMarginReq => "MaintMarginReq",
@@ -106,48 +152,21 @@ impl AccountSummaryTags {
             DayTradesRemaining => "DayTradesRemaining",
             Leverage => "Leverage",
             Ledger => "$LEDGER",
-            LedgerCurrency => "$LEDGER:CURRENCY",
+            // Display appends the currency
+            LedgerCurrency(_) => "$LEDGER:",
             LedgerAll => "$LEDGER:ALL",
-            AllTags => {
-                "AccountType,
-            NetLiquidation,
-            TotalCashValue,
-            SettledCash,
-            AccruedCash,
-            BuyingPower,
-            EquityWithLoanValue,
-            PreviousEquityWithLoanValue,
-            GrossPositionValue,
-            ReqTEquity,
-            ReqTMargin,
-            SMA,
-            InitMarginReq,
-            MaintMarginReq,
-            AvailableFunds,
-            ExcessLiquidity,
-            Cushion,Cushion,
-            FullInitMarginReq,
-            FullMaintMarginReq,
-            FullAvailableFunds,
-            FullExcessLiquidity,
-            LookAheadNextChange,
-            LookAheadInitMarginReq,
-            LookAheadMaintMarginReq,
-            LookAheadAvailableFunds,
-            LookAheadExcessLiquidity,
-            HighestSeverity,
-            DayTradesRemaining,
-            Leverage,
-            $LEDGER,
-            $LEDGER:CURRENCY,
-            $LEDGER:ALL"
-            }
+            // Display expands to the list of tags
+            AllTags => "AllTags",
         }
     }
 }
 
 impl Display for AccountSummaryTags {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
-        write!(f, "{}", self.display())
+        match self {
+            LedgerCurrency(currency) => write!(f, "{}{}", self.display(), currency),
+            AllTags => write!(f, "{}", AccountSummaryTags::join(&AccountSummaryTags::ALL)),
+            _ => write!(f, "{}", self.display()),
+        }
     }
 }
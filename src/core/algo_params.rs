@@ -1,7 +1,889 @@
-//! Utility functions that illustrate setting fields related to algo parameters
+//! Typed algo strategies, and utility functions that illustrate setting fields related to algo
+//! parameters
+use std::fmt::{Display, Error, Formatter};
+use std::str::FromStr;
+
+use strum_macros::EnumString;
+
 use crate::core::common::TagValue;
+use crate::core::errors::IBKRApiLibError;
 use crate::core::order::Order;
 
+// Percentage of volume parameters are fractions of the average daily volume
+const PCT_VOL_MIN: f64 = 0.01;
+const PCT_VOL_MAX: f64 = 0.5;
+
+//==================================================================================================
+#[derive(EnumString, Clone, Copy, Debug, PartialEq)]
+pub enum RiskAversion {
+    #[strum(serialize = "Get Done")]
+    GetDone,
+    Aggressive,
+    Neutral,
+    Passive,
+}
+
+impl Display for RiskAversion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match *self {
+            RiskAversion::GetDone => write!(f, "Get Done"),
+            RiskAversion::Aggressive => write!(f, "Aggressive"),
+            RiskAversion::Neutral => write!(f, "Neutral"),
+            RiskAversion::Passive => write!(f, "Passive"),
+        }
+    }
+}
+
+//==================================================================================================
+#[derive(EnumString, Clone, Copy, Debug, PartialEq)]
+pub enum AdaptivePriority {
+    Urgent,
+    Normal,
+    Patient,
+}
+
+impl Display for AdaptivePriority {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match *self {
+            AdaptivePriority::Urgent => write!(f, "Urgent"),
+            AdaptivePriority::Normal => write!(f, "Normal"),
+            AdaptivePriority::Patient => write!(f, "Patient"),
+        }
+    }
+}
+
+//==================================================================================================
+#[derive(EnumString, Clone, Copy, Debug, PartialEq)]
+pub enum TwapStrategyType {
+    Marketable,
+    #[strum(serialize = "Matching Midpoint")]
+    MatchingMidpoint,
+    #[strum(serialize = "Matching Same Side")]
+    MatchingSameSide,
+    #[strum(serialize = "Matching Last")]
+    MatchingLast,
+}
+
+impl Display for TwapStrategyType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match *self {
+            TwapStrategyType::Marketable => write!(f, "Marketable"),
+            TwapStrategyType::MatchingMidpoint => write!(f, "Matching Midpoint"),
+            TwapStrategyType::MatchingSameSide => write!(f, "Matching Same Side"),
+            TwapStrategyType::MatchingLast => write!(f, "Matching Last"),
+        }
+    }
+}
+
+//==================================================================================================
+/// IB algos and the broker algos from the samples, with typed parameters.
+///
+/// Times are passed through as TWS expects them, e.g. "09:00:00 US/Eastern", and are left empty
+/// when not set. Percentage of volume parameters are fractions between 0.01 and 0.5. Strategies
+/// this library does not model, or whose parameters could not be read back, are kept as `Other`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AlgoStrategy {
+    Adaptive {
+        priority: AdaptivePriority,
+    },
+    ArrivalPx {
+        max_pct_vol: f64,
+        risk_aversion: RiskAversion,
+        start_time: String,
+        end_time: String,
+        force_completion: bool,
+        allow_past_end_time: bool,
+        monetary_value: Option<f64>,
+    },
+    DarkIce {
+        display_size: i32,
+        start_time: String,
+        end_time: String,
+        allow_past_end_time: bool,
+        monetary_value: Option<f64>,
+    },
+    PctVol {
+        pct_vol: f64,
+        start_time: String,
+        end_time: String,
+        no_take_liq: bool,
+        monetary_value: Option<f64>,
+    },
+    /// Price variant percentage of volume
+    PctVolPx {
+        pct_vol: f64,
+        delta_pct_vol: f64,
+        min_pct_vol_4px: f64,
+        max_pct_vol_4px: f64,
+        start_time: String,
+        end_time: String,
+        no_take_liq: bool,
+        monetary_value: Option<f64>,
+    },
+    /// Size variant percentage of volume
+    PctVolSz {
+        start_pct_vol: f64,
+        end_pct_vol: f64,
+        start_time: String,
+        end_time: String,
+        no_take_liq: bool,
+        monetary_value: Option<f64>,
+    },
+    /// Time variant percentage of volume
+    PctVolTm {
+        start_pct_vol: f64,
+        end_pct_vol: f64,
+        start_time: String,
+        end_time: String,
+        no_take_liq: bool,
+        monetary_value: Option<f64>,
+    },
+    Twap {
+        strategy_type: TwapStrategyType,
+        start_time: String,
+        end_time: String,
+        allow_past_end_time: bool,
+        monetary_value: Option<f64>,
+    },
+    Vwap {
+        max_pct_vol: f64,
+        start_time: String,
+        end_time: String,
+        allow_past_end_time: bool,
+        no_take_liq: bool,
+        monetary_value: Option<f64>,
+    },
+    /// Accumulate/Distribute, sent as "AD"
+    AccumDistr {
+        component_size: i32,
+        time_between_orders: i32,
+        randomize_time_20: bool,
+        randomize_size_55: bool,
+        give_up: i32,
+        catch_up: bool,
+        wait_for_fill: bool,
+        active_time_start: String,
+        active_time_end: String,
+    },
+    BalanceImpactRisk {
+        max_pct_vol: f64,
+        risk_aversion: RiskAversion,
+        force_completion: bool,
+    },
+    MinImpact {
+        max_pct_vol: f64,
+    },
+    ClosePx {
+        max_pct_vol: f64,
+        risk_aversion: RiskAversion,
+        start_time: String,
+        force_completion: bool,
+        monetary_value: Option<f64>,
+    },
+    /// Jefferies VWAP, the order must be routed to JEFFALGO
+    JefferiesVwap {
+        start_time: String,
+        end_time: String,
+        relative_limit: f64,
+        max_volume_rate: f64,
+        exclude_auctions: String,
+        trigger_price: f64,
+        wow_price: f64,
+        min_fill_size: i32,
+        wow_order_pct: f64,
+        wow_mode: String,
+        is_buy_back: bool,
+        wow_reference: String,
+    },
+    /// CSFB Inline, the order must be routed to CSFBALGO
+    CsfbInline {
+        start_time: String,
+        end_time: String,
+        exec_style: String,
+        min_percent: i32,
+        max_percent: i32,
+        display_size: i32,
+        auction: String,
+        block_finder: bool,
+        block_price: f64,
+        min_block_size: i32,
+        max_block_size: i32,
+        i_would_price: f64,
+    },
+    /// QB Strobe, the order must be routed to QBALGO
+    QbStrobe {
+        start_time: String,
+        end_time: String,
+        benchmark: String,
+        percent_volume: f64,
+        no_clean_up: bool,
+    },
+    Other {
+        strategy: String,
+        params: Vec<TagValue>,
+    },
+}
+
+impl AlgoStrategy {
+    /// The algo_strategy value sent to TWS
+    pub fn name(&self) -> &str {
+        match self {
+            AlgoStrategy::Adaptive { .. } => "Adaptive",
+            AlgoStrategy::ArrivalPx { .. } => "ArrivalPx",
+            AlgoStrategy::DarkIce { .. } => "DarkIce",
+            AlgoStrategy::PctVol { .. } => "PctVol",
+            AlgoStrategy::PctVolPx { .. } => "PctVolPx",
+            AlgoStrategy::PctVolSz { .. } => "PctVolSz",
+            AlgoStrategy::PctVolTm { .. } => "PctVolTm",
+            AlgoStrategy::Twap { .. } => "Twap",
+            AlgoStrategy::Vwap { .. } => "Vwap",
+            AlgoStrategy::AccumDistr { .. } => "AD",
+            AlgoStrategy::BalanceImpactRisk { .. } => "BalanceImpactRisk",
+            AlgoStrategy::MinImpact { .. } => "MinImpact",
+            AlgoStrategy::ClosePx { .. } => "ClosePx",
+            AlgoStrategy::JefferiesVwap { .. } => "VWAP",
+            AlgoStrategy::CsfbInline { .. } => "INLINE",
+            AlgoStrategy::QbStrobe { .. } => "STROBE",
+            AlgoStrategy::Other { strategy, .. } => strategy,
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// The algo_params sent to TWS
+    pub fn to_tag_values(&self) -> Vec<TagValue> {
+        let mut params = ParamWriter::default();
+        match self {
+            AlgoStrategy::Adaptive { priority } => {
+                params.value("adaptivePriority", priority);
+            }
+            AlgoStrategy::ArrivalPx {
+                max_pct_vol,
+                risk_aversion,
+                start_time,
+                end_time,
+                force_completion,
+                allow_past_end_time,
+                monetary_value,
+            } => {
+                params.value("maxPctVol", max_pct_vol);
+                params.value("riskAversion", risk_aversion);
+                params.value("startTime", start_time);
+                params.value("endTime", end_time);
+                params.flag("forceCompletion", *force_completion);
+                params.flag("allowPastEndTime", *allow_past_end_time);
+                params.optional("monetaryValue", monetary_value);
+            }
+            AlgoStrategy::DarkIce {
+                display_size,
+                start_time,
+                end_time,
+                allow_past_end_time,
+                monetary_value,
+            } => {
+                params.value("displaySize", display_size);
+                params.value("startTime", start_time);
+                params.value("endTime", end_time);
+                params.flag("allowPastEndTime", *allow_past_end_time);
+                params.optional("monetaryValue", monetary_value);
+            }
+            AlgoStrategy::PctVol {
+                pct_vol,
+                start_time,
+                end_time,
+                no_take_liq,
+                monetary_value,
+            } => {
+                params.value("pctVol", pct_vol);
+                params.value("startTime", start_time);
+                params.value("endTime", end_time);
+                params.flag("noTakeLiq", *no_take_liq);
+                params.optional("monetaryValue", monetary_value);
+            }
+            AlgoStrategy::PctVolPx {
+                pct_vol,
+                delta_pct_vol,
+                min_pct_vol_4px,
+                max_pct_vol_4px,
+                start_time,
+                end_time,
+                no_take_liq,
+                monetary_value,
+            } => {
+                params.value("pctVol", pct_vol);
+                params.value("deltaPctVol", delta_pct_vol);
+                params.value("minPctVol4Px", min_pct_vol_4px);
+                params.value("maxPctVol4Px", max_pct_vol_4px);
+                params.value("startTime", start_time);
+                params.value("endTime", end_time);
+                params.flag("noTakeLiq", *no_take_liq);
+                params.optional("monetaryValue", monetary_value);
+            }
+            AlgoStrategy::PctVolSz {
+                start_pct_vol,
+                end_pct_vol,
+                start_time,
+                end_time,
+                no_take_liq,
+                monetary_value,
+            }
+            | AlgoStrategy::PctVolTm {
+                start_pct_vol,
+                end_pct_vol,
+                start_time,
+                end_time,
+                no_take_liq,
+                monetary_value,
+            } => {
+                params.value("startPctVol", start_pct_vol);
+                params.value("endPctVol", end_pct_vol);
+                params.value("startTime", start_time);
+                params.value("endTime", end_time);
+                params.flag("noTakeLiq", *no_take_liq);
+                params.optional("monetaryValue", monetary_value);
+            }
+            AlgoStrategy::Twap {
+                strategy_type,
+                start_time,
+                end_time,
+                allow_past_end_time,
+                monetary_value,
+            } => {
+                params.value("strategyType", strategy_type);
+                params.value("startTime", start_time);
+                params.value("endTime", end_time);
+                params.flag("allowPastEndTime", *allow_past_end_time);
+                params.optional("monetaryValue", monetary_value);
+            }
+            AlgoStrategy::Vwap {
+                max_pct_vol,
+                start_time,
+                end_time,
+                allow_past_end_time,
+                no_take_liq,
+                monetary_value,
+            } => {
+                params.value("maxPctVol", max_pct_vol);
+                params.value("startTime", start_time);
+                params.value("endTime", end_time);
+                params.flag("allowPastEndTime", *allow_past_end_time);
+                params.flag("noTakeLiq", *no_take_liq);
+                params.optional("monetaryValue", monetary_value);
+            }
+            AlgoStrategy::AccumDistr {
+                component_size,
+                time_between_orders,
+                randomize_time_20,
+                randomize_size_55,
+                give_up,
+                catch_up,
+                wait_for_fill,
+                active_time_start,
+                active_time_end,
+            } => {
+                params.value("ComponentSize", component_size);
+                params.value("TimeBetweenOrders", time_between_orders);
+                params.flag("RandomizeTime20", *randomize_time_20);
+                params.flag("RandomizeSize55", *randomize_size_55);
+                params.value("GiveUp", give_up);
+                params.flag("CatchUp", *catch_up);
+                params.flag("WaitForFill", *wait_for_fill);
+                params.value("activeTimeStart", active_time_start);
+                params.value("activeTimeEnd", active_time_end);
+            }
+            AlgoStrategy::BalanceImpactRisk {
+                max_pct_vol,
+                risk_aversion,
+                force_completion,
+            } => {
+                params.value("maxPctVol", max_pct_vol);
+                params.value("riskAversion", risk_aversion);
+                params.flag("forceCompletion", *force_completion);
+            }
+            AlgoStrategy::MinImpact { max_pct_vol } => {
+                params.value("maxPctVol", max_pct_vol);
+            }
+            AlgoStrategy::ClosePx {
+                max_pct_vol,
+                risk_aversion,
+                start_time,
+                force_completion,
+                monetary_value,
+            } => {
+                params.value("maxPctVol", max_pct_vol);
+                params.value("riskAversion", risk_aversion);
+                params.value("startTime", start_time);
+                params.flag("forceCompletion", *force_completion);
+                params.optional("monetaryValue", monetary_value);
+            }
+            AlgoStrategy::JefferiesVwap {
+                start_time,
+                end_time,
+                relative_limit,
+                max_volume_rate,
+                exclude_auctions,
+                trigger_price,
+                wow_price,
+                min_fill_size,
+                wow_order_pct,
+                wow_mode,
+                is_buy_back,
+                wow_reference,
+            } => {
+                params.value("startTime", start_time);
+                params.value("endTime", end_time);
+                params.value("relativeLimit", relative_limit);
+                params.value("maxVolumeRate", max_volume_rate);
+                params.value("excludeAuctions", exclude_auctions);
+                params.value("triggerPrice", trigger_price);
+                params.value("wowPrice", wow_price);
+                params.value("minFillSize", min_fill_size);
+                params.value("wowOrderPct", wow_order_pct);
+                params.value("wowMode", wow_mode);
+                params.flag("isBuyBack", *is_buy_back);
+                params.value("wowReference", wow_reference);
+            }
+            AlgoStrategy::CsfbInline {
+                start_time,
+                end_time,
+                exec_style,
+                min_percent,
+                max_percent,
+                display_size,
+                auction,
+                block_finder,
+                block_price,
+                min_block_size,
+                max_block_size,
+                i_would_price,
+            } => {
+                params.value("StartTime", start_time);
+                params.value("EndTime", end_time);
+                params.value("ExecStyle", exec_style);
+                params.value("MinPercent", min_percent);
+                params.value("MaxPercent", max_percent);
+                params.value("DisplaySize", display_size);
+                params.value("Auction", auction);
+                params.flag("BlockFinder", *block_finder);
+                params.value("BlockPrice", block_price);
+                params.value("MinBlockSize", min_block_size);
+                params.value("MaxBlockSize", max_block_size);
+                params.value("IWouldPrice", i_would_price);
+            }
+            AlgoStrategy::QbStrobe {
+                start_time,
+                end_time,
+                benchmark,
+                percent_volume,
+                no_clean_up,
+            } => {
+                params.value("startTime", start_time);
+                params.value("endTime", end_time);
+                params.value("benchmark", benchmark);
+                params.value("percentVolume", percent_volume);
+                params.flag("noCleanUp", *no_clean_up);
+            }
+            AlgoStrategy::Other { params: other, .. } => return other.clone(),
+        }
+        params.params
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Checks the parameters against the ranges TWS accepts
+    pub fn validate(&self) -> Result<(), IBKRApiLibError> {
+        match self {
+            AlgoStrategy::ArrivalPx {
+                max_pct_vol,
+                monetary_value,
+                ..
+            }
+            | AlgoStrategy::ClosePx {
+                max_pct_vol,
+                monetary_value,
+                ..
+            }
+            | AlgoStrategy::Vwap {
+                max_pct_vol,
+                monetary_value,
+                ..
+            } => {
+                self.check_pct_vol("maxPctVol", *max_pct_vol)?;
+                self.check_monetary_value(monetary_value)
+            }
+            AlgoStrategy::BalanceImpactRisk { max_pct_vol, .. }
+            | AlgoStrategy::MinImpact { max_pct_vol } => {
+                self.check_pct_vol("maxPctVol", *max_pct_vol)
+            }
+            AlgoStrategy::DarkIce {
+                display_size,
+                monetary_value,
+                ..
+            } => {
+                self.check_range("displaySize", *display_size as f64, 1.0, f64::MAX)?;
+                self.check_monetary_value(monetary_value)
+            }
+            AlgoStrategy::PctVol {
+                pct_vol,
+                monetary_value,
+                ..
+            } => {
+                self.check_pct_vol("pctVol", *pct_vol)?;
+                self.check_monetary_value(monetary_value)
+            }
+            AlgoStrategy::PctVolPx {
+                pct_vol,
+                delta_pct_vol,
+                min_pct_vol_4px,
+                max_pct_vol_4px,
+                monetary_value,
+                ..
+            } => {
+                self.check_pct_vol("pctVol", *pct_vol)?;
+                self.check_pct_vol("deltaPctVol", *delta_pct_vol)?;
+                self.check_pct_vol("minPctVol4Px", *min_pct_vol_4px)?;
+                self.check_pct_vol("maxPctVol4Px", *max_pct_vol_4px)?;
+                self.check_monetary_value(monetary_value)
+            }
+            AlgoStrategy::PctVolSz {
+                start_pct_vol,
+                end_pct_vol,
+                monetary_value,
+                ..
+            }
+            | AlgoStrategy::PctVolTm {
+                start_pct_vol,
+                end_pct_vol,
+                monetary_value,
+                ..
+            } => {
+                self.check_pct_vol("startPctVol", *start_pct_vol)?;
+                self.check_pct_vol("endPctVol", *end_pct_vol)?;
+                self.check_monetary_value(monetary_value)
+            }
+            AlgoStrategy::Twap { monetary_value, .. } => self.check_monetary_value(monetary_value),
+            AlgoStrategy::AccumDistr {
+                component_size,
+                time_between_orders,
+                give_up,
+                ..
+            } => {
+                self.check_range("ComponentSize", *component_size as f64, 1.0, f64::MAX)?;
+                self.check_range(
+                    "TimeBetweenOrders",
+                    *time_between_orders as f64,
+                    1.0,
+                    f64::MAX,
+                )?;
+                self.check_range("GiveUp", *give_up as f64, 0.0, f64::MAX)
+            }
+            AlgoStrategy::JefferiesVwap { min_fill_size, .. } => {
+                self.check_range("minFillSize", *min_fill_size as f64, 0.0, f64::MAX)
+            }
+            AlgoStrategy::CsfbInline {
+                min_percent,
+                max_percent,
+                ..
+            } => {
+                self.check_range("MinPercent", *min_percent as f64, 0.0, 100.0)?;
+                self.check_range(
+                    "MaxPercent",
+                    *max_percent as f64,
+                    *min_percent as f64,
+                    100.0,
+                )
+            }
+            AlgoStrategy::QbStrobe { percent_volume, .. } => {
+                self.check_range("percentVolume", *percent_volume, 0.0, 1.0)
+            }
+            AlgoStrategy::Adaptive { .. } | AlgoStrategy::Other { .. } => Ok(()),
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Validates the strategy and sets it as the algo_strategy and algo_params of the order
+    pub fn apply_to(&self, order: &mut Order) -> Result<(), IBKRApiLibError> {
+        self.validate()?;
+        self.write_to(order);
+        Ok(())
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn write_to(&self, order: &mut Order) {
+        order.algo_strategy = self.name().to_string();
+        order.algo_params = self.to_tag_values();
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Reads the strategy back from the algo_strategy and algo_params of an order, None if the
+    /// order has no algo. Unknown strategy names are returned as `Other`.
+    pub fn from_tag_values(
+        strategy: &str,
+        params: &[TagValue],
+    ) -> Result<Option<AlgoStrategy>, IBKRApiLibError> {
+        let params = ParamReader { strategy, params };
+        let algo = match strategy {
+            "" => return Ok(None),
+            "Adaptive" => AlgoStrategy::Adaptive {
+                priority: params.value("adaptivePriority")?,
+            },
+            "ArrivalPx" => AlgoStrategy::ArrivalPx {
+                max_pct_vol: params.value("maxPctVol")?,
+                risk_aversion: params.value("riskAversion")?,
+                start_time: params.text("startTime"),
+                end_time: params.text("endTime"),
+                force_completion: params.flag("forceCompletion")?,
+                allow_past_end_time: params.flag("allowPastEndTime")?,
+                monetary_value: params.optional("monetaryValue")?,
+            },
+            "DarkIce" => AlgoStrategy::DarkIce {
+                display_size: params.value("displaySize")?,
+                start_time: params.text("startTime"),
+                end_time: params.text("endTime"),
+                allow_past_end_time: params.flag("allowPastEndTime")?,
+                monetary_value: params.optional("monetaryValue")?,
+            },
+            "PctVol" => AlgoStrategy::PctVol {
+                pct_vol: params.value("pctVol")?,
+                start_time: params.text("startTime"),
+                end_time: params.text("endTime"),
+                no_take_liq: params.flag("noTakeLiq")?,
+                monetary_value: params.optional("monetaryValue")?,
+            },
+            "PctVolPx" => AlgoStrategy::PctVolPx {
+                pct_vol: params.value("pctVol")?,
+                delta_pct_vol: params.value("deltaPctVol")?,
+                min_pct_vol_4px: params.value("minPctVol4Px")?,
+                max_pct_vol_4px: params.value("maxPctVol4Px")?,
+                start_time: params.text("startTime"),
+                end_time: params.text("endTime"),
+                no_take_liq: params.flag("noTakeLiq")?,
+                monetary_value: params.optional("monetaryValue")?,
+            },
+            "PctVolSz" => AlgoStrategy::PctVolSz {
+                start_pct_vol: params.value("startPctVol")?,
+                end_pct_vol: params.value("endPctVol")?,
+                start_time: params.text("startTime"),
+                end_time: params.text("endTime"),
+                no_take_liq: params.flag("noTakeLiq")?,
+                monetary_value: params.optional("monetaryValue")?,
+            },
+            "PctVolTm" => AlgoStrategy::PctVolTm {
+                start_pct_vol: params.value("startPctVol")?,
+                end_pct_vol: params.value("endPctVol")?,
+                start_time: params.text("startTime"),
+                end_time: params.text("endTime"),
+                no_take_liq: params.flag("noTakeLiq")?,
+                monetary_value: params.optional("monetaryValue")?,
+            },
+            "Twap" => AlgoStrategy::Twap {
+                strategy_type: params.value("strategyType")?,
+                start_time: params.text("startTime"),
+                end_time: params.text("endTime"),
+                allow_past_end_time: params.flag("allowPastEndTime")?,
+                monetary_value: params.optional("monetaryValue")?,
+            },
+            "Vwap" => AlgoStrategy::Vwap {
+                max_pct_vol: params.value("maxPctVol")?,
+                start_time: params.text("startTime"),
+                end_time: params.text("endTime"),
+                allow_past_end_time: params.flag("allowPastEndTime")?,
+                no_take_liq: params.flag("noTakeLiq")?,
+                monetary_value: params.optional("monetaryValue")?,
+            },
+            "AD" => AlgoStrategy::AccumDistr {
+                component_size: params.value("ComponentSize")?,
+                time_between_orders: params.value("TimeBetweenOrders")?,
+                randomize_time_20: params.flag("RandomizeTime20")?,
+                randomize_size_55: params.flag("RandomizeSize55")?,
+                give_up: params.value("GiveUp")?,
+                catch_up: params.flag("CatchUp")?,
+                wait_for_fill: params.flag("WaitForFill")?,
+                active_time_start: params.text("activeTimeStart"),
+                active_time_end: params.text("activeTimeEnd"),
+            },
+            "BalanceImpactRisk" => AlgoStrategy::BalanceImpactRisk {
+                max_pct_vol: params.value("maxPctVol")?,
+                risk_aversion: params.value("riskAversion")?,
+                force_completion: params.flag("forceCompletion")?,
+            },
+            "MinImpact" => AlgoStrategy::MinImpact {
+                max_pct_vol: params.value("maxPctVol")?,
+            },
+            "ClosePx" => AlgoStrategy::ClosePx {
+                max_pct_vol: params.value("maxPctVol")?,
+                risk_aversion: params.value("riskAversion")?,
+                start_time: params.text("startTime"),
+                force_completion: params.flag("forceCompletion")?,
+                monetary_value: params.optional("monetaryValue")?,
+            },
+            "VWAP" => AlgoStrategy::JefferiesVwap {
+                start_time: params.text("startTime"),
+                end_time: params.text("endTime"),
+                relative_limit: params.value("relativeLimit")?,
+                max_volume_rate: params.value("maxVolumeRate")?,
+                exclude_auctions: params.text("excludeAuctions"),
+                trigger_price: params.value("triggerPrice")?,
+                wow_price: params.value("wowPrice")?,
+                min_fill_size: params.value("minFillSize")?,
+                wow_order_pct: params.value("wowOrderPct")?,
+                wow_mode: params.text("wowMode"),
+                is_buy_back: params.flag("isBuyBack")?,
+                wow_reference: params.text("wowReference"),
+            },
+            "INLINE" => AlgoStrategy::CsfbInline {
+                start_time: params.text("StartTime"),
+                end_time: params.text("EndTime"),
+                exec_style: params.text("ExecStyle"),
+                min_percent: params.value("MinPercent")?,
+                max_percent: params.value("MaxPercent")?,
+                display_size: params.value("DisplaySize")?,
+                auction: params.text("Auction"),
+                block_finder: params.flag("BlockFinder")?,
+                block_price: params.value("BlockPrice")?,
+                min_block_size: params.value("MinBlockSize")?,
+                max_block_size: params.value("MaxBlockSize")?,
+                i_would_price: params.value("IWouldPrice")?,
+            },
+            "STROBE" => AlgoStrategy::QbStrobe {
+                start_time: params.text("startTime"),
+                end_time: params.text("endTime"),
+                benchmark: params.text("benchmark"),
+                percent_volume: params.value("percentVolume")?,
+                no_clean_up: params.flag("noCleanUp")?,
+            },
+            _ => AlgoStrategy::Other {
+                strategy: strategy.to_string(),
+                params: params.params.to_vec(),
+            },
+        };
+        Ok(Some(algo))
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Reads the strategy of an order, see `from_tag_values`
+    pub fn from_order(order: &Order) -> Result<Option<AlgoStrategy>, IBKRApiLibError> {
+        AlgoStrategy::from_tag_values(&order.algo_strategy, &order.algo_params)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn check_pct_vol(&self, param: &'static str, value: f64) -> Result<(), IBKRApiLibError> {
+        self.check_range(param, value, PCT_VOL_MIN, PCT_VOL_MAX)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn check_monetary_value(&self, value: &Option<f64>) -> Result<(), IBKRApiLibError> {
+        match value {
+            Some(value) => self.check_range("monetaryValue", *value, 0.0, f64::MAX),
+            None => Ok(()),
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn check_range(
+        &self,
+        param: &'static str,
+        value: f64,
+        min: f64,
+        max: f64,
+    ) -> Result<(), IBKRApiLibError> {
+        if value >= min && value <= max {
+            return Ok(());
+        }
+        let reason = if max == f64::MAX {
+            format!("must be at least {}, got {}", min, value)
+        } else {
+            format!("must be between {} and {}, got {}", min, max, value)
+        };
+        Err(IBKRApiLibError::InvalidAlgoParam {
+            strategy: self.name().to_string(),
+            param,
+            reason,
+        })
+    }
+}
+
+//==================================================================================================
+#[derive(Default)]
+struct ParamWriter {
+    params: Vec<TagValue>,
+}
+
+impl ParamWriter {
+    fn value<T: Display>(&mut self, tag: &str, value: T) {
+        self.params
+            .push(TagValue::new(tag.to_string(), value.to_string()));
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn flag(&mut self, tag: &str, value: bool) {
+        self.value(tag, value as i32);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn optional<T: Display>(&mut self, tag: &str, value: &Option<T>) {
+        if let Some(value) = value {
+            self.value(tag, value);
+        }
+    }
+}
+
+//==================================================================================================
+struct ParamReader<'a> {
+    strategy: &'a str,
+    params: &'a [TagValue],
+}
+
+impl<'a> ParamReader<'a> {
+    fn get(&self, tag: &str) -> Option<&'a str> {
+        self.params
+            .iter()
+            .find(|param| param.tag == tag)
+            .map(|param| param.value.as_str())
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn text(&self, tag: &str) -> String {
+        self.get(tag).unwrap_or_default().to_string()
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn value<T: FromStr>(&self, tag: &'static str) -> Result<T, IBKRApiLibError> {
+        match self.optional(tag)? {
+            Some(value) => Ok(value),
+            None => Err(self.error(tag, "is missing".to_string())),
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn optional<T: FromStr>(&self, tag: &'static str) -> Result<Option<T>, IBKRApiLibError> {
+        match self.get(tag) {
+            None | Some("") => Ok(None),
+            Some(value) => value
+                .parse()
+                .map(Some)
+                .map_err(|_| self.error(tag, format!("has unexpected value '{}'", value))),
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    // TWS sends flags as 0/1, older samples used true/false
+    fn flag(&self, tag: &'static str) -> Result<bool, IBKRApiLibError> {
+        match self.get(tag).unwrap_or_default() {
+            "" | "0" | "false" => Ok(false),
+            "1" | "true" => Ok(true),
+            value => Err(self.error(tag, format!("has unexpected value '{}'", value))),
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn error(&self, tag: &'static str, reason: String) -> IBKRApiLibError {
+        IBKRApiLibError::InvalidAlgoParam {
+            strategy: self.strategy.to_string(),
+            param: tag,
+            reason,
+        }
+    }
+}
+
 //==================================================================================================
 /// Scale parameters
 pub fn fill_scale_params(
@@ -38,42 +920,23 @@ pub fn fill_scale_params(
 pub fn fill_arrival_price_params(
     base_order: &mut Order,
     max_pct_vol: f64,
-    risk_aversion: &str,
+    risk_aversion: RiskAversion,
     start_time: &str,
     end_time: &str,
     force_completion: bool,
     allow_past_time: bool,
     monetary_value: i32,
 ) {
-    base_order.algo_strategy = "ArrivalPx".to_string();
-
-    base_order.algo_params.push(TagValue::new(
-        "maxPctVol".to_string(),
-        max_pct_vol.to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "riskAversion".to_string(),
-        risk_aversion.to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "startTime".to_string(),
-        start_time.to_string(),
-    ));
-    base_order
-        .algo_params
-        .push(TagValue::new("endTime".to_string(), end_time.to_string()));
-    base_order.algo_params.push(TagValue::new(
-        "forceCompletion".to_string(),
-        force_completion.to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "allowPastEndTime".to_string(),
-        allow_past_time.to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "monetaryValue".to_string(),
-        monetary_value.to_string(),
-    ));
+    AlgoStrategy::ArrivalPx {
+        max_pct_vol,
+        risk_aversion,
+        start_time: start_time.to_string(),
+        end_time: end_time.to_string(),
+        force_completion,
+        allow_past_end_time: allow_past_time,
+        monetary_value: Some(monetary_value as f64),
+    }
+    .write_to(base_order);
 }
 
 //==================================================================================================
@@ -86,26 +949,14 @@ pub fn fill_dark_ice_params(
     allow_past_end_time: bool,
     monetary_value: f64,
 ) {
-    base_order.algo_strategy = "DarkIce".to_string();
-    base_order.algo_params.push(TagValue::new(
-        "displaySize".to_string(),
-        display_size.to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "startTime".to_string(),
-        start_time.to_string(),
-    ));
-    base_order
-        .algo_params
-        .push(TagValue::new("endTime".to_string(), end_time.to_string()));
-    base_order.algo_params.push(TagValue::new(
-        "allowPastEndTime".to_string(),
-        (allow_past_end_time as i32).to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "monetaryValue".to_string(),
-        monetary_value.to_string(),
-    ));
+    AlgoStrategy::DarkIce {
+        display_size,
+        start_time: start_time.to_string(),
+        end_time: end_time.to_string(),
+        allow_past_end_time,
+        monetary_value: Some(monetary_value),
+    }
+    .write_to(base_order);
 }
 
 //==================================================================================================
@@ -118,26 +969,14 @@ pub fn fill_pct_vol_params(
     no_take_liq: bool,
     monetary_value: f64,
 ) {
-    base_order.algo_strategy = "PctVol".to_string();
-
-    base_order
-        .algo_params
-        .push(TagValue::new("pctVol".to_string(), pct_vol.to_string()));
-    base_order.algo_params.push(TagValue::new(
-        "startTime".to_string(),
-        start_time.to_string(),
-    ));
-    base_order
-        .algo_params
-        .push(TagValue::new("endTime".to_string(), end_time.to_string()));
-    base_order.algo_params.push(TagValue::new(
-        "noTakeLiq".to_string(),
-        (no_take_liq as i32).to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "monetaryValue".to_string(),
-        monetary_value.to_string(),
-    ));
+    AlgoStrategy::PctVol {
+        pct_vol,
+        start_time: start_time.to_string(),
+        end_time: end_time.to_string(),
+        no_take_liq,
+        monetary_value: Some(monetary_value),
+    }
+    .write_to(base_order);
 }
 
 // ! [twap_params]
@@ -145,33 +984,20 @@ pub fn fill_pct_vol_params(
 /// Fill Twap parameters
 pub fn fill_twap_params(
     base_order: &mut Order,
-    strategy_type: &str,
+    strategy_type: TwapStrategyType,
     start_time: &str,
     end_time: &str,
     allow_past_end_time: bool,
     monetary_value: f64,
 ) {
-    base_order.algo_strategy = "Twap".to_string();
-
-    base_order.algo_params.push(TagValue::new(
-        "strategyType".to_string(),
-        strategy_type.to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "startTime".to_string(),
-        start_time.to_string(),
-    ));
-    base_order
-        .algo_params
-        .push(TagValue::new("endTime".to_string(), end_time.to_string()));
-    base_order.algo_params.push(TagValue::new(
-        "allowPastEndTime".to_string(),
-        (allow_past_end_time as i32).to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "monetaryValue".to_string(),
-        monetary_value.to_string(),
-    ));
+    AlgoStrategy::Twap {
+        strategy_type,
+        start_time: start_time.to_string(),
+        end_time: end_time.to_string(),
+        allow_past_end_time,
+        monetary_value: Some(monetary_value),
+    }
+    .write_to(base_order);
 }
 
 // ! [twap_params]
@@ -188,31 +1014,15 @@ pub fn fill_vwap_params(
     no_take_liq: bool,
     monetary_value: f64,
 ) {
-    base_order.algo_strategy = "Vwap".to_string();
-
-    base_order.algo_params.push(TagValue::new(
-        "maxPctVol".to_string(),
-        max_pct_vol.to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "startTime".to_string(),
-        start_time.to_string(),
-    ));
-    base_order
-        .algo_params
-        .push(TagValue::new("endTime".to_string(), end_time.to_string()));
-    base_order.algo_params.push(TagValue::new(
-        "allowPastEndTime".to_string(),
-        (allow_past_end_time as i32).to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "noTakeLiq".to_string(),
-        (no_take_liq as i32).to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "monetaryValue".to_string(),
-        monetary_value.to_string(),
-    ));
+    AlgoStrategy::Vwap {
+        max_pct_vol,
+        start_time: start_time.to_string(),
+        end_time: end_time.to_string(),
+        allow_past_end_time,
+        no_take_liq,
+        monetary_value: Some(monetary_value),
+    }
+    .write_to(base_order);
 }
 
 // ! [vwap_params]
@@ -232,43 +1042,18 @@ pub fn fill_accumulate_distribute_params(
     start_time: &str,
     end_time: &str,
 ) {
-    base_order.algo_strategy = "AD".to_string();
-
-    base_order.algo_params.push(TagValue::new(
-        "ComponentSize".to_string(),
-        component_size.to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "TimeBetweenOrders".to_string(),
-        time_between_orders.to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "RandomizeTime20".to_string(),
-        (randomize_time_20 as i32).to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "RandomizeSize55".to_string(),
-        (randomize_size_55 as i32).to_string(),
-    ));
-    base_order
-        .algo_params
-        .push(TagValue::new("GiveUp".to_string(), give_up.to_string()));
-    base_order.algo_params.push(TagValue::new(
-        "CatchUp".to_string(),
-        (catch_up as i32).to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "WaitForFill".to_string(),
-        (wait_for_fill as i32).to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "activeTimeStart".to_string(),
-        start_time.to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "activeTimeEnd".to_string(),
-        end_time.to_string(),
-    ));
+    AlgoStrategy::AccumDistr {
+        component_size,
+        time_between_orders,
+        randomize_time_20,
+        randomize_size_55,
+        give_up,
+        catch_up,
+        wait_for_fill,
+        active_time_start: start_time.to_string(),
+        active_time_end: end_time.to_string(),
+    }
+    .write_to(base_order);
 }
 
 //==================================================================================================
@@ -276,23 +1061,15 @@ pub fn fill_accumulate_distribute_params(
 pub fn fill_balance_impact_risk_params(
     base_order: &mut Order,
     max_pct_vol: f64,
-    risk_aversion: &str,
+    risk_aversion: RiskAversion,
     force_completion: bool,
 ) {
-    base_order.algo_strategy = "BalanceImpactRisk".to_string();
-
-    base_order.algo_params.push(TagValue::new(
-        "maxPctVol".to_string(),
-        max_pct_vol.to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "riskAversion".to_string(),
-        risk_aversion.to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "forceCompletion".to_string(),
-        force_completion.to_string(),
-    ));
+    AlgoStrategy::BalanceImpactRisk {
+        max_pct_vol,
+        risk_aversion,
+        force_completion,
+    }
+    .write_to(base_order);
 }
 
 // ! [balanceimpactrisk_params]
@@ -301,23 +1078,13 @@ pub fn fill_balance_impact_risk_params(
 //==================================================================================================
 /// Minimal impact parameters
 pub fn fill_min_impact_params(base_order: &mut Order, max_pct_vol: f64) {
-    base_order.algo_strategy = "MinImpact".to_string();
-
-    base_order.algo_params.push(TagValue::new(
-        "maxPctVol".to_string(),
-        max_pct_vol.to_string(),
-    ));
+    AlgoStrategy::MinImpact { max_pct_vol }.write_to(base_order);
 }
 
 //==================================================================================================
 /// Adaptive priority parameters
-pub fn fill_adaptive_params(base_order: &mut Order, priority: &str) {
-    base_order.algo_strategy = "Adaptive".to_string();
-
-    base_order.algo_params.push(TagValue::new(
-        "adaptivePriority".to_string(),
-        priority.to_string(),
-    ));
+pub fn fill_adaptive_params(base_order: &mut Order, priority: AdaptivePriority) {
+    AlgoStrategy::Adaptive { priority }.write_to(base_order);
 }
 
 //==================================================================================================
@@ -325,33 +1092,19 @@ pub fn fill_adaptive_params(base_order: &mut Order, priority: &str) {
 pub fn fill_close_price_params(
     base_order: &mut Order,
     max_pct_vol: f64,
-    risk_aversion: &str,
+    risk_aversion: RiskAversion,
     start_time: &str,
     force_completion: bool,
     monetary_value: f64,
 ) {
-    base_order.algo_strategy = "ClosePx".to_string();
-
-    base_order.algo_params.push(TagValue::new(
-        "maxPctVol".to_string(),
-        max_pct_vol.to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "riskAversion".to_string(),
-        risk_aversion.to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "startTime".to_string(),
-        start_time.to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "forceCompletion".to_string(),
-        (force_completion as i32).to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "monetaryValue".to_string(),
-        monetary_value.to_string(),
-    ));
+    AlgoStrategy::ClosePx {
+        max_pct_vol,
+        risk_aversion,
+        start_time: start_time.to_string(),
+        force_completion,
+        monetary_value: Some(monetary_value),
+    }
+    .write_to(base_order);
 }
 
 //==================================================================================================
@@ -367,38 +1120,17 @@ pub fn fill_price_variant_pct_vol_params(
     no_take_liq: bool,
     monetary_value: f64,
 ) {
-    base_order.algo_strategy = "PctVolPx".to_string();
-
-    base_order
-        .algo_params
-        .push(TagValue::new("pctVol".to_string(), pct_vol.to_string()));
-    base_order.algo_params.push(TagValue::new(
-        "deltaPctVol".to_string(),
-        delta_pct_vol.to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "minPctVol4Px".to_string(),
-        min_pct_vol_4px.to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "maxPctVol4Px".to_string(),
-        max_pct_vol_4px.to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "startTime".to_string(),
-        start_time.to_string(),
-    ));
-    base_order
-        .algo_params
-        .push(TagValue::new("endTime".to_string(), end_time.to_string()));
-    base_order.algo_params.push(TagValue::new(
-        "noTakeLiq".to_string(),
-        (no_take_liq as i32).to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "monetaryValue".to_string(),
-        monetary_value.to_string(),
-    ));
+    AlgoStrategy::PctVolPx {
+        pct_vol,
+        delta_pct_vol,
+        min_pct_vol_4px,
+        max_pct_vol_4px,
+        start_time: start_time.to_string(),
+        end_time: end_time.to_string(),
+        no_take_liq,
+        monetary_value: Some(monetary_value),
+    }
+    .write_to(base_order);
 }
 
 //==================================================================================================
@@ -412,31 +1144,15 @@ pub fn fill_size_variant_pct_vol_params(
     no_take_liq: bool,
     monetary_value: f64,
 ) {
-    base_order.algo_strategy = "PctVolSz".to_string();
-
-    base_order.algo_params.push(TagValue::new(
-        "startPctVol".to_string(),
-        start_pct_vol.to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "endPctVol".to_string(),
-        end_pct_vol.to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "startTime".to_string(),
-        start_time.to_string(),
-    ));
-    base_order
-        .algo_params
-        .push(TagValue::new("endTime".to_string(), end_time.to_string()));
-    base_order.algo_params.push(TagValue::new(
-        "noTakeLiq".to_string(),
-        (no_take_liq as i32).to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "monetaryValue".to_string(),
-        monetary_value.to_string(),
-    ));
+    AlgoStrategy::PctVolSz {
+        start_pct_vol,
+        end_pct_vol,
+        start_time: start_time.to_string(),
+        end_time: end_time.to_string(),
+        no_take_liq,
+        monetary_value: Some(monetary_value),
+    }
+    .write_to(base_order);
 }
 
 //==================================================================================================
@@ -450,31 +1166,15 @@ pub fn fill_time_variant_pct_vol_params(
     no_take_liq: bool,
     monetary_value: f64,
 ) {
-    base_order.algo_strategy = "PctVolTm".to_string();
-
-    base_order.algo_params.push(TagValue::new(
-        "startPctVol".to_string(),
-        start_pct_vol.to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "endPctVol".to_string(),
-        end_pct_vol.to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "startTime".to_string(),
-        start_time.to_string(),
-    ));
-    base_order
-        .algo_params
-        .push(TagValue::new("endTime".to_string(), end_time.to_string()));
-    base_order.algo_params.push(TagValue::new(
-        "noTakeLiq".to_string(),
-        (no_take_liq as i32).to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "monetaryValue".to_string(),
-        monetary_value.to_string(),
-    ));
+    AlgoStrategy::PctVolTm {
+        start_pct_vol,
+        end_pct_vol,
+        start_time: start_time.to_string(),
+        end_time: end_time.to_string(),
+        no_take_liq,
+        monetary_value: Some(monetary_value),
+    }
+    .write_to(base_order);
 }
 
 //==================================================================================================
@@ -495,53 +1195,21 @@ pub fn fill_jefferies_vwapparams(
     wow_reference: &str,
 ) {
     // must be direct-routed to "JEFFALGO"
-    base_order.algo_strategy = "VWAP".to_string();
-
-    base_order.algo_params.push(TagValue::new(
-        "startTime".to_string(),
-        start_time.to_string(),
-    ));
-    base_order
-        .algo_params
-        .push(TagValue::new("endTime".to_string(), end_time.to_string()));
-    base_order.algo_params.push(TagValue::new(
-        "relativeLimit".to_string(),
-        relative_limit.to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "maxVolumeRate".to_string(),
-        max_volume_rate.to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "excludeAuctions".to_string(),
-        exclude_auctions.to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "triggerPrice".to_string(),
-        trigger_price.to_string(),
-    ));
-    base_order
-        .algo_params
-        .push(TagValue::new("wowPrice".to_string(), wow_price.to_string()));
-    base_order.algo_params.push(TagValue::new(
-        "minFillSize".to_string(),
-        min_fill_size.to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "wowOrderPct".to_string(),
-        wow_order_pct.to_string(),
-    ));
-    base_order
-        .algo_params
-        .push(TagValue::new("wowMode".to_string(), wow_mode.to_string()));
-    base_order.algo_params.push(TagValue::new(
-        "isBuyBack".to_string(),
-        (is_buy_back as i32).to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "wowReference".to_string(),
-        wow_reference.to_string(),
-    ));
+    AlgoStrategy::JefferiesVwap {
+        start_time: start_time.to_string(),
+        end_time: end_time.to_string(),
+        relative_limit,
+        max_volume_rate,
+        exclude_auctions: exclude_auctions.to_string(),
+        trigger_price,
+        wow_price,
+        min_fill_size,
+        wow_order_pct,
+        wow_mode: wow_mode.to_string(),
+        is_buy_back,
+        wow_reference: wow_reference.to_string(),
+    }
+    .write_to(base_order);
 }
 
 //==================================================================================================
@@ -562,54 +1230,21 @@ pub fn fill_csfbinline_params(
     i_would_price: f64,
 ) {
     // must be direct-routed to "CSFBALGO"
-    base_order.algo_strategy = "INLINE".to_string();
-
-    base_order.algo_params.push(TagValue::new(
-        "StartTime".to_string(),
-        start_time.to_string(),
-    ));
-    base_order
-        .algo_params
-        .push(TagValue::new("EndTime".to_string(), end_time.to_string()));
-    base_order.algo_params.push(TagValue::new(
-        "ExecStyle".to_string(),
-        exec_style.to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "MinPercent".to_string(),
-        min_percent.to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "MaxPercent".to_string(),
-        max_percent.to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "DisplaySize".to_string(),
-        display_size.to_string(),
-    ));
-    base_order
-        .algo_params
-        .push(TagValue::new("Auction".to_string(), auction.to_string()));
-    base_order.algo_params.push(TagValue::new(
-        "BlockFinder".to_string(),
-        (block_finder as i32).to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "BlockPrice".to_string(),
-        block_price.to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "MinBlockSize".to_string(),
-        min_block_size.to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "MaxBlockSize".to_string(),
-        max_block_size.to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "IWouldPrice".to_string(),
-        i_would_price.to_string(),
-    ));
+    AlgoStrategy::CsfbInline {
+        start_time: start_time.to_string(),
+        end_time: end_time.to_string(),
+        exec_style: exec_style.to_string(),
+        min_percent,
+        max_percent,
+        display_size,
+        auction: auction.to_string(),
+        block_finder,
+        block_price,
+        min_block_size,
+        max_block_size,
+        i_would_price,
+    }
+    .write_to(base_order);
 }
 
 //==================================================================================================
@@ -624,27 +1259,13 @@ pub fn fill_qbalgo_in_line_params(
     no_clean_up: bool,
 ) {
     // must be direct-routed to "QBALGO"
-    base_order.algo_strategy = "STROBE".to_string();
-
-    base_order.algo_params.push(TagValue::new(
-        "startTime".to_string(),
-        start_time.to_string(),
-    ));
-    base_order
-        .algo_params
-        .push(TagValue::new("endTime".to_string(), end_time.to_string()));
-    //This example uses end_time instead of duration
-    //base_order.algo_params.push(TagValue::new("Duration".to_string(), str(duration.to_string())
-    base_order.algo_params.push(TagValue::new(
-        "benchmark".to_string(),
-        benchmark.to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "percentVolume".to_string(),
-        percent_volume.to_string(),
-    ));
-    base_order.algo_params.push(TagValue::new(
-        "noCleanUp".to_string(),
-        (no_clean_up as i32).to_string(),
-    ));
+    // This example uses end_time instead of duration
+    AlgoStrategy::QbStrobe {
+        start_time: start_time.to_string(),
+        end_time: end_time.to_string(),
+        benchmark: benchmark.to_string(),
+        percent_volume,
+        no_clean_up,
+    }
+    .write_to(base_order);
 }
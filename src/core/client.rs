@@ -4,28 +4,36 @@ use std::io::Write;
 use std::net::Shutdown;
 use std::net::TcpStream;
 use std::ops::Deref;
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::mpsc::{channel, Receiver, Sender, TryRecvError};
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender, TryRecvError};
 use std::sync::{Arc, Mutex};
-use std::{fmt::Debug, thread};
+use std::{fmt::Debug, thread, time};
 
+use chrono::{DateTime, Utc};
 use from_ascii::FromAscii;
 use log::*;
 
 use num_derive::FromPrimitive;
 
 use super::streamer::{Streamer, TcpStreamer};
+use crate::core::account_summary_tags::AccountSummaryTags;
 use crate::core::common::*;
 //use crate::core::wrapper::Wrapper;
-use crate::core::contract::Contract;
+use crate::core::contract::{Contract, ContractDetails};
+use crate::core::datetime::{format_news_datetime, format_tws_datetime};
 use crate::core::decoder::Decoder;
 use crate::core::errors::{IBKRApiLibError, TwsApiReportableError, TwsError};
 use crate::core::execution::ExecutionFilter;
-use crate::core::messages::make_field;
-use crate::core::messages::{make_field_handle_empty, read_msg};
-use crate::core::messages::{make_message, read_fields, ServerReqMsgDiscriminants, ServerRspMsg};
+use crate::core::fa::{fa_to_xml, FaConfig};
+use crate::core::historical_data::HistoricalDataRequest;
+use crate::core::messages::{
+    make_message, read_fields, read_msg, FieldWriter, ServerReqMsgDiscriminants, ServerRspMsg,
+};
+use crate::core::news::HistoricalNewsPages;
 use crate::core::order::Order;
 use crate::core::order_condition::Condition;
+use crate::core::order_group::{GroupMember, OrderGroup, OrderGroupKind};
+use crate::core::order_validation::{required_features, validate_order_for_server, OrderViolation};
 use crate::core::reader::Reader;
 use crate::core::scanner::ScannerSubscription;
 use crate::core::server_versions::*;
@@ -52,12 +60,13 @@ pub struct EClient {
     port: u32,
     extra_auth: bool,
     client_id: i32,
-    evt_chan: (Sender<ServerRspMsg>, Receiver<ServerRspMsg>),
+    pub(crate) evt_chan: (Sender<ServerRspMsg>, Receiver<ServerRspMsg>),
     pub(crate) server_version: i32,
     conn_time: String,
     pub conn_state: Arc<Mutex<ConnStatus>>,
     opt_capab: String,
     disconnect_requested: Arc<AtomicBool>,
+    next_order_id: AtomicI32,
 }
 
 impl EClient {
@@ -74,10 +83,11 @@ impl EClient {
             conn_state: Arc::new(Mutex::new(ConnStatus::DISCONNECTED)),
             opt_capab: "".to_string(),
             disconnect_requested: Arc::new(AtomicBool::new(false)),
+            next_order_id: AtomicI32::new(NO_VALID_ID),
         }
     }
-    fn send_request(&mut self, request: &str) -> Result<(), IBKRApiLibError> {
-        let bytes = make_message(request)?;
+    fn send_request(&mut self, request: FieldWriter) -> Result<(), IBKRApiLibError> {
+        let bytes = request.finish()?;
         self.send_bytes(bytes.as_slice())?;
         Ok(())
     }
@@ -184,7 +194,7 @@ impl EClient {
 
     pub fn get_event(&self) -> Result<Option<ServerRspMsg>, IBKRApiLibError> {
         match self.evt_chan.1.try_recv() {
-            Ok(i) => Ok(Some(i)),
+            Ok(i) => Ok(Some(self.observe(i))),
             Err(TryRecvError::Empty) => Ok(None),
             Err(TryRecvError::Disconnected) => {
                 Err(IBKRApiLibError::TryRecvError(TryRecvError::Disconnected))
@@ -192,6 +202,30 @@ impl EClient {
         }
     }
 
+    //----------------------------------------------------------------------------------------------
+    /// Waits up to `timeout` for the next event, returning `None` if none arrived
+    pub fn next_event(
+        &self,
+        timeout: time::Duration,
+    ) -> Result<Option<ServerRspMsg>, IBKRApiLibError> {
+        match self.evt_chan.1.recv_timeout(timeout) {
+            Ok(i) => Ok(Some(self.observe(i))),
+            Err(RecvTimeoutError::Timeout) => Ok(None),
+            Err(RecvTimeoutError::Disconnected) => Err(IBKRApiLibError::RecvTimeoutError(
+                RecvTimeoutError::Disconnected,
+            )),
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Keeps the next order id up to date with an event read by `get_event` or `next_event`
+    fn observe(&self, msg: ServerRspMsg) -> ServerRspMsg {
+        if let ServerRspMsg::NextValidId { order_id } = msg {
+            self.next_order_id.fetch_max(order_id, Ordering::AcqRel);
+        }
+        msg
+    }
+
     /// Checks connection status
     pub fn is_connected(&self) -> bool {
         let connected = match *self.conn_state.lock().unwrap().deref() {
@@ -211,6 +245,46 @@ impl EClient {
         self.server_version
     }
 
+    //----------------------------------------------------------------------------------------------
+    /// Checks whether the connected server supports the feature
+    pub fn supports(&self, feature: ServerFeature) -> bool {
+        feature.is_supported_by(self.server_version)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Report of the features supported by the connected server. Call after `connect` so that the
+    /// server version from the handshake is known.
+    pub fn capabilities(&self) -> ServerCapabilities {
+        ServerCapabilities::new(self.server_version)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Next order id this client will hand out, as last reported by a `NextValidId` event read
+    /// through `get_event` or set with `set_next_order_id`
+    pub fn next_order_id(&self) -> Option<i32> {
+        match self.next_order_id.load(Ordering::Acquire) {
+            NO_VALID_ID => None,
+            order_id => Some(order_id),
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Sets the next order id used by `place_bracket` and `place_oca_group`
+    pub fn set_next_order_id(&self, order_id: i32) {
+        self.next_order_id.store(order_id, Ordering::Release);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Reserves `count` consecutive order ids and returns the first one
+    fn allocate_order_ids(&self, count: i32) -> Result<i32, IBKRApiLibError> {
+        let first = self.next_order_id.fetch_add(count, Ordering::AcqRel);
+        if first == NO_VALID_ID {
+            self.next_order_id.store(NO_VALID_ID, Ordering::Release);
+            return Err(IBKRApiLibError::NoValidOrderId);
+        }
+        Ok(first)
+    }
+
     //----------------------------------------------------------------------------------------------
     /// Sets server logging level
     pub fn set_server_log_level(&mut self, log_evel: i32) -> Result<(), IBKRApiLibError> {
@@ -224,15 +298,15 @@ impl EClient {
         let version = 1;
         let _log_level = log_evel;
 
-        let mut msg = "".to_string();
+        let mut msg = FieldWriter::new();
 
         let message_id = ServerReqMsgDiscriminants::SetServerLoglevel as i32;
         let _x = message_id.to_be_bytes();
-        msg.push_str(&make_field(&message_id)?);
-        msg.push_str(&make_field(&version)?);
-        msg.push_str(&make_field(&_log_level)?);
+        msg.push(&message_id);
+        msg.push(&version);
+        msg.push(&_log_level);
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -250,12 +324,12 @@ impl EClient {
         let version = 2;
 
         let message_id: i32 = ServerReqMsgDiscriminants::ReqCurrentTime as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
-        msg.push_str(&make_field(&version)?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
+        msg.push(&version);
 
         debug!("Requesting current time: {}", msg.as_str());
-        self.send_request(msg.as_str())
+        self.send_request(msg)
     }
 
     //----------------------------------------------------------------------------------------------
@@ -278,20 +352,17 @@ impl EClient {
         self.check_connected(NO_VALID_ID)?;
 
         let version = 2;
-        let mut opt_capab = "".to_string();
+        let message_id = ServerReqMsgDiscriminants::StartApi as i32;
+
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
+        msg.push(&version);
+        msg.push(&self.client_id);
         if self.server_version >= MIN_SERVER_VER_OPTIONAL_CAPABILITIES as i32 {
-            opt_capab = make_field(&self.opt_capab)?;
+            msg.push(&self.opt_capab);
         }
 
-        let msg = format!(
-            "{}{}{}{}",
-            make_field(&mut (Some(ServerReqMsgDiscriminants::StartApi).unwrap() as i32))?,
-            make_field(&mut version.to_string())?,
-            make_field(&mut self.client_id.to_string())?,
-            opt_capab
-        );
-
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -329,109 +400,78 @@ impl EClient {
     ) -> Result<(), IBKRApiLibError> {
         self.check_connected(req_id)?;
 
-        if self.server_version() < MIN_SERVER_VER_DELTA_NEUTRAL {
-            if let Some(_value) = &contract.delta_neutral_contract {
-                let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                    req_id,
-                    TwsError::UpdateTws.code().to_string(),
-                    format!(
-                        "{}{}",
-                        TwsError::UpdateTws.message(),
-                        " It does not support delta-neutral orders."
-                    ),
-                ));
+        contract.validate()?;
 
-                return Err(err);
-            }
+        if contract.delta_neutral_contract.is_some() {
+            self.check_feature(ServerFeature::DeltaNeutral)?;
         }
 
-        if self.server_version() < MIN_SERVER_VER_REQ_MKT_DATA_CONID && contract.con_id > 0 {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                req_id,
-                TwsError::NotConnected.code().to_string(),
-                TwsError::NotConnected.message().to_string(),
-            ));
-            return Err(err);
+        if contract.con_id > 0 {
+            self.check_feature(ServerFeature::ReqMktDataConid)?;
         }
 
-        if self.server_version() < MIN_SERVER_VER_TRADING_CLASS && "" != contract.trading_class {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                req_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support trading_class parameter in req_mkt_data."
-                ),
-            ));
-
-            return Err(err);
+        if "" != contract.trading_class {
+            self.check_feature(ServerFeature::TradingClass)?;
         }
 
         let version = 11;
 
         let message_id: i32 = ServerReqMsgDiscriminants::ReqMktData as i32;
 
-        let mut msg = "".to_string();
+        let mut msg = FieldWriter::new();
 
         // send req mkt data msg
-        msg.push_str(&make_field(&message_id)?);
-        msg.push_str(&make_field(&version)?);
-        msg.push_str(&make_field(&req_id)?);
+        msg.push(&message_id);
+        msg.push(&version);
+        msg.push(&req_id);
 
         // send contract fields
         if self.server_version() >= MIN_SERVER_VER_REQ_MKT_DATA_CONID {
-            msg.push_str(&make_field(&contract.con_id)?);
-            msg.push_str(&make_field(&contract.symbol)?);
-
-            msg.push_str(&make_field(&contract.sec_type)?);
-            msg.push_str(&make_field(&contract.last_trade_date_or_contract_month)?);
-            msg.push_str(&make_field(&contract.strike)?);
-            msg.push_str(&make_field(&contract.right)?);
-            msg.push_str(&make_field(&contract.multiplier)?); // srv v15 and above
-            msg.push_str(&make_field(&contract.exchange)?);
-            msg.push_str(&make_field(&contract.primary_exchange)?); // srv v14 and above
-            msg.push_str(&make_field(&contract.currency)?);
-            msg.push_str(&make_field(&contract.local_symbol)?); //  srv v2 and above
+            msg.push(&contract.con_id);
+            msg.push(&contract.symbol);
+
+            msg.push(&contract.sec_type);
+            msg.push(&contract.last_trade_date_or_contract_month);
+            msg.push(&contract.strike);
+            msg.push(&contract.right);
+            msg.push(&contract.multiplier); // srv v15 and above
+            msg.push(&contract.exchange);
+            msg.push(&contract.primary_exchange); // srv v14 and above
+            msg.push(&contract.currency);
+            msg.push(&contract.local_symbol); //  srv v2 and above
         }
 
         if self.server_version() >= MIN_SERVER_VER_TRADING_CLASS {
-            msg.push_str(&make_field(&contract.trading_class)?);
+            msg.push(&contract.trading_class);
         }
         // Send combo legs for BAG requests(srv v8 and above)
         if contract.sec_type == "BAG" {
             let combo_legs_count = contract.combo_legs.len();
-            msg.push_str(&make_field(&combo_legs_count)?);
+            msg.push(&combo_legs_count);
             for combo_leg in &contract.combo_legs {
-                msg.push_str(&make_field(&combo_leg.con_id)?);
-                msg.push_str(&make_field(&combo_leg.ratio)?);
-                msg.push_str(&make_field(&combo_leg.action)?);
-                msg.push_str(&make_field(&combo_leg.exchange)?);
+                msg.push(&combo_leg.con_id);
+                msg.push(&combo_leg.ratio);
+                msg.push(&combo_leg.action);
+                msg.push(&combo_leg.exchange);
             }
         }
 
         if self.server_version() >= MIN_SERVER_VER_DELTA_NEUTRAL {
             if contract.delta_neutral_contract.is_some() {
-                msg.push_str(&make_field(&true)?);
-                msg.push_str(&make_field(
-                    &contract.delta_neutral_contract.as_ref().unwrap().con_id,
-                )?);
-                msg.push_str(&make_field(
-                    &contract.delta_neutral_contract.as_ref().unwrap().delta,
-                )?);
-                msg.push_str(&make_field(
-                    &contract.delta_neutral_contract.as_ref().unwrap().price,
-                )?);
+                msg.push(&true);
+                msg.push(&contract.delta_neutral_contract.as_ref().unwrap().con_id);
+                msg.push(&contract.delta_neutral_contract.as_ref().unwrap().delta);
+                msg.push(&contract.delta_neutral_contract.as_ref().unwrap().price);
             } else {
-                msg.push_str(&make_field(&false)?);
+                msg.push(&false);
             }
 
-            msg.push_str(&make_field(&String::from(generic_tick_list))?); // srv v31 and above
-            msg.push_str(&make_field(&snapshot)?); // srv v35 and above
+            msg.push(&String::from(generic_tick_list)); // srv v31 and above
+            msg.push(&snapshot); // srv v35 and above
         }
 
         if self.server_version() >= MIN_SERVER_VER_REQ_SMART_COMPONENTS {
-            msg.push_str(&make_field(&regulatory_snapshot)?);
+            msg.push(&regulatory_snapshot);
         }
 
         // send mktDataOptions parameter
@@ -451,10 +491,10 @@ impl EClient {
                 return Err(err);
             }
             let mkt_data_options_str = "";
-            msg.push_str(&make_field(&mkt_data_options_str)?);
+            msg.push(&mkt_data_options_str);
         }
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -469,12 +509,12 @@ impl EClient {
         let version = 2;
 
         let message_id: i32 = ServerReqMsgDiscriminants::CancelMktData as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
-        msg.push_str(&make_field(&version)?);
-        msg.push_str(&make_field(&req_id)?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
+        msg.push(&version);
+        msg.push(&req_id);
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -494,29 +534,17 @@ impl EClient {
     pub fn req_market_data_type(&mut self, market_data_type: i32) -> Result<(), IBKRApiLibError> {
         self.check_connected(NO_VALID_ID)?;
 
-        if self.server_version() < MIN_SERVER_VER_REQ_MARKET_DATA_TYPE {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                NO_VALID_ID,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support market data type requests."
-                ),
-            ));
+        self.check_feature(ServerFeature::ReqMarketDataType)?;
 
-            return Err(err);
-        }
-
-        let mut msg = "".to_string();
+        let mut msg = FieldWriter::new();
         let version = 1;
         let message_id = ServerReqMsgDiscriminants::ReqMarketDataType as i32;
 
-        msg.push_str(&make_field(&message_id)?);
-        msg.push_str(&make_field(&version)?);
-        msg.push_str(&make_field(&market_data_type)?);
+        msg.push(&message_id);
+        msg.push(&version);
+        msg.push(&market_data_type);
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -534,29 +562,17 @@ impl EClient {
     ) -> Result<(), IBKRApiLibError> {
         self.check_connected(req_id)?;
 
-        if self.server_version() < MIN_SERVER_VER_REQ_SMART_COMPONENTS {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                req_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support smart components request."
-                ),
-            ));
-
-            return Err(err);
-        }
+        self.check_feature(ServerFeature::ReqSmartComponents)?;
 
-        let mut msg = "".to_string();
+        let mut msg = FieldWriter::new();
 
         let message_id = ServerReqMsgDiscriminants::ReqSmartComponents as i32;
 
-        msg.push_str(&make_field(&message_id)?);
-        msg.push_str(&make_field(&req_id)?);
-        msg.push_str(&make_field(&String::from(bbo_exchange))?);
+        msg.push(&message_id);
+        msg.push(&req_id);
+        msg.push(&String::from(bbo_exchange));
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -572,28 +588,16 @@ impl EClient {
     pub fn req_market_rule(&mut self, market_rule_id: i32) -> Result<(), IBKRApiLibError> {
         self.check_connected(NO_VALID_ID)?;
 
-        if self.server_version() < MIN_SERVER_VER_MARKET_RULES {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                NO_VALID_ID,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support market rule requests."
-                ),
-            ));
+        self.check_feature(ServerFeature::MarketRules)?;
 
-            return Err(err);
-        }
-
-        let mut msg = "".to_string();
+        let mut msg = FieldWriter::new();
 
         let message_id = ServerReqMsgDiscriminants::ReqMarketRule as i32;
 
-        msg.push_str(&make_field(&message_id)?);
-        msg.push_str(&make_field(&market_rule_id)?);
+        msg.push(&message_id);
+        msg.push(&market_rule_id);
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -616,62 +620,40 @@ impl EClient {
     ) -> Result<(), IBKRApiLibError> {
         self.check_connected(NO_VALID_ID)?;
 
-        if self.server_version() < MIN_SERVER_VER_TICK_BY_TICK {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                req_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support tick-by-tick data requests."
-                ),
-            ));
-
-            return Err(err);
-        }
+        contract.validate()?;
 
-        if self.server_version() < MIN_SERVER_VER_TICK_BY_TICK_IGNORE_SIZE {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                req_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support ignore_size and number_of_ticks parameters in tick-by-tick data requests."
-                ),
-            ));
+        self.check_feature(ServerFeature::TickByTick)?;
 
-            return Err(err);
-        }
+        self.check_feature(ServerFeature::TickByTickIgnoreSize)?;
 
-        let mut msg = "".to_string();
+        let mut msg = FieldWriter::new();
 
         let message_id = ServerReqMsgDiscriminants::ReqTickByTickData as i32;
 
-        msg.push_str(&make_field(&message_id)?);
+        msg.push(&message_id);
 
         //    msg.push_str(&make_field(&OUT.REQ_TICK_BY_TICK_DATA)\
-        msg.push_str(&make_field(&req_id)?);
-        msg.push_str(&make_field(&contract.con_id)?);
-        msg.push_str(&make_field(&contract.symbol)?);
-        msg.push_str(&make_field(&contract.sec_type)?);
-        msg.push_str(&make_field(&contract.last_trade_date_or_contract_month)?);
-        msg.push_str(&make_field(&contract.strike)?);
-        msg.push_str(&make_field(&contract.right)?);
-        msg.push_str(&make_field(&contract.multiplier)?);
-        msg.push_str(&make_field(&contract.exchange)?);
-        msg.push_str(&make_field(&contract.primary_exchange)?);
-        msg.push_str(&make_field(&contract.currency)?);
-        msg.push_str(&make_field(&contract.local_symbol)?);
-        msg.push_str(&make_field(&contract.trading_class)?);
-        msg.push_str(&make_field(&(tick_type.to_string()))?);
+        msg.push(&req_id);
+        msg.push(&contract.con_id);
+        msg.push(&contract.symbol);
+        msg.push(&contract.sec_type);
+        msg.push(&contract.last_trade_date_or_contract_month);
+        msg.push(&contract.strike);
+        msg.push(&contract.right);
+        msg.push(&contract.multiplier);
+        msg.push(&contract.exchange);
+        msg.push(&contract.primary_exchange);
+        msg.push(&contract.currency);
+        msg.push(&contract.local_symbol);
+        msg.push(&contract.trading_class);
+        msg.push(&(tick_type.to_string()));
 
         if self.server_version() >= MIN_SERVER_VER_TICK_BY_TICK_IGNORE_SIZE {
-            msg.push_str(&make_field(&number_of_ticks)?);
-            msg.push_str(&make_field(&ignore_size)?);
+            msg.push(&number_of_ticks);
+            msg.push(&ignore_size);
         }
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -683,28 +665,16 @@ impl EClient {
     pub fn cancel_tick_by_tick_data(&mut self, req_id: i32) -> Result<(), IBKRApiLibError> {
         self.check_connected(req_id)?;
 
-        if self.server_version() < MIN_SERVER_VER_TICK_BY_TICK {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                req_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support tick-by-tick data requests."
-                ),
-            ));
+        self.check_feature(ServerFeature::TickByTick)?;
 
-            return Err(err);
-        }
-
-        let mut msg = "".to_string();
+        let mut msg = FieldWriter::new();
 
         let message_id = ServerReqMsgDiscriminants::CancelTickByTickData as i32;
 
-        msg.push_str(&make_field(&message_id)?);
-        msg.push_str(&make_field(&req_id)?);
+        msg.push(&message_id);
+        msg.push(&req_id);
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -732,64 +702,44 @@ impl EClient {
     ) -> Result<(), IBKRApiLibError> {
         self.check_connected(req_id)?;
 
-        if self.server_version() < MIN_SERVER_VER_REQ_CALC_IMPLIED_VOLAT {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                req_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support calculate_implied_volatility req."
-                ),
-            ));
+        contract.validate()?;
 
-            return Err(err);
-        }
-
-        if self.server_version() < MIN_SERVER_VER_TRADING_CLASS && "" != contract.trading_class {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                req_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support trading_class parameter in calculate_implied_volatility."
-                ),
-            ));
+        self.check_feature(ServerFeature::ReqCalcImpliedVolat)?;
 
-            return Err(err);
+        if "" != contract.trading_class {
+            self.check_feature(ServerFeature::TradingClass)?;
         }
 
         let version = 3;
 
-        let mut msg = "".to_string();
+        let mut msg = FieldWriter::new();
 
         let message_id = ServerReqMsgDiscriminants::ReqCalcImpliedVolat as i32;
 
-        msg.push_str(&make_field(&message_id)?);
+        msg.push(&message_id);
 
-        msg.push_str(&make_field(&version)?);
-        msg.push_str(&make_field(&req_id)?);
+        msg.push(&version);
+        msg.push(&req_id);
 
         // send contract fields
-        msg.push_str(&make_field(&contract.con_id)?);
-        msg.push_str(&make_field(&contract.symbol)?);
-        msg.push_str(&make_field(&contract.sec_type)?);
-        msg.push_str(&make_field(&contract.last_trade_date_or_contract_month)?);
-        msg.push_str(&make_field(&contract.strike)?);
-        msg.push_str(&make_field(&contract.right)?);
-        msg.push_str(&make_field(&contract.multiplier)?);
-        msg.push_str(&make_field(&contract.exchange)?);
-        msg.push_str(&make_field(&contract.primary_exchange)?);
-        msg.push_str(&make_field(&contract.currency)?);
-        msg.push_str(&make_field(&contract.local_symbol)?);
+        msg.push(&contract.con_id);
+        msg.push(&contract.symbol);
+        msg.push(&contract.sec_type);
+        msg.push(&contract.last_trade_date_or_contract_month);
+        msg.push(&contract.strike);
+        msg.push(&contract.right);
+        msg.push(&contract.multiplier);
+        msg.push(&contract.exchange);
+        msg.push(&contract.primary_exchange);
+        msg.push(&contract.currency);
+        msg.push(&contract.local_symbol);
 
         if self.server_version() >= MIN_SERVER_VER_TRADING_CLASS {
-            msg.push_str(&make_field(&contract.trading_class)?);
+            msg.push(&contract.trading_class);
         }
 
-        msg.push_str(&make_field(&option_price)?);
-        msg.push_str(&make_field(&under_price)?);
+        msg.push(&option_price);
+        msg.push(&under_price);
 
         if self.server_version() >= MIN_SERVER_VER_LINKING {
             let mut impl_vol_opt_str = "".to_string();
@@ -800,12 +750,12 @@ impl EClient {
                     .map(|x| format!("{}={};", x.tag, x.value))
                     .collect::<String>();
             }
-            msg.push_str(&make_field(&tag_values_count)?);
-            msg.push_str(&make_field(&impl_vol_opt_str)?);
+            msg.push(&tag_values_count);
+            msg.push(&impl_vol_opt_str);
         }
         error!("sending calculate_implied_volatility");
-        error!("{}", msg);
-        self.send_request(msg.as_str())?;
+        error!("{}", msg.as_str());
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -827,65 +777,43 @@ impl EClient {
     ) -> Result<(), IBKRApiLibError> {
         self.check_connected(req_id)?;
 
-        if self.server_version() < MIN_SERVER_VER_REQ_CALC_IMPLIED_VOLAT {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                req_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support calculateImpliedVolatility req."
-                ),
-            ));
-
-            return Err(err);
-        }
+        contract.validate()?;
 
-        if self.server_version() < MIN_SERVER_VER_TRADING_CLASS {
-            if "" != contract.trading_class {
-                let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                    req_id,
-                    TwsError::UpdateTws.code().to_string(),
-                    format!(
-                        "{}{}",
-                        TwsError::UpdateTws.message(),
-                        " It does not support trading_class parameter in calculateImpliedVolatility."
-                    ),
-                ));
+        self.check_feature(ServerFeature::ReqCalcImpliedVolat)?;
 
-                return Err(err);
-            }
+        if "" != contract.trading_class {
+            self.check_feature(ServerFeature::TradingClass)?;
         }
 
         let version = 3;
 
         // send req mkt data msg
-        let mut msg = "".to_string();
+        let mut msg = FieldWriter::new();
 
         let message_id = ServerReqMsgDiscriminants::ReqCalcOptionPrice as i32;
 
-        msg.push_str(&make_field(&message_id)?);
-        msg.push_str(&make_field(&version)?);
-        msg.push_str(&make_field(&req_id)?);
+        msg.push(&message_id);
+        msg.push(&version);
+        msg.push(&req_id);
         // send contract fields
-        msg.push_str(&make_field(&contract.con_id)?);
-        msg.push_str(&make_field(&contract.symbol)?);
-        msg.push_str(&make_field(&contract.sec_type)?);
-        msg.push_str(&make_field(&contract.last_trade_date_or_contract_month)?);
-        msg.push_str(&make_field(&contract.strike)?);
-        msg.push_str(&make_field(&contract.right)?);
-        msg.push_str(&make_field(&contract.multiplier)?);
-        msg.push_str(&make_field(&contract.exchange)?);
-        msg.push_str(&make_field(&contract.primary_exchange)?);
-        msg.push_str(&make_field(&contract.currency)?);
-        msg.push_str(&make_field(&contract.local_symbol)?);
+        msg.push(&contract.con_id);
+        msg.push(&contract.symbol);
+        msg.push(&contract.sec_type);
+        msg.push(&contract.last_trade_date_or_contract_month);
+        msg.push(&contract.strike);
+        msg.push(&contract.right);
+        msg.push(&contract.multiplier);
+        msg.push(&contract.exchange);
+        msg.push(&contract.primary_exchange);
+        msg.push(&contract.currency);
+        msg.push(&contract.local_symbol);
 
         if self.server_version() >= MIN_SERVER_VER_TRADING_CLASS {
-            msg.push_str(&make_field(&contract.trading_class)?);
+            msg.push(&contract.trading_class);
         }
 
-        msg.push_str(&make_field(&volatility)?);
-        msg.push_str(&make_field(&under_price)?);
+        msg.push(&volatility);
+        msg.push(&under_price);
 
         if self.server_version() >= MIN_SERVER_VER_LINKING {
             let _opt_prc_opt_str = "".to_string();
@@ -896,11 +824,11 @@ impl EClient {
                     .map(|x| format!("{}={};", x.tag, x.value))
                     .collect::<String>();
 
-                msg.push_str(&make_field(&tag_values_count)?);
-                msg.push_str(&make_field(&opt_prc_opt_str)?);
+                msg.push(&tag_values_count);
+                msg.push(&opt_prc_opt_str);
             }
         }
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -913,31 +841,19 @@ impl EClient {
     pub fn cancel_calculate_option_price(&mut self, req_id: i32) -> Result<(), IBKRApiLibError> {
         self.check_connected(req_id)?;
 
-        if self.server_version() < MIN_SERVER_VER_REQ_CALC_IMPLIED_VOLAT {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                req_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support calculateImpliedVolatility req."
-                ),
-            ));
-
-            return Err(err);
-        }
+        self.check_feature(ServerFeature::ReqCalcImpliedVolat)?;
 
         let version = 1;
 
-        let mut msg = "".to_string();
+        let mut msg = FieldWriter::new();
 
         let message_id = ServerReqMsgDiscriminants::CancelCalcOptionPrice as i32;
 
-        msg.push_str(&make_field(&message_id)?);
-        msg.push_str(&make_field(&version)?);
-        msg.push_str(&make_field(&req_id)?);
+        msg.push(&message_id);
+        msg.push(&version);
+        msg.push(&req_id);
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -952,31 +868,19 @@ impl EClient {
     ) -> Result<(), IBKRApiLibError> {
         self.check_connected(req_id)?;
 
-        if self.server_version() < MIN_SERVER_VER_REQ_CALC_IMPLIED_VOLAT {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                req_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support calculateImpliedVolatility req."
-                ),
-            ));
-
-            return Err(err);
-        }
+        self.check_feature(ServerFeature::ReqCalcImpliedVolat)?;
 
         let version = 1;
 
-        let mut msg = "".to_string();
+        let mut msg = FieldWriter::new();
 
         let message_id = ServerReqMsgDiscriminants::CancelCalcImpliedVolat as i32;
 
-        msg.push_str(&make_field(&message_id)?);
-        msg.push_str(&make_field(&version)?);
-        msg.push_str(&make_field(&req_id)?);
+        msg.push(&message_id);
+        msg.push(&version);
+        msg.push(&req_id);
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -1010,527 +914,77 @@ impl EClient {
     ) -> Result<(), IBKRApiLibError> {
         self.check_connected(req_id)?;
 
-        if self.server_version() < MIN_SERVER_VER_TRADING_CLASS {
-            if !contract.trading_class.is_empty() {
-                let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                    req_id,
-                    TwsError::UpdateTws.code().to_string(),
-                    format!(
-                        "{}{}",
-                        TwsError::UpdateTws.message(),
-                        " It does not support con_id, multiplier, trading_class parameter in exercise_options."
-                    ),
-                ));
-
-                return Err(err);
-            }
-        }
-
-        let version = 2;
-
-        // send req mkt data msg
-        let mut msg = "".to_string();
-
-        let message_id = ServerReqMsgDiscriminants::ExerciseOptions as i32;
-
-        msg.push_str(&make_field(&message_id)?);
-
-        msg.push_str(&make_field(&version)?);
-        msg.push_str(&make_field(&req_id)?);
-
-        // send contract fields
-        if self.server_version() >= MIN_SERVER_VER_TRADING_CLASS {
-            msg.push_str(&make_field(&contract.con_id)?);
-        }
-        msg.push_str(&make_field(&contract.symbol)?);
-        msg.push_str(&make_field(&contract.sec_type)?);
-        msg.push_str(&make_field(&contract.last_trade_date_or_contract_month)?);
-        msg.push_str(&make_field(&contract.strike)?);
-        msg.push_str(&make_field(&contract.right)?);
-        msg.push_str(&make_field(&contract.multiplier)?);
-        msg.push_str(&make_field(&contract.exchange)?);
-        msg.push_str(&make_field(&contract.currency)?);
-        msg.push_str(&make_field(&contract.local_symbol)?);
-        if self.server_version() >= MIN_SERVER_VER_TRADING_CLASS {
-            msg.push_str(&make_field(&contract.trading_class)?);
-        }
-        msg.push_str(&make_field(&exercise_action)?);
-        msg.push_str(&make_field(&exercise_quantity)?);
-        msg.push_str(&make_field(account)?);
-        msg.push_str(&make_field(&over_ride)?);
-
-        self.send_request(msg.as_str())?;
-        Ok(())
-    }
-
-    //#########################################################################
-    //################## Orders
-    //########################################################################
-
-    /// Call this function to place an order. The order status will
-    /// be returned by the Wrapper::order_status event.
-    ///
-    /// # Arguments
-    /// * order_id - The order id. You must specify a unique value. When the
-    ///              order status returns, it will be identified by this tag.
-    ///              This tag is also used when canceling the order.
-    /// * contract - This structure contains a description of the
-    ///              contract which is being traded.
-    /// * order - This structure contains the details of the order.
-    ///
-    /// Note: Each client MUST connect with a unique client_id.
-    pub fn place_order(
-        &mut self,
-        order_id: i32,
-        contract: &Contract,
-        order: &Order,
-    ) -> Result<(), IBKRApiLibError> {
-        self.check_connected(NO_VALID_ID)?;
-
-        if self.server_version() < MIN_SERVER_VER_DELTA_NEUTRAL {
-            if contract.delta_neutral_contract.is_some() {
-                let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                    NO_VALID_ID,
-                    TwsError::UpdateTws.code().to_string(),
-                    format!(
-                        "{}{}",
-                        TwsError::UpdateTws.message(),
-                        " It does not support delta-neutral orders."
-                    ),
-                ));
-
-                return Err(err);
-            }
-        }
-
-        if self.server_version() < MIN_SERVER_VER_SCALE_ORDERS2
-            && order.scale_subs_level_size != UNSET_INTEGER
-        {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                order_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support Subsequent Level Size for Scale orders."
-                ),
-            ));
-
-            return Err(err);
-        }
-
-        if self.server_version() < MIN_SERVER_VER_ALGO_ORDERS && !order.algo_strategy.is_empty() {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                order_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support algo orders."
-                ),
-            ));
-
-            return Err(err);
-        }
-
-        if self.server_version() < MIN_SERVER_VER_NOT_HELD && order.not_held {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                order_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support notHeld parameter."
-                ),
-            ));
-
-            return Err(err);
-        }
-
-        if self.server_version() < MIN_SERVER_VER_SEC_ID_TYPE
-            && (!contract.sec_id_type.is_empty() || !contract.sec_id.is_empty())
-        {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                order_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support sec_id_type && secId parameters."
-                ),
-            ));
-
-            return Err(err);
-        }
-
-        if self.server_version() < MIN_SERVER_VER_PLACE_ORDER_CONID && contract.con_id > 0 {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                order_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support con_id parameter."
-                ),
-            ));
-
-            return Err(err);
-        }
-
-        if self.server_version() < MIN_SERVER_VER_SSHORTX {
-            if order.exempt_code != -1 {
-                let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                    order_id,
-                    TwsError::UpdateTws.code().to_string(),
-                    format!(
-                        "{}{}",
-                        TwsError::UpdateTws.message(),
-                        " It does not support exempt_code parameter."
-                    ),
-                ));
-
-                return Err(err);
-            }
-            if contract.combo_legs.len() > 0
-                && contract.combo_legs.iter().any(|x| x.exempt_code != -1)
-            {
-                let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                    order_id,
-                    TwsError::UpdateTws.code().to_string(),
-                    format!(
-                        "{}{}",
-                        TwsError::UpdateTws.message(),
-                        " It does not support exempt_code parameter."
-                    ),
-                ));
-
-                return Err(err);
-            }
-        }
-        if self.server_version() < MIN_SERVER_VER_HEDGE_ORDERS && !order.hedge_type.is_empty() {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                order_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support hedge orders."
-                ),
-            ));
-
-            return Err(err);
-        }
-
-        if self.server_version() < MIN_SERVER_VER_OPT_OUT_SMART_ROUTING
-            && order.opt_out_smart_routing
-        {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                order_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support optOutSmartRouting parameter."
-                ),
-            ));
-
-            return Err(err);
-        }
-
-        if self.server_version() < MIN_SERVER_VER_DELTA_NEUTRAL_CONID
-            && (order.delta_neutral_con_id > 0
-                || !order.delta_neutral_settling_firm.is_empty()
-                || !order.delta_neutral_clearing_account.is_empty()
-                || !order.delta_neutral_clearing_intent.is_empty())
-        {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                order_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support deltaNeutral parameters: con_id, SettlingFirm, ClearingAccount, ClearingIntent."
-                ),
-            ));
-
-            return Err(err);
-        }
-
-        if self.server_version() < MIN_SERVER_VER_DELTA_NEUTRAL_OPEN_CLOSE
-            && (!order.delta_neutral_open_close.is_empty()
-                || order.delta_neutral_short_sale
-                || order.delta_neutral_short_sale_slot > 0
-                || !order.delta_neutral_designated_location.is_empty())
-        {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                order_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support deltaNeutral parameters: open_close, ShortSale, short_sale_slot, designated_location."
-                ),
-            ));
-
-            return Err(err);
-        }
-
-        if self.server_version() < MIN_SERVER_VER_SCALE_ORDERS3
-            && order.scale_price_increment > 0 as f64
-            && order.scale_price_increment != UNSET_DOUBLE
-            && (order.scale_price_adjust_value != UNSET_DOUBLE
-                || order.scale_price_adjust_interval != UNSET_INTEGER
-                || order.scale_profit_offset != UNSET_DOUBLE
-                || order.scale_auto_reset
-                || order.scale_init_position != UNSET_INTEGER
-                || order.scale_init_fill_qty != UNSET_INTEGER
-                || order.scale_random_percent)
-        {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                order_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support Scale order parameters: PriceAdjustValue, \
-                PriceAdjustInterval, ProfitOffset, AutoReset, InitPosition, InitFillQty && RandomPercent"
-                ),
-            ));
-
-            return Err(err);
-        }
-
-        if self.server_version() < MIN_SERVER_VER_ORDER_COMBO_LEGS_PRICE
-            && contract.sec_type == "BAG"
-            && order.order_combo_legs.len() > 0
-            && order
-                .order_combo_legs
-                .iter()
-                .any(|x| x.price != UNSET_DOUBLE)
-        {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                order_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support per-leg prices for order combo legs."
-                ),
-            ));
-
-            return Err(err);
-        }
-
-        if self.server_version() < MIN_SERVER_VER_TRAILING_PERCENT
-            && order.trailing_percent != UNSET_DOUBLE
-        {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                order_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support trailing percent parameter."
-                ),
-            ));
-
-            return Err(err);
-        }
-
-        if self.server_version() < MIN_SERVER_VER_TRADING_CLASS
-            && !contract.trading_class.is_empty()
-        {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                order_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support trading_class parameter in placeOrder."
-                ),
-            ));
-
-            return Err(err);
-        }
-
-        if self.server_version() < MIN_SERVER_VER_SCALE_TABLE
-            && (!order.scale_table.is_empty()
-                || !order.active_start_time.is_empty()
-                || !order.active_stop_time.is_empty())
-        {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                order_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support scaleTable, activeStartTime && activeStopTime parameters."
-                ),
-            ));
-
-            return Err(err);
-        }
-
-        if self.server_version() < MIN_SERVER_VER_ALGO_ID && order.algo_id != "" {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                order_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support algoId parameter."
-                ),
-            ));
-
-            return Err(err);
-        }
+        contract.validate()?;
 
-        if self.server_version() < MIN_SERVER_VER_ORDER_SOLICITED && order.solicited {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                order_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support order solicited parameter."
-                ),
-            ));
-
-            return Err(err);
-        }
-
-        if self.server_version() < MIN_SERVER_VER_MODELS_SUPPORT && !order.model_code.is_empty() {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                order_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support model code parameter."
-                ),
-            ));
-
-            return Err(err);
-        }
-
-        if self.server_version() < MIN_SERVER_VER_EXT_OPERATOR && !order.ext_operator.is_empty() {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                order_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support ext operator parameter."
-                ),
-            ));
-
-            return Err(err);
-        }
-
-        if self.server_version() < MIN_SERVER_VER_SOFT_DOLLAR_TIER
-            && (!order.soft_dollar_tier.name.is_empty() || !order.soft_dollar_tier.val.is_empty())
-        {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                order_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support soft dollar tier."
-                ),
-            ));
-
-            return Err(err);
-        }
-
-        if self.server_version() < MIN_SERVER_VER_CASH_QTY && order.cash_qty != 0.0 {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                order_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support cash quantity parameter."
-                ),
-            ));
-
-            return Err(err);
-        }
-
-        if self.server_version() < MIN_SERVER_VER_DECISION_MAKER
-            && (!order.mifid2decision_maker.is_empty() || !order.mifid2decision_algo.is_empty())
-        {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                order_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support MIFID II decision maker parameters."
-                ),
-            ));
-
-            return Err(err);
+        if !contract.trading_class.is_empty() {
+            self.check_feature(ServerFeature::TradingClass)?;
         }
 
-        if self.server_version() < MIN_SERVER_VER_MIFID_EXECUTION
-            && (!order.mifid2execution_trader.is_empty() || !order.mifid2execution_algo.is_empty())
-        {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                order_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support MIFID II execution parameters."
-                ),
-            ));
+        let version = 2;
 
-            return Err(err);
-        }
+        // send req mkt data msg
+        let mut msg = FieldWriter::new();
 
-        if self.server_version() < MIN_SERVER_VER_AUTO_PRICE_FOR_HEDGE
-            && order.dont_use_auto_price_for_hedge
-        {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                order_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support dontUseAutoPriceForHedge parameter."
-                ),
-            ));
+        let message_id = ServerReqMsgDiscriminants::ExerciseOptions as i32;
 
-            return Err(err);
-        }
+        msg.push(&message_id);
 
-        if self.server_version() < MIN_SERVER_VER_ORDER_CONTAINER && order.is_oms_container {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                order_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support oms container parameter."
-                ),
-            ));
+        msg.push(&version);
+        msg.push(&req_id);
 
-            return Err(err);
+        // send contract fields
+        if self.server_version() >= MIN_SERVER_VER_TRADING_CLASS {
+            msg.push(&contract.con_id);
+        }
+        msg.push(&contract.symbol);
+        msg.push(&contract.sec_type);
+        msg.push(&contract.last_trade_date_or_contract_month);
+        msg.push(&contract.strike);
+        msg.push(&contract.right);
+        msg.push(&contract.multiplier);
+        msg.push(&contract.exchange);
+        msg.push(&contract.currency);
+        msg.push(&contract.local_symbol);
+        if self.server_version() >= MIN_SERVER_VER_TRADING_CLASS {
+            msg.push(&contract.trading_class);
         }
+        msg.push(&exercise_action);
+        msg.push(&exercise_quantity);
+        msg.push(account);
+        msg.push(&over_ride);
 
-        if self.server_version() < MIN_SERVER_VER_PRICE_MGMT_ALGO && order.use_price_mgmt_algo {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                order_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support Use price management algo requests."
-                ),
-            ));
+        self.send_request(msg)?;
+        Ok(())
+    }
 
-            return Err(err);
+    //#########################################################################
+    //################## Orders
+    //########################################################################
+
+    /// Call this function to place an order. The order status will
+    /// be returned by the Wrapper::order_status event.
+    ///
+    /// # Arguments
+    /// * order_id - The order id. You must specify a unique value. When the
+    ///              order status returns, it will be identified by this tag.
+    ///              This tag is also used when canceling the order.
+    /// * contract - This structure contains a description of the
+    ///              contract which is being traded.
+    /// * order - This structure contains the details of the order.
+    ///
+    /// Note: Each client MUST connect with a unique client_id.
+    pub fn place_order(
+        &mut self,
+        order_id: i32,
+        contract: &Contract,
+        order: &Order,
+    ) -> Result<(), IBKRApiLibError> {
+        self.check_connected(NO_VALID_ID)?;
+
+        contract.validate()?;
+
+        for feature in required_features(contract, order) {
+            self.check_feature(feature)?;
         }
 
         let version: i32 = if self.server_version() < MIN_SERVER_VER_NOT_HELD {
@@ -1540,104 +994,104 @@ impl EClient {
         };
 
         //send place order msg
-        let mut msg = "".to_string();
+        let mut msg = FieldWriter::new();
 
         let message_id = ServerReqMsgDiscriminants::PlaceOrder as i32;
 
-        msg.push_str(&make_field(&message_id)?);
+        msg.push(&message_id);
 
         if self.server_version() < MIN_SERVER_VER_ORDER_CONTAINER {
-            msg.push_str(&make_field(&version)?);
+            msg.push(&version);
         }
 
-        msg.push_str(&make_field(&order_id)?);
+        msg.push(&order_id);
 
         // send contract fields
         if self.server_version() >= MIN_SERVER_VER_PLACE_ORDER_CONID {
-            msg.push_str(&make_field(&contract.con_id)?);
-        }
-        msg.push_str(&make_field(&contract.symbol)?);
-        msg.push_str(&make_field(&contract.sec_type)?);
-        msg.push_str(&make_field(&contract.last_trade_date_or_contract_month)?);
-        msg.push_str(&make_field(&contract.strike)?);
-        msg.push_str(&make_field(&contract.right)?);
-        msg.push_str(&make_field(&contract.multiplier)?); // srv v15 && above
-        msg.push_str(&make_field(&contract.exchange)?);
-        msg.push_str(&make_field(&contract.primary_exchange)?); // srv v14 && above
-        msg.push_str(&make_field(&contract.currency)?);
-        msg.push_str(&make_field(&contract.local_symbol)?); // srv v2 && above
+            msg.push(&contract.con_id);
+        }
+        msg.push(&contract.symbol);
+        msg.push(&contract.sec_type);
+        msg.push(&contract.last_trade_date_or_contract_month);
+        msg.push(&contract.strike);
+        msg.push(&contract.right);
+        msg.push(&contract.multiplier); // srv v15 && above
+        msg.push(&contract.exchange);
+        msg.push(&contract.primary_exchange); // srv v14 && above
+        msg.push(&contract.currency);
+        msg.push(&contract.local_symbol); // srv v2 && above
 
         if self.server_version() >= MIN_SERVER_VER_TRADING_CLASS {
-            msg.push_str(&make_field(&contract.trading_class)?);
+            msg.push(&contract.trading_class);
         }
 
         if self.server_version() >= MIN_SERVER_VER_SEC_ID_TYPE {
-            msg.push_str(&make_field(&contract.sec_id_type)?);
-            msg.push_str(&make_field(&contract.sec_id)?);
+            msg.push(&contract.sec_id_type);
+            msg.push(&contract.sec_id);
         }
 
         // send main order fields
-        msg.push_str(&make_field(&order.action)?);
+        msg.push(&order.action);
 
         if self.server_version() >= MIN_SERVER_VER_FRACTIONAL_POSITIONS {
-            msg.push_str(&make_field(&order.total_quantity)?);
+            msg.push(&order.total_quantity);
         } else {
-            msg.push_str(&make_field(&(order.total_quantity as i32))?);
+            msg.push(&(order.total_quantity as i32));
         }
 
-        msg.push_str(&make_field(&order.order_type)?);
+        msg.push(&order.order_type);
 
         if self.server_version() < MIN_SERVER_VER_ORDER_COMBO_LEGS_PRICE {
-            msg.push_str(&make_field(if order.lmt_price != UNSET_DOUBLE {
+            msg.push(if order.lmt_price != UNSET_DOUBLE {
                 &order.lmt_price
             } else {
-                &0
-            })?);
+                &0.0
+            });
         } else {
-            msg.push_str(&make_field_handle_empty(&order.lmt_price)?);
+            msg.push(&order.lmt_price);
         }
 
         if self.server_version() < MIN_SERVER_VER_TRAILING_PERCENT {
-            msg.push_str(&make_field(if order.aux_price != UNSET_DOUBLE {
+            msg.push(if order.aux_price != UNSET_DOUBLE {
                 &order.aux_price
             } else {
-                &0
-            })?);
+                &0.0
+            });
         } else {
-            msg.push_str(&make_field_handle_empty(&order.aux_price)?);
+            msg.push(&order.aux_price);
         }
 
         // send extended order fields
-        msg.push_str(&make_field(&order.tif)?);
-        msg.push_str(&make_field(&order.oca_group)?);
-        msg.push_str(&make_field(&order.account)?);
-        msg.push_str(&make_field(&order.open_close)?);
-        msg.push_str(&make_field(&(order.origin as i32))?);
-        msg.push_str(&make_field(&order.order_ref)?);
-        msg.push_str(&make_field(&order.transmit)?);
-        msg.push_str(&make_field(&order.parent_id)?); // srv v4 && above
-        msg.push_str(&make_field(&order.block_order)?); // srv v5 && above
-        msg.push_str(&make_field(&order.sweep_to_fill)?); // srv v5 && above
-        msg.push_str(&make_field(&order.display_size)?); // srv v5 && above
-        msg.push_str(&make_field(&order.trigger_method)?); // srv v5 && above
-        msg.push_str(&make_field(&order.outside_rth)?); // srv v5 && above
-        msg.push_str(&make_field(&order.hidden)?); // srv v7 && above
+        msg.push(&order.tif);
+        msg.push(&order.oca_group);
+        msg.push(&order.account);
+        msg.push(&order.open_close);
+        msg.push(&(order.origin as i32));
+        msg.push(&order.order_ref);
+        msg.push(&order.transmit);
+        msg.push(&order.parent_id); // srv v4 && above
+        msg.push(&order.block_order); // srv v5 && above
+        msg.push(&order.sweep_to_fill); // srv v5 && above
+        msg.push(&order.display_size); // srv v5 && above
+        msg.push(&order.trigger_method); // srv v5 && above
+        msg.push(&order.outside_rth); // srv v5 && above
+        msg.push(&order.hidden); // srv v7 && above
 
         // Send combo legs for BAG requests (srv v8 && above)
         if contract.sec_type == "BAG" {
             let combo_legs_count = contract.combo_legs.len();
-            msg.push_str(&make_field(&combo_legs_count)?);
+            msg.push(&combo_legs_count);
             if combo_legs_count > 0 {
                 for combo_leg in &contract.combo_legs {
-                    msg.push_str(&make_field(&combo_leg.con_id)?);
-                    msg.push_str(&make_field(&combo_leg.ratio)?);
-                    msg.push_str(&make_field(&combo_leg.action)?);
-                    msg.push_str(&make_field(&combo_leg.exchange)?);
-                    msg.push_str(&make_field(&(combo_leg.open_close as i32))?);
-                    msg.push_str(&make_field(&combo_leg.short_sale_slot)?); //srv v35 && above
-                    msg.push_str(&make_field(&combo_leg.designated_location)?); // srv v35 && above
+                    msg.push(&combo_leg.con_id);
+                    msg.push(&combo_leg.ratio);
+                    msg.push(&combo_leg.action);
+                    msg.push(&combo_leg.exchange);
+                    msg.push(&(combo_leg.open_close as i32));
+                    msg.push(&combo_leg.short_sale_slot); //srv v35 && above
+                    msg.push(&combo_leg.designated_location); // srv v35 && above
                     if self.server_version() >= MIN_SERVER_VER_SSHORTX_OLD {
-                        msg.push_str(&make_field(&combo_leg.exempt_code)?);
+                        msg.push(&combo_leg.exempt_code);
                     }
                 }
             }
@@ -1649,10 +1103,10 @@ impl EClient {
         {
             let order_combo_legs_count = order.order_combo_legs.len();
 
-            msg.push_str(&make_field(&order_combo_legs_count)?);
+            msg.push(&order_combo_legs_count);
             if order_combo_legs_count > 0 {
                 for order_combo_leg in &order.order_combo_legs {
-                    msg.push_str(&make_field_handle_empty(&order_combo_leg.price)?);
+                    msg.push(&order_combo_leg.price);
                 }
             }
         }
@@ -1661,11 +1115,11 @@ impl EClient {
             && contract.sec_type == "BAG"
         {
             let smart_combo_routing_params_count = order.smart_combo_routing_params.len();
-            msg.push_str(&make_field(&smart_combo_routing_params_count)?);
+            msg.push(&smart_combo_routing_params_count);
             if smart_combo_routing_params_count > 0 {
                 for tag_value in &order.smart_combo_routing_params {
-                    msg.push_str(&make_field(&tag_value.tag)?);
-                    msg.push_str(&make_field(&tag_value.value)?);
+                    msg.push(&tag_value.tag);
+                    msg.push(&tag_value.value);
                 }
             }
         }
@@ -1684,179 +1138,171 @@ impl EClient {
         //    #####################################################################
 
         // send deprecated sharesAllocation field
-        msg.push_str(&make_field(&"")?); // srv v9 && above
+        msg.push(&""); // srv v9 && above
 
-        msg.push_str(&make_field(&order.discretionary_amt)?); // srv v10 && above
-        msg.push_str(&make_field(&order.good_after_time)?); // srv v11 && above
-        msg.push_str(&make_field(&order.good_till_date)?); // srv v12 && above
+        msg.push(&order.discretionary_amt); // srv v10 && above
+        msg.push(&order.good_after_time); // srv v11 && above
+        msg.push(&order.good_till_date); // srv v12 && above
 
-        msg.push_str(&make_field(&order.fa_group)?); // srv v13 && above
-        msg.push_str(&make_field(&order.fa_method)?); // srv v13 && above
-        msg.push_str(&make_field(&order.fa_percentage)?); // srv v13 && above
-        msg.push_str(&make_field(&order.fa_profile)?); // srv v13 && above
+        msg.push(&order.fa_group); // srv v13 && above
+        msg.push(&order.fa_method); // srv v13 && above
+        msg.push(&order.fa_percentage); // srv v13 && above
+        msg.push(&order.fa_profile); // srv v13 && above
 
         if self.server_version() >= MIN_SERVER_VER_MODELS_SUPPORT {
-            msg.push_str(&make_field(&order.model_code)?);
+            msg.push(&order.model_code);
         }
 
         // institutional short saleslot data (srv v18 && above)
-        msg.push_str(&make_field(&order.short_sale_slot)?); // 0 for retail, 1 || 2 for institutions
-        msg.push_str(&make_field(&order.designated_location)?); // populate only when shortSaleSlot = 2.
+        msg.push(&order.short_sale_slot); // 0 for retail, 1 || 2 for institutions
+        msg.push(&order.designated_location); // populate only when shortSaleSlot = 2.
 
         if self.server_version() >= MIN_SERVER_VER_SSHORTX_OLD {
-            msg.push_str(&make_field(&order.exempt_code)?);
+            msg.push(&order.exempt_code);
         }
 
         // not needed anymore
         //bool isVolOrder = (order.orderType.CompareNoCase("VOL").as_ref() == 0)
 
         // srv v19 && above fields
-        msg.push_str(&make_field(&order.oca_type)?);
+        msg.push(&order.oca_type);
         //if( self.server_version() < 38) {
         // will never happen
         //      send( /* order.rthOnly */ false);
         //}
-        msg.push_str(&make_field(&order.rule80a)?);
-        msg.push_str(&make_field(&order.settling_firm)?);
-        msg.push_str(&make_field(&order.all_or_none)?);
-        msg.push_str(&make_field_handle_empty(&order.min_qty)?);
-        msg.push_str(&make_field_handle_empty(&order.percent_offset)?);
-        msg.push_str(&make_field(&order.e_trade_only)?);
-        msg.push_str(&make_field(&order.firm_quote_only)?);
-        msg.push_str(&make_field_handle_empty(&order.nbbo_price_cap)?);
-        msg.push_str(&make_field(&(order.auction_strategy as i32))?); // AUCTION_MATCH, AUCTION_IMPROVEMENT, AUCTION_TRANSPARENT
-        msg.push_str(&make_field_handle_empty(&order.starting_price)?);
-        msg.push_str(&make_field_handle_empty(&order.stock_ref_price)?);
-        msg.push_str(&make_field_handle_empty(&order.delta)?);
-        msg.push_str(&make_field_handle_empty(&order.stock_range_lower)?);
-        msg.push_str(&make_field_handle_empty(&order.stock_range_upper)?);
-
-        msg.push_str(&make_field(&order.override_percentage_constraints)?); //srv v22 && above
+        msg.push(&order.rule80a);
+        msg.push(&order.settling_firm);
+        msg.push(&order.all_or_none);
+        msg.push(&order.min_qty);
+        msg.push(&order.percent_offset);
+        msg.push(&order.e_trade_only);
+        msg.push(&order.firm_quote_only);
+        msg.push(&order.nbbo_price_cap);
+        msg.push(&(order.auction_strategy as i32)); // AUCTION_MATCH, AUCTION_IMPROVEMENT, AUCTION_TRANSPARENT
+        msg.push(&order.starting_price);
+        msg.push(&order.stock_ref_price);
+        msg.push(&order.delta);
+        msg.push(&order.stock_range_lower);
+        msg.push(&order.stock_range_upper);
+
+        msg.push(&order.override_percentage_constraints); //srv v22 && above
 
         // volatility orders (srv v26 && above)
-        msg.push_str(&make_field_handle_empty(&order.volatility)?);
-        msg.push_str(&make_field_handle_empty(&order.volatility_type)?);
-        msg.push_str(&make_field(&order.delta_neutral_order_type)?); // srv v28 && above
-        msg.push_str(&make_field_handle_empty(&order.delta_neutral_aux_price)?); // srv v28 && above
+        msg.push(&order.volatility);
+        msg.push(&order.volatility_type);
+        msg.push(&order.delta_neutral_order_type); // srv v28 && above
+        msg.push(&order.delta_neutral_aux_price); // srv v28 && above
 
         if self.server_version() >= MIN_SERVER_VER_DELTA_NEUTRAL_CONID
             && !order.delta_neutral_order_type.is_empty()
         {
-            msg.push_str(&make_field(&order.delta_neutral_con_id)?);
-            msg.push_str(&make_field(&order.delta_neutral_settling_firm)?);
-            msg.push_str(&make_field(&order.delta_neutral_clearing_account)?);
-            msg.push_str(&make_field(&order.delta_neutral_clearing_intent)?);
+            msg.push(&order.delta_neutral_con_id);
+            msg.push(&order.delta_neutral_settling_firm);
+            msg.push(&order.delta_neutral_clearing_account);
+            msg.push(&order.delta_neutral_clearing_intent);
         }
 
         if self.server_version() >= MIN_SERVER_VER_DELTA_NEUTRAL_OPEN_CLOSE
             && order.delta_neutral_order_type != ""
         {
-            msg.push_str(&make_field(&order.delta_neutral_open_close)?);
-            msg.push_str(&make_field(&order.delta_neutral_short_sale)?);
-            msg.push_str(&make_field(&order.delta_neutral_short_sale_slot)?);
-            msg.push_str(&make_field(&order.delta_neutral_designated_location)?);
+            msg.push(&order.delta_neutral_open_close);
+            msg.push(&order.delta_neutral_short_sale);
+            msg.push(&order.delta_neutral_short_sale_slot);
+            msg.push(&order.delta_neutral_designated_location);
         }
 
-        msg.push_str(&make_field(&order.continuous_update)?);
-        msg.push_str(&make_field_handle_empty(&order.reference_price_type)?);
-        msg.push_str(&make_field_handle_empty(&order.trail_stop_price)?); // srv v30 && above
+        msg.push(&order.continuous_update);
+        msg.push(&order.reference_price_type);
+        msg.push(&order.trail_stop_price); // srv v30 && above
 
         if self.server_version() >= MIN_SERVER_VER_TRAILING_PERCENT {
-            msg.push_str(&make_field_handle_empty(&order.trailing_percent)?);
+            msg.push(&order.trailing_percent);
         }
 
         // SCALE orders
         if self.server_version() >= MIN_SERVER_VER_SCALE_ORDERS2 {
-            msg.push_str(&make_field_handle_empty(&order.scale_init_level_size)?);
-            msg.push_str(&make_field_handle_empty(&order.scale_subs_level_size)?);
+            msg.push(&order.scale_init_level_size);
+            msg.push(&order.scale_subs_level_size);
         } else {
             // srv v35 && above)
-            msg.push_str(&make_field(&"")?); // for not supported scaleNumComponents
-            msg.push_str(&make_field_handle_empty(&order.scale_init_level_size)?);
+            msg.push(&""); // for not supported scaleNumComponents
+            msg.push(&order.scale_init_level_size);
             // for scaleComponentSize
         }
 
-        msg.push_str(&make_field_handle_empty(&order.scale_price_increment)?);
+        msg.push(&order.scale_price_increment);
 
         if self.server_version() >= MIN_SERVER_VER_SCALE_ORDERS3
             && order.scale_price_increment != UNSET_DOUBLE
             && order.scale_price_increment > 0.0
         {
-            msg.push_str(&make_field_handle_empty(&order.scale_price_adjust_value)?);
-            msg.push_str(&make_field_handle_empty(
-                &order.scale_price_adjust_interval,
-            )?);
-            msg.push_str(&make_field_handle_empty(&order.scale_profit_offset)?);
-            msg.push_str(&make_field(&order.scale_auto_reset)?);
-            msg.push_str(&make_field_handle_empty(&order.scale_init_position)?);
-            msg.push_str(&make_field_handle_empty(&order.scale_init_fill_qty)?);
-            msg.push_str(&make_field(&order.scale_random_percent)?);
+            msg.push(&order.scale_price_adjust_value);
+            msg.push(&order.scale_price_adjust_interval);
+            msg.push(&order.scale_profit_offset);
+            msg.push(&order.scale_auto_reset);
+            msg.push(&order.scale_init_position);
+            msg.push(&order.scale_init_fill_qty);
+            msg.push(&order.scale_random_percent);
         }
 
         if self.server_version() >= MIN_SERVER_VER_SCALE_TABLE {
-            msg.push_str(&make_field(&order.scale_table)?);
-            msg.push_str(&make_field(&order.active_start_time)?);
-            msg.push_str(&make_field(&order.active_stop_time)?);
+            msg.push(&order.scale_table);
+            msg.push(&order.active_start_time);
+            msg.push(&order.active_stop_time);
         }
 
         // HEDGE orders
         if self.server_version() >= MIN_SERVER_VER_HEDGE_ORDERS {
-            msg.push_str(&make_field(&order.hedge_type)?);
+            msg.push(&order.hedge_type);
 
             if !order.hedge_type.is_empty() {
-                msg.push_str(&make_field(&order.hedge_param)?);
+                msg.push(&order.hedge_param);
             }
         }
 
         if self.server_version() >= MIN_SERVER_VER_OPT_OUT_SMART_ROUTING {
-            msg.push_str(&make_field(&order.opt_out_smart_routing)?);
+            msg.push(&order.opt_out_smart_routing);
         }
 
         if self.server_version() >= MIN_SERVER_VER_PTA_ORDERS {
-            msg.push_str(&make_field(&order.clearing_account)?);
-            msg.push_str(&make_field(&order.clearing_intent)?);
+            msg.push(&order.clearing_account);
+            msg.push(&order.clearing_intent);
         }
 
         if self.server_version() >= MIN_SERVER_VER_NOT_HELD {
-            msg.push_str(&make_field(&order.not_held)?);
+            msg.push(&order.not_held);
         }
 
         if self.server_version() >= MIN_SERVER_VER_DELTA_NEUTRAL {
             if contract.delta_neutral_contract.is_some() {
-                msg.push_str(&make_field(&true)?);
-                msg.push_str(&make_field(
-                    &contract.delta_neutral_contract.as_ref().unwrap().con_id,
-                )?);
-                msg.push_str(&make_field(
-                    &contract.delta_neutral_contract.as_ref().unwrap().delta,
-                )?);
-                msg.push_str(&make_field(
-                    &contract.delta_neutral_contract.as_ref().unwrap().price,
-                )?);
+                msg.push(&true);
+                msg.push(&contract.delta_neutral_contract.as_ref().unwrap().con_id);
+                msg.push(&contract.delta_neutral_contract.as_ref().unwrap().delta);
+                msg.push(&contract.delta_neutral_contract.as_ref().unwrap().price);
             } else {
-                msg.push_str(&make_field(&false)?);
+                msg.push(&false);
             }
         }
 
         if self.server_version() >= MIN_SERVER_VER_ALGO_ORDERS {
-            msg.push_str(&make_field(&order.algo_strategy)?);
+            msg.push(&order.algo_strategy);
             if !order.algo_strategy.is_empty() {
                 let algo_params_count = order.algo_params.len();
-                msg.push_str(&make_field(&algo_params_count)?);
+                msg.push(&algo_params_count);
                 if algo_params_count > 0 {
                     for algo_param in &order.algo_params {
-                        msg.push_str(&make_field(&algo_param.tag)?);
-                        msg.push_str(&make_field(&algo_param.value)?);
+                        msg.push(&algo_param.tag);
+                        msg.push(&algo_param.value);
                     }
                 }
             }
         }
 
         if self.server_version() >= MIN_SERVER_VER_ALGO_ID {
-            msg.push_str(&make_field(&order.algo_id)?);
+            msg.push(&order.algo_id);
         }
 
-        msg.push_str(&make_field(&order.what_if)?); // srv v36 && above
+        msg.push(&order.what_if); // srv v36 && above
 
         // send miscOptions parameter
         if self.server_version() >= MIN_SERVER_VER_LINKING {
@@ -1865,93 +1311,195 @@ impl EClient {
                 .iter()
                 .map(|x| format!("{}={};", x.tag, x.value))
                 .collect::<String>();
-            msg.push_str(&make_field(&misc_options_str)?);
+            msg.push(&misc_options_str);
         }
 
         if self.server_version() >= MIN_SERVER_VER_ORDER_SOLICITED {
-            msg.push_str(&make_field(&order.solicited)?);
+            msg.push(&order.solicited);
         }
 
         if self.server_version() >= MIN_SERVER_VER_RANDOMIZE_SIZE_AND_PRICE {
-            msg.push_str(&make_field(&order.randomize_size)?);
-            msg.push_str(&make_field(&order.randomize_price)?);
+            msg.push(&order.randomize_size);
+            msg.push(&order.randomize_price);
         }
 
         if self.server_version() >= MIN_SERVER_VER_PEGGED_TO_BENCHMARK {
             if order.order_type == "PEG BENCH" {
-                msg.push_str(&make_field(&order.reference_contract_id)?);
-                msg.push_str(&make_field(&order.is_pegged_change_amount_decrease)?);
-                msg.push_str(&make_field(&order.pegged_change_amount)?);
-                msg.push_str(&make_field(&order.reference_change_amount)?);
-                msg.push_str(&make_field(&order.reference_exchange_id)?);
+                msg.push(&order.reference_contract_id);
+                msg.push(&order.is_pegged_change_amount_decrease);
+                msg.push(&order.pegged_change_amount);
+                msg.push(&order.reference_change_amount);
+                msg.push(&order.reference_exchange_id);
             }
 
-            msg.push_str(&make_field(&order.conditions.len())?);
+            msg.push(&order.conditions.len());
 
             if order.conditions.len() > 0 {
                 for cond in &order.conditions {
-                    msg.push_str(&make_field(&(cond.get_type() as i32))?);
-                    let vals = cond.make_fields()?;
-                    let vals_string = vals.iter().map(|val| val.clone()).collect::<String>();
-                    msg.push_str(vals_string.as_ref());
+                    msg.push(&(cond.get_type() as i32));
+                    for val in cond.make_fields()? {
+                        msg.push_raw(&val);
+                    }
                 }
 
-                msg.push_str(&make_field(&order.conditions_ignore_rth)?);
-                msg.push_str(&make_field(&order.conditions_cancel_order)?);
+                msg.push(&order.conditions_ignore_rth);
+                msg.push(&order.conditions_cancel_order);
             }
 
-            msg.push_str(&make_field(&order.adjusted_order_type)?);
-            msg.push_str(&make_field(&order.trigger_price)?);
-            msg.push_str(&make_field(&order.lmt_price_offset)?);
-            msg.push_str(&make_field(&order.adjusted_stop_price)?);
-            msg.push_str(&make_field(&order.adjusted_stop_limit_price)?);
-            msg.push_str(&make_field(&order.adjusted_trailing_amount)?);
-            msg.push_str(&make_field(&order.adjustable_trailing_unit)?);
+            msg.push(&order.adjusted_order_type);
+            msg.push(&order.trigger_price);
+            msg.push(&order.lmt_price_offset);
+            msg.push(&order.adjusted_stop_price);
+            msg.push(&order.adjusted_stop_limit_price);
+            msg.push(&order.adjusted_trailing_amount);
+            msg.push(&order.adjustable_trailing_unit);
         }
 
         if self.server_version() >= MIN_SERVER_VER_EXT_OPERATOR {
-            msg.push_str(&make_field(&order.ext_operator)?);
+            msg.push(&order.ext_operator);
         }
 
         if self.server_version() >= MIN_SERVER_VER_SOFT_DOLLAR_TIER {
-            msg.push_str(&make_field(&order.soft_dollar_tier.name)?);
-            msg.push_str(&make_field(&order.soft_dollar_tier.val)?);
+            msg.push(&order.soft_dollar_tier.name);
+            msg.push(&order.soft_dollar_tier.val);
         }
 
         if self.server_version() >= MIN_SERVER_VER_CASH_QTY {
-            msg.push_str(&make_field(&order.cash_qty)?);
+            msg.push(&order.cash_qty);
         }
 
         if self.server_version() >= MIN_SERVER_VER_DECISION_MAKER {
-            msg.push_str(&make_field(&order.mifid2decision_maker)?);
-            msg.push_str(&make_field(&order.mifid2decision_algo)?);
+            msg.push(&order.mifid2decision_maker);
+            msg.push(&order.mifid2decision_algo);
         }
 
         if self.server_version() >= MIN_SERVER_VER_MIFID_EXECUTION {
-            msg.push_str(&make_field(&order.mifid2execution_trader)?);
-            msg.push_str(&make_field(&order.mifid2execution_algo)?);
+            msg.push(&order.mifid2execution_trader);
+            msg.push(&order.mifid2execution_algo);
         }
 
         if self.server_version() >= MIN_SERVER_VER_AUTO_PRICE_FOR_HEDGE {
-            msg.push_str(&make_field(&order.dont_use_auto_price_for_hedge)?);
+            msg.push(&order.dont_use_auto_price_for_hedge);
         }
 
         if self.server_version() >= MIN_SERVER_VER_ORDER_CONTAINER {
-            msg.push_str(&make_field(&order.is_oms_container)?);
+            msg.push(&order.is_oms_container);
         }
 
         if self.server_version() >= MIN_SERVER_VER_D_PEG_ORDERS {
-            msg.push_str(&make_field(&order.discretionary_up_to_limit_price)?);
+            msg.push(&order.discretionary_up_to_limit_price);
         }
 
         if self.server_version() >= MIN_SERVER_VER_PRICE_MGMT_ALGO {
-            msg.push_str(&make_field_handle_empty(&order.use_price_mgmt_algo)?);
+            msg.push(&order.use_price_mgmt_algo);
         }
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
         Ok(())
     }
 
+    //----------------------------------------------------------------------------------------------
+    /// Checks an order before placing it, see `order_validation::validate_order`. Pass the
+    /// `ContractDetails` of the contract, if they were requested, to also check the order type,
+    /// exchange and price increments. Features the connected server does not support are
+    /// reported as `OrderViolation::Unsupported`.
+    pub fn preflight_order(
+        &self,
+        contract: &Contract,
+        order: &Order,
+        details: Option<&ContractDetails>,
+    ) -> Vec<OrderViolation> {
+        validate_order_for_server(contract, order, details, self.server_version)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Places a bracket order: a parent order with a take profit and a stop loss attached to it.
+    ///
+    /// Three consecutive order ids are allocated from `next_order_id`, the children get the
+    /// parent's id as `parent_id`, and only the stop loss is sent with `transmit` set so that
+    /// TWS activates the whole bracket at once. If sending one of the orders fails, the orders
+    /// already sent are cancelled.
+    /// # Arguments
+    /// * contract - The contract traded by all three orders
+    /// * parent - The entry order
+    /// * take_profit - The limit order closing the position at a profit
+    /// * stop_loss - The stop order closing the position at a loss
+    pub fn place_bracket(
+        &mut self,
+        contract: &Contract,
+        parent: Order,
+        take_profit: Order,
+        stop_loss: Order,
+    ) -> Result<OrderGroup, IBKRApiLibError> {
+        self.check_connected(NO_VALID_ID)?;
+
+        contract.validate()?;
+        parent.validate()?;
+        take_profit.validate()?;
+        stop_loss.validate()?;
+
+        let parent_id = self.allocate_order_ids(3)?;
+        let mut members = Vec::with_capacity(3);
+        for (offset, mut order) in vec![parent, take_profit, stop_loss].into_iter().enumerate() {
+            order.order_id = parent_id + offset as i32;
+            order.parent_id = if offset == 0 { 0 } else { parent_id };
+            members.push(GroupMember::new(contract.clone(), order));
+        }
+
+        let mut group = OrderGroup::new(OrderGroupKind::Bracket, members);
+        group.transmit(self)?;
+        Ok(group)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Places orders in a One-Cancels-All group, where a fill on one order cancels or reduces
+    /// the others according to `oca_type`.
+    ///
+    /// Consecutive order ids are allocated from `next_order_id`, and only the last order is sent
+    /// with `transmit` set so that TWS activates the whole group at once. A group needs at least
+    /// two orders. If sending one of them fails, the orders already sent are cancelled.
+    /// # Arguments
+    /// * oca_group - Name of the group, unique among the account's working OCA groups
+    /// * oca_type - How the remaining orders are handled after a fill
+    /// * orders - The contract and order of each member of the group
+    pub fn place_oca_group(
+        &mut self,
+        oca_group: &str,
+        oca_type: OcaType,
+        orders: Vec<(Contract, Order)>,
+    ) -> Result<OrderGroup, IBKRApiLibError> {
+        self.check_connected(NO_VALID_ID)?;
+
+        if orders.len() < 2 {
+            return Err(IBKRApiLibError::InvalidOrder {
+                order_type: orders
+                    .first()
+                    .map(|(_, order)| order.order_type.clone())
+                    .unwrap_or_default(),
+                reason: "needs at least one other order to form an OCA group",
+            });
+        }
+
+        for (contract, order) in orders.iter() {
+            contract.validate()?;
+            order.validate()?;
+        }
+
+        let first_id = self.allocate_order_ids(orders.len() as i32)?;
+        let oca_type = oca_type as i32;
+        let mut members = Vec::with_capacity(orders.len());
+        for (offset, (contract, mut order)) in orders.into_iter().enumerate() {
+            order.order_id = first_id + offset as i32;
+            order.oca_group = oca_group.to_string();
+            order.oca_type = oca_type;
+            members.push(GroupMember::new(contract, order));
+        }
+
+        let mut group = OrderGroup::new(OrderGroupKind::Oca, members);
+        group.transmit(self)?;
+        Ok(group)
+    }
+
     //----------------------------------------------------------------------------------------------
     /// Call this function to cancel an order.
     /// # Arguments
@@ -1961,15 +1509,15 @@ impl EClient {
 
         let version = 2;
 
-        let mut msg = "".to_string();
+        let mut msg = FieldWriter::new();
 
         let message_id = ServerReqMsgDiscriminants::CancelOrder as i32;
 
-        msg.push_str(&make_field(&message_id)?);
-        msg.push_str(&make_field(&version)?);
-        msg.push_str(&make_field(&order_id)?);
+        msg.push(&message_id);
+        msg.push(&version);
+        msg.push(&order_id);
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -1987,14 +1535,14 @@ impl EClient {
 
         let version = 1;
 
-        let mut msg = "".to_string();
+        let mut msg = FieldWriter::new();
 
         let message_id = ServerReqMsgDiscriminants::ReqOpenOrders as i32;
 
-        msg.push_str(&make_field(&message_id)?);
-        msg.push_str(&make_field(&version)?);
+        msg.push(&message_id);
+        msg.push(&version);
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -2015,15 +1563,15 @@ impl EClient {
 
         let version = 1;
 
-        let mut msg = "".to_string();
+        let mut msg = FieldWriter::new();
 
         let message_id = ServerReqMsgDiscriminants::ReqAutoOpenOrders as i32;
 
-        msg.push_str(&make_field(&message_id)?);
-        msg.push_str(&make_field(&version)?);
-        msg.push_str(&make_field(&b_auto_bind)?); // TRUE = subscribe, FALSE = unsubscribe
+        msg.push(&message_id);
+        msg.push(&version);
+        msg.push(&b_auto_bind); // TRUE = subscribe, FALSE = unsubscribe
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
 
         Ok(())
     }
@@ -2039,14 +1587,14 @@ impl EClient {
 
         let version = 1;
 
-        let mut msg = "".to_string();
+        let mut msg = FieldWriter::new();
 
         let message_id = ServerReqMsgDiscriminants::ReqAllOpenOrders as i32;
 
-        msg.push_str(&make_field(&message_id)?);
-        msg.push_str(&make_field(&version)?);
+        msg.push(&message_id);
+        msg.push(&version);
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
 
         Ok(())
     }
@@ -2061,14 +1609,14 @@ impl EClient {
 
         let version = 1;
 
-        let mut msg = "".to_string();
+        let mut msg = FieldWriter::new();
 
         let message_id = ServerReqMsgDiscriminants::ReqGlobalCancel as i32;
 
-        msg.push_str(&make_field(&message_id)?);
-        msg.push_str(&make_field(&version)?);
+        msg.push(&message_id);
+        msg.push(&version);
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
 
         Ok(())
     }
@@ -2087,15 +1635,15 @@ impl EClient {
         info!("req_ids is connected...");
         let version = 1;
 
-        let mut msg = "".to_string();
+        let mut msg = FieldWriter::new();
 
         let message_id = ServerReqMsgDiscriminants::ReqIds as i32;
 
-        msg.push_str(&make_field(&message_id)?);
-        msg.push_str(&make_field(&version)?);
-        msg.push_str(&make_field(&num_ids)?);
+        msg.push(&message_id);
+        msg.push(&version);
+        msg.push(&num_ids);
         info!("req_ids... sending request...");
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -2127,16 +1675,16 @@ impl EClient {
 
         let version = 2;
 
-        let mut msg = "".to_string();
+        let mut msg = FieldWriter::new();
 
         let message_id = ServerReqMsgDiscriminants::ReqAcctData as i32;
 
-        msg.push_str(&make_field(&message_id)?);
-        msg.push_str(&make_field(&version)?);
-        msg.push_str(&make_field(&subscribe)?); // TRUE = subscribe, FALSE = unsubscribe
-        msg.push_str(&make_field(&String::from(acct_code))?); // srv v9 and above, the account code.This will only be used for FA clients
+        msg.push(&message_id);
+        msg.push(&version);
+        msg.push(&subscribe); // TRUE = subscribe, FALSE = unsubscribe
+        msg.push(&String::from(acct_code)); // srv v9 and above, the account code.This will only be used for FA clients
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
 
         Ok(())
     }
@@ -2155,27 +1703,28 @@ impl EClient {
     /// * group_name - Set to All to return account summary data for all
     ///                accounts, or set to a specific Advisor Account Group name that has
     ///                already been created in TWS Global Configuration.
-    /// * tags- A comma-separated list of account tags.  See the AccountSummaryTags enum for valid values
+    /// * tags - The account tags to request, `AccountSummaryTags::AllTags` for all of them. The
+    ///          `$LEDGER` tags request the cash balances of each currency.
     pub fn req_account_summary(
         &mut self,
         req_id: i32,
         group_name: &str,
-        tags: &str,
+        tags: &[AccountSummaryTags],
     ) -> Result<(), IBKRApiLibError> {
         self.check_connected(NO_VALID_ID)?;
 
         let version = 2;
 
         let message_id: i32 = ServerReqMsgDiscriminants::ReqAccountSummary as i32;
-        let mut msg = "".to_string();
+        let mut msg = FieldWriter::new();
 
-        msg.push_str(&make_field(&message_id)?);
-        msg.push_str(&make_field(&version)?);
-        msg.push_str(&make_field(&req_id)?);
-        msg.push_str(&make_field(&String::from(group_name))?);
-        msg.push_str(&make_field(&String::from(tags))?);
+        msg.push(&message_id);
+        msg.push(&version);
+        msg.push(&req_id);
+        msg.push(&String::from(group_name));
+        msg.push(&AccountSummaryTags::join(tags));
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -2189,12 +1738,12 @@ impl EClient {
         let version = 1;
 
         let message_id: i32 = ServerReqMsgDiscriminants::CancelAccountSummary as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
-        msg.push_str(&make_field(&version)?);
-        msg.push_str(&make_field(&req_id)?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
+        msg.push(&version);
+        msg.push(&req_id);
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
 
         Ok(())
     }
@@ -2204,28 +1753,16 @@ impl EClient {
     pub fn req_positions(&mut self) -> Result<(), IBKRApiLibError> {
         self.check_connected(NO_VALID_ID)?;
 
-        if self.server_version() < MIN_SERVER_VER_POSITIONS {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                NO_VALID_ID,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support positions request."
-                ),
-            ));
-
-            return Err(err);
-        }
+        self.check_feature(ServerFeature::Positions)?;
 
         let version = 1;
 
         let message_id: i32 = ServerReqMsgDiscriminants::ReqPositions as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
-        msg.push_str(&make_field(&version)?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
+        msg.push(&version);
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
 
         Ok(())
     }
@@ -2235,27 +1772,15 @@ impl EClient {
     pub fn cancel_positions(&mut self) -> Result<(), IBKRApiLibError> {
         self.check_connected(NO_VALID_ID)?;
 
-        if self.server_version() < MIN_SERVER_VER_POSITIONS {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                NO_VALID_ID,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support positions request."
-                ),
-            ));
-
-            return Err(err);
-        }
+        self.check_feature(ServerFeature::Positions)?;
 
         let version = 1;
 
         let message_id: i32 = ServerReqMsgDiscriminants::CancelPositions as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
-        msg.push_str(&make_field(&version)?);
-        self.send_request(msg.as_str())?;
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
+        msg.push(&version);
+        self.send_request(msg)?;
 
         Ok(())
     }
@@ -2277,33 +1802,21 @@ impl EClient {
     ) -> Result<(), IBKRApiLibError> {
         self.check_connected(req_id)?;
 
-        if self.server_version() < MIN_SERVER_VER_POSITIONS {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                req_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support positions multi request."
-                ),
-            ));
-
-            return Err(err);
-        }
+        self.check_feature(ServerFeature::Positions)?;
 
         let version = 1;
         let req_id = req_id;
         let account = account;
         let model_code = model_code;
         let message_id: i32 = ServerReqMsgDiscriminants::ReqPositionsMulti as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
-        msg.push_str(&make_field(&version)?);
-        msg.push_str(&make_field(&req_id)?);
-        msg.push_str(&make_field(&String::from(account))?);
-        msg.push_str(&make_field(&String::from(model_code))?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
+        msg.push(&version);
+        msg.push(&req_id);
+        msg.push(&String::from(account));
+        msg.push(&String::from(model_code));
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
 
         Ok(())
     }
@@ -2316,29 +1829,17 @@ impl EClient {
     pub fn cancel_positions_multi(&mut self, req_id: i32) -> Result<(), IBKRApiLibError> {
         self.check_connected(req_id)?;
 
-        if self.server_version() < MIN_SERVER_VER_POSITIONS {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                req_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support positions multi request."
-                ),
-            ));
-
-            return Err(err);
-        }
+        self.check_feature(ServerFeature::Positions)?;
 
         let version = 1;
         let req_id = req_id;
         let message_id: i32 = ServerReqMsgDiscriminants::CancelPositionsMulti as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
-        msg.push_str(&make_field(&version)?);
-        msg.push_str(&make_field(&req_id)?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
+        msg.push(&version);
+        msg.push(&req_id);
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -2360,19 +1861,7 @@ impl EClient {
     ) -> Result<(), IBKRApiLibError> {
         self.check_connected(req_id)?;
 
-        if self.server_version() < MIN_SERVER_VER_MODELS_SUPPORT {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                req_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support account updates multi request."
-                ),
-            ));
-
-            return Err(err);
-        }
+        self.check_feature(ServerFeature::ModelsSupport)?;
 
         let version = 1;
         let mut_req_id = req_id;
@@ -2381,15 +1870,15 @@ impl EClient {
         let mut_ledger_and_nlv = ledger_and_nlv;
 
         let message_id: i32 = ServerReqMsgDiscriminants::ReqAccountUpdatesMulti as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
-        msg.push_str(&make_field(&version)?);
-        msg.push_str(&make_field(&mut_req_id)?);
-        msg.push_str(&make_field(&String::from(mut_account))?);
-        msg.push_str(&make_field(&String::from(mut_model_code))?);
-        msg.push_str(&make_field(&mut_ledger_and_nlv)?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
+        msg.push(&version);
+        msg.push(&mut_req_id);
+        msg.push(&String::from(mut_account));
+        msg.push(&String::from(mut_model_code));
+        msg.push(&mut_ledger_and_nlv);
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
 
         Ok(())
     }
@@ -2402,29 +1891,17 @@ impl EClient {
     pub fn cancel_account_updates_multi(&mut self, req_id: i32) -> Result<(), IBKRApiLibError> {
         self.check_connected(req_id)?;
 
-        if self.server_version() < MIN_SERVER_VER_MODELS_SUPPORT {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                req_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support account updates multi request."
-                ),
-            ));
-
-            return Err(err);
-        }
+        self.check_feature(ServerFeature::ModelsSupport)?;
 
         let version = 1;
         let mut_req_id = req_id;
         let message_id: i32 = ServerReqMsgDiscriminants::CancelAccountUpdatesMulti as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
-        msg.push_str(&make_field(&version)?);
-        msg.push_str(&make_field(&mut_req_id)?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
+        msg.push(&version);
+        msg.push(&mut_req_id);
 
-        self.send_request(msg.as_str())
+        self.send_request(msg)
     }
 
     //#########################################################################
@@ -2444,28 +1921,16 @@ impl EClient {
     ) -> Result<(), IBKRApiLibError> {
         self.check_connected(req_id)?;
 
-        if self.server_version() < MIN_SERVER_VER_PNL {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                req_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support PnL request."
-                ),
-            ));
-
-            return Err(err);
-        }
+        self.check_feature(ServerFeature::Pnl)?;
 
         let message_id: i32 = ServerReqMsgDiscriminants::ReqPnl as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
-        msg.push_str(&make_field(&req_id)?);
-        msg.push_str(&make_field(&String::from(account))?);
-        msg.push_str(&make_field(&String::from(model_code))?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
+        msg.push(&req_id);
+        msg.push(&String::from(account));
+        msg.push(&String::from(model_code));
 
-        self.send_request(msg.as_str())
+        self.send_request(msg)
     }
 
     //----------------------------------------------------------------------------------------------
@@ -2476,26 +1941,14 @@ impl EClient {
     pub fn cancel_pnl(&mut self, req_id: i32) -> Result<(), IBKRApiLibError> {
         self.check_connected(req_id)?;
 
-        if self.server_version() < MIN_SERVER_VER_PNL {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                req_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support PnL request."
-                ),
-            ));
-
-            return Err(err);
-        }
+        self.check_feature(ServerFeature::Pnl)?;
 
         let message_id: i32 = ServerReqMsgDiscriminants::CancelPnl as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
-        msg.push_str(&make_field(&req_id)?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
+        msg.push(&req_id);
 
-        self.send_request(msg.as_str())
+        self.send_request(msg)
     }
 
     //----------------------------------------------------------------------------------------------
@@ -2515,29 +1968,17 @@ impl EClient {
     ) -> Result<(), IBKRApiLibError> {
         self.check_connected(req_id)?;
 
-        if self.server_version() < MIN_SERVER_VER_PNL {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                req_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support PnL request."
-                ),
-            ));
-
-            return Err(err);
-        }
+        self.check_feature(ServerFeature::Pnl)?;
 
         let message_id: i32 = ServerReqMsgDiscriminants::ReqPnlSingle as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
-        msg.push_str(&make_field(&req_id)?);
-        msg.push_str(&make_field(&String::from(account))?);
-        msg.push_str(&make_field(&String::from(model_code))?);
-        msg.push_str(&make_field(&con_id)?);
-
-        self.send_request(msg.as_str())
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
+        msg.push(&req_id);
+        msg.push(&String::from(account));
+        msg.push(&String::from(model_code));
+        msg.push(&con_id);
+
+        self.send_request(msg)
     }
 
     //----------------------------------------------------------------------------------------------
@@ -2548,26 +1989,14 @@ impl EClient {
     pub fn cancel_pnl_single(&mut self, req_id: i32) -> Result<(), IBKRApiLibError> {
         self.check_connected(req_id)?;
 
-        if self.server_version() < MIN_SERVER_VER_PNL {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                req_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support PnL request."
-                ),
-            ));
-
-            return Err(err);
-        }
+        self.check_feature(ServerFeature::Pnl)?;
 
         let message_id: i32 = ServerReqMsgDiscriminants::CancelPnlSingle as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
-        msg.push_str(&make_field(&req_id)?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
+        msg.push(&req_id);
 
-        self.send_request(msg.as_str())
+        self.send_request(msg)
     }
 
     //#########################################################################
@@ -2597,21 +2026,21 @@ impl EClient {
 
         let version = 3;
         let message_id: i32 = ServerReqMsgDiscriminants::ReqExecutions as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
-        msg.push_str(&make_field(&version)?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
+        msg.push(&version);
         if self.server_version() >= MIN_SERVER_VER_EXECUTION_DATA_CHAIN {
-            msg.push_str(&make_field(&req_id)?);
+            msg.push(&req_id);
         }
-        msg.push_str(&make_field(&exec_filter.client_id)?);
-        msg.push_str(&make_field(&exec_filter.acct_code)?);
-        msg.push_str(&make_field(&exec_filter.time)?);
-        msg.push_str(&make_field(&exec_filter.symbol)?);
-        msg.push_str(&make_field(&exec_filter.sec_type)?);
-        msg.push_str(&make_field(&exec_filter.exchange)?);
-        msg.push_str(&make_field(&exec_filter.side)?);
-
-        self.send_request(msg.as_str())
+        msg.push(&exec_filter.client_id);
+        msg.push(&exec_filter.acct_code);
+        msg.push(&exec_filter.time);
+        msg.push(&exec_filter.symbol);
+        msg.push(&exec_filter.sec_type);
+        msg.push(&exec_filter.exchange);
+        msg.push(&exec_filter.side);
+
+        self.send_request(msg)
     }
 
     //#########################################################################
@@ -2633,105 +2062,71 @@ impl EClient {
     ) -> Result<(), IBKRApiLibError> {
         self.check_connected(req_id)?;
 
-        if self.server_version() < MIN_SERVER_VER_SEC_ID_TYPE {
-            if contract.sec_id_type != "" || contract.sec_id != "" {
-                let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                    req_id,
-                    TwsError::UpdateTws.code().to_string(),
-                    format!(
-                        "{}{}",
-                        TwsError::UpdateTws.message(),
-                        " It does not support sec_id_type and secId parameters."
-                    ),
-                ));
+        contract.validate_for_lookup()?;
 
-                return Err(err);
-            }
+        if contract.sec_id_type != "" || contract.sec_id != "" {
+            self.check_feature(ServerFeature::SecIdType)?;
         }
 
-        if self.server_version() < MIN_SERVER_VER_TRADING_CLASS {
-            if contract.trading_class != "" {
-                let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                    req_id,
-                    TwsError::UpdateTws.code().to_string(),
-                    format!(
-                        "{}{}",
-                        TwsError::UpdateTws.message(),
-                        " It does not support trading_class parameter in req_contract_details."
-                    ),
-                ));
-
-                return Err(err);
-            }
+        if contract.trading_class != "" {
+            self.check_feature(ServerFeature::TradingClass)?;
         }
 
-        if self.server_version() < MIN_SERVER_VER_LINKING {
-            if contract.primary_exchange != "" {
-                let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                    req_id,
-                    TwsError::UpdateTws.code().to_string(),
-                    format!(
-                        "{}{}",
-                        TwsError::UpdateTws.message(),
-                        " It does not support primary_exchange parameter in req_contract_details."
-                    ),
-                ));
-
-                return Err(err);
-            }
+        if contract.primary_exchange != "" {
+            self.check_feature(ServerFeature::Linking)?;
         }
 
         let version = 8;
 
         let message_id: i32 = ServerReqMsgDiscriminants::ReqContractData as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
-        msg.push_str(&make_field(&version)?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
+        msg.push(&version);
 
         if self.server_version() >= MIN_SERVER_VER_CONTRACT_DATA_CHAIN {
-            msg.push_str(&make_field(&req_id)?);
+            msg.push(&req_id);
         }
 
         // send contract fields
-        msg.push_str(&make_field(&contract.con_id)?); // srv v37 and above
-        msg.push_str(&make_field(&contract.symbol)?);
+        msg.push(&contract.con_id); // srv v37 and above
+        msg.push(&contract.symbol);
 
-        msg.push_str(&make_field(&contract.sec_type)?);
-        msg.push_str(&make_field(&contract.last_trade_date_or_contract_month)?);
-        msg.push_str(&make_field(&contract.strike)?);
-        msg.push_str(&make_field(&contract.right)?);
-        msg.push_str(&make_field(&contract.multiplier)?); // srv v15 and above
+        msg.push(&contract.sec_type);
+        msg.push(&contract.last_trade_date_or_contract_month);
+        msg.push(&contract.strike);
+        msg.push(&contract.right);
+        msg.push(&contract.multiplier); // srv v15 and above
 
         if self.server_version() >= MIN_SERVER_VER_PRIMARYEXCH {
-            msg.push_str(&make_field(&contract.exchange)?);
-            msg.push_str(&make_field(&contract.primary_exchange)?);
+            msg.push(&contract.exchange);
+            msg.push(&contract.primary_exchange);
         } else if self.server_version() >= MIN_SERVER_VER_LINKING {
             if contract.primary_exchange != ""
                 && (contract.exchange == "BEST" || contract.exchange == "SMART")
             {
-                msg.push_str(&make_field(&format!(
+                msg.push(&format!(
                     "{}:{}",
                     &contract.exchange, &contract.primary_exchange
-                ))?);
+                ));
             }
         } else {
-            msg.push_str(&make_field(&contract.exchange)?);
+            msg.push(&contract.exchange);
         }
 
-        msg.push_str(&make_field(&contract.currency)?);
-        msg.push_str(&make_field(&contract.local_symbol)?);
+        msg.push(&contract.currency);
+        msg.push(&contract.local_symbol);
 
         if self.server_version() >= MIN_SERVER_VER_TRADING_CLASS {
-            msg.push_str(&make_field(&contract.trading_class)?);
-            msg.push_str(&make_field(&contract.include_expired)?); // srv v31 and above
+            msg.push(&contract.trading_class);
+            msg.push(&contract.include_expired); // srv v31 and above
         }
 
         if self.server_version() >= MIN_SERVER_VER_SEC_ID_TYPE {
-            msg.push_str(&make_field(&contract.sec_id_type)?);
-            msg.push_str(&make_field(&contract.sec_id)?);
+            msg.push(&contract.sec_id_type);
+            msg.push(&contract.sec_id);
         }
 
-        self.send_request(msg.as_str())
+        self.send_request(msg)
     }
 
     //#########################################################################
@@ -2741,25 +2136,13 @@ impl EClient {
     pub fn req_mkt_depth_exchanges(&mut self) -> Result<(), IBKRApiLibError> {
         self.check_connected(NO_VALID_ID)?;
 
-        if self.server_version() < MIN_SERVER_VER_REQ_MKT_DEPTH_EXCHANGES {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                NO_VALID_ID,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support market depth exchanges request."
-                ),
-            ));
-
-            return Err(err);
-        }
+        self.check_feature(ServerFeature::ReqMktDepthExchanges)?;
 
         let message_id: i32 = ServerReqMsgDiscriminants::ReqMktDepthExchanges as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
 
-        self.send_request(msg.as_str())
+        self.send_request(msg)
     }
 
     //----------------------------------------------------------------------------------------------
@@ -2793,50 +2176,18 @@ impl EClient {
     ) -> Result<(), IBKRApiLibError> {
         self.check_connected(NO_VALID_ID)?;
 
-        if self.server_version() < MIN_SERVER_VER_TRADING_CLASS {
-            if &contract.trading_class != "" || *&contract.con_id > 0 {
-                let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                    req_id,
-                    TwsError::UpdateTws.code().to_string(),
-                    format!(
-                        "{}{}",
-                        TwsError::UpdateTws.message(),
-                        " It does not support con_id and trading_class parameters in req_mkt_depth."
-                    ),
-                ));
+        contract.validate()?;
 
-                return Err(err);
-            }
+        if &contract.trading_class != "" || *&contract.con_id > 0 {
+            self.check_feature(ServerFeature::TradingClass)?;
         }
 
-        if self.server_version() < MIN_SERVER_VER_SMART_DEPTH && is_smart_depth {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                req_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support SMART depth request."
-                ),
-            ));
-
-            return Err(err);
+        if is_smart_depth {
+            self.check_feature(ServerFeature::SmartDepth)?;
         }
 
-        if self.server_version() < MIN_SERVER_VER_MKT_DEPTH_PRIM_EXCHANGE
-            && contract.primary_exchange != ""
-        {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                req_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support primary_exchange parameter in req_mkt_depth."
-                ),
-            ));
-
-            return Err(err);
+        if contract.primary_exchange != "" {
+            self.check_feature(ServerFeature::MktDepthPrimExchange)?;
         }
 
         let version = 5;
@@ -2844,36 +2195,36 @@ impl EClient {
         // send req mkt depth msg
 
         let message_id: i32 = ServerReqMsgDiscriminants::ReqMktDepth as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
-        msg.push_str(&make_field(&version)?);
-        msg.push_str(&make_field(&req_id)?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
+        msg.push(&version);
+        msg.push(&req_id);
 
         // send contract fields
         if self.server_version() >= MIN_SERVER_VER_TRADING_CLASS {
-            msg.push_str(&make_field(&contract.con_id)?);
+            msg.push(&contract.con_id);
         }
-        msg.push_str(&make_field(&contract.symbol)?);
-        msg.push_str(&make_field(&contract.sec_type)?);
-        msg.push_str(&make_field(&contract.last_trade_date_or_contract_month)?);
-        msg.push_str(&make_field(&contract.strike)?);
-        msg.push_str(&make_field(&contract.right)?);
-        msg.push_str(&make_field(&contract.multiplier)?); // srv v15 and above
-        msg.push_str(&make_field(&contract.exchange)?);
+        msg.push(&contract.symbol);
+        msg.push(&contract.sec_type);
+        msg.push(&contract.last_trade_date_or_contract_month);
+        msg.push(&contract.strike);
+        msg.push(&contract.right);
+        msg.push(&contract.multiplier); // srv v15 and above
+        msg.push(&contract.exchange);
 
         if self.server_version() >= MIN_SERVER_VER_MKT_DEPTH_PRIM_EXCHANGE {
-            msg.push_str(&make_field(&contract.primary_exchange)?);
+            msg.push(&contract.primary_exchange);
         }
-        msg.push_str(&make_field(&contract.currency)?);
-        msg.push_str(&make_field(&contract.local_symbol)?);
+        msg.push(&contract.currency);
+        msg.push(&contract.local_symbol);
 
         if self.server_version() >= MIN_SERVER_VER_TRADING_CLASS {
-            msg.push_str(&make_field(&contract.trading_class)?);
+            msg.push(&contract.trading_class);
         }
-        msg.push_str(&make_field(&num_rows)?); // srv v19 and above
+        msg.push(&num_rows); // srv v19 and above
 
         if self.server_version() >= MIN_SERVER_VER_SMART_DEPTH {
-            msg.push_str(&make_field(&is_smart_depth)?);
+            msg.push(&is_smart_depth);
         }
         // send mkt_depth_options parameter
         if self.server_version() >= MIN_SERVER_VER_LINKING {
@@ -2892,9 +2243,9 @@ impl EClient {
                 return Err(err);
             }
             let mkt_data_options_str = "";
-            msg.push_str(&make_field(&mkt_data_options_str)?);
+            msg.push(&mkt_data_options_str);
         }
-        self.send_request(msg.as_str())
+        self.send_request(msg)
     }
 
     //----------------------------------------------------------------------------------------------
@@ -2906,39 +2257,29 @@ impl EClient {
     //  * is_smart_depth - specifies SMART depth request
     //
     pub fn cancel_mkt_depth(
-        &mut self,
-        req_id: i32,
-        is_smart_depth: bool,
-    ) -> Result<(), IBKRApiLibError> {
-        self.check_connected(NO_VALID_ID)?;
-
-        if self.server_version() < MIN_SERVER_VER_SMART_DEPTH && is_smart_depth {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                req_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support SMART depth cancel."
-                ),
-            ));
+        &mut self,
+        req_id: i32,
+        is_smart_depth: bool,
+    ) -> Result<(), IBKRApiLibError> {
+        self.check_connected(NO_VALID_ID)?;
 
-            return Err(err);
+        if is_smart_depth {
+            self.check_feature(ServerFeature::SmartDepth)?;
         }
 
         let version = 1;
 
         let message_id: i32 = ServerReqMsgDiscriminants::CancelMktDepth as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
-        msg.push_str(&make_field(&version)?);
-        msg.push_str(&make_field(&req_id)?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
+        msg.push(&version);
+        msg.push(&req_id);
 
         if self.server_version() >= MIN_SERVER_VER_SMART_DEPTH {
-            msg.push_str(&make_field(&is_smart_depth)?);
+            msg.push(&is_smart_depth);
         }
 
-        self.send_request(msg.as_str())
+        self.send_request(msg)
     }
 
     //#########################################################################
@@ -2957,12 +2298,12 @@ impl EClient {
         let version = 1;
 
         let message_id: i32 = ServerReqMsgDiscriminants::ReqNewsBulletins as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
-        msg.push_str(&make_field(&version)?);
-        msg.push_str(&make_field(&all_msgs)?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
+        msg.push(&version);
+        msg.push(&all_msgs);
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -2974,10 +2315,10 @@ impl EClient {
         let version = 1;
 
         let message_id: i32 = ServerReqMsgDiscriminants::CancelNewsBulletins as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
-        msg.push_str(&make_field(&version)?);
-        self.send_request(msg.as_str())?;
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
+        msg.push(&version);
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -2993,10 +2334,10 @@ impl EClient {
 
         let version = 1;
         let message_id: i32 = ServerReqMsgDiscriminants::ReqManagedAccts as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
-        msg.push_str(&make_field(&version)?);
-        self.send_request(msg.as_str())
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
+        msg.push(&version);
+        self.send_request(msg)
     }
 
     //----------------------------------------------------------------------------------------------
@@ -3013,12 +2354,12 @@ impl EClient {
 
         let version = 1;
         let message_id: i32 = ServerReqMsgDiscriminants::ReqFa as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
-        msg.push_str(&make_field(&version)?);
-        msg.push_str(&make_field(&(fa_data as i32))?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
+        msg.push(&version);
+        msg.push(&(fa_data as i32));
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -3038,14 +2379,21 @@ impl EClient {
 
         let version = 1;
         let message_id: i32 = ServerReqMsgDiscriminants::ReplaceFa as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
 
-        msg.push_str(&make_field(&version)?);
-        msg.push_str(&make_field(&fa_data)?);
-        msg.push_str(&make_field(&String::from(cxml))?);
+        msg.push(&version);
+        msg.push(&fa_data);
+        msg.push(&String::from(cxml));
+
+        self.send_request(msg)
+    }
 
-        self.send_request(msg.as_str())
+    //----------------------------------------------------------------------------------------------
+    /// Replaces the FA groups, profiles or aliases with `items`, see `FaGroup`, `FaProfile` and
+    /// `FaAlias`. `diff_fa` shows what the replacement changes.
+    pub fn replace_fa_config<T: FaConfig>(&mut self, items: &[T]) -> Result<(), IBKRApiLibError> {
+        self.replace_fa(T::DATA_TYPE, &fa_to_xml(items))
     }
 
     //#########################################################################
@@ -3057,144 +2405,91 @@ impl EClient {
     ///
     /// # Arguments
     /// * req_id - The id of the request. Must be a unique value. When the
-    ///            market data returns, it whatToShowill be identified by this tag. This is also
+    ///            market data returns, it will be identified by this tag. This is also
     ///            used when canceling the market data.
-    /// * contract - This object contains a description of the contract for which
-    ///              market data is being requested.
-    /// * end_date_time - Defines a query end date and time at any point during the past 6 mos.
-    ///                   Valid values include any date/time within the past six months in the format:
-    ///                   yyyymmdd HH:mm:ss ttt where "ttt" is the optional time zone.
-    /// * duration_str - Set the query duration up to one week, using a time unit
-    ///                  of seconds, days or weeks. Valid values include any integer followed by a space
-    ///                  and then S (seconds)); D (days) or W (week). If no unit is specified, seconds is used.
-    /// * bar_size_setting - See the BarSize enum for valid values. Specifies the size of the bars that will be returned (within IB/TWS listimits).
-    ///                      Valid values include:
-    ///     * 1 sec
-    ///     * 5 secs
-    ///     * 15 secs
-    ///     * 30 secs
-    ///     * 1 min
-    ///     * 2 mins
-    ///     * 3 mins
-    ///     * 5 mins
-    ///     * 15 mins
-    ///     * 30 mins
-    ///     * 1 hour
-    ///     * 1 day
-    /// * what_to_show - See the WhatToShow enum for valid values.  Determines the nature of data beinging extracted. Valid values include:
-    ///
-    ///     * TRADES
-    ///     * MIDPOINT
-    ///     * BID
-    ///     * ASK
-    ///     * BID_ASK
-    ///     * HISTORICAL_VOLATILITY
-    ///     * OPTION_IMPLIED_VOLATILITY
-    /// * use_rth - Determines whether to return all data available during the requested time span,
-    ///             or only data that falls within regular trading hours. Valid values include:
-    ///
-    ///     * 0 - all data is returned even where the market in question was outside of its
-    ///                       regular trading hours.
-    ///     * 1 - only data within the regular trading hours is returned, even if the
-    ///                       requested time span falls partially or completely outside of the RTH.
-    /// * format_date - Determines the date format applied to returned bars. validd values include:
-    ///
-    ///     * 1 - dates applying to bars returned in the format: yyyymmdd{space}{space}hh:mm:dd
-    ///     * 2 - dates are returned as a long integer specifying the number of seconds since 1/1/1970 GMT.
-    /// *chart_options: - For internal use only. Use default value XYZ.
+    /// * request - The contract, duration, bar size and data type of the bars, see
+    ///             `HistoricalDataRequest::builder`. Invalid combinations are rejected before
+    ///             sending.
     pub fn req_historical_data(
         &mut self,
         req_id: i32,
-        contract: &Contract,
-        end_date_time: &str,
-        duration_str: &str,
-        bar_size_setting: &str,
-        what_to_show: &str,
-        use_rth: i32,
-        format_date: i32,
-        keep_up_to_date: bool,
-        chart_options: Vec<TagValue>,
+        request: &HistoricalDataRequest,
     ) -> Result<(), IBKRApiLibError> {
         self.check_connected(NO_VALID_ID)?;
 
-        if self.server_version() < MIN_SERVER_VER_TRADING_CLASS {
-            if &contract.trading_class != "" || contract.con_id > 0 {
-                let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                    req_id,
-                    TwsError::UpdateTws.code().to_string(),
-                    format!(
-                        "{}{}",
-                        TwsError::UpdateTws.message(),
-                        " It does not support con_id and trading_class parameters in req_historical_data."
-                    ),
-                ));
+        request.validate()?;
+        let contract = &request.contract;
 
-                return Err(err);
-            }
+        if &contract.trading_class != "" || contract.con_id > 0 {
+            self.check_feature(ServerFeature::TradingClass)?;
         }
 
         let version = 6;
 
         // send req mkt data msg
         let message_id: i32 = ServerReqMsgDiscriminants::ReqHistoricalData as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
 
         if self.server_version() < MIN_SERVER_VER_SYNT_REALTIME_BARS {
-            msg.push_str(&make_field(&version)?);
+            msg.push(&version);
         }
 
-        msg.push_str(&make_field(&req_id)?);
+        msg.push(&req_id);
 
         // Send contract fields
         if self.server_version() >= MIN_SERVER_VER_TRADING_CLASS {
-            msg.push_str(&make_field(&contract.con_id)?);
-            msg.push_str(&make_field(&contract.symbol)?);
-            msg.push_str(&make_field(&contract.sec_type)?);
-            msg.push_str(&make_field(&contract.last_trade_date_or_contract_month)?);
-            msg.push_str(&make_field(&contract.strike)?);
-            msg.push_str(&make_field(&contract.right)?);
-            msg.push_str(&make_field(&contract.multiplier)?);
-            msg.push_str(&make_field(&contract.exchange)?);
-            msg.push_str(&make_field(&contract.primary_exchange)?);
-            msg.push_str(&make_field(&contract.currency)?);
-            msg.push_str(&make_field(&contract.local_symbol)?);
+            msg.push(&contract.con_id);
+            msg.push(&contract.symbol);
+            msg.push(&contract.sec_type);
+            msg.push(&contract.last_trade_date_or_contract_month);
+            msg.push(&contract.strike);
+            msg.push(&contract.right);
+            msg.push(&contract.multiplier);
+            msg.push(&contract.exchange);
+            msg.push(&contract.primary_exchange);
+            msg.push(&contract.currency);
+            msg.push(&contract.local_symbol);
         }
         if self.server_version() >= MIN_SERVER_VER_TRADING_CLASS {
-            msg.push_str(&make_field(&contract.trading_class)?);
+            msg.push(&contract.trading_class);
         }
-        msg.push_str(&make_field(&contract.include_expired)?); // srv v31 and above
+        msg.push(&contract.include_expired); // srv v31 and above
 
-        msg.push_str(&make_field(&String::from(end_date_time))?); // srv v20 and above
-        msg.push_str(&make_field(&String::from(bar_size_setting))?); // srv v20 and above
-        msg.push_str(&make_field(&String::from(duration_str))?);
-        msg.push_str(&make_field(&use_rth)?);
-        msg.push_str(&make_field(&String::from(what_to_show))?);
-        msg.push_str(&make_field(&format_date)?); // srv v16 and above
+        let end_date_time = request
+            .end_date_time
+            .map_or_else(String::new, |end| format_tws_datetime(&end));
+        msg.push(&end_date_time); // srv v20 and above
+        msg.push(&request.bar_size.to_string()); // srv v20 and above
+        msg.push(&request.duration.to_string());
+        msg.push(&request.use_rth);
+        msg.push(&request.what_to_show.to_string());
+        msg.push(&request.format_date); // srv v16 and above
 
         // Send combo legs for BAG requests
         if contract.sec_type == "BAG" {
-            msg.push_str(&make_field(&contract.combo_legs.len())?);
+            msg.push(&contract.combo_legs.len());
             for combo_leg in &contract.combo_legs {
-                msg.push_str(&make_field(&combo_leg.con_id)?);
-                msg.push_str(&make_field(&combo_leg.ratio)?);
-                msg.push_str(&make_field(&combo_leg.action)?);
-                msg.push_str(&make_field(&combo_leg.exchange)?);
+                msg.push(&combo_leg.con_id);
+                msg.push(&combo_leg.ratio);
+                msg.push(&combo_leg.action);
+                msg.push(&combo_leg.exchange);
             }
         }
         if self.server_version() >= MIN_SERVER_VER_SYNT_REALTIME_BARS {
-            msg.push_str(&make_field(&keep_up_to_date)?);
+            msg.push(&request.keep_up_to_date);
         }
         // Send chart_options parameter
         if self.server_version() >= MIN_SERVER_VER_LINKING {
-            let chart_options_str = chart_options
+            let chart_options_str = request
+                .chart_options
                 .iter()
                 .map(|x| format!("{}={};", x.tag, x.value))
                 .collect::<String>();
-            msg.push_str(&make_field(&chart_options_str)?);
+            msg.push(&chart_options_str);
         }
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -3211,13 +2506,13 @@ impl EClient {
         let version = 1;
 
         let message_id: i32 = ServerReqMsgDiscriminants::CancelHistoricalData as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
 
-        msg.push_str(&make_field(&version)?);
-        msg.push_str(&make_field(&req_id)?);
+        msg.push(&version);
+        msg.push(&req_id);
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
 
         Ok(())
     }
@@ -3228,7 +2523,7 @@ impl EClient {
     /// # Arguments
     /// * req_id	- an identifier for the request
     /// * contract	- contract object for which head timestamp is being requested
-    /// * what_to_show	- type of data for head timestamp - "BID", "ASK", "TRADES", etc
+    /// * what_to_show	- type of data for head timestamp, e.g. `WhatToShow::Trades`
     /// * use_rth	- use regular trading hours only, 1 for yes or 0 for no
     /// * format_date	set to 1 to obtain the bars' time as yyyyMMdd HH:mm:ss, set to 2 to obtain it like system time format in seconds
     ///
@@ -3238,49 +2533,39 @@ impl EClient {
         &mut self,
         req_id: i32,
         contract: &Contract,
-        what_to_show: &str,
+        what_to_show: WhatToShow,
         use_rth: i32,
         format_date: i32,
     ) -> Result<(), IBKRApiLibError> {
         self.check_connected(NO_VALID_ID)?;
 
-        if self.server_version() < MIN_SERVER_VER_REQ_HEAD_TIMESTAMP {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                req_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support head time stamp requests."
-                ),
-            ));
+        contract.validate()?;
 
-            return Err(err);
-        }
+        self.check_feature(ServerFeature::ReqHeadTimestamp)?;
 
         let message_id: i32 = ServerReqMsgDiscriminants::ReqHeadTimestamp as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
-
-        msg.push_str(&make_field(&req_id)?);
-        msg.push_str(&make_field(&contract.con_id)?);
-        msg.push_str(&make_field(&contract.symbol)?);
-        msg.push_str(&make_field(&contract.sec_type)?);
-        msg.push_str(&make_field(&contract.last_trade_date_or_contract_month)?);
-        msg.push_str(&make_field(&contract.strike)?);
-        msg.push_str(&make_field(&contract.right)?);
-        msg.push_str(&make_field(&contract.multiplier)?);
-        msg.push_str(&make_field(&contract.exchange)?);
-        msg.push_str(&make_field(&contract.primary_exchange)?);
-        msg.push_str(&make_field(&contract.currency)?);
-        msg.push_str(&make_field(&contract.local_symbol)?);
-        msg.push_str(&make_field(&contract.trading_class)?);
-        msg.push_str(&make_field(&contract.include_expired)?);
-        msg.push_str(&make_field(&use_rth)?);
-        msg.push_str(&make_field(&String::from(what_to_show))?);
-        msg.push_str(&make_field(&format_date)?);
-
-        self.send_request(msg.as_str())?;
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
+
+        msg.push(&req_id);
+        msg.push(&contract.con_id);
+        msg.push(&contract.symbol);
+        msg.push(&contract.sec_type);
+        msg.push(&contract.last_trade_date_or_contract_month);
+        msg.push(&contract.strike);
+        msg.push(&contract.right);
+        msg.push(&contract.multiplier);
+        msg.push(&contract.exchange);
+        msg.push(&contract.primary_exchange);
+        msg.push(&contract.currency);
+        msg.push(&contract.local_symbol);
+        msg.push(&contract.trading_class);
+        msg.push(&contract.include_expired);
+        msg.push(&use_rth);
+        msg.push(&what_to_show.to_string());
+        msg.push(&format_date);
+
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -3292,27 +2577,15 @@ impl EClient {
     pub fn cancel_head_time_stamp(&mut self, req_id: i32) -> Result<(), IBKRApiLibError> {
         self.check_connected(NO_VALID_ID)?;
 
-        if self.server_version() < MIN_SERVER_VER_CANCEL_HEADTIMESTAMP {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                req_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support head time stamp requests."
-                ),
-            ));
-
-            return Err(err);
-        }
+        self.check_feature(ServerFeature::CancelHeadtimestamp)?;
 
         let message_id: i32 = ServerReqMsgDiscriminants::CancelHeadTimestamp as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
 
-        msg.push_str(&make_field(&req_id)?);
+        msg.push(&req_id);
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -3323,52 +2596,42 @@ impl EClient {
     /// * ticker_id - an identifier for the request
     /// * contract - Contract object for which histogram is being requested
     /// * use_rth - use regular trading hours only, 1 for yes or 0 for no
-    /// * time_period - period of which data is being requested, e.g. "3 days"
+    /// * time_period - period of which data is being requested, e.g. 3 days
     pub fn req_histogram_data(
         &mut self,
         ticker_id: i32,
         contract: &Contract,
         use_rth: bool,
-        time_period: &str,
+        time_period: Duration,
     ) -> Result<(), IBKRApiLibError> {
         self.check_connected(NO_VALID_ID)?;
 
-        if self.server_version() < MIN_SERVER_VER_REQ_HISTOGRAM {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                NO_VALID_ID,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support histogram requests."
-                ),
-            ));
+        contract.validate()?;
 
-            return Err(err);
-        }
+        self.check_feature(ServerFeature::ReqHistogram)?;
 
         let message_id: i32 = ServerReqMsgDiscriminants::ReqHistogramData as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
-
-        msg.push_str(&make_field(&ticker_id)?);
-        msg.push_str(&make_field(&contract.con_id)?);
-        msg.push_str(&make_field(&contract.symbol)?);
-        msg.push_str(&make_field(&contract.sec_type)?);
-        msg.push_str(&make_field(&contract.last_trade_date_or_contract_month)?);
-        msg.push_str(&make_field(&contract.strike)?);
-        msg.push_str(&make_field(&contract.right)?);
-        msg.push_str(&make_field(&contract.multiplier)?);
-        msg.push_str(&make_field(&contract.exchange)?);
-        msg.push_str(&make_field(&contract.primary_exchange)?);
-        msg.push_str(&make_field(&contract.currency)?);
-        msg.push_str(&make_field(&contract.local_symbol)?);
-        msg.push_str(&make_field(&contract.trading_class)?);
-        msg.push_str(&make_field(&contract.include_expired)?);
-        msg.push_str(&make_field(&use_rth)?);
-        msg.push_str(&make_field(&String::from(time_period))?);
-
-        self.send_request(msg.as_str())?;
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
+
+        msg.push(&ticker_id);
+        msg.push(&contract.con_id);
+        msg.push(&contract.symbol);
+        msg.push(&contract.sec_type);
+        msg.push(&contract.last_trade_date_or_contract_month);
+        msg.push(&contract.strike);
+        msg.push(&contract.right);
+        msg.push(&contract.multiplier);
+        msg.push(&contract.exchange);
+        msg.push(&contract.primary_exchange);
+        msg.push(&contract.currency);
+        msg.push(&contract.local_symbol);
+        msg.push(&contract.trading_class);
+        msg.push(&contract.include_expired);
+        msg.push(&use_rth);
+        msg.push(&time_period.period());
+
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -3380,26 +2643,14 @@ impl EClient {
     pub fn cancel_histogram_data(&mut self, ticker_id: i32) -> Result<(), IBKRApiLibError> {
         self.check_connected(NO_VALID_ID)?;
 
-        if self.server_version() < MIN_SERVER_VER_REQ_HISTOGRAM {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                NO_VALID_ID,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support histogram requests."
-                ),
-            ));
-
-            return Err(err);
-        }
+        self.check_feature(ServerFeature::ReqHistogram)?;
 
         let message_id: i32 = ServerReqMsgDiscriminants::CancelHistogramData as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
-        msg.push_str(&make_field(&ticker_id)?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
+        msg.push(&ticker_id);
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -3409,8 +2660,8 @@ impl EClient {
     /// # Arguments
     /// * req_id - id of the request
     /// * contract - Contract object that is subject of query
-    /// * start_date_time,i.e.	"20170701 12:01:00". Uses TWS timezone specified at login.
-    /// * end_date_time,i.e.	"20170701 13:01:00". In TWS timezone. Exactly one of start time and end time has to be defined.
+    /// * start_date_time - Start of the ticks requested
+    /// * end_date_time - End of the ticks requested. Exactly one of start time and end time has to be defined.
     /// * number_of_ticks - Number of distinct data points. Max currently 1000 per request.
     /// * what_to_show - (Bid_Ask, Midpoint, Trades) Type of data requested.
     /// * use_rth - Data from regular trading hours (1), or all available hours (0)
@@ -3420,8 +2671,8 @@ impl EClient {
         &mut self,
         req_id: i32,
         contract: &Contract,
-        start_date_time: &str,
-        end_date_time: &str,
+        start_date_time: Option<DateTime<Utc>>,
+        end_date_time: Option<DateTime<Utc>>,
         number_of_ticks: i32,
         what_to_show: &str,
         use_rth: i32,
@@ -3430,53 +2681,46 @@ impl EClient {
     ) -> Result<(), IBKRApiLibError> {
         self.check_connected(NO_VALID_ID)?;
 
-        if self.server_version() < MIN_SERVER_VER_HISTORICAL_TICKS {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                NO_VALID_ID,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support historical ticks requests."
-                ),
-            ));
+        contract.validate()?;
 
-            return Err(err);
-        }
+        self.check_feature(ServerFeature::HistoricalTicks)?;
 
         let message_id: i32 = ServerReqMsgDiscriminants::ReqHistoricalTicks as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
-
-        msg.push_str(&make_field(&req_id)?);
-        msg.push_str(&make_field(&contract.con_id)?);
-        msg.push_str(&make_field(&contract.symbol)?);
-        msg.push_str(&make_field(&contract.sec_type)?);
-        msg.push_str(&make_field(&contract.last_trade_date_or_contract_month)?);
-        msg.push_str(&make_field(&contract.strike)?);
-        msg.push_str(&make_field(&contract.right)?);
-        msg.push_str(&make_field(&contract.multiplier)?);
-        msg.push_str(&make_field(&contract.exchange)?);
-        msg.push_str(&make_field(&contract.primary_exchange)?);
-        msg.push_str(&make_field(&contract.currency)?);
-        msg.push_str(&make_field(&contract.local_symbol)?);
-        msg.push_str(&make_field(&contract.trading_class)?);
-        msg.push_str(&make_field(&contract.include_expired)?);
-        msg.push_str(&make_field(&String::from(start_date_time))?);
-        msg.push_str(&make_field(&String::from(end_date_time))?);
-        msg.push_str(&make_field(&number_of_ticks)?);
-        msg.push_str(&make_field(&String::from(what_to_show))?);
-        msg.push_str(&make_field(&use_rth)?);
-        msg.push_str(&make_field(&ignore_size)?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
+
+        msg.push(&req_id);
+        msg.push(&contract.con_id);
+        msg.push(&contract.symbol);
+        msg.push(&contract.sec_type);
+        msg.push(&contract.last_trade_date_or_contract_month);
+        msg.push(&contract.strike);
+        msg.push(&contract.right);
+        msg.push(&contract.multiplier);
+        msg.push(&contract.exchange);
+        msg.push(&contract.primary_exchange);
+        msg.push(&contract.currency);
+        msg.push(&contract.local_symbol);
+        msg.push(&contract.trading_class);
+        msg.push(&contract.include_expired);
+        let start_date_time =
+            start_date_time.map_or_else(String::new, |start| format_tws_datetime(&start));
+        let end_date_time = end_date_time.map_or_else(String::new, |end| format_tws_datetime(&end));
+        msg.push(&start_date_time);
+        msg.push(&end_date_time);
+        msg.push(&number_of_ticks);
+        msg.push(&String::from(what_to_show));
+        msg.push(&use_rth);
+        msg.push(&ignore_size);
 
         let misc_options_string = misc_options
             .iter()
             .map(|x| format!("{}={};", x.tag, x.value))
             .collect::<String>();
 
-        msg.push_str(&make_field(&misc_options_string)?);
+        msg.push(&misc_options_string);
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -3484,7 +2728,8 @@ impl EClient {
     //################## Market Scanners
     //#########################################################################
     /// Requests an XML list of scanner parameters valid in TWS.
-    /// Not all parameters are valid from API scanner.
+    /// Not all parameters are valid from API scanner. ScannerCatalog::parse() reads
+    /// the list, and ScannerSubscription::validate() checks a subscription against it.
     pub fn req_scanner_parameters(&mut self) -> Result<(), IBKRApiLibError> {
         /*Requests an XML string that describes all possible scanner queries*/
 
@@ -3492,11 +2737,11 @@ impl EClient {
 
         let version = 1;
         let message_id: i32 = ServerReqMsgDiscriminants::ReqScannerParameters as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
-        msg.push_str(&make_field(&version)?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
+        msg.push(&version);
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -3517,53 +2762,39 @@ impl EClient {
         self.check_connected(req_id)?;
 
         error!("Server version: {}", self.server_version());
-        if self.server_version() < MIN_SERVER_VER_SCANNER_GENERIC_OPTS {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                req_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support API scanner subscription generic filter options"
-                ),
-            ));
-
-            return Err(err);
-        }
+        self.check_feature(ServerFeature::ScannerGenericOpts)?;
 
         let version = 4;
 
         let message_id: i32 = ServerReqMsgDiscriminants::ReqScannerSubscription as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
 
         if self.server_version() < MIN_SERVER_VER_SCANNER_GENERIC_OPTS {
-            msg.push_str(&make_field(&version)?);
-        }
-        msg.push_str(&make_field(&req_id)?);
-        msg.push_str(&make_field_handle_empty(&subscription.number_of_rows)?);
-        msg.push_str(&make_field(&subscription.instrument)?);
-        msg.push_str(&make_field(&subscription.location_code)?);
-        msg.push_str(&make_field(&subscription.scan_code)?);
-        msg.push_str(&make_field_handle_empty(&subscription.above_price)?);
-        msg.push_str(&make_field_handle_empty(&subscription.below_price)?);
-        msg.push_str(&make_field_handle_empty(&subscription.above_volume)?);
-        msg.push_str(&make_field_handle_empty(&subscription.market_cap_above)?);
-        msg.push_str(&make_field_handle_empty(&subscription.market_cap_below)?);
-        msg.push_str(&make_field(&subscription.moody_rating_above)?);
-        msg.push_str(&make_field(&subscription.moody_rating_below)?);
-        msg.push_str(&make_field(&subscription.sp_rating_above)?);
-        msg.push_str(&make_field(&subscription.sp_rating_below)?);
-        msg.push_str(&make_field(&subscription.maturity_date_above)?);
-        msg.push_str(&make_field(&subscription.maturity_date_below)?);
-        msg.push_str(&make_field_handle_empty(&subscription.coupon_rate_above)?);
-        msg.push_str(&make_field_handle_empty(&subscription.coupon_rate_below)?);
-        msg.push_str(&make_field(&subscription.exclude_convertible)?);
-        msg.push_str(&make_field_handle_empty(
-            &subscription.average_option_volume_above,
-        )?); // srv v25 and above
-        msg.push_str(&make_field(&subscription.scanner_setting_pairs)?); // srv v25 and above
-        msg.push_str(&make_field(&subscription.stock_type_filter)?); // srv v27 and above
+            msg.push(&version);
+        }
+        msg.push(&req_id);
+        msg.push(&subscription.number_of_rows);
+        msg.push(&subscription.instrument);
+        msg.push(&subscription.location_code);
+        msg.push(&subscription.scan_code);
+        msg.push(&subscription.above_price);
+        msg.push(&subscription.below_price);
+        msg.push(&subscription.above_volume);
+        msg.push(&subscription.market_cap_above);
+        msg.push(&subscription.market_cap_below);
+        msg.push(&subscription.moody_rating_above);
+        msg.push(&subscription.moody_rating_below);
+        msg.push(&subscription.sp_rating_above);
+        msg.push(&subscription.sp_rating_below);
+        msg.push(&subscription.maturity_date_above);
+        msg.push(&subscription.maturity_date_below);
+        msg.push(&subscription.coupon_rate_above);
+        msg.push(&subscription.coupon_rate_below);
+        msg.push(&subscription.exclude_convertible);
+        msg.push(&subscription.average_option_volume_above); // srv v25 and above
+        msg.push(&subscription.scanner_setting_pairs); // srv v25 and above
+        msg.push(&subscription.stock_type_filter); // srv v27 and above
 
         // Send scanner_subscription_filter_options parameter
         if self.server_version() >= MIN_SERVER_VER_SCANNER_GENERIC_OPTS {
@@ -3573,7 +2804,7 @@ impl EClient {
                 .map(|x| format!("{}={};", x.tag, x.value))
                 .collect::<String>();
 
-            msg.push_str(&make_field(&scanner_subscription_filter)?);
+            msg.push(&scanner_subscription_filter);
         }
         // Send scanner_subscription_options parameter
         if self.server_version() >= MIN_SERVER_VER_LINKING {
@@ -3581,11 +2812,11 @@ impl EClient {
                 .iter()
                 .map(|x| format!("{}={};", x.tag, x.value))
                 .collect::<String>();
-            msg.push_str(&make_field(&scanner_subscription_options)?);
+            msg.push(&scanner_subscription_options);
         }
         error!("req_scanner_subscription");
-        error!("{}", msg);
-        self.send_request(msg.as_str())?;
+        error!("{}", msg.as_str());
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -3602,13 +2833,13 @@ impl EClient {
         let version = 1;
 
         let message_id: i32 = ServerReqMsgDiscriminants::CancelScannerSubscription as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
 
-        msg.push_str(&make_field(&version)?);
-        msg.push_str(&make_field(&req_id)?);
+        msg.push(&version);
+        msg.push(&req_id);
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -3625,7 +2856,7 @@ impl EClient {
     /// * contract - This object contains a description of the contract
     ///              for which real time bars are being requested
     /// * bar_size - Currently only 5 second bars are supported, if any other
-    ///              value is used, an exception will be thrown.
+    ///              value is used, an error is returned.
     /// * what_to_show - Determines the nature of the data extracted. Valid
     ///                  values include:
     ///                  * TRADES
@@ -3644,58 +2875,62 @@ impl EClient {
         &mut self,
         req_id: i32,
         contract: &Contract,
-        bar_size: i32,
-        what_to_show: &str,
+        bar_size: BarSize,
+        what_to_show: WhatToShow,
         use_rth: bool,
         real_time_bars_options: Vec<TagValue>,
     ) -> Result<(), IBKRApiLibError> {
         self.check_connected(NO_VALID_ID)?;
 
-        if self.server_version() < MIN_SERVER_VER_TRADING_CLASS {
-            if !contract.trading_class.is_empty() {
-                let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                    NO_VALID_ID,
-                    TwsError::UpdateTws.code().to_string(),
-                    format!(
-                        "{}{}",
-                        TwsError::UpdateTws.message(),
-                        " It does not support con_id and trading_class parameter in req_real_time_bars."
-                    ),
-                ));
+        contract.validate()?;
 
-                return Err(err);
-            }
+        if bar_size != BarSize::_5Secs {
+            return Err(IBKRApiLibError::InvalidHistoricalRequest {
+                reason: format!(
+                    "real time bars are only available as 5 secs, not {}",
+                    bar_size
+                ),
+            });
+        }
+        if !what_to_show.is_real_time() {
+            return Err(IBKRApiLibError::InvalidHistoricalRequest {
+                reason: format!("real time bars are not available for {}", what_to_show),
+            });
+        }
+
+        if !contract.trading_class.is_empty() {
+            self.check_feature(ServerFeature::TradingClass)?;
         }
 
         let version = 3;
 
         let message_id: i32 = ServerReqMsgDiscriminants::ReqRealTimeBars as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
 
-        msg.push_str(&make_field(&version)?);
-        msg.push_str(&make_field(&req_id)?);
+        msg.push(&version);
+        msg.push(&req_id);
 
         // Send contract fields
         if self.server_version() >= MIN_SERVER_VER_TRADING_CLASS {
-            msg.push_str(&make_field(&contract.con_id)?);
-        }
-        msg.push_str(&make_field(&contract.symbol)?);
-        msg.push_str(&make_field(&contract.sec_type)?);
-        msg.push_str(&make_field(&contract.last_trade_date_or_contract_month)?);
-        msg.push_str(&make_field(&contract.strike)?);
-        msg.push_str(&make_field(&contract.right)?);
-        msg.push_str(&make_field(&contract.multiplier)?);
-        msg.push_str(&make_field(&contract.exchange)?);
-        msg.push_str(&make_field(&contract.primary_exchange)?);
-        msg.push_str(&make_field(&contract.currency)?);
-        msg.push_str(&make_field(&contract.local_symbol)?);
+            msg.push(&contract.con_id);
+        }
+        msg.push(&contract.symbol);
+        msg.push(&contract.sec_type);
+        msg.push(&contract.last_trade_date_or_contract_month);
+        msg.push(&contract.strike);
+        msg.push(&contract.right);
+        msg.push(&contract.multiplier);
+        msg.push(&contract.exchange);
+        msg.push(&contract.primary_exchange);
+        msg.push(&contract.currency);
+        msg.push(&contract.local_symbol);
         if self.server_version() >= MIN_SERVER_VER_TRADING_CLASS {
-            msg.push_str(&make_field(&contract.trading_class)?);
+            msg.push(&contract.trading_class);
         }
-        msg.push_str(&make_field(&bar_size)?);
-        msg.push_str(&make_field(&String::from(what_to_show))?);
-        msg.push_str(&make_field(&use_rth)?);
+        msg.push(&(bar_size.seconds() as i32));
+        msg.push(&what_to_show.to_string());
+        msg.push(&use_rth);
 
         // Send real_time_bars_options parameter
         if self.server_version() >= MIN_SERVER_VER_LINKING {
@@ -3704,10 +2939,10 @@ impl EClient {
                 .map(|x| format!("{}={};", x.tag, x.value))
                 .collect::<String>();
 
-            msg.push_str(&make_field(&real_time_bars_options_str)?);
+            msg.push(&real_time_bars_options_str);
         }
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -3723,13 +2958,13 @@ impl EClient {
 
         // Send req mkt data msg
         let message_id: i32 = ServerReqMsgDiscriminants::CancelRealTimeBars as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
 
-        msg.push_str(&make_field(&version)?);
-        msg.push_str(&make_field(&req_id)?);
+        msg.push(&version);
+        msg.push(&req_id);
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -3739,7 +2974,8 @@ impl EClient {
     /// Call this function to receive fundamental data for
     /// stocks. The appropriate market data subscription must be set up in
     /// Account Management before you can receive this data.
-    /// Fundamental data will be returned at EWrapper.fundamentalData().
+    /// Fundamental data will be returned at EWrapper.fundamentalData(), and
+    /// fundamentals::parse_fundamental_data() turns it into a typed report.
     ///
     /// req_fundamental_data() can handle conid specified in the Contract object,
     /// but not trading_class or multiplier. This is because req_fundamental_data()
@@ -3767,54 +3003,32 @@ impl EClient {
     ) -> Result<(), IBKRApiLibError> {
         self.check_connected(NO_VALID_ID)?;
 
-        let version = 2;
-
-        if self.server_version() < MIN_SERVER_VER_FUNDAMENTAL_DATA {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                req_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support fundamental data request."
-                ),
-            ));
+        contract.validate()?;
 
-            return Err(err);
-        }
+        let version = 2;
 
-        if self.server_version() < MIN_SERVER_VER_TRADING_CLASS {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                req_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support con_id parameter in fundamental data request."
-                ),
-            ));
+        self.check_feature(ServerFeature::FundamentalData)?;
 
-            return Err(err);
-        }
+        self.check_feature(ServerFeature::TradingClass)?;
 
         let message_id: i32 = ServerReqMsgDiscriminants::ReqFundamentalData as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
 
-        msg.push_str(&make_field(&version)?);
-        msg.push_str(&make_field(&req_id)?);
+        msg.push(&version);
+        msg.push(&req_id);
 
         // Send contract fields
         if self.server_version() >= MIN_SERVER_VER_TRADING_CLASS {
-            msg.push_str(&make_field(&contract.con_id)?);
+            msg.push(&contract.con_id);
         }
-        msg.push_str(&make_field(&contract.symbol)?);
-        msg.push_str(&make_field(&contract.sec_type)?);
-        msg.push_str(&make_field(&contract.exchange)?);
-        msg.push_str(&make_field(&contract.primary_exchange)?);
-        msg.push_str(&make_field(&contract.currency)?);
-        msg.push_str(&make_field(&contract.local_symbol)?);
-        msg.push_str(&make_field(&String::from(report_type))?);
+        msg.push(&contract.symbol);
+        msg.push(&contract.sec_type);
+        msg.push(&contract.exchange);
+        msg.push(&contract.primary_exchange);
+        msg.push(&contract.currency);
+        msg.push(&contract.local_symbol);
+        msg.push(&String::from(report_type));
 
         if self.server_version() >= MIN_SERVER_VER_LINKING {
             let tags_value_count = fundamental_data_options.len();
@@ -3823,11 +3037,11 @@ impl EClient {
                 .map(|x| format!("{}={};", x.tag, x.value))
                 .collect::<String>();
 
-            msg.push_str(&make_field(&tags_value_count)?);
-            msg.push_str(&make_field(&fund_data_opt_str)?);
+            msg.push(&tags_value_count);
+            msg.push(&fund_data_opt_str);
         }
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -3839,30 +3053,18 @@ impl EClient {
     pub fn cancel_fundamental_data(&mut self, req_id: i32) -> Result<(), IBKRApiLibError> {
         self.check_connected(NO_VALID_ID)?;
 
-        if self.server_version() < MIN_SERVER_VER_FUNDAMENTAL_DATA {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                req_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support fundamental data request."
-                ),
-            ));
-
-            return Err(err);
-        }
+        self.check_feature(ServerFeature::FundamentalData)?;
 
         let version = 1;
 
         let message_id: i32 = ServerReqMsgDiscriminants::CancelFundamentalData as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
 
-        msg.push_str(&make_field(&version)?);
-        msg.push_str(&make_field(&req_id)?);
+        msg.push(&version);
+        msg.push(&req_id);
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -3874,25 +3076,13 @@ impl EClient {
     pub fn req_news_providers(&mut self) -> Result<(), IBKRApiLibError> {
         self.check_connected(NO_VALID_ID)?;
 
-        if self.server_version() < MIN_SERVER_VER_REQ_NEWS_PROVIDERS {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                NO_VALID_ID,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support news providers request."
-                ),
-            ));
-
-            return Err(err);
-        }
+        self.check_feature(ServerFeature::ReqNewsProviders)?;
 
         let message_id: i32 = ServerReqMsgDiscriminants::ReqNewsProviders as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -3914,27 +3104,15 @@ impl EClient {
     ) -> Result<(), IBKRApiLibError> {
         self.check_connected(NO_VALID_ID)?;
 
-        if self.server_version() < MIN_SERVER_VER_REQ_NEWS_ARTICLE {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                req_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support news article request."
-                ),
-            ));
-
-            return Err(err);
-        }
+        self.check_feature(ServerFeature::ReqNewsArticle)?;
 
         let message_id: i32 = ServerReqMsgDiscriminants::ReqNewsArticle as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
 
-        msg.push_str(&make_field(&req_id)?);
-        msg.push_str(&make_field(&String::from(provider_code))?);
-        msg.push_str(&make_field(&String::from(article_id))?);
+        msg.push(&req_id);
+        msg.push(&String::from(provider_code));
+        msg.push(&String::from(article_id));
 
         // Send news_article_options parameter
         if self.server_version() >= MIN_SERVER_VER_NEWS_QUERY_ORIGINS {
@@ -3942,10 +3120,10 @@ impl EClient {
                 .iter()
                 .map(|x| format!("{}={};", x.tag, x.value))
                 .collect::<String>();
-            msg.push_str(&make_field(&news_article_options_str)?);
+            msg.push(&news_article_options_str);
         }
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -3957,8 +3135,8 @@ impl EClient {
     /// * req_id - id of the request
     /// * con_id - contract id
     /// * provider_codes - a '+'-separated list of provider codes
-    /// * start_date_time	- marks the (exclusive) start of the date range. The format is yyyy-MM-dd HH:mm:ss.0
-    /// * end_date_time	- marks the (inclusive) end of the date range. The format is yyyy-MM-dd HH:mm:ss.0
+    /// * start_date_time	- marks the (exclusive) start of the date range, `None` for no limit
+    /// * end_date_time	- marks the (inclusive) end of the date range, `None` for no limit
     /// * total_results	- the maximum number of headlines to fetch (1 - 300)
     /// * historical_news_options	reserved for internal use. Should be defined as null.
     pub fn req_historical_news(
@@ -3966,37 +3144,29 @@ impl EClient {
         req_id: i32,
         con_id: i32,
         provider_codes: &str,
-        start_date_time: &str,
-        end_date_time: &str,
+        start_date_time: Option<DateTime<Utc>>,
+        end_date_time: Option<DateTime<Utc>>,
         total_results: i32,
         historical_news_options: Vec<TagValue>,
     ) -> Result<(), IBKRApiLibError> {
         self.check_connected(NO_VALID_ID)?;
 
-        if self.server_version() < MIN_SERVER_VER_REQ_HISTORICAL_NEWS {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                req_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support historical news request."
-                ),
-            ));
-
-            return Err(err);
-        }
+        self.check_feature(ServerFeature::ReqHistoricalNews)?;
 
         let message_id: i32 = ServerReqMsgDiscriminants::ReqHistoricalNews as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
-
-        msg.push_str(&make_field(&req_id)?);
-        msg.push_str(&make_field(&con_id)?);
-        msg.push_str(&make_field(&String::from(provider_codes))?);
-        msg.push_str(&make_field(&String::from(start_date_time))?);
-        msg.push_str(&make_field(&String::from(end_date_time))?);
-        msg.push_str(&make_field(&total_results)?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
+
+        msg.push(&req_id);
+        msg.push(&con_id);
+        msg.push(&String::from(provider_codes));
+        let start_date_time =
+            start_date_time.map_or_else(String::new, |start| format_news_datetime(&start));
+        let end_date_time =
+            end_date_time.map_or_else(String::new, |end| format_news_datetime(&end));
+        msg.push(&start_date_time);
+        msg.push(&end_date_time);
+        msg.push(&total_results);
 
         // Send historical_news_options parameter
         if self.server_version() >= MIN_SERVER_VER_NEWS_QUERY_ORIGINS {
@@ -4004,13 +3174,31 @@ impl EClient {
                 .iter()
                 .map(|x| format!("{}={};", x.tag, x.value))
                 .collect::<String>();
-            msg.push_str(&make_field(&historical_news_options_str)?);
+            msg.push(&historical_news_options_str);
         }
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
         Ok(())
     }
 
+    //----------------------------------------------------------------------------------------------
+    /// Iterates over pages of historical news headlines, sending `req_historical_news` for each
+    /// page. Set the date range, page size and timeout on the returned `HistoricalNewsPages`.
+    ///
+    /// # Arguments
+    ///
+    /// * req_id - id of the requests
+    /// * con_id - contract id
+    /// * provider_codes - a '+'-separated list of provider codes
+    pub fn historical_news(
+        &mut self,
+        req_id: i32,
+        con_id: i32,
+        provider_codes: &str,
+    ) -> HistoricalNewsPages<'_> {
+        HistoricalNewsPages::new(self, req_id, con_id, provider_codes)
+    }
+
     //#########################################################################
     //################## Display Groups
     //#########################################################################
@@ -4025,29 +3213,17 @@ impl EClient {
     pub fn query_display_groups(&mut self, req_id: i32) -> Result<(), IBKRApiLibError> {
         self.check_connected(NO_VALID_ID)?;
 
-        if self.server_version() < MIN_SERVER_VER_LINKING {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                req_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support query_display_groups request."
-                ),
-            ));
-
-            return Err(err);
-        }
+        self.check_feature(ServerFeature::Linking)?;
 
         let version = 1;
 
         let message_id: i32 = ServerReqMsgDiscriminants::QueryDisplayGroups as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
-        msg.push_str(&make_field(&version)?);
-        msg.push_str(&make_field(&req_id)?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
+        msg.push(&version);
+        msg.push(&req_id);
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -4064,31 +3240,19 @@ impl EClient {
     ) -> Result<(), IBKRApiLibError> {
         self.check_connected(NO_VALID_ID)?;
 
-        if self.server_version() < MIN_SERVER_VER_LINKING {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                req_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support subscribe_to_group_events request."
-                ),
-            ));
-
-            return Err(err);
-        }
+        self.check_feature(ServerFeature::Linking)?;
 
         let version = 1;
 
         let message_id: i32 = ServerReqMsgDiscriminants::SubscribeToGroupEvents as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
 
-        msg.push_str(&make_field(&version)?);
-        msg.push_str(&make_field(&req_id)?);
-        msg.push_str(&make_field(&group_id)?);
+        msg.push(&version);
+        msg.push(&req_id);
+        msg.push(&group_id);
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -4109,31 +3273,19 @@ impl EClient {
     ) -> Result<(), IBKRApiLibError> {
         self.check_connected(NO_VALID_ID)?;
 
-        if self.server_version() < MIN_SERVER_VER_LINKING {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                req_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support update_display_group request."
-                ),
-            ));
-
-            return Err(err);
-        }
+        self.check_feature(ServerFeature::Linking)?;
 
         let version = 1;
 
         let message_id: i32 = ServerReqMsgDiscriminants::UpdateDisplayGroup as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
 
-        msg.push_str(&make_field(&version)?);
-        msg.push_str(&make_field(&req_id)?);
-        msg.push_str(&make_field(&String::from(contract_info))?);
+        msg.push(&version);
+        msg.push(&req_id);
+        msg.push(&String::from(contract_info));
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -4145,30 +3297,18 @@ impl EClient {
     pub fn unsubscribe_from_group_events(&mut self, req_id: i32) -> Result<(), IBKRApiLibError> {
         self.check_connected(NO_VALID_ID)?;
 
-        if self.server_version() < MIN_SERVER_VER_LINKING {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                req_id,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support unsubscribe_from_group_events request."
-                ),
-            ));
-
-            return Err(err);
-        }
+        self.check_feature(ServerFeature::Linking)?;
 
         let version = 1;
 
         let message_id: i32 = ServerReqMsgDiscriminants::UnsubscribeFromGroupEvents as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
 
-        msg.push_str(&make_field(&version)?);
-        msg.push_str(&make_field(&req_id)?);
+        msg.push(&version);
+        msg.push(&req_id);
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -4181,19 +3321,7 @@ impl EClient {
     ) -> Result<(), IBKRApiLibError> {
         self.check_connected(NO_VALID_ID)?;
 
-        if self.server_version() < MIN_SERVER_VER_LINKING {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                NO_VALID_ID,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support verification request."
-                ),
-            ));
-
-            return Err(err);
-        }
+        self.check_feature(ServerFeature::Linking)?;
 
         if !self.extra_auth {
             let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
@@ -4212,14 +3340,14 @@ impl EClient {
         let version = 1;
 
         let message_id: i32 = ServerReqMsgDiscriminants::VerifyRequest as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
 
-        msg.push_str(&make_field(&version)?);
-        msg.push_str(&make_field(&String::from(api_name))?);
-        msg.push_str(&make_field(&String::from(api_version))?);
+        msg.push(&version);
+        msg.push(&String::from(api_name));
+        msg.push(&String::from(api_version));
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -4228,30 +3356,18 @@ impl EClient {
     pub fn verify_message(&mut self, api_data: &'static str) -> Result<(), IBKRApiLibError> {
         self.check_connected(NO_VALID_ID)?;
 
-        if self.server_version() < MIN_SERVER_VER_LINKING {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                NO_VALID_ID,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support verification request."
-                ),
-            ));
-
-            return Err(err);
-        }
+        self.check_feature(ServerFeature::Linking)?;
 
         let version = 1;
 
         let message_id: i32 = ServerReqMsgDiscriminants::VerifyMessage as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
 
-        msg.push_str(&make_field(&version)?);
-        msg.push_str(&make_field(&api_data)?);
+        msg.push(&version);
+        msg.push(&api_data);
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -4265,19 +3381,7 @@ impl EClient {
     ) -> Result<(), IBKRApiLibError> {
         self.check_connected(NO_VALID_ID)?;
 
-        if self.server_version() < MIN_SERVER_VER_LINKING {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                NO_VALID_ID,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support verification request."
-                ),
-            ));
-
-            return Err(err);
-        }
+        self.check_feature(ServerFeature::Linking)?;
 
         if !self.extra_auth {
             let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
@@ -4296,15 +3400,15 @@ impl EClient {
         let version = 1;
 
         let message_id: i32 = ServerReqMsgDiscriminants::VerifyAndAuthRequest as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
 
-        msg.push_str(&make_field(&version)?);
-        msg.push_str(&make_field(&String::from(api_name))?);
-        msg.push_str(&make_field(&String::from(api_version))?);
-        msg.push_str(&make_field(&String::from(opaque_isv_key))?);
+        msg.push(&version);
+        msg.push(&String::from(api_name));
+        msg.push(&String::from(api_version));
+        msg.push(&String::from(opaque_isv_key));
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -4317,31 +3421,19 @@ impl EClient {
     ) -> Result<(), IBKRApiLibError> {
         self.check_connected(NO_VALID_ID)?;
 
-        if self.server_version() < MIN_SERVER_VER_LINKING {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                NO_VALID_ID,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support verification request."
-                ),
-            ));
-
-            return Err(err);
-        }
+        self.check_feature(ServerFeature::Linking)?;
 
         let version = 1;
 
         let message_id: i32 = ServerReqMsgDiscriminants::VerifyAndAuthMessage as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
 
-        msg.push_str(&make_field(&version)?);
-        msg.push_str(&make_field(&String::from(api_data))?);
-        msg.push_str(&make_field(&String::from(xyz_response))?);
+        msg.push(&version);
+        msg.push(&String::from(api_data));
+        msg.push(&String::from(xyz_response));
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -4364,31 +3456,19 @@ impl EClient {
     ) -> Result<(), IBKRApiLibError> {
         self.check_connected(NO_VALID_ID)?;
 
-        if self.server_version() < MIN_SERVER_VER_SEC_DEF_OPT_PARAMS_REQ {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                NO_VALID_ID,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support security pub fninition option request."
-                ),
-            ));
-
-            return Err(err);
-        }
+        self.check_feature(ServerFeature::SecDefOptParamsReq)?;
 
         let message_id: i32 = ServerReqMsgDiscriminants::ReqSecDefOptParams as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
 
-        msg.push_str(&make_field(&req_id)?);
-        msg.push_str(&make_field(&String::from(underlying_symbol))?);
-        msg.push_str(&make_field(&String::from(fut_fop_exchange))?);
-        msg.push_str(&make_field(&String::from(underlying_sec_type))?);
-        msg.push_str(&make_field(&underlying_con_id)?);
+        msg.push(&req_id);
+        msg.push(&String::from(underlying_symbol));
+        msg.push(&String::from(fut_fop_exchange));
+        msg.push(&String::from(underlying_sec_type));
+        msg.push(&underlying_con_id);
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -4403,12 +3483,12 @@ impl EClient {
         self.check_connected(NO_VALID_ID)?;
 
         let message_id: i32 = ServerReqMsgDiscriminants::ReqSoftDollarTiers as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
 
-        msg.push_str(&make_field(&req_id)?);
+        msg.push(&req_id);
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -4417,25 +3497,13 @@ impl EClient {
     pub fn req_family_codes(&mut self) -> Result<(), IBKRApiLibError> {
         self.check_connected(NO_VALID_ID)?;
 
-        if self.server_version() < MIN_SERVER_VER_REQ_FAMILY_CODES {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                NO_VALID_ID,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " It does not support family codes request."
-                ),
-            ));
-
-            return Err(err);
-        }
+        self.check_feature(ServerFeature::ReqFamilyCodes)?;
 
         let message_id: i32 = ServerReqMsgDiscriminants::ReqFamilyCodes as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -4452,28 +3520,16 @@ impl EClient {
     ) -> Result<(), IBKRApiLibError> {
         self.check_connected(NO_VALID_ID)?;
 
-        if self.server_version() < MIN_SERVER_VER_REQ_MATCHING_SYMBOLS {
-            let err = IBKRApiLibError::ApiError(TwsApiReportableError::new(
-                NO_VALID_ID,
-                TwsError::UpdateTws.code().to_string(),
-                format!(
-                    "{}{}",
-                    TwsError::UpdateTws.message(),
-                    " t does not support matching symbols request."
-                ),
-            ));
-
-            return Err(err);
-        }
+        self.check_feature(ServerFeature::ReqMatchingSymbols)?;
 
         let message_id: i32 = ServerReqMsgDiscriminants::ReqMatchingSymbols as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
 
-        msg.push_str(&make_field(&req_id)?);
-        msg.push_str(&make_field(&String::from(pattern))?);
+        msg.push(&req_id);
+        msg.push(&String::from(pattern));
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
         Ok(())
     }
 
@@ -4487,16 +3543,30 @@ impl EClient {
         self.check_connected(NO_VALID_ID)?;
 
         let message_id: i32 = ServerReqMsgDiscriminants::ReqCompletedOrders as i32;
-        let mut msg = "".to_string();
-        msg.push_str(&make_field(&message_id)?);
+        let mut msg = FieldWriter::new();
+        msg.push(&message_id);
 
-        msg.push_str(&make_field(&api_only)?);
+        msg.push(&api_only);
 
-        self.send_request(msg.as_str())?;
+        self.send_request(msg)?;
         Ok(())
     }
 
-    //------------------------------------------------------------------------------------------------
+    //----------------------------------------------------------------------------------------------
+    /// Returns an `Unsupported` error if the connected server does not support the feature
+    fn check_feature(&self, feature: ServerFeature) -> Result<(), IBKRApiLibError> {
+        if self.supports(feature) {
+            Ok(())
+        } else {
+            Err(IBKRApiLibError::Unsupported {
+                feature,
+                server_version: self.server_version,
+                required: feature.required_version(),
+            })
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
     /// check if client is connected to TWS
     fn check_connected(&mut self, req_id: i32) -> Result<(), IBKRApiLibError> {
         match self.is_connected() {
@@ -1,12 +1,19 @@
 //! Common types
 use std::fmt::Display;
 use std::fmt::{self, Error, Formatter};
+use std::str::FromStr;
 use strum_macros::EnumString;
 
+use chrono::{DateTime, FixedOffset, Utc};
+
 use num_derive::FromPrimitive;
+use rust_decimal::prelude::FromPrimitive as _;
+use rust_decimal::Decimal;
 
 use serde::{Deserialize, Serialize};
 
+use crate::core::datetime::{parse_epoch, parse_tws_datetime};
+
 pub const NO_VALID_ID: i32 = -1;
 pub const MAX_MSG_LEN: i64 = 0xFFFFFF; //16Mb - 1byte
 
@@ -19,7 +26,9 @@ pub const UNSET_INTEGER_I32_AS_U64: u64 = std::i32::MAX as u64;
 //==================================================================================================
 /// Tick types
 #[repr(i32)]
-#[derive(EnumString, Serialize, Deserialize, Clone, Debug, FromPrimitive, Copy)]
+#[derive(
+    EnumString, Serialize, Deserialize, Clone, Debug, FromPrimitive, Copy, PartialEq, Eq, Hash,
+)]
 pub enum TickType {
     BidSize = 0,
     Bid = 1,
@@ -320,6 +329,12 @@ impl BarData {
             average,
         }
     }
+
+    //----------------------------------------------------------------------------------------------
+    /// The bar's date parsed, see `parse_tws_datetime` for the formats and `default_tz`
+    pub fn timestamp(&self, default_tz: FixedOffset) -> Option<DateTime<FixedOffset>> {
+        parse_tws_datetime(&self.date, default_tz)
+    }
 }
 
 impl fmt::Display for BarData {
@@ -375,6 +390,12 @@ impl RealTimeBar {
             count,
         }
     }
+
+    //----------------------------------------------------------------------------------------------
+    /// Start time of the bar
+    pub fn timestamp(&self) -> Option<DateTime<Utc>> {
+        parse_epoch(self.date_time.trim().parse().ok()?)
+    }
 }
 
 impl fmt::Display for RealTimeBar {
@@ -594,7 +615,7 @@ impl fmt::Display for FamilyCode {
 }
 
 //==================================================================================================
-#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 pub struct PriceIncrement {
     pub low_edge: f64,
     pub increment: f64,
@@ -631,6 +652,11 @@ impl HistoricalTick {
     pub fn new(time: i32, price: f64, size: i32) -> Self {
         HistoricalTick { time, price, size }
     }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn timestamp(&self) -> Option<DateTime<Utc>> {
+        parse_epoch(self.time as i64)
+    }
 }
 
 impl fmt::Display for HistoricalTick {
@@ -672,6 +698,11 @@ impl HistoricalTickBidAsk {
             size_ask,
         }
     }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn timestamp(&self) -> Option<DateTime<Utc>> {
+        parse_epoch(self.time as i64)
+    }
 }
 
 impl fmt::Display for HistoricalTickBidAsk {
@@ -718,6 +749,11 @@ impl HistoricalTickLast {
             special_conditions,
         }
     }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn timestamp(&self) -> Option<DateTime<Utc>> {
+        parse_epoch(self.time as i64)
+    }
 }
 
 impl fmt::Display for HistoricalTickLast {
@@ -795,7 +831,7 @@ impl fmt::Display for NewsProvider {
 }
 
 //==================================================================================================
-#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 pub struct TagValue {
     pub tag: String,
     pub value: String,
@@ -955,7 +991,7 @@ impl Display for TriggerMethod {
 
 //==================================================================================================
 #[repr(i32)]
-#[derive(Serialize, Deserialize, Clone, FromPrimitive, Debug)]
+#[derive(EnumString, Serialize, Deserialize, Clone, FromPrimitive, Debug)]
 pub enum Action {
     BUY,
     SELL,
@@ -1028,7 +1064,7 @@ impl Display for OcaType {
 
 //==================================================================================================
 #[repr(i32)]
-#[derive(Serialize, Deserialize, Clone, FromPrimitive, Debug)]
+#[derive(EnumString, Serialize, Deserialize, Clone, FromPrimitive, Debug)]
 pub enum TimeInForce {
     DAY,
     GTC,
@@ -1059,6 +1095,104 @@ impl Display for TimeInForce {
     }
 }
 
+//==================================================================================================
+/// Order types, displayed as the `order_type` string sent to TWS
+#[repr(i32)]
+#[derive(EnumString, Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum OrderType {
+    #[strum(serialize = "MKT")]
+    Market,
+    #[strum(serialize = "LMT")]
+    Limit,
+    #[strum(serialize = "STP")]
+    Stop,
+    #[strum(serialize = "STP LMT")]
+    StopLimit,
+    #[strum(serialize = "STP PRT")]
+    StopProtect,
+    #[strum(serialize = "TRAIL")]
+    Trail,
+    #[strum(serialize = "TRAIL LIMIT")]
+    TrailLimit,
+    #[strum(serialize = "MIT")]
+    MarketIfTouched,
+    #[strum(serialize = "LIT")]
+    LimitIfTouched,
+    #[strum(serialize = "MTL")]
+    MarketToLimit,
+    #[strum(serialize = "MKT PRT")]
+    MarketProtect,
+    #[strum(serialize = "MOC")]
+    MarketOnClose,
+    #[strum(serialize = "LOC")]
+    LimitOnClose,
+    #[strum(serialize = "REL")]
+    Relative,
+    #[strum(serialize = "PASSV REL")]
+    PassiveRelative,
+    #[strum(serialize = "REL + LMT")]
+    RelativeLimit,
+    #[strum(serialize = "REL + MKT")]
+    RelativeMarket,
+    #[strum(serialize = "LMT + MKT")]
+    LimitMarket,
+    #[strum(serialize = "PEG MID")]
+    PeggedToMidpoint,
+    #[strum(serialize = "PEG MKT")]
+    PeggedToMarket,
+    #[strum(serialize = "PEG STK")]
+    PeggedToStock,
+    #[strum(serialize = "PEG BENCH")]
+    PeggedToBenchmark,
+    #[strum(serialize = "MIDPRICE")]
+    Midprice,
+    #[strum(serialize = "BOX TOP")]
+    BoxTop,
+    #[strum(serialize = "SNAP MKT")]
+    SnapMarket,
+    #[strum(serialize = "SNAP MID")]
+    SnapMidpoint,
+    #[strum(serialize = "SNAP PRIM")]
+    SnapPrimary,
+    #[strum(serialize = "VOL")]
+    Volatility,
+}
+
+impl Display for OrderType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match *self {
+            OrderType::Market => write!(f, "MKT"),
+            OrderType::Limit => write!(f, "LMT"),
+            OrderType::Stop => write!(f, "STP"),
+            OrderType::StopLimit => write!(f, "STP LMT"),
+            OrderType::StopProtect => write!(f, "STP PRT"),
+            OrderType::Trail => write!(f, "TRAIL"),
+            OrderType::TrailLimit => write!(f, "TRAIL LIMIT"),
+            OrderType::MarketIfTouched => write!(f, "MIT"),
+            OrderType::LimitIfTouched => write!(f, "LIT"),
+            OrderType::MarketToLimit => write!(f, "MTL"),
+            OrderType::MarketProtect => write!(f, "MKT PRT"),
+            OrderType::MarketOnClose => write!(f, "MOC"),
+            OrderType::LimitOnClose => write!(f, "LOC"),
+            OrderType::Relative => write!(f, "REL"),
+            OrderType::PassiveRelative => write!(f, "PASSV REL"),
+            OrderType::RelativeLimit => write!(f, "REL + LMT"),
+            OrderType::RelativeMarket => write!(f, "REL + MKT"),
+            OrderType::LimitMarket => write!(f, "LMT + MKT"),
+            OrderType::PeggedToMidpoint => write!(f, "PEG MID"),
+            OrderType::PeggedToMarket => write!(f, "PEG MKT"),
+            OrderType::PeggedToStock => write!(f, "PEG STK"),
+            OrderType::PeggedToBenchmark => write!(f, "PEG BENCH"),
+            OrderType::Midprice => write!(f, "MIDPRICE"),
+            OrderType::BoxTop => write!(f, "BOX TOP"),
+            OrderType::SnapMarket => write!(f, "SNAP MKT"),
+            OrderType::SnapMidpoint => write!(f, "SNAP MID"),
+            OrderType::SnapPrimary => write!(f, "SNAP PRIM"),
+            OrderType::Volatility => write!(f, "VOL"),
+        }
+    }
+}
+
 //==================================================================================================
 #[repr(i32)]
 #[derive(Serialize, Deserialize, Clone, FromPrimitive, Debug)]
@@ -1107,7 +1241,7 @@ impl Display for FundamentalType {
 
 //==================================================================================================
 #[repr(i32)]
-#[derive(Serialize, Deserialize, Clone, FromPrimitive, Debug)]
+#[derive(Serialize, Deserialize, Clone, Copy, FromPrimitive, Debug, PartialEq)]
 pub enum WhatToShow {
     Trades,
     Midpoint,
@@ -1135,7 +1269,7 @@ impl Display for WhatToShow {
             WhatToShow::HistoricalVolatility => write!(f, "HISTORICAL_VOLATILITY"),
             WhatToShow::OptionImpliedVolatility => write!(f, "OPTION_IMPLIED_VOLATILITY"),
             WhatToShow::YieldAsk => write!(f, "YIELD_ASK"),
-            WhatToShow::YieldBid => write!(f, "YIELD_ASK"),
+            WhatToShow::YieldBid => write!(f, "YIELD_BID"),
             WhatToShow::YieldBidAsk => write!(f, "YIELD_BID_ASK"),
             WhatToShow::YieldLast => write!(f, "YIELD_LAST"),
             WhatToShow::AdjustedLast => write!(f, "ADJUSTED_LAST"),
@@ -1143,9 +1277,19 @@ impl Display for WhatToShow {
     }
 }
 
+impl WhatToShow {
+    /// True for the data types `req_real_time_bars` supports
+    pub fn is_real_time(&self) -> bool {
+        matches!(
+            self,
+            WhatToShow::Trades | WhatToShow::Midpoint | WhatToShow::Bid | WhatToShow::Ask
+        )
+    }
+}
+
 //==================================================================================================
 #[repr(i32)]
-#[derive(Serialize, Deserialize, Clone, FromPrimitive, Debug)]
+#[derive(Serialize, Deserialize, Clone, Copy, FromPrimitive, Debug, PartialEq)]
 pub enum BarSize {
     _1Secs,
     _5Secs,
@@ -1192,9 +1336,35 @@ impl Display for BarSize {
     }
 }
 
+impl BarSize {
+    /// Length of a bar in seconds, a month counts 30 days
+    pub fn seconds(&self) -> u64 {
+        match *self {
+            BarSize::_1Secs => 1,
+            BarSize::_5Secs => 5,
+            BarSize::_10Secs => 10,
+            BarSize::_15Secs => 15,
+            BarSize::_30Secs => 30,
+            BarSize::_1Min => 60,
+            BarSize::_2Mins => 120,
+            BarSize::_3Mins => 180,
+            BarSize::_5Mins => 300,
+            BarSize::_10Mins => 600,
+            BarSize::_15Mins => 900,
+            BarSize::_20Mins => 1200,
+            BarSize::_30Mins => 1800,
+            BarSize::_1Hour => 3600,
+            BarSize::_4Hours => 14400,
+            BarSize::_1Day => 86400,
+            BarSize::_1Week => 604_800,
+            BarSize::_1Month => 2_592_000,
+        }
+    }
+}
+
 //==================================================================================================
 #[repr(i32)]
-#[derive(Serialize, Deserialize, Clone, FromPrimitive, Debug)]
+#[derive(Serialize, Deserialize, Clone, Copy, FromPrimitive, Debug, PartialEq)]
 pub enum DurationUnit {
     SECOND,
     DAY,
@@ -1215,6 +1385,60 @@ impl Display for DurationUnit {
     }
 }
 
+//==================================================================================================
+/// Length of a historical data request, e.g. `Duration::new(2, DurationUnit::DAY)` for "2 D"
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct Duration {
+    pub n: u32,
+    pub unit: DurationUnit,
+}
+
+impl Duration {
+    pub fn new(n: u32, unit: DurationUnit) -> Self {
+        Duration { n, unit }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Length in seconds, a month counts 30 days and a year 365 days
+    pub fn seconds(&self) -> u64 {
+        let unit: u64 = match self.unit {
+            DurationUnit::SECOND => 1,
+            DurationUnit::DAY => 86400,
+            DurationUnit::WEEK => 604_800,
+            DurationUnit::MONTH => 2_592_000,
+            DurationUnit::YEAR => 31_536_000,
+        };
+        self.n as u64 * unit
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// The duration in the format of `req_histogram_data` periods, e.g. "3 days"
+    pub fn period(&self) -> String {
+        let unit = match self.unit {
+            DurationUnit::SECOND => "second",
+            DurationUnit::DAY => "day",
+            DurationUnit::WEEK => "week",
+            DurationUnit::MONTH => "month",
+            DurationUnit::YEAR => "year",
+        };
+        let plural = if self.n == 1 { "" } else { "s" };
+        format!("{} {}{}", self.n, unit, plural)
+    }
+}
+
+impl Display for Duration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        let unit = match self.unit {
+            DurationUnit::SECOND => "S",
+            DurationUnit::DAY => "D",
+            DurationUnit::WEEK => "W",
+            DurationUnit::MONTH => "M",
+            DurationUnit::YEAR => "Y",
+        };
+        write!(f, "{} {}", self.n, unit)
+    }
+}
+
 //==================================================================================================
 #[repr(i32)]
 #[derive(Serialize, Deserialize, Clone, FromPrimitive, Debug)]
@@ -1327,6 +1551,7 @@ pub enum SecType {
     BSK,
     ICU,
     ICS,
+    CRYPTO,
 }
 
 impl Display for SecType {
@@ -1355,6 +1580,7 @@ impl Display for SecType {
             SecType::BSK => write!(f, "BSK"),
             SecType::ICU => write!(f, "ICU"),
             SecType::ICS => write!(f, "ICS"),
+            SecType::CRYPTO => write!(f, "CRYPTO"),
         }
     }
 }
@@ -1425,3 +1651,20 @@ impl UsePriceMgmtAlgo {
         }
     }
 }
+
+//==================================================================================================
+/// Parses an amount TWS sends as text, such as a margin or account value. Returns `None` for
+/// empty text and for `UNSET_DOUBLE`, which TWS sends when it has no value.
+pub(crate) fn parse_decimal(amount: &str) -> Option<Decimal> {
+    let amount = amount.trim();
+    if amount.is_empty() {
+        return None;
+    }
+    if let Ok(value) = Decimal::from_str(amount) {
+        return Some(value);
+    }
+    match amount.parse::<f64>() {
+        Ok(value) if value != UNSET_DOUBLE && value.is_finite() => Decimal::from_f64(value),
+        _ => None,
+    }
+}
@@ -5,7 +5,8 @@ use num_derive::FromPrimitive;
 use serde::{Deserialize, Serialize};
 // 0.2.6 (the trait)
 
-use crate::core::common::TagValue;
+use crate::core::common::{Right, SecIdType, SecType, TagValue, UNSET_DOUBLE};
+use crate::core::errors::IBKRApiLibError;
 
 //==================================================================================================
 #[repr(i32)]
@@ -218,6 +219,166 @@ impl Contract {
             delta_neutral_contract,
         }
     }
+
+    //----------------------------------------------------------------------------------------------
+    /// Creates a contract builder, see `ContractBuilder`
+    pub fn builder() -> ContractBuilder {
+        ContractBuilder::new()
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Stock contract, e.g. `Contract::stock("IBM", "SMART", "USD")`
+    pub fn stock(symbol: &str, exchange: &str, currency: &str) -> Self {
+        Contract::with_sec_type(SecType::STK, symbol, exchange, currency)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Option contract. The expiry is given as YYYYMMDD or YYYYMM
+    pub fn option(
+        symbol: &str,
+        last_trade_date_or_contract_month: &str,
+        strike: f64,
+        right: Right,
+        exchange: &str,
+        currency: &str,
+    ) -> Self {
+        let mut contract = Contract::with_sec_type(SecType::OPT, symbol, exchange, currency);
+        contract.last_trade_date_or_contract_month = last_trade_date_or_contract_month.to_string();
+        contract.strike = strike;
+        contract.right = right.to_string();
+        contract
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Futures contract. The contract month is given as YYYYMM or YYYYMMDD
+    pub fn future(
+        symbol: &str,
+        last_trade_date_or_contract_month: &str,
+        exchange: &str,
+        currency: &str,
+    ) -> Self {
+        let mut contract = Contract::with_sec_type(SecType::FUT, symbol, exchange, currency);
+        contract.last_trade_date_or_contract_month = last_trade_date_or_contract_month.to_string();
+        contract
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Currency pair on IDEALPRO, e.g. `Contract::forex("EUR", "USD")` for EUR.USD
+    pub fn forex(base: &str, quote: &str) -> Self {
+        Contract::with_sec_type(SecType::CASH, base, "IDEALPRO", quote)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Index contract, e.g. `Contract::index("SPX", "CBOE", "USD")`
+    pub fn index(symbol: &str, exchange: &str, currency: &str) -> Self {
+        Contract::with_sec_type(SecType::IND, symbol, exchange, currency)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Bond identified by its CUSIP
+    pub fn bond_by_cusip(cusip: &str, exchange: &str, currency: &str) -> Self {
+        let mut contract = Contract::with_sec_type(SecType::BOND, "", exchange, currency);
+        contract.sec_id_type = SecIdType::CUSIP.to_string();
+        contract.sec_id = cusip.to_string();
+        contract
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Contract for difference, e.g. `Contract::cfd("IBDE30", "SMART", "EUR")`
+    pub fn cfd(symbol: &str, exchange: &str, currency: &str) -> Self {
+        Contract::with_sec_type(SecType::CFD, symbol, exchange, currency)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Crypto currency, e.g. `Contract::crypto("ETH", "PAXOS", "USD")`
+    pub fn crypto(symbol: &str, exchange: &str, currency: &str) -> Self {
+        Contract::with_sec_type(SecType::CRYPTO, symbol, exchange, currency)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn with_sec_type(sec_type: SecType, symbol: &str, exchange: &str, currency: &str) -> Self {
+        Contract {
+            symbol: symbol.to_string(),
+            sec_type: sec_type.to_string(),
+            exchange: exchange.to_string(),
+            currency: currency.to_string(),
+            ..Default::default()
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Checks that the fields required for the contract's security type are present, so that
+    /// the contract can be used for market data, historical data and orders.
+    ///
+    /// A contract with a con_id is taken as fully identified. Otherwise an exchange is always
+    /// required, options need expiry, strike and right and futures need an expiry, unless the
+    /// local symbol is given instead.
+    pub fn validate(&self) -> Result<(), IBKRApiLibError> {
+        if self.con_id > 0 {
+            return Ok(());
+        }
+        self.validate_for_lookup()?;
+
+        if self.exchange.is_empty() {
+            return Err(self.invalid("exchange"));
+        }
+        if !self.sec_id_type.is_empty() || !self.local_symbol.is_empty() {
+            return Ok(());
+        }
+
+        let is_option = matches!(self.sec_type.as_str(), "OPT" | "FOP" | "WAR" | "IOPT");
+        let is_future = matches!(self.sec_type.as_str(), "FUT" | "FWD");
+
+        if (is_option || is_future) && self.last_trade_date_or_contract_month.is_empty() {
+            return Err(self.invalid("last_trade_date_or_contract_month"));
+        }
+        if is_option {
+            if self.strike <= 0.0 || self.strike == UNSET_DOUBLE {
+                return Err(self.invalid("strike"));
+            }
+            if !matches!(self.right.as_str(), "P" | "PUT" | "C" | "CALL") {
+                return Err(self.invalid("right"));
+            }
+        }
+        Ok(())
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Looser check used for contract details requests, where any field other than the
+    /// security type and an identifier may be left empty to match several contracts.
+    pub fn validate_for_lookup(&self) -> Result<(), IBKRApiLibError> {
+        if self.con_id > 0 {
+            return Ok(());
+        }
+        if self.sec_type.is_empty() {
+            return Err(self.invalid("sec_type"));
+        }
+        if !self.sec_id_type.is_empty() {
+            return if self.sec_id.is_empty() {
+                Err(self.invalid("sec_id"))
+            } else {
+                Ok(())
+            };
+        }
+
+        match self.sec_type.as_str() {
+            "BAG" if self.combo_legs.is_empty() => Err(self.invalid("combo_legs")),
+            "BAG" => Ok(()),
+            "CASH" if self.currency.is_empty() => Err(self.invalid("currency")),
+            _ if self.symbol.is_empty() && self.local_symbol.is_empty() => {
+                Err(self.invalid("symbol"))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn invalid(&self, field: &'static str) -> IBKRApiLibError {
+        IBKRApiLibError::InvalidContract {
+            sec_type: self.sec_type.clone(),
+            field,
+        }
+    }
 }
 
 impl Display for Contract {
@@ -269,6 +430,141 @@ impl Display for Contract {
     }
 }
 
+//==================================================================================================
+/// Fluent builder for `Contract` using the typed security type, right and security id enums.
+///
+/// ```
+/// use ibtwsapi::core::common::{Right, SecType};
+/// use ibtwsapi::core::contract::ContractBuilder;
+///
+/// let contract = ContractBuilder::new()
+///     .sec_type(SecType::OPT)
+///     .symbol("GOOG")
+///     .last_trade_date_or_contract_month("20261218")
+///     .strike(150.0)
+///     .right(Right::Call)
+///     .multiplier("100")
+///     .exchange("SMART")
+///     .currency("USD")
+///     .build()
+///     .unwrap();
+/// assert_eq!("C", contract.right);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ContractBuilder {
+    contract: Contract,
+}
+
+impl ContractBuilder {
+    pub fn new() -> Self {
+        ContractBuilder::default()
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn con_id(mut self, con_id: i32) -> Self {
+        self.contract.con_id = con_id;
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn symbol(mut self, symbol: &str) -> Self {
+        self.contract.symbol = symbol.to_string();
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn sec_type(mut self, sec_type: SecType) -> Self {
+        self.contract.sec_type = sec_type.to_string();
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn last_trade_date_or_contract_month(mut self, date_or_month: &str) -> Self {
+        self.contract.last_trade_date_or_contract_month = date_or_month.to_string();
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn strike(mut self, strike: f64) -> Self {
+        self.contract.strike = strike;
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn right(mut self, right: Right) -> Self {
+        self.contract.right = right.to_string();
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn multiplier(mut self, multiplier: &str) -> Self {
+        self.contract.multiplier = multiplier.to_string();
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn exchange(mut self, exchange: &str) -> Self {
+        self.contract.exchange = exchange.to_string();
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn primary_exchange(mut self, primary_exchange: &str) -> Self {
+        self.contract.primary_exchange = primary_exchange.to_string();
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn currency(mut self, currency: &str) -> Self {
+        self.contract.currency = currency.to_string();
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn local_symbol(mut self, local_symbol: &str) -> Self {
+        self.contract.local_symbol = local_symbol.to_string();
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn trading_class(mut self, trading_class: &str) -> Self {
+        self.contract.trading_class = trading_class.to_string();
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn include_expired(mut self, include_expired: bool) -> Self {
+        self.contract.include_expired = include_expired;
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn sec_id(mut self, sec_id_type: SecIdType, sec_id: &str) -> Self {
+        self.contract.sec_id_type = sec_id_type.to_string();
+        self.contract.sec_id = sec_id.to_string();
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn combo_leg(mut self, combo_leg: ComboLeg) -> Self {
+        self.contract.combo_legs.push(combo_leg);
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn delta_neutral_contract(mut self, delta_neutral: DeltaNeutralContract) -> Self {
+        self.contract.delta_neutral_contract = Some(delta_neutral);
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Returns the contract if the fields required by its security type are present
+    pub fn build(self) -> Result<Contract, IBKRApiLibError> {
+        self.contract.validate()?;
+        Ok(self.contract)
+    }
+}
+
 //==================================================================================================
 #[derive(Serialize, Deserialize, Clone, Debug, Default)]
 pub struct ContractDetails {
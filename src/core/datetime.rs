@@ -0,0 +1,207 @@
+//! Conversion between chrono date times and the timestamp strings used by TWS
+use chrono::{
+    DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc, Weekday,
+};
+
+const HOUR: i32 = 3600;
+
+//==================================================================================================
+/// Daylight saving rule of a named time zone
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Rule {
+    Fixed,
+    /// Second Sunday of March 02:00 to first Sunday of November 02:00 local time since 2007, first
+    /// Sunday of April to last Sunday of October from 1987 and last Sunday of April before
+    UnitedStates,
+    /// Last Sunday of March 01:00 UTC to last Sunday of October 01:00 UTC
+    Europe,
+    /// First Sunday of October 02:00 to first Sunday of April 02:00, local standard time
+    Australia,
+}
+
+//==================================================================================================
+/// Standard offset in seconds and daylight saving rule of the zone names TWS sends and accepts.
+/// Abbreviations designate a fixed offset, region names and the WET, CET, MET and EET zones follow
+/// the rule of their region. Ambiguous abbreviations such as IST are not accepted.
+fn zone(name: &str) -> Option<(i32, Rule)> {
+    // Older TWS versions send contract time zone ids such as "CST (Central Standard Time)" for
+    // the zone of a region
+    if let Some((abbreviation, _)) = name.split_once(" (") {
+        let region = match abbreviation {
+            "EST" => "US/Eastern",
+            "CST" => "US/Central",
+            "MST" => "US/Mountain",
+            "PST" => "US/Pacific",
+            abbreviation => abbreviation,
+        };
+        return zone(region);
+    }
+    let zone = match name {
+        "UTC" | "GMT" | "UCT" | "Z" | "Etc/UTC" | "Etc/GMT" => (0, Rule::Fixed),
+        "EST" => (-5 * HOUR, Rule::Fixed),
+        "EDT" => (-4 * HOUR, Rule::Fixed),
+        "CST" => (-6 * HOUR, Rule::Fixed),
+        "CDT" => (-5 * HOUR, Rule::Fixed),
+        "MST" => (-7 * HOUR, Rule::Fixed),
+        "MDT" => (-6 * HOUR, Rule::Fixed),
+        "PST" => (-8 * HOUR, Rule::Fixed),
+        "PDT" => (-7 * HOUR, Rule::Fixed),
+        "BST" | "WEST" => (HOUR, Rule::Fixed),
+        "CEST" | "MEST" => (2 * HOUR, Rule::Fixed),
+        "EEST" | "MSK" | "Europe/Moscow" => (3 * HOUR, Rule::Fixed),
+        "Asia/Kolkata" | "Asia/Calcutta" => (5 * HOUR + 30 * 60, Rule::Fixed),
+        "HKT" | "SGT" | "Hongkong" | "PRC" | "Asia/Hong_Kong" | "Asia/Shanghai"
+        | "Asia/Singapore" => (8 * HOUR, Rule::Fixed),
+        "JST" | "KST" | "Japan" | "Asia/Tokyo" | "Asia/Seoul" => (9 * HOUR, Rule::Fixed),
+        "AEST" => (10 * HOUR, Rule::Fixed),
+        "AEDT" => (11 * HOUR, Rule::Fixed),
+        "US/Eastern" | "America/New_York" | "EST5EDT" => (-5 * HOUR, Rule::UnitedStates),
+        "America/Toronto" | "Canada/Eastern" => (-5 * HOUR, Rule::UnitedStates),
+        "US/Central" | "America/Chicago" | "CST6CDT" => (-6 * HOUR, Rule::UnitedStates),
+        "US/Mountain" | "America/Denver" | "MST7MDT" => (-7 * HOUR, Rule::UnitedStates),
+        "US/Pacific" | "America/Los_Angeles" | "PST8PDT" => (-8 * HOUR, Rule::UnitedStates),
+        "Europe/London" | "Europe/Dublin" | "Europe/Lisbon" => (0, Rule::Europe),
+        "GB" | "GB-Eire" | "Eire" | "WET" => (0, Rule::Europe),
+        "CET" | "MET" | "Europe/Amsterdam" | "Europe/Berlin" | "Europe/Brussels"
+        | "Europe/Madrid" | "Europe/Paris" | "Europe/Rome" | "Europe/Stockholm"
+        | "Europe/Vienna" | "Europe/Zurich" => (HOUR, Rule::Europe),
+        "EET" | "Europe/Athens" | "Europe/Helsinki" => (2 * HOUR, Rule::Europe),
+        "Australia/NSW" | "Australia/Sydney" | "Australia/ACT" | "Australia/Canberra" => {
+            (10 * HOUR, Rule::Australia)
+        }
+        "Australia/Victoria" | "Australia/Melbourne" => (10 * HOUR, Rule::Australia),
+        _ => return None,
+    };
+    Some(zone)
+}
+
+//==================================================================================================
+/// The n-th `weekday` of a month, counting from the end when `n` is negative
+fn nth_weekday(year: i32, month: u32, weekday: Weekday, n: i8) -> Option<NaiveDate> {
+    if n > 0 {
+        return NaiveDate::from_weekday_of_month_opt(year, month, weekday, n as u8);
+    }
+    (1..=5)
+        .rev()
+        .find_map(|n| NaiveDate::from_weekday_of_month_opt(year, month, weekday, n))
+}
+
+//==================================================================================================
+/// Offset of a TWS time zone name at a local wall clock time, e.g. "US/Eastern", "EDT" or the
+/// legacy "EST (Eastern Standard Time)"
+pub fn zone_offset(name: &str, local: &NaiveDateTime) -> Option<FixedOffset> {
+    let (standard, rule) = zone(name)?;
+    let at = |date: Option<NaiveDate>, hour| date.and_then(|date| date.and_hms_opt(hour, 0, 0));
+    let daylight = match rule {
+        Rule::Fixed => false,
+        Rule::UnitedStates => {
+            let year = local.year();
+            let (start, end) = match year {
+                _ if year >= 2007 => (
+                    nth_weekday(year, 3, Weekday::Sun, 2),
+                    nth_weekday(year, 11, Weekday::Sun, 1),
+                ),
+                _ if year >= 1987 => (
+                    nth_weekday(year, 4, Weekday::Sun, 1),
+                    nth_weekday(year, 10, Weekday::Sun, -1),
+                ),
+                _ => (
+                    nth_weekday(year, 4, Weekday::Sun, -1),
+                    nth_weekday(year, 10, Weekday::Sun, -1),
+                ),
+            };
+            let start = at(start, 2)?;
+            // the repeated hour of the change back is read as standard time
+            let end = at(end, 1)?;
+            start <= *local && *local < end
+        }
+        Rule::Europe => {
+            let year = local.year();
+            let start = at(nth_weekday(year, 3, Weekday::Sun, -1), 1)?;
+            let end = at(nth_weekday(year, 10, Weekday::Sun, -1), 1)?;
+            let utc = *local - chrono::Duration::seconds(standard as i64);
+            start <= utc && utc < end
+        }
+        Rule::Australia => {
+            let year = local.year();
+            let end = at(nth_weekday(year, 4, Weekday::Sun, 1), 2)?;
+            let start = at(nth_weekday(year, 10, Weekday::Sun, 1), 2)?;
+            *local < end || start <= *local
+        }
+    };
+    FixedOffset::east_opt(if daylight { standard + HOUR } else { standard })
+}
+
+//==================================================================================================
+/// Formats a date time as TWS expects it in requests and order fields, "yyyymmdd hh:mm:ss UTC"
+pub fn format_tws_datetime<Tz: TimeZone>(date_time: &DateTime<Tz>) -> String {
+    date_time
+        .with_timezone(&Utc)
+        .format("%Y%m%d %H:%M:%S UTC")
+        .to_string()
+}
+
+//==================================================================================================
+/// Formats a date time the way `req_historical_news` expects it, "yyyy-mm-dd hh:mm:ss.0" in UTC
+pub fn format_news_datetime<Tz: TimeZone>(date_time: &DateTime<Tz>) -> String {
+    date_time
+        .with_timezone(&Utc)
+        .format("%Y-%m-%d %H:%M:%S.0")
+        .to_string()
+}
+
+//==================================================================================================
+/// Converts the epoch seconds TWS sends for ticks, real time bars and bars requested with
+/// `format_date` 2
+pub fn parse_epoch(seconds: i64) -> Option<DateTime<Utc>> {
+    DateTime::from_timestamp(seconds, 0)
+}
+
+//==================================================================================================
+/// Parses a timestamp sent by TWS.
+///
+/// Accepts epoch seconds, "yyyymmdd", "yyyymmdd-hh:mm:ss" in UTC, "yyyy-mm-dd hh:mm:ss.f" and
+/// "yyyymmdd hh:mm:ss" optionally followed by a time zone name. Times without a zone are in the
+/// zone TWS was logged in with, which the API does not report, and are read in `default_tz`.
+/// Returns `None` for text in another format or with an unknown zone.
+pub fn parse_tws_datetime(text: &str, default_tz: FixedOffset) -> Option<DateTime<FixedOffset>> {
+    let text = text.trim();
+    if text.len() > 8 && text.bytes().all(|byte| byte.is_ascii_digit()) {
+        let utc = parse_epoch(text.parse().ok()?)?;
+        return Some(utc.with_timezone(&FixedOffset::east_opt(0)?));
+    }
+
+    let mut parts = text.split_whitespace();
+    let date = parts.next()?;
+    let time = parts.next();
+    let zone_name = parts.next();
+    if parts.next().is_some() {
+        return None;
+    }
+
+    let (local, zone_name) = match date.split_once('-') {
+        // "yyyymmdd-hh:mm:ss" is UTC while "yyyy-mm-dd" is a plain date
+        Some((day, clock)) if day.len() == 8 && time.is_none() => {
+            let local = NaiveDate::parse_from_str(day, "%Y%m%d")
+                .ok()?
+                .and_time(NaiveTime::parse_from_str(clock, "%H:%M:%S").ok()?);
+            (local, Some("UTC"))
+        }
+        _ => {
+            let date = NaiveDate::parse_from_str(date, "%Y%m%d")
+                .or_else(|_| NaiveDate::parse_from_str(date, "%Y-%m-%d"))
+                .ok()?;
+            let time = match time {
+                Some(time) => NaiveTime::parse_from_str(time, "%H:%M:%S%.f").ok()?,
+                None => NaiveTime::MIN,
+            };
+            (date.and_time(time), zone_name)
+        }
+    };
+
+    let offset = match zone_name {
+        Some(name) => zone_offset(name, &local)?,
+        None => default_tz,
+    };
+    offset.from_local_datetime(&local).single()
+}
@@ -22,7 +22,7 @@ use crate::core::common::{
     MAX_MSG_LEN, NO_VALID_ID, UNSET_DOUBLE, UNSET_INTEGER,
 };
 use crate::core::contract::{Contract, ContractDescription, ContractDetails, DeltaNeutralContract};
-use crate::core::errors::{IBKRApiLibError, TwsError};
+use crate::core::errors::{IBKRApiLibError, TwsApiReportableError, TwsError};
 use crate::core::execution::Execution;
 use crate::core::messages::{read_fields, ServerRspMsg, ServerRspMsgDiscriminants};
 use crate::core::order::{Order, OrderState, SoftDollarTier};
@@ -40,66 +40,72 @@ use crate::core::server_versions::{
 };
 
 //==================================================================================================
-pub fn decode_i32(iter: &mut Iter<String>) -> Result<i32, IBKRApiLibError> {
-    let next = iter.next();
+/// A BadMessage error for a message the decoder cannot read
+fn bad_message(detail: &str) -> IBKRApiLibError {
+    IBKRApiLibError::ApiError(TwsApiReportableError::new(
+        NO_VALID_ID,
+        TwsError::BadMessage.code().to_string(),
+        format!("{} {}", TwsError::BadMessage.message(), detail),
+    ))
+}
+
+//==================================================================================================
+/// The next field of a message, failing when the message is shorter than its layout
+fn next_field<'a>(iter: &mut Iter<'a, String>) -> Result<&'a String, IBKRApiLibError> {
+    iter.next()
+        .ok_or_else(|| bad_message("message is shorter than its layout"))
+}
 
-    let val: i32 = next.unwrap().parse().unwrap_or(0);
+//==================================================================================================
+pub fn decode_i32(iter: &mut Iter<String>) -> Result<i32, IBKRApiLibError> {
+    let val: i32 = next_field(iter)?.parse().unwrap_or(0);
     Ok(val)
 }
 
 //==================================================================================================
 pub fn decode_tick_type(iter: &mut Iter<String>) -> Result<TickType, IBKRApiLibError> {
-    let next = iter.next();
-
-    let val: TickType = next.unwrap().parse().unwrap_or(TickType::NotSet);
+    let val: TickType = next_field(iter)?
+        .parse::<i32>()
+        .ok()
+        .and_then(FromPrimitive::from_i32)
+        .unwrap_or(TickType::NotSet);
     Ok(val)
 }
 
 //==================================================================================================
 pub fn decode_i32_show_unset(iter: &mut Iter<String>) -> Result<i32, IBKRApiLibError> {
-    let next = iter.next();
-    //info!("{:?}", next);
-    let retval: i32 = next.unwrap().parse().unwrap_or(0);
+    let retval: i32 = next_field(iter)?.parse().unwrap_or(0);
     Ok(if retval == 0 { UNSET_INTEGER } else { retval })
 }
 
 //==================================================================================================
 pub fn decode_i64(iter: &mut Iter<String>) -> Result<i64, IBKRApiLibError> {
-    let next = iter.next();
-    //info!("{:?}", next);
-    let val: i64 = next.unwrap().parse().unwrap_or(0);
+    let val: i64 = next_field(iter)?.parse().unwrap_or(0);
     Ok(val)
 }
 
 //==================================================================================================
 pub fn decode_f64(iter: &mut Iter<String>) -> Result<f64, IBKRApiLibError> {
-    let next = iter.next();
-    //info!("{:?}", next);
-    let val = next.unwrap().parse().unwrap_or(0.0);
+    let val = next_field(iter)?.parse().unwrap_or(0.0);
     Ok(val)
 }
 
 //==================================================================================================
 pub fn decode_f64_show_unset(iter: &mut Iter<String>) -> Result<f64, IBKRApiLibError> {
-    let next = iter.next();
-    //info!("{:?}", next);
-    let retval: f64 = next.unwrap().parse().unwrap_or(0.0);
+    let retval: f64 = next_field(iter)?.parse().unwrap_or(0.0);
     Ok(if retval == 0.0 { UNSET_DOUBLE } else { retval })
 }
 
 //==================================================================================================
 pub fn decode_string(iter: &mut Iter<String>) -> Result<String, IBKRApiLibError> {
-    let next = iter.next();
-    //info!("{:?}", next);
-    let val = next.unwrap().parse().unwrap_or("".to_string());
+    let val = next_field(iter)?.to_string();
     Ok(val)
 }
 
 //==================================================================================================
+/// A missing flag reads as false, since servers omit trailing flags they do not send
 pub fn decode_bool(iter: &mut Iter<String>) -> Result<bool, IBKRApiLibError> {
-    let next = iter.next();
-    //info!("{:?}", next);
-    let retval: i32 = next.unwrap_or(&"0".to_string()).parse().unwrap_or(0);
+    let retval: i32 = iter.next().map_or(0, |field| field.parse().unwrap_or(0));
     Ok(retval != 0)
 }
 
@@ -132,7 +138,7 @@ impl Decoder {
             return Ok(());
         }
 
-        let msg_id = i32::from_str(fields.get(0).unwrap().as_str())?;
+        let msg_id = i32::from_str(next_field(&mut fields.iter())?)?;
 
         match FromPrimitive::from_i32(msg_id) {
             Some(ServerRspMsgDiscriminants::TickPrice) => self.process_tick_price(fields)?,
@@ -292,7 +298,7 @@ impl Decoder {
                 self.process_symbol_samples(fields)?
             }
             Some(ServerRspMsgDiscriminants::TickByTick) => self.process_tick_by_tick(fields)?,
-            Some(ServerRspMsgDiscriminants::TickEfp) => self.process_tick_by_tick(fields)?,
+            Some(ServerRspMsgDiscriminants::TickEfp) => self.process_tick_efp(fields)?,
             Some(ServerRspMsgDiscriminants::TickGeneric) => self.process_tick_generic(fields)?,
             Some(ServerRspMsgDiscriminants::TickNews) => self.process_tick_news(fields)?,
             Some(ServerRspMsgDiscriminants::TickOptionComputation) => {
@@ -315,7 +321,7 @@ impl Decoder {
             }
 
             Some(ServerRspMsgDiscriminants::VerifyMessageApi) => {
-                self.process_verify_completed(fields)?
+                self.process_verify_message_api(fields)?
             }
 
             Some(ServerRspMsgDiscriminants::VerifyAndAuthMessageApi) => {
@@ -325,7 +331,7 @@ impl Decoder {
                 self.process_reroute_mkt_depth_req(fields)?
             }
 
-            _ => panic!("Received unkown message id!!  Exiting..."),
+            _ => return Err(bad_message(&format!("message has unknown id {}", msg_id))),
         }
         Ok(())
     }
@@ -572,14 +578,14 @@ impl Decoder {
         contract.contract.sec_type = decode_string(&mut fields_itr)?;
         contract.cusip = decode_string(&mut fields_itr)?;
         contract.coupon = decode_f64(&mut fields_itr)?;
-        self.read_last_trade_date(&mut contract, true, fields_itr.next().unwrap())?;
+        self.read_last_trade_date(&mut contract, true, next_field(&mut fields_itr)?)?;
         contract.issue_date = decode_string(&mut fields_itr)?;
         contract.ratings = decode_string(&mut fields_itr)?;
         contract.bond_type = decode_string(&mut fields_itr)?;
         contract.coupon_type = decode_string(&mut fields_itr)?;
-        contract.convertible = i32::from_str(fields_itr.next().unwrap().as_ref())? != 0;
-        contract.callable = i32::from_str(fields_itr.next().unwrap().as_ref())? != 0;
-        contract.putable = i32::from_str(fields_itr.next().unwrap().as_ref())? != 0;
+        contract.convertible = i32::from_str(next_field(&mut fields_itr)?.as_ref())? != 0;
+        contract.callable = i32::from_str(next_field(&mut fields_itr)?.as_ref())? != 0;
+        contract.putable = i32::from_str(next_field(&mut fields_itr)?.as_ref())? != 0;
         contract.desc_append = decode_string(&mut fields_itr)?;
         contract.contract.exchange = decode_string(&mut fields_itr)?;
         contract.contract.currency = decode_string(&mut fields_itr)?;
@@ -611,8 +617,8 @@ impl Decoder {
                 contract.sec_id_list = vec![];
                 for _ in 0..sec_id_list_count {
                     contract.sec_id_list.push(TagValue::new(
-                        fields_itr.next().unwrap().parse().unwrap(),
-                        fields_itr.next().unwrap().parse().unwrap(),
+                        decode_string(&mut fields_itr)?,
+                        decode_string(&mut fields_itr)?,
                     ));
                 }
             }
@@ -643,9 +649,9 @@ impl Decoder {
         fields_itr.next();
 
         let mut commission_report = CommissionReport::default();
-        commission_report.exec_id = fields_itr.next().unwrap().to_string();
+        commission_report.exec_id = next_field(&mut fields_itr)?.to_string();
         commission_report.commission = decode_f64(&mut fields_itr)?;
-        commission_report.currency = fields_itr.next().unwrap().to_string();
+        commission_report.currency = next_field(&mut fields_itr)?.to_string();
         commission_report.realized_pnl = decode_f64(&mut fields_itr)?;
         commission_report.yield_ = decode_f64(&mut fields_itr)?;
         commission_report.yield_redemption_date = decode_string(&mut fields_itr)?;
@@ -709,7 +715,7 @@ impl Decoder {
 
         contract.contract.symbol = decode_string(&mut fields_itr)?;
         contract.contract.sec_type = decode_string(&mut fields_itr)?;
-        self.read_last_trade_date(&mut contract, false, fields_itr.next().unwrap())?;
+        self.read_last_trade_date(&mut contract, false, next_field(&mut fields_itr)?)?;
         contract.contract.strike = decode_f64(&mut fields_itr)?;
         contract.contract.right = decode_string(&mut fields_itr)?;
         contract.contract.exchange = decode_string(&mut fields_itr)?;
@@ -979,9 +985,9 @@ impl Decoder {
         if version >= 9 {
             execution.ev_rule = decode_string(&mut fields_itr)?;
 
-            let tmp_ev_mult = (&mut fields_itr).peekable().peek().unwrap().as_str();
+            let tmp_ev_mult = decode_string(&mut fields_itr)?;
             if tmp_ev_mult != "" {
-                execution.ev_multiplier = decode_f64(&mut fields_itr)?;
+                execution.ev_multiplier = tmp_ev_mult.parse().unwrap_or(0.0);
             } else {
                 execution.ev_multiplier = 1.0;
             }
@@ -1074,12 +1080,12 @@ impl Decoder {
         //throw away message_id
         fields_itr.next();
 
-        let fundamental_data = ServerRspMsg::FundamentalData {
+        let head_timestamp = ServerRspMsg::HeadTimestamp {
             req_id: decode_i32(&mut fields_itr)?,
-            data: decode_string(&mut fields_itr)?,
+            head_timestamp: decode_string(&mut fields_itr)?,
         };
 
-        self.send_queue.send(fundamental_data).unwrap();
+        self.send_queue.send(head_timestamp).unwrap();
 
         Ok(())
     }
@@ -1126,8 +1132,6 @@ impl Decoder {
         let start_date = decode_string(&mut fields_itr)?; // ver 2 field
         let end_date = decode_string(&mut fields_itr)?; // ver 2 field
 
-        let _peek = *(fields_itr.clone()).peekable().peek().unwrap();
-
         let bar_count = decode_i32(&mut fields_itr)?;
 
         for _ in 0..bar_count {
@@ -2336,7 +2340,6 @@ impl Decoder {
     }
 
     //----------------------------------------------------------------------------------------------
-    #[allow(dead_code)]
     fn process_tick_efp(&mut self, fields: &[String]) -> Result<(), IBKRApiLibError> {
         let mut fields_itr = fields.iter();
 
@@ -2403,7 +2406,7 @@ impl Decoder {
 
         let tick_news = ServerRspMsg::TickNews {
             ticker_id: decode_i32(&mut fields_itr)?,
-            time_stamp: decode_i32(&mut fields_itr)?,
+            time_stamp: decode_i64(&mut fields_itr)?,
             provider_code: decode_string(&mut fields_itr)?,
             article_id: decode_string(&mut fields_itr)?,
             headline: decode_string(&mut fields_itr)?,
@@ -2567,7 +2570,6 @@ impl Decoder {
         fields_itr.next();
         //throw away version
         fields_itr.next();
-        let _is_successful_str = decode_string(&mut fields_itr)?;
         let is_successful = "true" == decode_string(&mut fields_itr)?;
         let error_text = decode_string(&mut fields_itr)?;
 
@@ -2609,7 +2611,6 @@ impl Decoder {
         //throw away version
         fields_itr.next();
 
-        let _is_successful_str = decode_string(&mut fields_itr)?;
         let is_successful = "true" == decode_string(&mut fields_itr)?;
         let error_text = decode_string(&mut fields_itr)?;
         let verify_completed = ServerRspMsg::VerifyCompleted {
@@ -2622,7 +2623,6 @@ impl Decoder {
     }
 
     //----------------------------------------------------------------------------------------------
-    #[allow(dead_code)]
     fn process_verify_message_api(&mut self, fields: &[String]) -> Result<(), IBKRApiLibError> {
         let mut fields_itr = fields.iter();
         //throw away message_id
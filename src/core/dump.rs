@@ -0,0 +1,1065 @@
+//! Human readable dumps of framed protocol messages
+//!
+//! Incoming messages are run through the `Decoder` and the resulting `ServerRspMsg` is flattened
+//! into labeled fields. Outgoing requests are labeled with the field layouts `EClient` writes
+//! them with. Anything past a known layout is labeled by position.
+use std::fmt;
+use std::fmt::Display;
+use std::sync::mpsc::channel;
+use std::sync::{Arc, Mutex};
+
+use num_traits::FromPrimitive;
+use serde::ser::{self, Serialize};
+
+use crate::core::client::ConnStatus;
+use crate::core::common::{UNSET_DOUBLE, UNSET_INTEGER, UNSET_LONG};
+use crate::core::decoder::Decoder;
+use crate::core::errors::{IBKRApiLibError, TwsApiReportableError, TwsError};
+use crate::core::messages::{
+    read_fields, read_msg, ServerReqMsgDiscriminants, ServerRspMsg, ServerRspMsgDiscriminants,
+};
+use crate::core::server_versions::{
+    MIN_SERVER_VER_ORDER_CONTAINER, MIN_SERVER_VER_SCANNER_GENERIC_OPTS,
+    MIN_SERVER_VER_SYNT_REALTIME_BARS,
+};
+
+/// Prefix sent by the client ahead of the version handshake
+const API_PREFIX: &[u8] = b"API\0";
+
+const UNSET_LABEL: &str = "<unset>";
+
+/// Contract fields as `EClient` sends them for most requests
+const CONTRACT_FIELDS: &[&str] = &[
+    "contract.con_id",
+    "contract.symbol",
+    "contract.sec_type",
+    "contract.last_trade_date_or_contract_month",
+    "contract.strike",
+    "contract.right",
+    "contract.multiplier",
+    "contract.exchange",
+    "contract.primary_exchange",
+    "contract.currency",
+    "contract.local_symbol",
+    "contract.trading_class",
+];
+
+//==================================================================================================
+/// Which side of the connection sent a message
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Direction {
+    /// TWS to client
+    Incoming,
+    /// Client to TWS
+    Outgoing,
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Direction::Incoming => write!(f, "<-"),
+            Direction::Outgoing => write!(f, "->"),
+        }
+    }
+}
+
+//==================================================================================================
+#[derive(Clone, Debug, PartialEq)]
+pub struct DumpedField {
+    pub label: String,
+    pub value: String,
+}
+
+impl DumpedField {
+    pub fn new(label: &str, value: &str) -> Self {
+        DumpedField {
+            label: label.to_string(),
+            value: value.to_string(),
+        }
+    }
+}
+
+//==================================================================================================
+/// A single message broken out into its name and labeled fields
+#[derive(Clone, Debug)]
+pub struct DumpedMessage {
+    pub direction: Direction,
+    pub msg_id: Option<i32>,
+    /// `ServerRspMsgDiscriminants` or `ServerReqMsgDiscriminants` name
+    pub name: String,
+    /// Fields exactly as they appeared on the wire
+    pub raw_fields: Vec<String>,
+    pub fields: Vec<DumpedField>,
+    /// Set when the message could not be fully interpreted
+    pub note: Option<String>,
+}
+
+impl DumpedMessage {
+    /// Value of the first field with the given label
+    pub fn field(&self, label: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|field| field.label == label)
+            .map(|field| field.value.as_str())
+    }
+}
+
+impl Display for DumpedMessage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.direction, self.name)?;
+        if let Some(msg_id) = self.msg_id {
+            write!(f, " ({})", msg_id)?;
+        }
+        writeln!(f, " [{} fields]", self.raw_fields.len())?;
+        if let Some(note) = &self.note {
+            writeln!(f, "    !! {}", note)?;
+        }
+        let width = self
+            .fields
+            .iter()
+            .map(|field| field.label.len())
+            .max()
+            .unwrap_or(0);
+        for field in &self.fields {
+            writeln!(
+                f,
+                "    {:width$} = {:?}",
+                field.label,
+                field.value,
+                width = width
+            )?;
+        }
+        Ok(())
+    }
+}
+
+//==================================================================================================
+fn bad_message(detail: &str) -> IBKRApiLibError {
+    IBKRApiLibError::ApiError(TwsApiReportableError::new(
+        -1,
+        TwsError::BadMessage.code().to_string(),
+        format!("{} {}", TwsError::BadMessage.message(), detail),
+    ))
+}
+
+//==================================================================================================
+/// Parses a textual byte dump into bytes.
+///
+/// Accepts the decimal arrays used in the client tests (`[0, 0, 0, 5, 54, 50, 0, ...]`),
+/// `0x` prefixed hex bytes, whitespace separated hex pairs (`00 00 00 05 36`) and one
+/// contiguous hex string. Text following `//` or `#` on a line is ignored.
+pub fn parse_hex_dump(text: &str) -> Result<Vec<u8>, IBKRApiLibError> {
+    let body = text
+        .lines()
+        .map(|line| {
+            let end = [line.find("//"), line.find('#')]
+                .iter()
+                .filter_map(|pos| *pos)
+                .min()
+                .unwrap_or(line.len());
+            &line[..end]
+        })
+        .collect::<Vec<&str>>()
+        .join("\n");
+
+    let decimal = body.contains(',') || body.contains('[');
+    let tokens: Vec<&str> = body
+        .split(|c: char| c.is_whitespace() || c == ',' || c == '[' || c == ']' || c == ';')
+        .filter(|token| !token.is_empty())
+        .collect();
+
+    let mut bytes = Vec::new();
+    for token in tokens {
+        if let Some(hex) = token
+            .strip_prefix("0x")
+            .or_else(|| token.strip_prefix("0X"))
+        {
+            bytes.push(parse_byte(hex, 16, token)?);
+        } else if decimal {
+            bytes.push(parse_byte(token, 10, token)?);
+        } else if token.len() % 2 == 0 {
+            for i in (0..token.len()).step_by(2) {
+                let pair = token.get(i..i + 2).ok_or_else(|| bad_message(token))?;
+                bytes.push(parse_byte(pair, 16, token)?);
+            }
+        } else {
+            return Err(bad_message(&format!("odd length hex token '{}'", token)));
+        }
+    }
+    Ok(bytes)
+}
+
+fn parse_byte(digits: &str, radix: u32, token: &str) -> Result<u8, IBKRApiLibError> {
+    u8::from_str_radix(digits, radix)
+        .map_err(|_| bad_message(&format!("invalid byte '{}' in dump", token)))
+}
+
+//==================================================================================================
+/// Splits a stream of length prefixed messages into their fields and dumps each of them.
+///
+/// A leading `API\0` handshake prefix is recognised on outgoing streams, and a trailing partial
+/// frame is reported instead of being silently dropped.
+pub fn dump_bytes(
+    bytes: &[u8],
+    direction: Direction,
+    server_version: i32,
+) -> Result<Vec<DumpedMessage>, IBKRApiLibError> {
+    let mut messages = Vec::new();
+    let mut buf = bytes.to_vec();
+
+    if buf.starts_with(API_PREFIX) {
+        buf.drain(..API_PREFIX.len());
+        let (size, text, rest) = read_msg(&buf)?;
+        if rest.len() != buf.len() || size == 0 {
+            messages.push(DumpedMessage {
+                direction: Direction::Outgoing,
+                msg_id: None,
+                name: "Handshake".to_string(),
+                raw_fields: vec![text.clone()],
+                fields: vec![DumpedField::new("client_versions", &text)],
+                note: None,
+            });
+            buf = rest;
+        }
+    }
+
+    while !buf.is_empty() {
+        let (size, text, rest) = read_msg(&buf)?;
+        if rest.len() == buf.len() {
+            return Err(bad_message(&format!(
+                "truncated frame: {} byte payload announced, {} bytes available",
+                size,
+                buf.len().saturating_sub(4)
+            )));
+        }
+        let fields = read_fields(&text);
+        let first = messages.is_empty();
+        messages.push(
+            if first && direction == Direction::Incoming && is_server_hello(&fields) {
+                server_hello(fields)
+            } else {
+                dump_fields(&fields, direction, server_version)
+            },
+        );
+        buf = rest;
+    }
+
+    Ok(messages)
+}
+
+// The first thing TWS sends is its server version and connection time, without a message id
+fn is_server_hello(fields: &[String]) -> bool {
+    fields.len() == 2
+        && fields[0]
+            .parse::<i32>()
+            .map(|id| ServerRspMsgDiscriminants::from_i32(id).is_none())
+            .unwrap_or(false)
+}
+
+fn server_hello(fields: Vec<String>) -> DumpedMessage {
+    DumpedMessage {
+        direction: Direction::Incoming,
+        msg_id: None,
+        name: "ServerVersion".to_string(),
+        fields: vec![
+            DumpedField::new("server_version", &fields[0]),
+            DumpedField::new("conn_time", &fields[1]),
+        ],
+        raw_fields: fields,
+        note: None,
+    }
+}
+
+//==================================================================================================
+/// Dumps the fields of one message that has already been split with `read_fields`
+pub fn dump_fields(fields: &[String], direction: Direction, server_version: i32) -> DumpedMessage {
+    let msg_id = fields.first().and_then(|id| id.parse::<i32>().ok());
+    let mut dumped = DumpedMessage {
+        direction,
+        msg_id,
+        name: "Unknown".to_string(),
+        raw_fields: fields.to_vec(),
+        fields: vec![],
+        note: None,
+    };
+
+    match direction {
+        Direction::Incoming => {
+            if let Some(id) = msg_id.and_then(ServerRspMsgDiscriminants::from_i32) {
+                dumped.name = format!("{:?}", id);
+                match decode_incoming(fields, server_version) {
+                    Ok(decoded) if !decoded.is_empty() => {
+                        dumped.fields.push(DumpedField::new("msg_id", &fields[0]));
+                        dumped.fields.extend(label_decoded(&decoded));
+                    }
+                    Ok(_) => {
+                        dumped.note = Some("decoder produced no message".to_string());
+                        dumped.fields = label_positional(fields, &[]);
+                    }
+                    Err(err) => {
+                        dumped.note = Some(format!("decode failed: {}", err));
+                        dumped.fields = label_positional(fields, &[]);
+                    }
+                }
+                return dumped;
+            }
+        }
+        Direction::Outgoing => {
+            if let Some(id) = msg_id.and_then(ServerReqMsgDiscriminants::from_i32) {
+                dumped.name = format!("{:?}", id);
+                dumped.fields = label_positional(fields, &request_layout(id, server_version));
+                return dumped;
+            }
+        }
+    }
+
+    dumped.note = Some("unknown message id".to_string());
+    dumped.fields = label_positional(fields, &[]);
+    dumped
+}
+
+//==================================================================================================
+fn decode_incoming(
+    fields: &[String],
+    server_version: i32,
+) -> Result<Vec<ServerRspMsg>, IBKRApiLibError> {
+    let (_msg_sender, msg_queue) = channel::<String>();
+    let (send_queue, received) = channel::<ServerRspMsg>();
+    let mut decoder = Decoder::new(
+        msg_queue,
+        send_queue,
+        server_version,
+        Arc::new(Mutex::new(ConnStatus::CONNECTED)),
+    );
+
+    decoder.interpret(fields)?;
+    Ok(received.try_iter().collect())
+}
+
+//==================================================================================================
+fn label_decoded(decoded: &[ServerRspMsg]) -> Vec<DumpedField> {
+    let mut labeled = Vec::new();
+    for (i, msg) in decoded.iter().enumerate() {
+        let mut collector = FieldCollector::default();
+        if decoded.len() > 1 {
+            collector.path.push(format!("{}[{}]", msg, i));
+        }
+        // Serializing into the collector cannot fail, it only records values
+        if msg.serialize(&mut collector).is_ok() {
+            labeled.extend(collector.fields);
+        }
+    }
+    labeled
+}
+
+//==================================================================================================
+fn label_positional(fields: &[String], layout: &[&str]) -> Vec<DumpedField> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(i, value)| match layout.get(i) {
+            Some(label) => DumpedField::new(label, value),
+            None => DumpedField::new(&format!("field[{}]", i), value),
+        })
+        .collect()
+}
+
+//==================================================================================================
+/// Leading fields of each request in the order `EClient` writes them.
+///
+/// Layouts stop where the rest of the message depends on its contents (combo legs, tag value
+/// lists, conditions), from there on fields are labeled by position.
+fn request_layout(id: ServerReqMsgDiscriminants, server_version: i32) -> Vec<&'static str> {
+    use ServerReqMsgDiscriminants::*;
+
+    let with_contract = |head: &[&'static str], tail: &[&'static str]| {
+        let mut layout = head.to_vec();
+        layout.extend_from_slice(CONTRACT_FIELDS);
+        layout.extend_from_slice(tail);
+        layout
+    };
+    let versioned_below = |min_version: i32| {
+        if server_version < min_version {
+            vec!["msg_id", "version"]
+        } else {
+            vec!["msg_id"]
+        }
+    };
+
+    match id {
+        ReqMktData => with_contract(&["msg_id", "version", "req_id"], &[]),
+        CancelMktData
+        | CancelScannerSubscription
+        | CancelHistoricalData
+        | CancelRealTimeBars
+        | CancelFundamentalData
+        | CancelCalcImpliedVolat
+        | CancelCalcOptionPrice
+        | CancelAccountSummary
+        | QueryDisplayGroups
+        | UnsubscribeFromGroupEvents
+        | CancelPositionsMulti
+        | CancelAccountUpdatesMulti => vec!["msg_id", "version", "req_id"],
+        PlaceOrder => {
+            let mut head = versioned_below(MIN_SERVER_VER_ORDER_CONTAINER);
+            head.push("order_id");
+            with_contract(
+                &head,
+                &[
+                    "contract.sec_id_type",
+                    "contract.sec_id",
+                    "order.action",
+                    "order.total_quantity",
+                    "order.order_type",
+                    "order.lmt_price",
+                    "order.aux_price",
+                    "order.tif",
+                    "order.oca_group",
+                    "order.account",
+                    "order.open_close",
+                    "order.origin",
+                    "order.order_ref",
+                    "order.transmit",
+                    "order.parent_id",
+                    "order.block_order",
+                    "order.sweep_to_fill",
+                    "order.display_size",
+                    "order.trigger_method",
+                    "order.outside_rth",
+                    "order.hidden",
+                ],
+            )
+        }
+        CancelOrder => vec!["msg_id", "version", "order_id"],
+        ReqOpenOrders | ReqAllOpenOrders | ReqGlobalCancel | ReqManagedAccts | ReqPositions
+        | CancelPositions | CancelNewsBulletins | ReqScannerParameters | ReqCurrentTime => {
+            vec!["msg_id", "version"]
+        }
+        ReqAcctData => vec!["msg_id", "version", "subscribe", "acct_code"],
+        ReqExecutions => vec![
+            "msg_id",
+            "version",
+            "req_id",
+            "exec_filter.client_id",
+            "exec_filter.acct_code",
+            "exec_filter.time",
+            "exec_filter.symbol",
+            "exec_filter.sec_type",
+            "exec_filter.exchange",
+            "exec_filter.side",
+        ],
+        ReqIds => vec!["msg_id", "version", "num_ids"],
+        ReqContractData => with_contract(
+            &["msg_id", "version", "req_id"],
+            &[
+                "contract.include_expired",
+                "contract.sec_id_type",
+                "contract.sec_id",
+            ],
+        ),
+        ReqMktDepth => with_contract(
+            &["msg_id", "version", "req_id"],
+            &["num_rows", "is_smart_depth", "mkt_depth_options"],
+        ),
+        CancelMktDepth => vec!["msg_id", "version", "req_id", "is_smart_depth"],
+        ReqNewsBulletins => vec!["msg_id", "version", "all_msgs"],
+        SetServerLoglevel => vec!["msg_id", "version", "log_level"],
+        ReqAutoOpenOrders => vec!["msg_id", "version", "auto_bind"],
+        ReqFa => vec!["msg_id", "version", "fa_data"],
+        ReplaceFa => vec!["msg_id", "version", "fa_data", "cxml"],
+        ReqHistoricalData => {
+            let mut head = versioned_below(MIN_SERVER_VER_SYNT_REALTIME_BARS);
+            head.push("req_id");
+            with_contract(
+                &head,
+                &[
+                    "contract.include_expired",
+                    "end_date_time",
+                    "bar_size_setting",
+                    "duration_str",
+                    "use_rth",
+                    "what_to_show",
+                    "format_date",
+                ],
+            )
+        }
+        ExerciseOptions => vec![
+            "msg_id",
+            "version",
+            "req_id",
+            "contract.con_id",
+            "contract.symbol",
+            "contract.sec_type",
+            "contract.last_trade_date_or_contract_month",
+            "contract.strike",
+            "contract.right",
+            "contract.multiplier",
+            "contract.exchange",
+            "contract.currency",
+            "contract.local_symbol",
+            "contract.trading_class",
+            "exercise_action",
+            "exercise_quantity",
+            "account",
+            "override",
+        ],
+        ReqScannerSubscription => {
+            let mut layout = versioned_below(MIN_SERVER_VER_SCANNER_GENERIC_OPTS);
+            layout.extend_from_slice(&[
+                "req_id",
+                "subscription.number_of_rows",
+                "subscription.instrument",
+                "subscription.location_code",
+                "subscription.scan_code",
+                "subscription.above_price",
+                "subscription.below_price",
+                "subscription.above_volume",
+                "subscription.market_cap_above",
+                "subscription.market_cap_below",
+                "subscription.moody_rating_above",
+                "subscription.moody_rating_below",
+                "subscription.sp_rating_above",
+                "subscription.sp_rating_below",
+                "subscription.maturity_date_above",
+                "subscription.maturity_date_below",
+                "subscription.coupon_rate_above",
+                "subscription.coupon_rate_below",
+                "subscription.exclude_convertible",
+                "subscription.average_option_volume_above",
+                "subscription.scanner_setting_pairs",
+                "subscription.stock_type_filter",
+            ]);
+            if server_version >= MIN_SERVER_VER_SCANNER_GENERIC_OPTS {
+                layout.push("scanner_subscription_filter");
+            }
+            layout.push("scanner_subscription_options");
+            layout
+        }
+        ReqRealTimeBars => with_contract(
+            &["msg_id", "version", "req_id"],
+            &[
+                "bar_size",
+                "what_to_show",
+                "use_rth",
+                "real_time_bars_options",
+            ],
+        ),
+        ReqFundamentalData => vec![
+            "msg_id",
+            "version",
+            "req_id",
+            "contract.con_id",
+            "contract.symbol",
+            "contract.sec_type",
+            "contract.exchange",
+            "contract.primary_exchange",
+            "contract.currency",
+            "contract.local_symbol",
+            "report_type",
+            "tag_values_count",
+            "fund_data_options",
+        ],
+        ReqCalcImpliedVolat => with_contract(
+            &["msg_id", "version", "req_id"],
+            &[
+                "option_price",
+                "under_price",
+                "tag_values_count",
+                "impl_vol_options",
+            ],
+        ),
+        ReqCalcOptionPrice => with_contract(
+            &["msg_id", "version", "req_id"],
+            &[
+                "volatility",
+                "under_price",
+                "tag_values_count",
+                "opt_prc_options",
+            ],
+        ),
+        ReqMarketDataType => vec!["msg_id", "version", "market_data_type"],
+        ReqAccountSummary => vec!["msg_id", "version", "req_id", "group_name", "tags"],
+        VerifyRequest => vec!["msg_id", "version", "api_name", "api_version"],
+        VerifyMessage => vec!["msg_id", "version", "api_data"],
+        SubscribeToGroupEvents => vec!["msg_id", "version", "req_id", "group_id"],
+        UpdateDisplayGroup => vec!["msg_id", "version", "req_id", "contract_info"],
+        StartApi => vec!["msg_id", "version", "client_id", "optional_capabilities"],
+        VerifyAndAuthRequest => vec![
+            "msg_id",
+            "version",
+            "api_name",
+            "api_version",
+            "opaque_isv_key",
+        ],
+        VerifyAndAuthMessage => vec!["msg_id", "version", "api_data", "xyz_response"],
+        ReqPositionsMulti => vec!["msg_id", "version", "req_id", "account", "model_code"],
+        ReqAccountUpdatesMulti => vec![
+            "msg_id",
+            "version",
+            "req_id",
+            "account",
+            "model_code",
+            "ledger_and_nlv",
+        ],
+        ReqSecDefOptParams => vec![
+            "msg_id",
+            "req_id",
+            "underlying_symbol",
+            "fut_fop_exchange",
+            "underlying_sec_type",
+            "underlying_con_id",
+        ],
+        ReqSoftDollarTiers | CancelTickByTickData | CancelPnl | CancelPnlSingle
+        | CancelHeadTimestamp => vec!["msg_id", "req_id"],
+        ReqFamilyCodes | ReqMktDepthExchanges | ReqNewsProviders => vec!["msg_id"],
+        ReqMatchingSymbols => vec!["msg_id", "req_id", "pattern"],
+        ReqSmartComponents => vec!["msg_id", "req_id", "bbo_exchange"],
+        ReqNewsArticle => vec![
+            "msg_id",
+            "req_id",
+            "provider_code",
+            "article_id",
+            "news_article_options",
+        ],
+        ReqHistoricalNews => vec![
+            "msg_id",
+            "req_id",
+            "con_id",
+            "provider_codes",
+            "start_date_time",
+            "end_date_time",
+            "total_results",
+            "historical_news_options",
+        ],
+        ReqHeadTimestamp => with_contract(
+            &["msg_id", "req_id"],
+            &[
+                "contract.include_expired",
+                "use_rth",
+                "what_to_show",
+                "format_date",
+            ],
+        ),
+        ReqHistogramData => with_contract(
+            &["msg_id", "ticker_id"],
+            &["contract.include_expired", "use_rth", "time_period"],
+        ),
+        CancelHistogramData => vec!["msg_id", "ticker_id"],
+        ReqMarketRule => vec!["msg_id", "market_rule_id"],
+        ReqPnl => vec!["msg_id", "req_id", "account", "model_code"],
+        ReqPnlSingle => vec!["msg_id", "req_id", "account", "model_code", "con_id"],
+        ReqHistoricalTicks => with_contract(
+            &["msg_id", "req_id"],
+            &[
+                "contract.include_expired",
+                "start_date_time",
+                "end_date_time",
+                "number_of_ticks",
+                "what_to_show",
+                "use_rth",
+                "ignore_size",
+                "misc_options",
+            ],
+        ),
+        ReqTickByTickData => with_contract(
+            &["msg_id", "req_id"],
+            &["tick_type", "number_of_ticks", "ignore_size"],
+        ),
+        ReqCompletedOrders => vec!["msg_id", "api_only"],
+    }
+}
+
+//==================================================================================================
+/// serde `Serializer` that records every leaf value of a message with its dotted field path
+#[derive(Default)]
+struct FieldCollector {
+    path: Vec<String>,
+    fields: Vec<DumpedField>,
+    pending_key: Option<String>,
+    // The message variant is already given by the message name, so the outermost one is skipped
+    entered_message: bool,
+}
+
+#[derive(Debug)]
+struct CollectError(String);
+
+impl Display for CollectError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for CollectError {}
+
+impl ser::Error for CollectError {
+    fn custom<T: Display>(msg: T) -> Self {
+        CollectError(msg.to_string())
+    }
+}
+
+impl FieldCollector {
+    fn label(&self) -> String {
+        let mut label = String::new();
+        for segment in &self.path {
+            if !label.is_empty() && !segment.starts_with('[') {
+                label.push('.');
+            }
+            label.push_str(segment);
+        }
+        label
+    }
+
+    fn leaf(&mut self, value: String) -> Result<(), CollectError> {
+        let label = self.label();
+        self.fields.push(DumpedField { label, value });
+        Ok(())
+    }
+
+    fn nested<T: ?Sized + Serialize>(
+        &mut self,
+        segment: String,
+        value: &T,
+    ) -> Result<(), CollectError> {
+        self.path.push(segment);
+        let result = value.serialize(&mut *self);
+        self.path.pop();
+        result
+    }
+}
+
+/// Tracks position within a sequence, struct or map being collected
+struct Compound<'a> {
+    collector: &'a mut FieldCollector,
+    index: usize,
+    // Variant segment pushed for nested enums, popped when the compound ends
+    pushed: bool,
+}
+
+impl<'a> Compound<'a> {
+    fn new(collector: &'a mut FieldCollector, variant: Option<&str>) -> Self {
+        let pushed = match variant {
+            Some(variant) if collector.entered_message => {
+                collector.path.push(variant.to_string());
+                true
+            }
+            _ => false,
+        };
+        collector.entered_message = true;
+        Compound {
+            collector,
+            index: 0,
+            pushed,
+        }
+    }
+
+    fn element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), CollectError> {
+        let segment = format!("[{}]", self.index);
+        self.index += 1;
+        self.collector.nested(segment, value)
+    }
+
+    fn end_seq(self) -> Result<(), CollectError> {
+        if self.index == 0 {
+            self.collector.leaf("[]".to_string())?;
+        }
+        self.end()
+    }
+
+    fn end(self) -> Result<(), CollectError> {
+        if self.pushed {
+            self.collector.path.pop();
+        }
+        Ok(())
+    }
+}
+
+impl<'a> ser::Serializer for &'a mut FieldCollector {
+    type Ok = ();
+    type Error = CollectError;
+    type SerializeSeq = Compound<'a>;
+    type SerializeTuple = Compound<'a>;
+    type SerializeTupleStruct = Compound<'a>;
+    type SerializeTupleVariant = Compound<'a>;
+    type SerializeMap = Compound<'a>;
+    type SerializeStruct = Compound<'a>;
+    type SerializeStructVariant = Compound<'a>;
+
+    fn serialize_bool(self, v: bool) -> Result<(), CollectError> {
+        self.leaf(v.to_string())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), CollectError> {
+        self.leaf(v.to_string())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), CollectError> {
+        self.leaf(v.to_string())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), CollectError> {
+        if v == UNSET_INTEGER {
+            self.leaf(UNSET_LABEL.to_string())
+        } else {
+            self.leaf(v.to_string())
+        }
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), CollectError> {
+        if v == UNSET_LONG {
+            self.leaf(UNSET_LABEL.to_string())
+        } else {
+            self.leaf(v.to_string())
+        }
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), CollectError> {
+        self.leaf(v.to_string())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), CollectError> {
+        self.leaf(v.to_string())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), CollectError> {
+        self.leaf(v.to_string())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), CollectError> {
+        self.leaf(v.to_string())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), CollectError> {
+        self.leaf(v.to_string())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), CollectError> {
+        if v == UNSET_DOUBLE {
+            self.leaf(UNSET_LABEL.to_string())
+        } else {
+            self.leaf(v.to_string())
+        }
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), CollectError> {
+        self.leaf(v.to_string())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), CollectError> {
+        self.leaf(v.to_string())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), CollectError> {
+        self.leaf(format!("{:?}", v))
+    }
+
+    fn serialize_none(self) -> Result<(), CollectError> {
+        self.leaf("None".to_string())
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<(), CollectError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), CollectError> {
+        self.leaf("()".to_string())
+    }
+
+    fn serialize_unit_struct(self, name: &'static str) -> Result<(), CollectError> {
+        self.leaf(name.to_string())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<(), CollectError> {
+        self.leaf(variant.to_string())
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), CollectError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), CollectError> {
+        if self.entered_message {
+            self.nested(variant.to_string(), value)
+        } else {
+            self.entered_message = true;
+            value.serialize(self)
+        }
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Compound<'a>, CollectError> {
+        Ok(Compound::new(self, None))
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Compound<'a>, CollectError> {
+        Ok(Compound::new(self, None))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a>, CollectError> {
+        Ok(Compound::new(self, None))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a>, CollectError> {
+        Ok(Compound::new(self, Some(variant)))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Compound<'a>, CollectError> {
+        Ok(Compound::new(self, None))
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a>, CollectError> {
+        Ok(Compound::new(self, None))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a>, CollectError> {
+        Ok(Compound::new(self, Some(variant)))
+    }
+}
+
+impl<'a> ser::SerializeSeq for Compound<'a> {
+    type Ok = ();
+    type Error = CollectError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), CollectError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), CollectError> {
+        self.end_seq()
+    }
+}
+
+impl<'a> ser::SerializeTuple for Compound<'a> {
+    type Ok = ();
+    type Error = CollectError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), CollectError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), CollectError> {
+        self.end_seq()
+    }
+}
+
+impl<'a> ser::SerializeTupleStruct for Compound<'a> {
+    type Ok = ();
+    type Error = CollectError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), CollectError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), CollectError> {
+        self.end_seq()
+    }
+}
+
+impl<'a> ser::SerializeTupleVariant for Compound<'a> {
+    type Ok = ();
+    type Error = CollectError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), CollectError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), CollectError> {
+        self.end_seq()
+    }
+}
+
+impl<'a> ser::SerializeMap for Compound<'a> {
+    type Ok = ();
+    type Error = CollectError;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), CollectError> {
+        let mut key_collector = FieldCollector::default();
+        key.serialize(&mut key_collector)?;
+        self.collector.pending_key = Some(
+            key_collector
+                .fields
+                .into_iter()
+                .map(|field| field.value)
+                .collect::<Vec<String>>()
+                .join(","),
+        );
+        Ok(())
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), CollectError> {
+        let key = self.collector.pending_key.take().unwrap_or_default();
+        self.index += 1;
+        self.collector.nested(format!("[{}]", key), value)
+    }
+
+    fn end(self) -> Result<(), CollectError> {
+        self.end_seq()
+    }
+}
+
+impl<'a> ser::SerializeStruct for Compound<'a> {
+    type Ok = ();
+    type Error = CollectError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), CollectError> {
+        self.collector.nested(key.to_string(), value)
+    }
+
+    fn end(self) -> Result<(), CollectError> {
+        Compound::end(self)
+    }
+}
+
+impl<'a> ser::SerializeStructVariant for Compound<'a> {
+    type Ok = ();
+    type Error = CollectError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), CollectError> {
+        self.collector.nested(key.to_string(), value)
+    }
+
+    fn end(self) -> Result<(), CollectError> {
+        Compound::end(self)
+    }
+}
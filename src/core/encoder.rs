@@ -0,0 +1,1458 @@
+//! Encodes ServerRspMsg values into the field layout expected by the Decoder.  This is the inverse
+//! of the decoder and is intended for simulators, fake gateways and replay tools.
+use crate::core::common::{BarData, TickMsgType, TickType, UNSET_DOUBLE, UNSET_INTEGER};
+use crate::core::contract::{Contract, ContractDetails};
+use crate::core::errors::IBKRApiLibError;
+use crate::core::messages::{make_message, ServerRspMsg, ServerRspMsgDiscriminants};
+use crate::core::order_condition::{Condition, OrderConditionEnum};
+use crate::core::order_encoder::OrderEncoder;
+use crate::core::scanner::ScanData;
+use crate::core::server_versions::{
+    MIN_SERVER_VER_AGG_GROUP, MIN_SERVER_VER_FRACTIONAL_POSITIONS, MIN_SERVER_VER_LAST_LIQUIDITY,
+    MIN_SERVER_VER_MARKET_CAP_PRICE, MIN_SERVER_VER_MARKET_RULES,
+    MIN_SERVER_VER_MD_SIZE_MULTIPLIER, MIN_SERVER_VER_MODELS_SUPPORT,
+    MIN_SERVER_VER_ORDER_CONTAINER, MIN_SERVER_VER_PAST_LIMIT, MIN_SERVER_VER_PRE_OPEN_BID_ASK,
+    MIN_SERVER_VER_REALIZED_PNL, MIN_SERVER_VER_REAL_EXPIRATION_DATE,
+    MIN_SERVER_VER_SERVICE_DATA_TYPE, MIN_SERVER_VER_SMART_DEPTH,
+    MIN_SERVER_VER_SYNT_REALTIME_BARS, MIN_SERVER_VER_UNDERLYING_INFO,
+    MIN_SERVER_VER_UNREALIZED_PNL,
+};
+
+/// Version written for messages that still carry a version field
+const MSG_VERSION: i32 = 1;
+/// Highest open order version understood by the decoder before the version field was dropped
+const OPEN_ORDER_VERSION: i32 = 34;
+/// Highest contract data version understood by the decoder
+const CONTRACT_DATA_VERSION: i32 = 8;
+/// Highest bond contract data version understood by the decoder
+const BOND_CONTRACT_DATA_VERSION: i32 = 6;
+/// Highest execution data version understood by the decoder before the version field was dropped
+const EXECUTION_DATA_VERSION: i32 = 10;
+/// Highest portfolio value version understood by the decoder
+const PORTFOLIO_VALUE_VERSION: i32 = 8;
+/// Highest position data version understood by the decoder
+const POSITION_DATA_VERSION: i32 = 3;
+/// Highest option computation version understood by the decoder
+const TICK_OPTION_COMPUTATION_VERSION: i32 = 6;
+
+//==================================================================================================
+pub fn encode_i32(fields: &mut Vec<String>, val: i32) {
+    fields.push(val.to_string());
+}
+
+//==================================================================================================
+/// Inverse of `decode_i32_show_unset`: UNSET_INTEGER is written as an empty field
+pub fn encode_i32_show_unset(fields: &mut Vec<String>, val: i32) {
+    if val == UNSET_INTEGER {
+        fields.push("".to_string());
+    } else {
+        encode_i32(fields, val);
+    }
+}
+
+//==================================================================================================
+pub fn encode_i64(fields: &mut Vec<String>, val: i64) {
+    fields.push(val.to_string());
+}
+
+//==================================================================================================
+/// UNSET_DOUBLE is written in exponent notation, which parses back to the same value
+pub fn encode_f64(fields: &mut Vec<String>, val: f64) {
+    if val == UNSET_DOUBLE {
+        fields.push(format!("{:e}", val));
+    } else {
+        fields.push(val.to_string());
+    }
+}
+
+//==================================================================================================
+/// Inverse of `decode_f64_show_unset`: UNSET_DOUBLE is written as an empty field
+pub fn encode_f64_show_unset(fields: &mut Vec<String>, val: f64) {
+    if val == UNSET_DOUBLE {
+        fields.push("".to_string());
+    } else {
+        encode_f64(fields, val);
+    }
+}
+
+//==================================================================================================
+pub fn encode_string(fields: &mut Vec<String>, val: &str) {
+    fields.push(val.to_string());
+}
+
+//==================================================================================================
+pub fn encode_bool(fields: &mut Vec<String>, val: bool) {
+    encode_i32(fields, val as i32);
+}
+
+//==================================================================================================
+/// Appends the fields of an order condition, without the leading condition type
+pub fn encode_condition(
+    fields: &mut Vec<String>,
+    condition: &OrderConditionEnum,
+) -> Result<(), IBKRApiLibError> {
+    for field in condition.make_fields()? {
+        fields.push(field.trim_end_matches('\0').to_string());
+    }
+    Ok(())
+}
+
+//==================================================================================================
+fn encode_msg_id(fields: &mut Vec<String>, msg_id: ServerRspMsgDiscriminants) {
+    encode_i32(fields, msg_id as i32);
+}
+
+//==================================================================================================
+fn encode_option_computation_value(fields: &mut Vec<String>, val: f64, not_computed: f64) {
+    if val == f64::MAX {
+        encode_f64(fields, not_computed);
+    } else {
+        encode_f64(fields, val);
+    }
+}
+
+//==================================================================================================
+pub struct Encoder {
+    pub server_version: i32,
+}
+
+impl Encoder {
+    pub fn new(server_version: i32) -> Self {
+        Encoder { server_version }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Encode a message into a complete, length prefixed frame
+    pub fn encode(&self, msg: &ServerRspMsg) -> Result<Vec<u8>, IBKRApiLibError> {
+        let fields = self.encode_fields(msg)?;
+        make_message(&join_fields(&fields))
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Encode a message into its fields, starting with the message id.
+    ///
+    /// A few messages do not map one to one onto the wire:
+    ///  * `TickPrice` carries no size, so a size of 0 is written.  Use `encode_tick_price_fields`
+    ///    to supply one.
+    ///  * `HistoricalData` is written as a single bar with empty start and end dates and
+    ///    `HistoricalDataEnd` as an empty batch.  Use `encode_historical_data_fields` for a batch.
+    ///  * `ScannerData` is written as a single row and `ScannerDataEnd` as an empty batch.  Use
+    ///    `encode_scanner_data_fields` for a batch.
+    pub fn encode_fields(&self, msg: &ServerRspMsg) -> Result<Vec<String>, IBKRApiLibError> {
+        let mut fields = vec![];
+        match msg {
+            ServerRspMsg::TickPrice {
+                req_id,
+                tick_type,
+                price,
+                tick_attr,
+            } => {
+                let mut attr_mask = 0;
+                if tick_attr.can_auto_execute {
+                    attr_mask |= 1;
+                }
+                if self.server_version >= MIN_SERVER_VER_PAST_LIMIT && tick_attr.past_limit {
+                    attr_mask |= 2;
+                }
+                if self.server_version >= MIN_SERVER_VER_PRE_OPEN_BID_ASK && tick_attr.pre_open {
+                    attr_mask |= 4;
+                }
+                return Ok(self.encode_tick_price_fields(*req_id, *tick_type, *price, 0, attr_mask));
+            }
+            ServerRspMsg::TickSize {
+                req_id,
+                tick_type,
+                size,
+            } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::TickSize);
+                encode_i32(&mut fields, MSG_VERSION);
+                encode_i32(&mut fields, *req_id);
+                encode_i32(&mut fields, *tick_type as i32);
+                encode_i32(&mut fields, *size);
+            }
+            ServerRspMsg::OrderStatus {
+                order_id,
+                status,
+                filled,
+                remaining,
+                avg_fill_price,
+                perm_id,
+                parent_id,
+                last_fill_price,
+                client_id,
+                why_held,
+                mkt_cap_price,
+            } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::OrderStatus);
+                if self.server_version < MIN_SERVER_VER_MARKET_CAP_PRICE {
+                    encode_i32(&mut fields, MSG_VERSION);
+                }
+                encode_i32(&mut fields, *order_id);
+                encode_string(&mut fields, status);
+                self.encode_position(&mut fields, *filled);
+                self.encode_position(&mut fields, *remaining);
+                encode_f64(&mut fields, *avg_fill_price);
+                encode_i32(&mut fields, *perm_id);
+                encode_i32(&mut fields, *parent_id);
+                encode_f64(&mut fields, *last_fill_price);
+                encode_i32(&mut fields, *client_id);
+                encode_string(&mut fields, why_held);
+                if self.server_version >= MIN_SERVER_VER_MARKET_CAP_PRICE {
+                    encode_f64(&mut fields, *mkt_cap_price);
+                }
+            }
+            ServerRspMsg::ErrMsg {
+                req_id,
+                error_code,
+                error_str,
+            } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::ErrMsg);
+                encode_i32(&mut fields, MSG_VERSION);
+                encode_i32(&mut fields, *req_id);
+                encode_i32(&mut fields, *error_code);
+                encode_string(&mut fields, error_str);
+            }
+            ServerRspMsg::OpenOrder {
+                order_id,
+                contract,
+                order,
+                order_state,
+            } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::OpenOrder);
+                let mut version = self.server_version;
+                if self.server_version < MIN_SERVER_VER_ORDER_CONTAINER {
+                    version = OPEN_ORDER_VERSION;
+                    encode_i32(&mut fields, version);
+                }
+                let mut order = order.clone();
+                order.order_id = *order_id;
+                OrderEncoder::new(contract, &order, order_state, version, self.server_version)
+                    .encode_open(&mut fields)?;
+            }
+            ServerRspMsg::AcctValue {
+                key,
+                val,
+                currency,
+                account_name,
+            } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::AcctValue);
+                encode_i32(&mut fields, MSG_VERSION);
+                encode_string(&mut fields, key);
+                encode_string(&mut fields, val);
+                encode_string(&mut fields, currency);
+                encode_string(&mut fields, account_name);
+            }
+            ServerRspMsg::PortfolioValue {
+                contract,
+                position,
+                market_price,
+                market_value,
+                average_cost,
+                unrealized_pnl,
+                realized_pnl,
+                account_name,
+            } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::PortfolioValue);
+                encode_i32(&mut fields, PORTFOLIO_VALUE_VERSION);
+                encode_i32(&mut fields, contract.con_id);
+                encode_string(&mut fields, &contract.symbol);
+                encode_string(&mut fields, &contract.sec_type);
+                encode_string(&mut fields, &contract.last_trade_date_or_contract_month);
+                encode_f64(&mut fields, contract.strike);
+                encode_string(&mut fields, &contract.right);
+                encode_string(&mut fields, &contract.multiplier);
+                encode_string(&mut fields, &contract.primary_exchange);
+                encode_string(&mut fields, &contract.currency);
+                encode_string(&mut fields, &contract.local_symbol);
+                encode_string(&mut fields, &contract.trading_class);
+                self.encode_position(&mut fields, *position);
+                encode_f64(&mut fields, *market_price);
+                encode_f64(&mut fields, *market_value);
+                encode_f64(&mut fields, *average_cost);
+                encode_f64(&mut fields, *unrealized_pnl);
+                encode_f64(&mut fields, *realized_pnl);
+                encode_string(&mut fields, account_name);
+            }
+            ServerRspMsg::AcctUpdateTime { time_stamp } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::AcctUpdateTime);
+                encode_i32(&mut fields, MSG_VERSION);
+                encode_string(&mut fields, time_stamp);
+            }
+            ServerRspMsg::NextValidId { order_id } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::NextValidId);
+                encode_i32(&mut fields, MSG_VERSION);
+                encode_i32(&mut fields, *order_id);
+            }
+            ServerRspMsg::ContractData {
+                req_id,
+                contract_details,
+            } => self.encode_contract_data(&mut fields, *req_id, contract_details),
+            ServerRspMsg::ExecutionData {
+                req_id,
+                contract,
+                execution,
+            } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::ExecutionData);
+                let mut version = self.server_version;
+                if self.server_version < MIN_SERVER_VER_LAST_LIQUIDITY {
+                    version = EXECUTION_DATA_VERSION;
+                    encode_i32(&mut fields, version);
+                }
+                if version >= 7 {
+                    encode_i32(&mut fields, *req_id);
+                }
+                encode_i32(&mut fields, execution.order_id);
+
+                encode_i32(&mut fields, contract.con_id);
+                encode_string(&mut fields, &contract.symbol);
+                encode_string(&mut fields, &contract.sec_type);
+                encode_string(&mut fields, &contract.last_trade_date_or_contract_month);
+                encode_f64(&mut fields, contract.strike);
+                encode_string(&mut fields, &contract.right);
+                if version >= 9 {
+                    encode_string(&mut fields, &contract.multiplier);
+                }
+                encode_string(&mut fields, &contract.exchange);
+                encode_string(&mut fields, &contract.currency);
+                encode_string(&mut fields, &contract.local_symbol);
+                if version >= 10 {
+                    encode_string(&mut fields, &contract.trading_class);
+                }
+
+                encode_string(&mut fields, &execution.exec_id);
+                encode_string(&mut fields, &execution.time);
+                encode_string(&mut fields, &execution.acct_number);
+                encode_string(&mut fields, &execution.exchange);
+                encode_string(&mut fields, &execution.side);
+                self.encode_position(&mut fields, execution.shares);
+                encode_f64(&mut fields, execution.price);
+                encode_i32(&mut fields, execution.perm_id);
+                encode_i32(&mut fields, execution.client_id);
+                encode_i32(&mut fields, execution.liquidation);
+                if version >= 6 {
+                    encode_f64(&mut fields, execution.cum_qty);
+                    encode_f64(&mut fields, execution.avg_price);
+                }
+                if version >= 8 {
+                    encode_string(&mut fields, &execution.order_ref);
+                }
+                if version >= 9 {
+                    encode_string(&mut fields, &execution.ev_rule);
+                    encode_f64(&mut fields, execution.ev_multiplier);
+                }
+                if self.server_version >= MIN_SERVER_VER_MODELS_SUPPORT {
+                    encode_string(&mut fields, &execution.model_code);
+                }
+                if self.server_version >= MIN_SERVER_VER_LAST_LIQUIDITY {
+                    encode_i32(&mut fields, execution.last_liquidity);
+                }
+            }
+            ServerRspMsg::MarketDepth {
+                req_id,
+                position,
+                operation,
+                side,
+                price,
+                size,
+            } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::MarketDepth);
+                encode_i32(&mut fields, MSG_VERSION);
+                encode_i32(&mut fields, *req_id);
+                encode_i32(&mut fields, *position);
+                encode_i32(&mut fields, *operation);
+                encode_i32(&mut fields, *side);
+                encode_f64(&mut fields, *price);
+                encode_i32(&mut fields, *size);
+            }
+            ServerRspMsg::MarketDepthL2 {
+                req_id,
+                position,
+                market_maker,
+                operation,
+                side,
+                price,
+                size,
+                is_smart_depth,
+            } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::MarketDepthL2);
+                encode_i32(&mut fields, MSG_VERSION);
+                encode_i32(&mut fields, *req_id);
+                encode_i32(&mut fields, *position);
+                encode_string(&mut fields, market_maker);
+                encode_i32(&mut fields, *operation);
+                encode_i32(&mut fields, *side);
+                encode_f64(&mut fields, *price);
+                encode_i32(&mut fields, *size);
+                if self.server_version >= MIN_SERVER_VER_SMART_DEPTH {
+                    encode_bool(&mut fields, *is_smart_depth);
+                }
+            }
+            ServerRspMsg::NewsBulletins {
+                msg_id,
+                msg_type,
+                news_message,
+                origin_exch,
+            } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::NewsBulletins);
+                encode_i32(&mut fields, MSG_VERSION);
+                encode_i32(&mut fields, *msg_id);
+                encode_i32(&mut fields, *msg_type);
+                encode_string(&mut fields, news_message);
+                encode_string(&mut fields, origin_exch);
+            }
+            ServerRspMsg::ManagedAccts { accounts_list } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::ManagedAccts);
+                encode_i32(&mut fields, MSG_VERSION);
+                encode_string(&mut fields, accounts_list);
+            }
+            ServerRspMsg::ReceiveFa { fa_data, cxml } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::ReceiveFa);
+                encode_i32(&mut fields, MSG_VERSION);
+                encode_i32(&mut fields, fa_data.clone() as i32);
+                encode_string(&mut fields, cxml);
+            }
+            ServerRspMsg::HistoricalData { req_id, bar } => {
+                return Ok(self.encode_historical_data_fields(
+                    *req_id,
+                    "",
+                    "",
+                    std::slice::from_ref(bar),
+                ));
+            }
+            ServerRspMsg::HistoricalDataEnd { req_id, start, end } => {
+                return Ok(self.encode_historical_data_fields(*req_id, start, end, &[]));
+            }
+            ServerRspMsg::BondContractData {
+                req_id,
+                contract_details,
+            } => self.encode_bond_contract_data(&mut fields, *req_id, contract_details),
+            ServerRspMsg::ScannerParameters { xml } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::ScannerParameters);
+                encode_i32(&mut fields, MSG_VERSION);
+                encode_string(&mut fields, xml);
+            }
+            ServerRspMsg::ScannerData {
+                req_id,
+                rank,
+                contract_details,
+                distance,
+                benchmark,
+                projection,
+                legs_str,
+            } => {
+                let scan_data = ScanData::new(
+                    contract_details.clone(),
+                    *rank,
+                    distance.clone(),
+                    benchmark.clone(),
+                    projection.clone(),
+                    legs_str.clone(),
+                );
+                return Ok(self.encode_scanner_data_fields(*req_id, &[scan_data]));
+            }
+            ServerRspMsg::ScannerDataEnd { req_id } => {
+                return Ok(self.encode_scanner_data_fields(*req_id, &[]));
+            }
+            ServerRspMsg::TickOptionComputation {
+                ticker_id,
+                tick_type,
+                implied_vol,
+                delta,
+                opt_price,
+                pv_dividend,
+                gamma,
+                vega,
+                theta,
+                und_price,
+            } => {
+                encode_msg_id(
+                    &mut fields,
+                    ServerRspMsgDiscriminants::TickOptionComputation,
+                );
+                encode_i32(&mut fields, TICK_OPTION_COMPUTATION_VERSION);
+                encode_i32(&mut fields, *ticker_id);
+                encode_i32(&mut fields, *tick_type as i32);
+                // -1 and -2 are the "not yet computed" indicators
+                encode_option_computation_value(&mut fields, *implied_vol, -1.0);
+                encode_option_computation_value(&mut fields, *delta, -2.0);
+                encode_option_computation_value(&mut fields, *opt_price, -1.0);
+                encode_option_computation_value(&mut fields, *pv_dividend, -1.0);
+                encode_option_computation_value(&mut fields, *gamma, -2.0);
+                encode_option_computation_value(&mut fields, *vega, -2.0);
+                encode_option_computation_value(&mut fields, *theta, -2.0);
+                encode_option_computation_value(&mut fields, *und_price, -1.0);
+            }
+            ServerRspMsg::TickGeneric {
+                ticker_id,
+                tick_type,
+                value,
+            } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::TickGeneric);
+                encode_i32(&mut fields, MSG_VERSION);
+                encode_i32(&mut fields, *ticker_id);
+                encode_i32(&mut fields, *tick_type as i32);
+                encode_f64(&mut fields, *value);
+            }
+            ServerRspMsg::TickString {
+                req_id,
+                tick_type,
+                value,
+            } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::TickString);
+                encode_i32(&mut fields, MSG_VERSION);
+                encode_i32(&mut fields, *req_id);
+                encode_i32(&mut fields, *tick_type as i32);
+                encode_string(&mut fields, value);
+            }
+            ServerRspMsg::TickEfp {
+                ticker_id,
+                tick_type,
+                basis_points,
+                formatted_basis_points,
+                implied_futures_price,
+                hold_days,
+                future_last_trade_date,
+                dividend_impact,
+                dividends_to_last_trade_date,
+            } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::TickEfp);
+                encode_i32(&mut fields, MSG_VERSION);
+                encode_i32(&mut fields, *ticker_id);
+                encode_i32(&mut fields, *tick_type as i32);
+                encode_f64(&mut fields, *basis_points);
+                encode_string(&mut fields, formatted_basis_points);
+                encode_f64(&mut fields, *implied_futures_price);
+                encode_i32(&mut fields, *hold_days);
+                encode_string(&mut fields, future_last_trade_date);
+                encode_f64(&mut fields, *dividend_impact);
+                encode_f64(&mut fields, *dividends_to_last_trade_date);
+            }
+            ServerRspMsg::CurrentTime { time } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::CurrentTime);
+                encode_i32(&mut fields, MSG_VERSION);
+                encode_i64(&mut fields, *time);
+            }
+            ServerRspMsg::RealTimeBars { req_id, bar } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::RealTimeBars);
+                encode_i32(&mut fields, MSG_VERSION);
+                encode_i32(&mut fields, *req_id);
+                encode_string(&mut fields, &bar.date_time);
+                encode_f64(&mut fields, bar.open);
+                encode_f64(&mut fields, bar.high);
+                encode_f64(&mut fields, bar.low);
+                encode_f64(&mut fields, bar.close);
+                encode_i64(&mut fields, bar.volume);
+                encode_f64(&mut fields, bar.wap);
+                encode_i32(&mut fields, bar.count);
+            }
+            ServerRspMsg::FundamentalData { req_id, data } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::FundamentalData);
+                encode_i32(&mut fields, MSG_VERSION);
+                encode_i32(&mut fields, *req_id);
+                encode_string(&mut fields, data);
+            }
+            ServerRspMsg::ContractDataEnd { req_id } => {
+                encode_versioned_req_id(
+                    &mut fields,
+                    ServerRspMsgDiscriminants::ContractDataEnd,
+                    *req_id,
+                );
+            }
+            ServerRspMsg::OpenOrderEnd => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::OpenOrderEnd);
+            }
+            ServerRspMsg::AcctDownloadEnd { account_name } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::AcctDownloadEnd);
+                encode_i32(&mut fields, MSG_VERSION);
+                encode_string(&mut fields, account_name);
+            }
+            ServerRspMsg::ExecutionDataEnd { req_id } => {
+                encode_versioned_req_id(
+                    &mut fields,
+                    ServerRspMsgDiscriminants::ExecutionDataEnd,
+                    *req_id,
+                );
+            }
+            ServerRspMsg::DeltaNeutralValidation {
+                req_id,
+                delta_neutral_contract,
+            } => {
+                encode_msg_id(
+                    &mut fields,
+                    ServerRspMsgDiscriminants::DeltaNeutralValidation,
+                );
+                encode_i32(&mut fields, MSG_VERSION);
+                encode_i32(&mut fields, *req_id);
+                encode_i32(&mut fields, delta_neutral_contract.con_id);
+                encode_f64(&mut fields, delta_neutral_contract.delta);
+                encode_f64(&mut fields, delta_neutral_contract.price);
+            }
+            ServerRspMsg::TickSnapshotEnd { req_id } => {
+                encode_versioned_req_id(
+                    &mut fields,
+                    ServerRspMsgDiscriminants::TickSnapshotEnd,
+                    *req_id,
+                );
+            }
+            ServerRspMsg::MarketDataType {
+                req_id,
+                market_data_type,
+            } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::MarketDataType);
+                encode_i32(&mut fields, MSG_VERSION);
+                encode_i32(&mut fields, *req_id);
+                encode_i32(&mut fields, *market_data_type);
+            }
+            ServerRspMsg::CommissionReport { commission_report } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::CommissionReport);
+                encode_i32(&mut fields, MSG_VERSION);
+                encode_string(&mut fields, &commission_report.exec_id);
+                encode_f64(&mut fields, commission_report.commission);
+                encode_string(&mut fields, &commission_report.currency);
+                encode_f64(&mut fields, commission_report.realized_pnl);
+                encode_f64(&mut fields, commission_report.yield_);
+                encode_string(&mut fields, &commission_report.yield_redemption_date);
+            }
+            ServerRspMsg::PositionData {
+                account,
+                contract,
+                position,
+                avg_cost,
+            } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::PositionData);
+                encode_i32(&mut fields, POSITION_DATA_VERSION);
+                encode_string(&mut fields, account);
+                encode_position_contract(&mut fields, contract);
+                self.encode_position(&mut fields, *position);
+                encode_f64(&mut fields, *avg_cost);
+            }
+            ServerRspMsg::PositionEnd => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::PositionEnd);
+            }
+            ServerRspMsg::AccountSummary {
+                req_id,
+                account,
+                tag,
+                value,
+                currency,
+            } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::AccountSummary);
+                encode_i32(&mut fields, MSG_VERSION);
+                encode_i32(&mut fields, *req_id);
+                encode_string(&mut fields, account);
+                encode_string(&mut fields, tag);
+                encode_string(&mut fields, value);
+                encode_string(&mut fields, currency);
+            }
+            ServerRspMsg::AccountSummaryEnd { req_id } => {
+                encode_versioned_req_id(
+                    &mut fields,
+                    ServerRspMsgDiscriminants::AccountSummaryEnd,
+                    *req_id,
+                );
+            }
+            ServerRspMsg::VerifyMessageApi { api_data } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::VerifyMessageApi);
+                encode_i32(&mut fields, MSG_VERSION);
+                encode_string(&mut fields, api_data);
+            }
+            ServerRspMsg::VerifyCompleted {
+                is_successful,
+                error_text,
+            } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::VerifyCompleted);
+                encode_i32(&mut fields, MSG_VERSION);
+                encode_string(&mut fields, &is_successful.to_string());
+                encode_string(&mut fields, error_text);
+            }
+            ServerRspMsg::DisplayGroupList { req_id, groups } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::DisplayGroupList);
+                encode_i32(&mut fields, MSG_VERSION);
+                encode_i32(&mut fields, *req_id);
+                encode_string(&mut fields, groups);
+            }
+            ServerRspMsg::DisplayGroupUpdated {
+                req_id,
+                contract_info,
+            } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::DisplayGroupUpdated);
+                encode_i32(&mut fields, MSG_VERSION);
+                encode_i32(&mut fields, *req_id);
+                encode_string(&mut fields, contract_info);
+            }
+            ServerRspMsg::VerifyAndAuthMessageApi {
+                api_data,
+                xyz_challenge,
+            } => {
+                encode_msg_id(
+                    &mut fields,
+                    ServerRspMsgDiscriminants::VerifyAndAuthMessageApi,
+                );
+                encode_i32(&mut fields, MSG_VERSION);
+                encode_string(&mut fields, api_data);
+                encode_string(&mut fields, xyz_challenge);
+            }
+            ServerRspMsg::VerifyAndAuthCompleted {
+                is_successful,
+                error_text,
+            } => {
+                encode_msg_id(
+                    &mut fields,
+                    ServerRspMsgDiscriminants::VerifyAndAuthCompleted,
+                );
+                encode_i32(&mut fields, MSG_VERSION);
+                encode_string(&mut fields, &is_successful.to_string());
+                encode_string(&mut fields, error_text);
+            }
+            ServerRspMsg::PositionMulti {
+                req_id,
+                account,
+                model_code,
+                contract,
+                position,
+                avg_cost,
+            } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::PositionMulti);
+                encode_i32(&mut fields, MSG_VERSION);
+                encode_i32(&mut fields, *req_id);
+                encode_string(&mut fields, account);
+                encode_position_contract(&mut fields, contract);
+                encode_f64(&mut fields, *position);
+                encode_f64(&mut fields, *avg_cost);
+                encode_string(&mut fields, model_code);
+            }
+            ServerRspMsg::PositionMultiEnd { req_id } => {
+                encode_versioned_req_id(
+                    &mut fields,
+                    ServerRspMsgDiscriminants::PositionMultiEnd,
+                    *req_id,
+                );
+            }
+            ServerRspMsg::AccountUpdateMulti {
+                req_id,
+                account,
+                model_code,
+                key,
+                value,
+                currency,
+            } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::AccountUpdateMulti);
+                encode_i32(&mut fields, MSG_VERSION);
+                encode_i32(&mut fields, *req_id);
+                encode_string(&mut fields, account);
+                encode_string(&mut fields, model_code);
+                encode_string(&mut fields, key);
+                encode_string(&mut fields, value);
+                encode_string(&mut fields, currency);
+            }
+            ServerRspMsg::AccountUpdateMultiEnd { req_id } => {
+                encode_versioned_req_id(
+                    &mut fields,
+                    ServerRspMsgDiscriminants::AccountUpdateMultiEnd,
+                    *req_id,
+                );
+            }
+            ServerRspMsg::SecurityDefinitionOptionParameter {
+                req_id,
+                exchange,
+                underlying_con_id,
+                trading_class,
+                multiplier,
+                expirations,
+                strikes,
+            } => {
+                encode_msg_id(
+                    &mut fields,
+                    ServerRspMsgDiscriminants::SecurityDefinitionOptionParameter,
+                );
+                encode_i32(&mut fields, *req_id);
+                encode_string(&mut fields, exchange);
+                encode_i32(&mut fields, *underlying_con_id);
+                encode_string(&mut fields, trading_class);
+                encode_string(&mut fields, multiplier);
+                let mut expirations = expirations.iter().collect::<Vec<_>>();
+                expirations.sort();
+                encode_i32(&mut fields, expirations.len() as i32);
+                for expiration in expirations {
+                    encode_string(&mut fields, expiration);
+                }
+                let mut strikes = strikes.iter().collect::<Vec<_>>();
+                strikes.sort();
+                encode_i32(&mut fields, strikes.len() as i32);
+                for strike in strikes {
+                    encode_string(&mut fields, &strike.to_string());
+                }
+            }
+            ServerRspMsg::SecurityDefinitionOptionParameterEnd { req_id } => {
+                encode_msg_id(
+                    &mut fields,
+                    ServerRspMsgDiscriminants::SecurityDefinitionOptionParameterEnd,
+                );
+                encode_i32(&mut fields, *req_id);
+            }
+            ServerRspMsg::SoftDollarTiers { req_id, tiers } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::SoftDollarTiers);
+                encode_i32(&mut fields, *req_id);
+                encode_i32(&mut fields, tiers.len() as i32);
+                for tier in tiers {
+                    encode_string(&mut fields, &tier.name);
+                    encode_string(&mut fields, &tier.val);
+                    encode_string(&mut fields, &tier.display_name);
+                }
+            }
+            ServerRspMsg::FamilyCodes { family_codes } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::FamilyCodes);
+                encode_i32(&mut fields, family_codes.len() as i32);
+                for family_code in family_codes {
+                    encode_string(&mut fields, &family_code.account_id);
+                    encode_string(&mut fields, &family_code.family_code_str);
+                }
+            }
+            ServerRspMsg::SymbolSamples {
+                req_id,
+                contract_descriptions,
+            } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::SymbolSamples);
+                encode_i32(&mut fields, *req_id);
+                encode_i32(&mut fields, contract_descriptions.len() as i32);
+                for con_desc in contract_descriptions {
+                    encode_i32(&mut fields, con_desc.contract.con_id);
+                    encode_string(&mut fields, &con_desc.contract.symbol);
+                    encode_string(&mut fields, &con_desc.contract.sec_type);
+                    encode_string(&mut fields, &con_desc.contract.primary_exchange);
+                    encode_string(&mut fields, &con_desc.contract.currency);
+                    encode_i32(&mut fields, con_desc.derivative_sec_types.len() as i32);
+                    for deriv_sec_type in &con_desc.derivative_sec_types {
+                        encode_string(&mut fields, deriv_sec_type);
+                    }
+                }
+            }
+            ServerRspMsg::MktDepthExchanges {
+                depth_mkt_data_descriptions,
+            } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::MktDepthExchanges);
+                encode_i32(&mut fields, depth_mkt_data_descriptions.len() as i32);
+                for desc in depth_mkt_data_descriptions {
+                    encode_string(&mut fields, &desc.exchange);
+                    encode_string(&mut fields, &desc.sec_type);
+                    if self.server_version >= MIN_SERVER_VER_SERVICE_DATA_TYPE {
+                        encode_string(&mut fields, &desc.listing_exch);
+                        encode_string(&mut fields, &desc.service_data_type);
+                        encode_i32(&mut fields, desc.agg_group);
+                    } else {
+                        // boolean notSuppIsL2
+                        encode_bool(&mut fields, desc.service_data_type != "Deep");
+                    }
+                }
+            }
+            ServerRspMsg::TickReqParams {
+                ticker_id,
+                min_tick,
+                bbo_exchange,
+                snapshot_permissions,
+            } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::TickReqParams);
+                encode_i32(&mut fields, *ticker_id);
+                encode_f64(&mut fields, *min_tick);
+                encode_string(&mut fields, bbo_exchange);
+                encode_i32(&mut fields, *snapshot_permissions);
+            }
+            ServerRspMsg::SmartComponents {
+                req_id,
+                smart_components,
+            } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::SmartComponents);
+                encode_i32(&mut fields, *req_id);
+                encode_i32(&mut fields, smart_components.len() as i32);
+                for smart_component in smart_components {
+                    encode_i32(&mut fields, smart_component.bit_number);
+                    encode_string(&mut fields, &smart_component.exchange);
+                    encode_string(&mut fields, &smart_component.exchange_letter);
+                }
+            }
+            ServerRspMsg::NewsArticle {
+                req_id,
+                article_type,
+                article_text,
+            } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::NewsArticle);
+                encode_i32(&mut fields, *req_id);
+                encode_i32(&mut fields, *article_type);
+                encode_string(&mut fields, article_text);
+            }
+            ServerRspMsg::TickNews {
+                ticker_id,
+                time_stamp,
+                provider_code,
+                article_id,
+                headline,
+                extra_data,
+            } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::TickNews);
+                encode_i32(&mut fields, *ticker_id);
+                encode_i64(&mut fields, *time_stamp);
+                encode_string(&mut fields, provider_code);
+                encode_string(&mut fields, article_id);
+                encode_string(&mut fields, headline);
+                encode_string(&mut fields, extra_data);
+            }
+            ServerRspMsg::NewsProviders { news_providers } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::NewsProviders);
+                encode_i32(&mut fields, news_providers.len() as i32);
+                for provider in news_providers {
+                    encode_string(&mut fields, &provider.code);
+                    encode_string(&mut fields, &provider.name);
+                }
+            }
+            ServerRspMsg::HistoricalNews {
+                req_id,
+                time,
+                provider_code,
+                article_id,
+                headline,
+            } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::HistoricalNews);
+                encode_i32(&mut fields, *req_id);
+                encode_string(&mut fields, time);
+                encode_string(&mut fields, provider_code);
+                encode_string(&mut fields, article_id);
+                encode_string(&mut fields, headline);
+            }
+            ServerRspMsg::HistoricalNewsEnd { req_id, has_more } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::HistoricalNewsEnd);
+                encode_i32(&mut fields, *req_id);
+                encode_bool(&mut fields, *has_more);
+            }
+            ServerRspMsg::HeadTimestamp {
+                req_id,
+                head_timestamp,
+            } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::HeadTimestamp);
+                encode_i32(&mut fields, *req_id);
+                encode_string(&mut fields, head_timestamp);
+            }
+            ServerRspMsg::HistogramData { req_id, items } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::HistogramData);
+                encode_i32(&mut fields, *req_id);
+                encode_i32(&mut fields, items.len() as i32);
+                for item in items {
+                    encode_f64(&mut fields, item.price);
+                    encode_i32(&mut fields, item.count);
+                }
+            }
+            ServerRspMsg::HistoricalDataUpdate { req_id, bar } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::HistoricalDataUpdate);
+                encode_i32(&mut fields, *req_id);
+                encode_i32(&mut fields, bar.bar_count);
+                encode_string(&mut fields, &bar.date);
+                encode_f64(&mut fields, bar.open);
+                encode_f64(&mut fields, bar.close);
+                encode_f64(&mut fields, bar.high);
+                encode_f64(&mut fields, bar.low);
+                encode_f64(&mut fields, bar.average);
+                encode_i64(&mut fields, bar.volume);
+            }
+            ServerRspMsg::RerouteMktDataReq {
+                req_id,
+                con_id,
+                exchange,
+            } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::RerouteMktDataReq);
+                encode_i32(&mut fields, *req_id);
+                encode_i32(&mut fields, *con_id);
+                encode_string(&mut fields, exchange);
+            }
+            ServerRspMsg::RerouteMktDepthReq {
+                req_id,
+                con_id,
+                exchange,
+            } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::RerouteMktDepthReq);
+                encode_i32(&mut fields, *req_id);
+                encode_i32(&mut fields, *con_id);
+                encode_string(&mut fields, exchange);
+            }
+            ServerRspMsg::MarketRule {
+                market_rule_id,
+                price_increments,
+            } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::MarketRule);
+                encode_i32(&mut fields, *market_rule_id);
+                encode_i32(&mut fields, price_increments.len() as i32);
+                for price_increment in price_increments {
+                    encode_f64(&mut fields, price_increment.low_edge);
+                    encode_f64(&mut fields, price_increment.increment);
+                }
+            }
+            ServerRspMsg::Pnl {
+                req_id,
+                daily_pnl,
+                unrealized_pnl,
+                realized_pnl,
+            } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::Pnl);
+                encode_i32(&mut fields, *req_id);
+                encode_f64(&mut fields, *daily_pnl);
+                self.encode_pnl_components(&mut fields, *unrealized_pnl, *realized_pnl);
+            }
+            ServerRspMsg::PnlSingle {
+                req_id,
+                pos,
+                daily_pnl,
+                unrealized_pnl,
+                realized_pnl,
+                value,
+            } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::PnlSingle);
+                encode_i32(&mut fields, *req_id);
+                encode_i32(&mut fields, *pos);
+                encode_f64(&mut fields, *daily_pnl);
+                self.encode_pnl_components(&mut fields, *unrealized_pnl, *realized_pnl);
+                encode_f64(&mut fields, *value);
+            }
+            ServerRspMsg::HistoricalTicks {
+                req_id,
+                ticks,
+                done,
+            } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::HistoricalTicks);
+                encode_i32(&mut fields, *req_id);
+                encode_i32(&mut fields, ticks.len() as i32);
+                for tick in ticks {
+                    encode_i32(&mut fields, tick.time);
+                    encode_string(&mut fields, ""); // for consistency
+                    encode_f64(&mut fields, tick.price);
+                    encode_i32(&mut fields, tick.size);
+                }
+                encode_bool(&mut fields, *done);
+            }
+            ServerRspMsg::HistoricalTicksBidAsk {
+                req_id,
+                ticks,
+                done,
+            } => {
+                encode_msg_id(
+                    &mut fields,
+                    ServerRspMsgDiscriminants::HistoricalTicksBidAsk,
+                );
+                encode_i32(&mut fields, *req_id);
+                encode_i32(&mut fields, ticks.len() as i32);
+                for tick in ticks {
+                    encode_i32(&mut fields, tick.time);
+                    let mut mask = 0;
+                    if tick.tick_attrib_bid_ask.ask_past_high {
+                        mask |= 1;
+                    }
+                    if tick.tick_attrib_bid_ask.bid_past_low {
+                        mask |= 2;
+                    }
+                    encode_i32(&mut fields, mask);
+                    encode_f64(&mut fields, tick.price_bid);
+                    encode_f64(&mut fields, tick.price_ask);
+                    encode_i32(&mut fields, tick.size_bid);
+                    encode_i32(&mut fields, tick.size_ask);
+                }
+                encode_bool(&mut fields, *done);
+            }
+            ServerRspMsg::HistoricalTicksLast {
+                req_id,
+                ticks,
+                done,
+            } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::HistoricalTicksLast);
+                encode_i32(&mut fields, *req_id);
+                encode_i32(&mut fields, ticks.len() as i32);
+                for tick in ticks {
+                    encode_i32(&mut fields, tick.time);
+                    let mut mask = 0;
+                    if tick.tick_attrib_last.past_limit {
+                        mask |= 1;
+                    }
+                    if tick.tick_attrib_last.unreported {
+                        mask |= 2;
+                    }
+                    encode_i32(&mut fields, mask);
+                    encode_f64(&mut fields, tick.price);
+                    encode_i32(&mut fields, tick.size);
+                    encode_string(&mut fields, &tick.exchange);
+                    encode_string(&mut fields, &tick.special_conditions);
+                }
+                encode_bool(&mut fields, *done);
+            }
+            ServerRspMsg::TickByTick {
+                req_id,
+                tick_type,
+                time,
+                tick_msg,
+            } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::TickByTick);
+                encode_i32(&mut fields, *req_id);
+                encode_i32(&mut fields, *tick_type);
+                encode_i64(&mut fields, *time);
+                match tick_msg {
+                    TickMsgType::AllLast {
+                        price,
+                        size,
+                        tick_attrib_last,
+                        exchange,
+                        special_conditions,
+                    } => {
+                        encode_f64(&mut fields, *price);
+                        encode_i32(&mut fields, *size);
+                        let mut mask = 0;
+                        if tick_attrib_last.past_limit {
+                            mask |= 1;
+                        }
+                        if tick_attrib_last.unreported {
+                            mask |= 2;
+                        }
+                        encode_i32(&mut fields, mask);
+                        encode_string(&mut fields, exchange);
+                        encode_string(&mut fields, special_conditions);
+                    }
+                    TickMsgType::BidAsk {
+                        bid_price,
+                        ask_price,
+                        bid_size,
+                        ask_size,
+                        tick_attrib_bid_ask,
+                    } => {
+                        encode_f64(&mut fields, *bid_price);
+                        encode_f64(&mut fields, *ask_price);
+                        encode_i32(&mut fields, *bid_size);
+                        encode_i32(&mut fields, *ask_size);
+                        let mut mask = 0;
+                        if tick_attrib_bid_ask.bid_past_low {
+                            mask |= 1;
+                        }
+                        if tick_attrib_bid_ask.ask_past_high {
+                            mask |= 2;
+                        }
+                        encode_i32(&mut fields, mask);
+                    }
+                    TickMsgType::MidPoint { mid_point } => {
+                        encode_f64(&mut fields, *mid_point);
+                    }
+                }
+            }
+            ServerRspMsg::OrderBound {
+                req_id,
+                api_client_id,
+                api_order_id,
+            } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::OrderBound);
+                encode_i32(&mut fields, *req_id);
+                encode_i32(&mut fields, *api_client_id);
+                encode_i32(&mut fields, *api_order_id);
+            }
+            ServerRspMsg::CompletedOrder {
+                contract,
+                order,
+                order_state,
+            } => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::CompletedOrder);
+                OrderEncoder::new(
+                    contract,
+                    order,
+                    order_state,
+                    UNSET_INTEGER,
+                    self.server_version,
+                )
+                .encode_completed(&mut fields)?;
+            }
+            ServerRspMsg::CompletedOrdersEnd => {
+                encode_msg_id(&mut fields, ServerRspMsgDiscriminants::CompletedOrdersEnd);
+            }
+        }
+        Ok(fields)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Encode a tick price message.  `attr_mask` holds the raw tick attribute bits.
+    pub fn encode_tick_price_fields(
+        &self,
+        req_id: i32,
+        tick_type: TickType,
+        price: f64,
+        size: i32,
+        attr_mask: i32,
+    ) -> Vec<String> {
+        let mut fields = vec![];
+        encode_msg_id(&mut fields, ServerRspMsgDiscriminants::TickPrice);
+        encode_i32(&mut fields, MSG_VERSION);
+        encode_i32(&mut fields, req_id);
+        encode_i32(&mut fields, tick_type as i32);
+        encode_f64(&mut fields, price);
+        encode_i32(&mut fields, size);
+        encode_i32(&mut fields, attr_mask);
+        fields
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Encode a batch of historical bars.  The decoder emits one `HistoricalData` per bar
+    /// followed by `HistoricalDataEnd`.
+    pub fn encode_historical_data_fields(
+        &self,
+        req_id: i32,
+        start: &str,
+        end: &str,
+        bars: &[BarData],
+    ) -> Vec<String> {
+        let mut fields = vec![];
+        encode_msg_id(&mut fields, ServerRspMsgDiscriminants::HistoricalData);
+        if self.server_version < MIN_SERVER_VER_SYNT_REALTIME_BARS {
+            encode_i32(&mut fields, MSG_VERSION);
+        }
+        encode_i32(&mut fields, req_id);
+        encode_string(&mut fields, start);
+        encode_string(&mut fields, end);
+        encode_i32(&mut fields, bars.len() as i32);
+        for bar in bars {
+            encode_string(&mut fields, &bar.date);
+            encode_f64(&mut fields, bar.open);
+            encode_f64(&mut fields, bar.high);
+            encode_f64(&mut fields, bar.low);
+            encode_f64(&mut fields, bar.close);
+            if self.server_version < MIN_SERVER_VER_SYNT_REALTIME_BARS {
+                encode_i32(&mut fields, bar.volume as i32);
+            } else {
+                encode_i64(&mut fields, bar.volume);
+            }
+            encode_f64(&mut fields, bar.average);
+            if self.server_version < MIN_SERVER_VER_SYNT_REALTIME_BARS {
+                encode_string(&mut fields, "false"); //has_gaps
+            }
+            encode_i32(&mut fields, bar.bar_count);
+        }
+        fields
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Encode a batch of scanner rows.  The decoder emits one `ScannerData` per row followed by
+    /// `ScannerDataEnd`.
+    pub fn encode_scanner_data_fields(&self, req_id: i32, rows: &[ScanData]) -> Vec<String> {
+        let mut fields = vec![];
+        encode_msg_id(&mut fields, ServerRspMsgDiscriminants::ScannerData);
+        encode_i32(&mut fields, MSG_VERSION);
+        encode_i32(&mut fields, req_id);
+        encode_i32(&mut fields, rows.len() as i32);
+        for row in rows {
+            let contract = &row.contract.contract;
+            encode_i32(&mut fields, row.rank);
+            encode_i32(&mut fields, contract.con_id);
+            encode_string(&mut fields, &contract.symbol);
+            encode_string(&mut fields, &contract.sec_type);
+            encode_string(&mut fields, &contract.last_trade_date_or_contract_month);
+            encode_f64(&mut fields, contract.strike);
+            encode_string(&mut fields, &contract.right);
+            encode_string(&mut fields, &contract.exchange);
+            encode_string(&mut fields, &contract.currency);
+            encode_string(&mut fields, &contract.local_symbol);
+            encode_string(&mut fields, &row.contract.market_name);
+            encode_string(&mut fields, &contract.trading_class);
+            encode_string(&mut fields, &row.distance);
+            encode_string(&mut fields, &row.benchmark);
+            encode_string(&mut fields, &row.projection);
+            encode_string(&mut fields, &row.legs);
+        }
+        fields
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_position(&self, fields: &mut Vec<String>, position: f64) {
+        if self.server_version >= MIN_SERVER_VER_FRACTIONAL_POSITIONS {
+            encode_f64(fields, position);
+        } else {
+            encode_i32(fields, position as i32);
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_pnl_components(&self, fields: &mut Vec<String>, unrealized: f64, realized: f64) {
+        if self.server_version >= MIN_SERVER_VER_UNREALIZED_PNL {
+            encode_f64(fields, unrealized);
+        }
+        if self.server_version >= MIN_SERVER_VER_REALIZED_PNL {
+            encode_f64(fields, realized);
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_contract_data(
+        &self,
+        fields: &mut Vec<String>,
+        req_id: i32,
+        contract_details: &ContractDetails,
+    ) {
+        let contract = &contract_details.contract;
+        let version = CONTRACT_DATA_VERSION;
+        encode_msg_id(fields, ServerRspMsgDiscriminants::ContractData);
+        encode_i32(fields, version);
+        encode_i32(fields, req_id);
+        encode_string(fields, &contract.symbol);
+        encode_string(fields, &contract.sec_type);
+        encode_string(
+            fields,
+            &join_date_parts(&[
+                &contract.last_trade_date_or_contract_month,
+                &contract_details.last_trade_time,
+            ]),
+        );
+        encode_f64(fields, contract.strike);
+        encode_string(fields, &contract.right);
+        encode_string(fields, &contract.exchange);
+        encode_string(fields, &contract.currency);
+        encode_string(fields, &contract.local_symbol);
+        encode_string(fields, &contract_details.market_name);
+        encode_string(fields, &contract.trading_class);
+        encode_i32(fields, contract.con_id);
+        encode_f64(fields, contract_details.min_tick);
+        if self.server_version >= MIN_SERVER_VER_MD_SIZE_MULTIPLIER {
+            encode_i32(fields, contract_details.md_size_multiplier);
+        }
+        encode_string(fields, &contract.multiplier);
+        encode_string(fields, &contract_details.order_types);
+        encode_string(fields, &contract_details.valid_exchanges);
+        encode_i32(fields, contract_details.price_magnifier);
+        encode_i32(fields, contract_details.under_con_id);
+        encode_string(fields, &contract_details.long_name);
+        encode_string(fields, &contract.primary_exchange);
+        encode_string(fields, &contract_details.contract_month);
+        encode_string(fields, &contract_details.industry);
+        encode_string(fields, &contract_details.category);
+        encode_string(fields, &contract_details.subcategory);
+        encode_string(fields, &contract_details.time_zone_id);
+        encode_string(fields, &contract_details.trading_hours);
+        encode_string(fields, &contract_details.liquid_hours);
+        encode_string(fields, &contract_details.ev_rule);
+        encode_f64(fields, contract_details.ev_multiplier);
+        encode_i32(fields, contract_details.sec_id_list.len() as i32);
+        for tag_value in &contract_details.sec_id_list {
+            encode_string(fields, &tag_value.tag);
+            encode_string(fields, &tag_value.value);
+        }
+        if self.server_version >= MIN_SERVER_VER_AGG_GROUP {
+            encode_i32(fields, contract_details.agg_group);
+        }
+        if self.server_version >= MIN_SERVER_VER_UNDERLYING_INFO {
+            encode_string(fields, &contract_details.under_symbol);
+            encode_string(fields, &contract_details.under_sec_type);
+        }
+        if self.server_version >= MIN_SERVER_VER_MARKET_RULES {
+            encode_string(fields, &contract_details.market_rule_ids);
+        }
+        if self.server_version >= MIN_SERVER_VER_REAL_EXPIRATION_DATE {
+            encode_string(fields, &contract_details.real_expiration_date);
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_bond_contract_data(
+        &self,
+        fields: &mut Vec<String>,
+        req_id: i32,
+        contract_details: &ContractDetails,
+    ) {
+        let contract = &contract_details.contract;
+        encode_msg_id(fields, ServerRspMsgDiscriminants::BondContractData);
+        encode_i32(fields, BOND_CONTRACT_DATA_VERSION);
+        encode_i32(fields, req_id);
+        encode_string(fields, &contract.symbol);
+        encode_string(fields, &contract.sec_type);
+        encode_string(fields, &contract_details.cusip);
+        encode_f64(fields, contract_details.coupon);
+        encode_string(
+            fields,
+            &join_date_parts(&[
+                &contract_details.maturity,
+                &contract_details.last_trade_time,
+                &contract_details.time_zone_id,
+            ]),
+        );
+        encode_string(fields, &contract_details.issue_date);
+        encode_string(fields, &contract_details.ratings);
+        encode_string(fields, &contract_details.bond_type);
+        encode_string(fields, &contract_details.coupon_type);
+        encode_bool(fields, contract_details.convertible);
+        encode_bool(fields, contract_details.callable);
+        encode_bool(fields, contract_details.putable);
+        encode_string(fields, &contract_details.desc_append);
+        encode_string(fields, &contract.exchange);
+        encode_string(fields, &contract.currency);
+        encode_string(fields, &contract_details.market_name);
+        encode_string(fields, &contract.trading_class);
+        encode_i32(fields, contract.con_id);
+        encode_f64(fields, contract_details.min_tick);
+        if self.server_version >= MIN_SERVER_VER_MD_SIZE_MULTIPLIER {
+            encode_i32(fields, contract_details.md_size_multiplier);
+        }
+        encode_string(fields, &contract_details.order_types);
+        encode_string(fields, &contract_details.valid_exchanges);
+        encode_string(fields, &contract_details.next_option_date);
+        encode_string(fields, &contract_details.next_option_type);
+        encode_bool(fields, contract_details.next_option_partial);
+        encode_string(fields, &contract_details.notes);
+        encode_string(fields, &contract_details.long_name);
+        encode_string(fields, &contract_details.ev_rule);
+        encode_f64(fields, contract_details.ev_multiplier);
+        encode_i32(fields, contract_details.sec_id_list.len() as i32);
+        for tag_value in &contract_details.sec_id_list {
+            encode_string(fields, &tag_value.tag);
+            encode_string(fields, &tag_value.value);
+        }
+        if self.server_version >= MIN_SERVER_VER_AGG_GROUP {
+            encode_i32(fields, contract_details.agg_group);
+        }
+        if self.server_version >= MIN_SERVER_VER_MARKET_RULES {
+            encode_string(fields, &contract_details.market_rule_ids);
+        }
+    }
+}
+
+//==================================================================================================
+fn encode_versioned_req_id(
+    fields: &mut Vec<String>,
+    msg_id: ServerRspMsgDiscriminants,
+    req_id: i32,
+) {
+    encode_msg_id(fields, msg_id);
+    encode_i32(fields, MSG_VERSION);
+    encode_i32(fields, req_id);
+}
+
+//==================================================================================================
+fn encode_position_contract(fields: &mut Vec<String>, contract: &Contract) {
+    encode_i32(fields, contract.con_id);
+    encode_string(fields, &contract.symbol);
+    encode_string(fields, &contract.sec_type);
+    encode_string(fields, &contract.last_trade_date_or_contract_month);
+    encode_f64(fields, contract.strike);
+    encode_string(fields, &contract.right);
+    encode_string(fields, &contract.multiplier);
+    encode_string(fields, &contract.exchange);
+    encode_string(fields, &contract.currency);
+    encode_string(fields, &contract.local_symbol);
+    encode_string(fields, &contract.trading_class);
+}
+
+//==================================================================================================
+/// Dates that carry a time and/or time zone are sent as a single space separated field
+fn join_date_parts(parts: &[&str]) -> String {
+    parts
+        .iter()
+        .filter(|part| !part.is_empty())
+        .cloned()
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+//==================================================================================================
+/// Join encoded fields into a NUL terminated message body, ready for `make_message`
+pub fn join_fields(fields: &[String]) -> String {
+    let mut msg = String::new();
+    for field in fields {
+        msg.push_str(field);
+        msg.push('\0');
+    }
+    msg
+}
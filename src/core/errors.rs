@@ -3,6 +3,8 @@ use std::num::{ParseFloatError, ParseIntError};
 use std::sync::mpsc::{RecvError, RecvTimeoutError, TryRecvError};
 use std::{error, fmt, io};
 
+use crate::core::server_versions::ServerFeature;
+
 const ALREADY_CONNECTED: (i32, &str) = (501, "Already connected.");
 const CONNECT_FAIL: (i32, &str) = (502, "Couldn't connect to TWS. Confirm that \"Enable ActiveX and Socket EClients\"
                                             is enabled and connection port is the same as \"Socket Port\" on the
@@ -84,6 +86,43 @@ pub enum IBKRApiLibError {
     TryRecvError(TryRecvError),
     RecvTimeoutError(RecvTimeoutError),
     ApiError(TwsApiReportableError),
+    Unsupported {
+        feature: ServerFeature,
+        server_version: i32,
+        required: i32,
+    },
+    InvalidContract {
+        sec_type: String,
+        field: &'static str,
+    },
+    InvalidOrder {
+        order_type: String,
+        reason: &'static str,
+    },
+    InvalidAlgoParam {
+        strategy: String,
+        param: &'static str,
+        reason: String,
+    },
+    NoValidOrderId,
+    InvalidHistoricalRequest {
+        reason: String,
+    },
+    InvalidXml {
+        reason: String,
+    },
+    InvalidScannerSubscription {
+        reason: String,
+    },
+    InvalidNewsArticle {
+        reason: String,
+    },
+    UnknownMarketRule {
+        reason: String,
+    },
+    InvalidTradingHours {
+        reason: String,
+    },
 }
 
 impl fmt::Display for IBKRApiLibError {
@@ -98,6 +137,52 @@ impl fmt::Display for IBKRApiLibError {
             IBKRApiLibError::TryRecvError(ref err) => write!(f, "TryRecieve error: {}", err),
             IBKRApiLibError::RecvTimeoutError(ref err) => write!(f, "Reader Send error {}", err),
             IBKRApiLibError::ApiError(ref err) => write!(f, "TWS Error: {}", err),
+            IBKRApiLibError::Unsupported {
+                feature,
+                server_version,
+                required,
+            } => write!(
+                f,
+                "Unsupported feature: {} requires server version {}, connected server version is {}",
+                feature, required, server_version
+            ),
+            IBKRApiLibError::InvalidContract { sec_type, field } => write!(
+                f,
+                "Invalid contract: sec_type '{}' requires a valid {}",
+                sec_type, field
+            ),
+            IBKRApiLibError::InvalidOrder { order_type, reason } => {
+                write!(f, "Invalid order: order_type '{}' {}", order_type, reason)
+            }
+            IBKRApiLibError::InvalidAlgoParam {
+                strategy,
+                param,
+                reason,
+            } => write!(
+                f,
+                "Invalid algo parameter: {} of strategy '{}' {}",
+                param, strategy, reason
+            ),
+            IBKRApiLibError::NoValidOrderId => write!(
+                f,
+                "No valid order id, wait for NextValidId or set one with set_next_order_id"
+            ),
+            IBKRApiLibError::InvalidHistoricalRequest { reason } => {
+                write!(f, "Invalid historical data request: {}", reason)
+            }
+            IBKRApiLibError::InvalidXml { reason } => write!(f, "Invalid XML: {}", reason),
+            IBKRApiLibError::InvalidScannerSubscription { reason } => {
+                write!(f, "Invalid scanner subscription: {}", reason)
+            }
+            IBKRApiLibError::InvalidNewsArticle { reason } => {
+                write!(f, "Invalid news article: {}", reason)
+            }
+            IBKRApiLibError::UnknownMarketRule { reason } => {
+                write!(f, "Unknown market rule: {}", reason)
+            }
+            IBKRApiLibError::InvalidTradingHours { reason } => {
+                write!(f, "Invalid trading hours: {}", reason)
+            }
         }
     }
 }
@@ -114,6 +199,52 @@ impl fmt::Debug for IBKRApiLibError {
             IBKRApiLibError::TryRecvError(ref err) => write!(f, "TryReceive error {}", err),
             IBKRApiLibError::RecvTimeoutError(ref err) => write!(f, "Reader Send error {}", err),
             IBKRApiLibError::ApiError(ref err) => write!(f, "TWS Error: {}", err),
+            IBKRApiLibError::Unsupported {
+                feature,
+                server_version,
+                required,
+            } => write!(
+                f,
+                "Unsupported feature: {} requires server version {}, connected server version is {}",
+                feature, required, server_version
+            ),
+            IBKRApiLibError::InvalidContract { sec_type, field } => write!(
+                f,
+                "Invalid contract: sec_type '{}' requires a valid {}",
+                sec_type, field
+            ),
+            IBKRApiLibError::InvalidOrder { order_type, reason } => {
+                write!(f, "Invalid order: order_type '{}' {}", order_type, reason)
+            }
+            IBKRApiLibError::InvalidAlgoParam {
+                strategy,
+                param,
+                reason,
+            } => write!(
+                f,
+                "Invalid algo parameter: {} of strategy '{}' {}",
+                param, strategy, reason
+            ),
+            IBKRApiLibError::NoValidOrderId => write!(
+                f,
+                "No valid order id, wait for NextValidId or set one with set_next_order_id"
+            ),
+            IBKRApiLibError::InvalidHistoricalRequest { reason } => {
+                write!(f, "Invalid historical data request: {}", reason)
+            }
+            IBKRApiLibError::InvalidXml { reason } => write!(f, "Invalid XML: {}", reason),
+            IBKRApiLibError::InvalidScannerSubscription { reason } => {
+                write!(f, "Invalid scanner subscription: {}", reason)
+            }
+            IBKRApiLibError::InvalidNewsArticle { reason } => {
+                write!(f, "Invalid news article: {}", reason)
+            }
+            IBKRApiLibError::UnknownMarketRule { reason } => {
+                write!(f, "Unknown market rule: {}", reason)
+            }
+            IBKRApiLibError::InvalidTradingHours { reason } => {
+                write!(f, "Invalid trading hours: {}", reason)
+            }
         }
     }
 }
@@ -132,6 +263,17 @@ impl error::Error for IBKRApiLibError {
             IBKRApiLibError::TryRecvError(ref err) => Some(err),
             IBKRApiLibError::RecvTimeoutError(ref err) => Some(err),
             IBKRApiLibError::ApiError(ref err) => Some(err),
+            IBKRApiLibError::Unsupported { .. } => None,
+            IBKRApiLibError::InvalidContract { .. } => None,
+            IBKRApiLibError::InvalidOrder { .. } => None,
+            IBKRApiLibError::InvalidAlgoParam { .. } => None,
+            IBKRApiLibError::NoValidOrderId => None,
+            IBKRApiLibError::InvalidHistoricalRequest { .. } => None,
+            IBKRApiLibError::InvalidXml { .. } => None,
+            IBKRApiLibError::InvalidScannerSubscription { .. } => None,
+            IBKRApiLibError::InvalidNewsArticle { .. } => None,
+            IBKRApiLibError::UnknownMarketRule { .. } => None,
+            IBKRApiLibError::InvalidTradingHours { .. } => None,
         }
     }
 }
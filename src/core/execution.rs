@@ -1,8 +1,11 @@
 //! Types related to executions
 use std::fmt::{Display, Error, Formatter};
 
+use chrono::{DateTime, FixedOffset};
 use serde::{Deserialize, Serialize};
 
+use crate::core::datetime::parse_tws_datetime;
+
 //==================================================================================================
 #[derive(Serialize, Deserialize, Clone, Debug, Default)]
 pub struct Execution {
@@ -68,6 +71,12 @@ impl Execution {
             last_liquidity,
         }
     }
+
+    //----------------------------------------------------------------------------------------------
+    /// Execution time parsed, see `parse_tws_datetime` for the formats and `default_tz`
+    pub fn timestamp(&self, default_tz: FixedOffset) -> Option<DateTime<FixedOffset>> {
+        parse_tws_datetime(&self.time, default_tz)
+    }
 }
 
 impl Display for Execution {
@@ -0,0 +1,394 @@
+//! Typed Financial Advisor groups, allocation profiles and account aliases, read from and written
+//! to the XML exchanged by `EClient::request_fa` and `EClient::replace_fa`
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumString;
+
+use crate::core::common::FaDataType;
+use crate::core::errors::IBKRApiLibError;
+use crate::core::xml::XmlElement;
+
+//==================================================================================================
+/// How an order placed for a group is split between its accounts
+#[derive(EnumString, Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum FaGroupMethod {
+    EqualQuantity,
+    NetLiq,
+    AvailableEquity,
+    PctChange,
+}
+
+impl Display for FaGroupMethod {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+//==================================================================================================
+/// How the amounts of an allocation profile are read
+#[repr(i32)]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum FaProfileType {
+    Percentages = 1,
+    FinancialRatios = 2,
+    Shares = 3,
+}
+
+//==================================================================================================
+/// A group, list, profile or alias in the FA configuration, identified by its key
+pub trait FaConfig: Sized + Clone + PartialEq + Display {
+    const DATA_TYPE: FaDataType;
+    /// Root element of the list in the XML
+    const LIST: &'static str;
+    /// Element of each entry in the list
+    const ITEM: &'static str;
+
+    fn key(&self) -> &str;
+    fn from_xml(element: &XmlElement) -> Result<Self, IBKRApiLibError>;
+    fn to_xml(&self) -> XmlElement;
+}
+
+//==================================================================================================
+/// Parses the XML received with `EWrapper::receive_fa`
+pub fn parse_fa<T: FaConfig>(xml: &str) -> Result<Vec<T>, IBKRApiLibError> {
+    let root = XmlElement::parse(xml)?;
+    if root.name != T::LIST {
+        return Err(invalid(format!(
+            "expected {} but found {}",
+            T::LIST,
+            root.name
+        )));
+    }
+    root.children_named(T::ITEM).map(T::from_xml).collect()
+}
+
+//==================================================================================================
+/// Writes the XML `EClient::replace_fa` sends for `items`
+pub fn fa_to_xml<T: FaConfig>(items: &[T]) -> String {
+    items
+        .iter()
+        .fold(XmlElement::new(T::LIST), |list, item| {
+            list.with_child(item.to_xml())
+        })
+        .to_document()
+}
+
+//==================================================================================================
+/// A difference between the current FA configuration and its replacement
+#[derive(Clone, Debug, PartialEq)]
+pub enum FaChange<T> {
+    Added(T),
+    Removed(T),
+    Modified { current: T, replacement: T },
+}
+
+impl<T: FaConfig> Display for FaChange<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            FaChange::Added(item) => write!(f, "+ {}", item),
+            FaChange::Removed(item) => write!(f, "- {}", item),
+            FaChange::Modified {
+                current,
+                replacement,
+            } => write!(f, "~ {} -> {}", current, replacement),
+        }
+    }
+}
+
+//==================================================================================================
+/// What a `replace_fa` of `replacement` would change in `current`: entries are matched by key,
+/// modified and removed ones in the order of `current`, followed by the added ones
+pub fn diff_fa<T: FaConfig>(current: &[T], replacement: &[T]) -> Vec<FaChange<T>> {
+    let mut changes = vec![];
+    for item in current {
+        match replacement.iter().find(|other| other.key() == item.key()) {
+            Some(other) if other != item => changes.push(FaChange::Modified {
+                current: item.clone(),
+                replacement: other.clone(),
+            }),
+            Some(_) => (),
+            None => changes.push(FaChange::Removed(item.clone())),
+        }
+    }
+    for item in replacement {
+        if !current.iter().any(|other| other.key() == item.key()) {
+            changes.push(FaChange::Added(item.clone()));
+        }
+    }
+    changes
+}
+
+//==================================================================================================
+fn invalid(reason: String) -> IBKRApiLibError {
+    IBKRApiLibError::InvalidXml { reason }
+}
+
+//==================================================================================================
+fn required<'a>(element: &'a XmlElement, name: &str) -> Result<&'a str, IBKRApiLibError> {
+    element
+        .child_text(name)
+        .ok_or_else(|| invalid(format!("{} without {}", element.name, name)))
+}
+
+//==================================================================================================
+/// A group of accounts an order can be placed for
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct FaGroup {
+    pub name: String,
+    pub accounts: Vec<String>,
+    pub default_method: FaGroupMethod,
+}
+
+impl FaGroup {
+    pub fn new(name: &str, default_method: FaGroupMethod) -> Self {
+        FaGroup {
+            name: name.to_string(),
+            accounts: vec![],
+            default_method,
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn with_account(mut self, account: &str) -> Self {
+        self.accounts.push(account.to_string());
+        self
+    }
+}
+
+impl FaConfig for FaGroup {
+    const DATA_TYPE: FaDataType = FaDataType::GROUPS;
+    const LIST: &'static str = "ListOfGroups";
+    const ITEM: &'static str = "Group";
+
+    fn key(&self) -> &str {
+        &self.name
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn from_xml(element: &XmlElement) -> Result<Self, IBKRApiLibError> {
+        let method = required(element, "defaultMethod")?;
+        let accounts = element
+            .child("ListOfAccts")
+            .map(|list| {
+                list.children_named("String")
+                    .map(|account| account.text.clone())
+                    .collect()
+            })
+            .unwrap_or_default();
+        Ok(FaGroup {
+            name: required(element, "name")?.to_string(),
+            accounts,
+            default_method: FaGroupMethod::from_str(method)
+                .map_err(|_| invalid(format!("unknown group method {}", method)))?,
+        })
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn to_xml(&self) -> XmlElement {
+        let accounts = self.accounts.iter().fold(
+            XmlElement::new("ListOfAccts").with_attribute("varName", "list"),
+            |list, account| list.with_child(XmlElement::new("String").with_text(account)),
+        );
+        XmlElement::new(Self::ITEM)
+            .with_child(XmlElement::new("name").with_text(&self.name))
+            .with_child(accounts)
+            .with_child(
+                XmlElement::new("defaultMethod").with_text(&self.default_method.to_string()),
+            )
+    }
+}
+
+impl Display for FaGroup {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "group {} ({}): {}",
+            self.name,
+            self.default_method,
+            self.accounts.join(",")
+        )
+    }
+}
+
+//==================================================================================================
+/// Amount of an allocation profile for one account
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct FaAllocation {
+    pub account: String,
+    pub amount: f64,
+}
+
+//==================================================================================================
+/// A profile allocating an order between accounts by percentages, ratios or share counts
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct FaProfile {
+    pub name: String,
+    pub profile_type: FaProfileType,
+    pub allocations: Vec<FaAllocation>,
+}
+
+impl FaProfile {
+    pub fn new(name: &str, profile_type: FaProfileType) -> Self {
+        FaProfile {
+            name: name.to_string(),
+            profile_type,
+            allocations: vec![],
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn with_allocation(mut self, account: &str, amount: f64) -> Self {
+        self.allocations.push(FaAllocation {
+            account: account.to_string(),
+            amount,
+        });
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Quantity each account receives of an order for `total_quantity`, before IB's rounding to
+    /// whole shares. Share profiles ignore the order quantity.
+    pub fn allocate(&self, total_quantity: f64) -> Vec<(String, f64)> {
+        let total_amount: f64 = self.allocations.iter().map(|alloc| alloc.amount).sum();
+        self.allocations
+            .iter()
+            .map(|alloc| {
+                let quantity = match self.profile_type {
+                    FaProfileType::Percentages => total_quantity * alloc.amount / 100.0,
+                    FaProfileType::FinancialRatios if total_amount != 0.0 => {
+                        total_quantity * alloc.amount / total_amount
+                    }
+                    FaProfileType::FinancialRatios => 0.0,
+                    FaProfileType::Shares => alloc.amount,
+                };
+                (alloc.account.clone(), quantity)
+            })
+            .collect()
+    }
+}
+
+impl FaConfig for FaProfile {
+    const DATA_TYPE: FaDataType = FaDataType::PROFILES;
+    const LIST: &'static str = "ListOfAllocationProfiles";
+    const ITEM: &'static str = "AllocationProfile";
+
+    fn key(&self) -> &str {
+        &self.name
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn from_xml(element: &XmlElement) -> Result<Self, IBKRApiLibError> {
+        let profile_type = required(element, "type")?;
+        let profile_type = match profile_type {
+            "1" => FaProfileType::Percentages,
+            "2" => FaProfileType::FinancialRatios,
+            "3" => FaProfileType::Shares,
+            _ => return Err(invalid(format!("unknown profile type {}", profile_type))),
+        };
+        let mut allocations = vec![];
+        if let Some(list) = element.child("ListOfAllocations") {
+            for allocation in list.children_named("Allocation") {
+                let amount = required(allocation, "amount")?;
+                allocations.push(FaAllocation {
+                    account: required(allocation, "acct")?.to_string(),
+                    amount: amount
+                        .parse()
+                        .map_err(|_| invalid(format!("invalid amount {}", amount)))?,
+                });
+            }
+        }
+        Ok(FaProfile {
+            name: required(element, "name")?.to_string(),
+            profile_type,
+            allocations,
+        })
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn to_xml(&self) -> XmlElement {
+        let allocations = self.allocations.iter().fold(
+            XmlElement::new("ListOfAllocations").with_attribute("varName", "listOfAllocations"),
+            |list, alloc| {
+                list.with_child(
+                    XmlElement::new("Allocation")
+                        .with_child(XmlElement::new("acct").with_text(&alloc.account))
+                        // TWS writes amounts with a decimal point
+                        .with_child(
+                            XmlElement::new("amount").with_text(&format!("{:?}", alloc.amount)),
+                        ),
+                )
+            },
+        );
+        XmlElement::new(Self::ITEM)
+            .with_child(XmlElement::new("name").with_text(&self.name))
+            .with_child(XmlElement::new("type").with_text(&(self.profile_type as i32).to_string()))
+            .with_child(allocations)
+    }
+}
+
+impl Display for FaProfile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let allocations = self
+            .allocations
+            .iter()
+            .map(|alloc| format!("{}={}", alloc.account, alloc.amount))
+            .collect::<Vec<String>>();
+        write!(
+            f,
+            "profile {} ({:?}): {}",
+            self.name,
+            self.profile_type,
+            allocations.join(",")
+        )
+    }
+}
+
+//==================================================================================================
+/// An alias TWS shows in place of an account number
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct FaAlias {
+    pub account: String,
+    pub alias: String,
+}
+
+impl FaAlias {
+    pub fn new(account: &str, alias: &str) -> Self {
+        FaAlias {
+            account: account.to_string(),
+            alias: alias.to_string(),
+        }
+    }
+}
+
+impl FaConfig for FaAlias {
+    const DATA_TYPE: FaDataType = FaDataType::ALIASES;
+    const LIST: &'static str = "ListOfAccountAliases";
+    const ITEM: &'static str = "AccountAlias";
+
+    fn key(&self) -> &str {
+        &self.account
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn from_xml(element: &XmlElement) -> Result<Self, IBKRApiLibError> {
+        Ok(FaAlias {
+            account: required(element, "account")?.to_string(),
+            alias: element.child_text("alias").unwrap_or_default().to_string(),
+        })
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn to_xml(&self) -> XmlElement {
+        XmlElement::new(Self::ITEM)
+            .with_child(XmlElement::new("account").with_text(&self.account))
+            .with_child(XmlElement::new("alias").with_text(&self.alias))
+    }
+}
+
+impl Display for FaAlias {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "alias {} of {}", self.alias, self.account)
+    }
+}
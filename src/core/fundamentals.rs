@@ -0,0 +1,623 @@
+//! Typed reports parsed from the XML of `EWrapper::fundamental_data`, see `FundamentalType`
+use chrono::NaiveDate;
+
+use crate::core::common::FundamentalType;
+use crate::core::errors::IBKRApiLibError;
+use crate::core::xml::XmlElement;
+
+//==================================================================================================
+/// A parsed fundamental data report
+#[derive(Clone, Debug, PartialEq)]
+pub enum FundamentalReport {
+    Snapshot(Snapshot),
+    FinSummary(FinSummary),
+    Ratios(Vec<Ratio>),
+    FinStatements(FinStatements),
+    Estimates(Estimates),
+    Calendar(Vec<CalendarEvent>),
+}
+
+//==================================================================================================
+/// Parses the data of `req_fundamental_data` for a report of `report_type`. Ownership reports
+/// are not supported.
+pub fn parse_fundamental_data(
+    report_type: &FundamentalType,
+    xml: &str,
+) -> Result<FundamentalReport, IBKRApiLibError> {
+    let report = match report_type {
+        FundamentalType::ReportSnapshot => FundamentalReport::Snapshot(Snapshot::parse(xml)?),
+        FundamentalType::ReportsFinSummary => {
+            FundamentalReport::FinSummary(FinSummary::parse(xml)?)
+        }
+        FundamentalType::ReportRatios => FundamentalReport::Ratios(parse_ratios(xml)?),
+        FundamentalType::ReportsFinStatements => {
+            FundamentalReport::FinStatements(FinStatements::parse(xml)?)
+        }
+        FundamentalType::RESC => FundamentalReport::Estimates(Estimates::parse(xml)?),
+        FundamentalType::CalendarReport => FundamentalReport::Calendar(parse_calendar(xml)?),
+        FundamentalType::ReportsOwnership => {
+            return Err(invalid(format!(
+                "{} reports are not supported",
+                report_type
+            )))
+        }
+    };
+    Ok(report)
+}
+
+//==================================================================================================
+fn invalid(reason: String) -> IBKRApiLibError {
+    IBKRApiLibError::InvalidXml { reason }
+}
+
+//==================================================================================================
+/// Parses `xml` and checks the name of its root element
+fn parse_root(xml: &str, name: &str) -> Result<XmlElement, IBKRApiLibError> {
+    let root = XmlElement::parse(xml)?;
+    if root.name != name {
+        return Err(invalid(format!(
+            "expected {} but found {}",
+            name, root.name
+        )));
+    }
+    Ok(root)
+}
+
+//==================================================================================================
+fn number(text: &str) -> Option<f64> {
+    text.trim().parse().ok()
+}
+
+//==================================================================================================
+/// Dates are "yyyy-mm-dd", optionally followed by a time
+fn date(text: &str) -> Option<NaiveDate> {
+    text.get(..10)
+        .and_then(|day| NaiveDate::parse_from_str(day, "%Y-%m-%d").ok())
+}
+
+//==================================================================================================
+fn attribute_date(element: &XmlElement, name: &str) -> Option<NaiveDate> {
+    element.attribute(name).and_then(date)
+}
+
+//==================================================================================================
+fn attribute_string(element: &XmlElement, name: &str) -> String {
+    element.attribute(name).unwrap_or_default().to_string()
+}
+
+//==================================================================================================
+fn child_string(element: &XmlElement, name: &str) -> String {
+    element.child_text(name).unwrap_or_default().to_string()
+}
+
+//==================================================================================================
+/// Text of the first child called `name` whose `Type` attribute is `value`
+fn typed_child<'a>(element: &'a XmlElement, name: &'a str, value: &str) -> Option<&'a str> {
+    element
+        .children_named(name)
+        .find(|child| child.attribute("Type") == Some(value))
+        .map(|child| child.text.as_str())
+}
+
+//==================================================================================================
+/// A ratio or forecast field, e.g. "PEEXCLXOR" in the group "Price and Volume"
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ratio {
+    pub group: String,
+    pub field: String,
+    /// The value of numeric ratios
+    pub value: Option<f64>,
+    /// The value as sent, also set for date and text ratios
+    pub text: String,
+}
+
+//==================================================================================================
+/// Ratios of the `Group` children of a `Ratios` element
+fn ratio_groups(ratios: &XmlElement) -> Vec<Ratio> {
+    ratios
+        .children_named("Group")
+        .flat_map(|group| {
+            let id = attribute_string(group, "ID");
+            group.children_named("Ratio").map(move |ratio| Ratio {
+                group: id.clone(),
+                field: attribute_string(ratio, "FieldName"),
+                value: if ratio.attribute("Type") == Some("N") {
+                    number(&ratio.text)
+                } else {
+                    None
+                },
+                text: ratio.text.clone(),
+            })
+        })
+        .collect()
+}
+
+//==================================================================================================
+/// Parses a ReportRatios report, whose ratios are grouped in a `Ratios` element at the root or
+/// one level below it
+pub fn parse_ratios(xml: &str) -> Result<Vec<Ratio>, IBKRApiLibError> {
+    let root = XmlElement::parse(xml)?;
+    let ratios = if root.name == "Ratios" {
+        &root
+    } else {
+        root.child("Ratios")
+            .ok_or_else(|| invalid(format!("{} without Ratios", root.name)))?
+    };
+    Ok(ratio_groups(ratios))
+}
+
+//==================================================================================================
+/// Company overview of a ReportSnapshot report
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Snapshot {
+    pub company_name: String,
+    pub ticker: String,
+    pub exchange: String,
+    pub employees: Option<f64>,
+    pub shares_outstanding: Option<f64>,
+    pub reporting_currency: String,
+    pub business_summary: String,
+    /// Industry classifications, e.g. "IT Services & Consulting - NEC"
+    pub industries: Vec<String>,
+    pub ratios: Vec<Ratio>,
+    /// Consensus forecasts, with the value of the current period
+    pub forecasts: Vec<Ratio>,
+}
+
+impl Snapshot {
+    pub fn parse(xml: &str) -> Result<Self, IBKRApiLibError> {
+        let root = parse_root(xml, "ReportSnapshot")?;
+        let mut snapshot = Snapshot::default();
+        if let Some(ids) = root.child("CoIDs") {
+            snapshot.company_name = typed_child(ids, "CoID", "CompanyName")
+                .unwrap_or_default()
+                .to_string();
+        }
+        let issue = root
+            .child("Issues")
+            .and_then(|issues| issues.children.first());
+        if let Some(issue) = issue {
+            snapshot.ticker = typed_child(issue, "IssueID", "Ticker")
+                .unwrap_or_default()
+                .to_string();
+            if let Some(exchange) = issue.child("Exchange") {
+                snapshot.exchange = attribute_string(exchange, "Code");
+            }
+        }
+        if let Some(info) = root.child("CoGeneralInfo") {
+            snapshot.employees = info.child_text("Employees").and_then(number);
+            snapshot.shares_outstanding = info.child_text("SharesOut").and_then(number);
+            if let Some(currency) = info.child("ReportingCurrency") {
+                snapshot.reporting_currency = attribute_string(currency, "Code");
+            }
+        }
+        if let Some(text) = root.child("TextInfo") {
+            snapshot.business_summary = typed_child(text, "Text", "Business Summary")
+                .unwrap_or_default()
+                .to_string();
+        }
+        if let Some(industries) = root
+            .child("peerInfo")
+            .and_then(|peers| peers.child("IndustryInfo"))
+        {
+            snapshot.industries = industries
+                .children_named("Industry")
+                .map(|industry| industry.text.clone())
+                .collect();
+        }
+        if let Some(ratios) = root.child("Ratios") {
+            snapshot.ratios = ratio_groups(ratios);
+        }
+        if let Some(forecasts) = root.child("ForecastData") {
+            snapshot.forecasts = forecasts
+                .children_named("Ratio")
+                .map(|ratio| {
+                    let text = ratio.child_text("Value").unwrap_or_default().to_string();
+                    Ratio {
+                        group: attribute_string(forecasts, "ConsensusType"),
+                        field: attribute_string(ratio, "FieldName"),
+                        value: if ratio.attribute("Type") == Some("N") {
+                            number(&text)
+                        } else {
+                            None
+                        },
+                        text,
+                    }
+                })
+                .collect();
+        }
+        Ok(snapshot)
+    }
+}
+
+//==================================================================================================
+/// A value of a ReportsFinSummary series, for a period ending at `as_of_date`
+#[derive(Clone, Debug, PartialEq)]
+pub struct PeriodValue {
+    pub as_of_date: Option<NaiveDate>,
+    /// "A" for annual, "P" for preliminary, "R" for restated and "TTM" for trailing twelve
+    /// months
+    pub report_type: String,
+    /// Length of the period, e.g. "3M" or "12M"
+    pub period: String,
+    pub value: f64,
+}
+
+//==================================================================================================
+/// A dividend of a ReportsFinSummary report
+#[derive(Clone, Debug, PartialEq)]
+pub struct Dividend {
+    pub dividend_type: String,
+    pub ex_date: Option<NaiveDate>,
+    pub record_date: Option<NaiveDate>,
+    pub pay_date: Option<NaiveDate>,
+    pub declaration_date: Option<NaiveDate>,
+    pub amount: f64,
+}
+
+//==================================================================================================
+/// Earnings, dividends and revenue of a ReportsFinSummary report
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FinSummary {
+    pub currency: String,
+    pub eps: Vec<PeriodValue>,
+    pub dividend_per_share: Vec<PeriodValue>,
+    pub total_revenue: Vec<PeriodValue>,
+    pub dividends: Vec<Dividend>,
+}
+
+impl FinSummary {
+    pub fn parse(xml: &str) -> Result<Self, IBKRApiLibError> {
+        let root = parse_root(xml, "FinancialSummary")?;
+        let series = |list: &str, item: &str| -> Vec<PeriodValue> {
+            root.child(list)
+                .map(|list| {
+                    list.children_named(item)
+                        .filter_map(|value| {
+                            Some(PeriodValue {
+                                as_of_date: attribute_date(value, "asofDate"),
+                                report_type: attribute_string(value, "reportType"),
+                                period: attribute_string(value, "period"),
+                                value: number(&value.text)?,
+                            })
+                        })
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        let dividends = root
+            .child("Dividends")
+            .map(|list| {
+                list.children_named("Dividend")
+                    .filter_map(|dividend| {
+                        Some(Dividend {
+                            dividend_type: attribute_string(dividend, "type"),
+                            ex_date: attribute_date(dividend, "exDate"),
+                            record_date: attribute_date(dividend, "recordDate"),
+                            pay_date: attribute_date(dividend, "payDate"),
+                            declaration_date: attribute_date(dividend, "declarationDate"),
+                            amount: number(&dividend.text)?,
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Ok(FinSummary {
+            currency: root
+                .child("EPSs")
+                .map(|eps| attribute_string(eps, "currency"))
+                .unwrap_or_default(),
+            eps: series("EPSs", "EPS"),
+            dividend_per_share: series("DividendPerShares", "DividendPerShare"),
+            total_revenue: series("TotalRevenues", "TotalRevenue"),
+            dividends,
+        })
+    }
+}
+
+//==================================================================================================
+/// Kind of a financial statement
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StatementType {
+    /// "INC"
+    Income,
+    /// "BAL"
+    BalanceSheet,
+    /// "CAS"
+    CashFlow,
+}
+
+impl StatementType {
+    fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "INC" => Some(StatementType::Income),
+            "BAL" => Some(StatementType::BalanceSheet),
+            "CAS" => Some(StatementType::CashFlow),
+            _ => None,
+        }
+    }
+}
+
+//==================================================================================================
+/// Line items of one financial statement, keyed by chart of accounts code, e.g. "SREV"
+#[derive(Clone, Debug, PartialEq)]
+pub struct Statement {
+    pub statement_type: StatementType,
+    pub statement_date: Option<NaiveDate>,
+    pub line_items: Vec<(String, f64)>,
+}
+
+impl Statement {
+    pub fn line_item(&self, coa_code: &str) -> Option<f64> {
+        self.line_items
+            .iter()
+            .find(|(code, _)| code == coa_code)
+            .map(|(_, value)| *value)
+    }
+}
+
+//==================================================================================================
+/// The statements of an annual or interim fiscal period
+#[derive(Clone, Debug, PartialEq)]
+pub struct FiscalPeriod {
+    pub end_date: Option<NaiveDate>,
+    pub fiscal_year: i32,
+    /// Quarter of interim periods, `None` for annual ones
+    pub period_number: Option<i32>,
+    pub statements: Vec<Statement>,
+}
+
+impl FiscalPeriod {
+    pub fn statement(&self, statement_type: StatementType) -> Option<&Statement> {
+        self.statements
+            .iter()
+            .find(|statement| statement.statement_type == statement_type)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn parse(period: &XmlElement) -> Result<Self, IBKRApiLibError> {
+        let mut statements = vec![];
+        for statement in period.children_named("Statement") {
+            let code = statement.attribute("Type").unwrap_or_default();
+            let statement_type = StatementType::from_code(code)
+                .ok_or_else(|| invalid(format!("unknown statement type {}", code)))?;
+            statements.push(Statement {
+                statement_type,
+                statement_date: statement
+                    .child("FPHeader")
+                    .and_then(|header| header.child_text("StatementDate"))
+                    .and_then(date),
+                line_items: statement
+                    .children_named("lineItem")
+                    .filter_map(|item| {
+                        Some((attribute_string(item, "coaCode"), number(&item.text)?))
+                    })
+                    .collect(),
+            });
+        }
+        Ok(FiscalPeriod {
+            end_date: attribute_date(period, "EndDate"),
+            fiscal_year: period
+                .attribute("FiscalYear")
+                .and_then(|year| year.parse().ok())
+                .unwrap_or_default(),
+            period_number: period
+                .attribute("FiscalPeriodNumber")
+                .and_then(|number| number.parse().ok()),
+            statements,
+        })
+    }
+}
+
+//==================================================================================================
+/// Description of a chart of accounts code, e.g. "SREV" is the "Revenue" of income statements
+#[derive(Clone, Debug, PartialEq)]
+pub struct CoaItem {
+    pub coa_code: String,
+    pub statement_type: Option<StatementType>,
+    pub description: String,
+}
+
+//==================================================================================================
+/// Financial statements by period of a ReportsFinStatements report, most recent first
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FinStatements {
+    pub coa_map: Vec<CoaItem>,
+    pub annual: Vec<FiscalPeriod>,
+    pub interim: Vec<FiscalPeriod>,
+}
+
+impl FinStatements {
+    pub fn parse(xml: &str) -> Result<Self, IBKRApiLibError> {
+        let root = parse_root(xml, "ReportFinancialStatements")?;
+        let statements = root
+            .child("FinancialStatements")
+            .ok_or_else(|| invalid("report without FinancialStatements".to_string()))?;
+        let periods = |list: &str| -> Result<Vec<FiscalPeriod>, IBKRApiLibError> {
+            match statements.child(list) {
+                Some(list) => list
+                    .children_named("FiscalPeriod")
+                    .map(FiscalPeriod::parse)
+                    .collect(),
+                None => Ok(vec![]),
+            }
+        };
+        Ok(FinStatements {
+            coa_map: statements
+                .child("COAMap")
+                .map(|map| {
+                    map.children_named("mapItem")
+                        .map(|item| CoaItem {
+                            coa_code: attribute_string(item, "coaItem"),
+                            statement_type: item
+                                .attribute("statementType")
+                                .and_then(StatementType::from_code),
+                            description: item.text.clone(),
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+            annual: periods("AnnualPeriods")?,
+            interim: periods("InterimPeriods")?,
+        })
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn description(&self, coa_code: &str) -> Option<&str> {
+        self.coa_map
+            .iter()
+            .find(|item| item.coa_code == coa_code)
+            .map(|item| item.description.as_str())
+    }
+}
+
+//==================================================================================================
+/// A reported value of a RESC report, e.g. the EPS of fiscal year 2019
+#[derive(Clone, Debug, PartialEq)]
+pub struct Actual {
+    /// Measure, e.g. "EPS", "REVENUE" or "DPS"
+    pub measure: String,
+    /// "A" for annual and "Q" for quarterly periods
+    pub period_type: String,
+    pub fiscal_year: i32,
+    pub end_month: i32,
+    pub value: f64,
+}
+
+//==================================================================================================
+/// Current analyst consensus of a RESC report for a measure and period
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConsensusEstimate {
+    pub measure: String,
+    pub period_type: String,
+    pub fiscal_year: i32,
+    pub end_month: i32,
+    pub mean: Option<f64>,
+    pub median: Option<f64>,
+    pub high: Option<f64>,
+    pub low: Option<f64>,
+    pub std_dev: Option<f64>,
+    pub num_of_estimates: Option<f64>,
+}
+
+//==================================================================================================
+/// Actuals and consensus estimates of a RESC report
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Estimates {
+    pub actuals: Vec<Actual>,
+    pub estimates: Vec<ConsensusEstimate>,
+}
+
+impl Estimates {
+    pub fn parse(xml: &str) -> Result<Self, IBKRApiLibError> {
+        let root = parse_root(xml, "REarnEstCons")?;
+        // calls `f` with the measure and each FYPeriod of the items of a list
+        fn periods<'a, T>(
+            list: Option<&'a XmlElement>,
+            item: &'a str,
+            f: impl Fn(&'a XmlElement, &'a XmlElement) -> Option<T>,
+        ) -> Vec<T> {
+            list.map(|list| {
+                list.children_named(item)
+                    .flat_map(|measure| {
+                        measure
+                            .children_named("FYPeriod")
+                            .filter_map(|period| f(measure, period))
+                            .collect::<Vec<T>>()
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+        }
+        let year = |period: &XmlElement, name: &str| -> i32 {
+            period
+                .attribute(name)
+                .and_then(|year| year.parse().ok())
+                .unwrap_or_default()
+        };
+
+        let actuals = periods(
+            root.child("Actuals")
+                .and_then(|list| list.child("FYActuals")),
+            "FYActual",
+            |measure, period| {
+                Some(Actual {
+                    measure: attribute_string(measure, "type"),
+                    period_type: attribute_string(period, "periodType"),
+                    fiscal_year: year(period, "fYear"),
+                    end_month: year(period, "endMonth"),
+                    value: period.child_text("ActValue").and_then(number)?,
+                })
+            },
+        );
+        let estimates = periods(
+            root.child("ConsEstimates")
+                .and_then(|list| list.child("FYEstimates")),
+            "FYEstimate",
+            |measure, period| {
+                let current = |estimate_type: &str| {
+                    period
+                        .children_named("ConsEstimate")
+                        .find(|estimate| estimate.attribute("type") == Some(estimate_type))
+                        .and_then(|estimate| {
+                            estimate
+                                .children_named("ConsValue")
+                                .find(|value| value.attribute("dateType") == Some("CURR"))
+                        })
+                        .and_then(|value| number(&value.text))
+                };
+                Some(ConsensusEstimate {
+                    measure: attribute_string(measure, "type"),
+                    period_type: attribute_string(period, "periodType"),
+                    fiscal_year: year(period, "fYear"),
+                    end_month: year(period, "endMonth"),
+                    mean: current("Mean"),
+                    median: current("Median"),
+                    high: current("High"),
+                    low: current("Low"),
+                    std_dev: current("StdDev"),
+                    num_of_estimates: current("NumOfEst"),
+                })
+            },
+        );
+        Ok(Estimates { actuals, estimates })
+    }
+}
+
+//==================================================================================================
+/// An event of a CalendarReport, such as an earnings release
+#[derive(Clone, Debug, PartialEq)]
+pub struct CalendarEvent {
+    /// Element of the event, e.g. "Earnings"
+    pub event_type: String,
+    /// Fiscal period the event reports on, e.g. "Q1 2020"
+    pub period: String,
+    pub date: Option<NaiveDate>,
+    /// Time of day as sent, e.g. "Before Market"
+    pub time: String,
+}
+
+//==================================================================================================
+/// Parses the events of the lists of a CalendarReport, e.g. the `Earnings` of `EarningsList`.
+/// Dates are "mm/dd/yyyy" or "yyyy-mm-dd".
+pub fn parse_calendar(xml: &str) -> Result<Vec<CalendarEvent>, IBKRApiLibError> {
+    let root = parse_root(xml, "CalendarReport")?;
+    let events = root
+        .children
+        .iter()
+        .filter(|list| list.name.ends_with("List"))
+        .flat_map(|list| list.children.iter())
+        .map(|event| {
+            let day = child_string(event, "Date");
+            CalendarEvent {
+                event_type: event.name.clone(),
+                period: child_string(event, "Period"),
+                date: NaiveDate::parse_from_str(&day, "%m/%d/%Y")
+                    .ok()
+                    .or_else(|| date(&day)),
+                time: child_string(event, "Time"),
+            }
+        })
+        .collect();
+    Ok(events)
+}
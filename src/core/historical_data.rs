@@ -0,0 +1,197 @@
+//! Typed parameters of historical data requests
+use chrono::{DateTime, TimeZone, Utc};
+
+use crate::core::common::{BarSize, Duration, DurationUnit, TagValue, WhatToShow};
+use crate::core::contract::Contract;
+use crate::core::errors::IBKRApiLibError;
+
+/// Smallest bar size IB serves for each request duration, in seconds: a request may use the
+/// bar size of the first row whose duration is at least the requested one
+const MIN_BAR_SIZES: [(u64, u64); 11] = [
+    (60, 1),
+    (120, 1),
+    (1800, 1),
+    (3600, 5),
+    (14400, 10),
+    (28800, 30),
+    (86400, 60),
+    (172_800, 120),
+    (604_800, 180),
+    (2_592_000, 1800),
+    (31_536_000, 86400),
+];
+
+//==================================================================================================
+/// Checks a duration and bar size against the combinations IB allows for historical bars
+pub fn validate_bar_size(duration: &Duration, bar_size: BarSize) -> Result<(), IBKRApiLibError> {
+    if duration.n == 0 {
+        return Err(invalid(format!("duration {} is empty", duration)));
+    }
+    if duration.unit == DurationUnit::SECOND && duration.n > 86400 {
+        return Err(invalid(format!(
+            "duration {} exceeds 86400 S, use days instead",
+            duration
+        )));
+    }
+    let seconds = duration.seconds();
+    if bar_size.seconds() > seconds {
+        return Err(invalid(format!(
+            "bar size {} is longer than the duration {}",
+            bar_size, duration
+        )));
+    }
+    let min_bar_size = MIN_BAR_SIZES
+        .iter()
+        .find(|(max_duration, _)| seconds <= *max_duration)
+        .map_or(86400, |(_, min_bar_size)| *min_bar_size);
+    if bar_size.seconds() < min_bar_size {
+        return Err(invalid(format!(
+            "bar size {} is too small for the duration {}",
+            bar_size, duration
+        )));
+    }
+    Ok(())
+}
+
+//==================================================================================================
+fn invalid(reason: String) -> IBKRApiLibError {
+    IBKRApiLibError::InvalidHistoricalRequest { reason }
+}
+
+//==================================================================================================
+/// Parameters of `EClient::req_historical_data`, created with `HistoricalDataRequest::builder`
+#[derive(Clone, Debug)]
+pub struct HistoricalDataRequest {
+    pub contract: Contract,
+    /// End of the requested bars, `None` for up to the present
+    pub end_date_time: Option<DateTime<Utc>>,
+    pub duration: Duration,
+    pub bar_size: BarSize,
+    pub what_to_show: WhatToShow,
+    /// Only return data from regular trading hours
+    pub use_rth: bool,
+    /// 1 for bar dates as "yyyymmdd hh:mm:ss", 2 for epoch seconds
+    pub format_date: i32,
+    /// Keep sending the last bar as it updates, requires an end_date_time of `None`
+    pub keep_up_to_date: bool,
+    pub chart_options: Vec<TagValue>,
+}
+
+impl HistoricalDataRequest {
+    pub fn builder(
+        contract: &Contract,
+        duration: Duration,
+        bar_size: BarSize,
+        what_to_show: WhatToShow,
+    ) -> HistoricalDataRequestBuilder {
+        HistoricalDataRequestBuilder::new(contract, duration, bar_size, what_to_show)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn validate(&self) -> Result<(), IBKRApiLibError> {
+        self.contract.validate()?;
+        validate_bar_size(&self.duration, self.bar_size)?;
+        if self.format_date != 1 && self.format_date != 2 {
+            return Err(invalid(format!(
+                "format_date {} is neither 1 nor 2",
+                self.format_date
+            )));
+        }
+        if self.keep_up_to_date {
+            if self.end_date_time.is_some() {
+                return Err(invalid(
+                    "keep_up_to_date requires the bars to end at the present".to_string(),
+                ));
+            }
+            if self.bar_size.seconds() < 5 {
+                return Err(invalid(
+                    "keep_up_to_date requires bars of 5 secs or longer".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+//==================================================================================================
+/// Fluent builder for `HistoricalDataRequest`.
+///
+/// ```
+/// use ibtwsapi::core::common::{BarSize, Duration, DurationUnit, WhatToShow};
+/// use ibtwsapi::core::contract::Contract;
+/// use ibtwsapi::core::historical_data::HistoricalDataRequest;
+///
+/// let request = HistoricalDataRequest::builder(
+///     &Contract::stock("IBM", "SMART", "USD"),
+///     Duration::new(2, DurationUnit::DAY),
+///     BarSize::_5Mins,
+///     WhatToShow::Trades,
+/// )
+/// .use_rth(true)
+/// .build()
+/// .unwrap();
+/// assert_eq!("2 D", request.duration.to_string());
+/// ```
+#[derive(Clone, Debug)]
+pub struct HistoricalDataRequestBuilder {
+    request: HistoricalDataRequest,
+}
+
+impl HistoricalDataRequestBuilder {
+    pub fn new(
+        contract: &Contract,
+        duration: Duration,
+        bar_size: BarSize,
+        what_to_show: WhatToShow,
+    ) -> Self {
+        HistoricalDataRequestBuilder {
+            request: HistoricalDataRequest {
+                contract: contract.clone(),
+                end_date_time: None,
+                duration,
+                bar_size,
+                what_to_show,
+                use_rth: false,
+                format_date: 1,
+                keep_up_to_date: false,
+                chart_options: vec![],
+            },
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn end_date_time<Tz: TimeZone>(mut self, end_date_time: &DateTime<Tz>) -> Self {
+        self.request.end_date_time = Some(end_date_time.with_timezone(&Utc));
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn use_rth(mut self, use_rth: bool) -> Self {
+        self.request.use_rth = use_rth;
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn format_date(mut self, format_date: i32) -> Self {
+        self.request.format_date = format_date;
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn keep_up_to_date(mut self, keep_up_to_date: bool) -> Self {
+        self.request.keep_up_to_date = keep_up_to_date;
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn chart_options(mut self, chart_options: Vec<TagValue>) -> Self {
+        self.request.chart_options = chart_options;
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn build(self) -> Result<HistoricalDataRequest, IBKRApiLibError> {
+        self.request.validate()?;
+        Ok(self.request)
+    }
+}
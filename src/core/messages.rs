@@ -1,26 +1,26 @@
 //! Functions for processing messages
-use std::any::Any;
 use std::collections::HashSet;
 use std::convert::TryInto;
-use std::io::Write;
+use std::fmt::Write;
 use std::string::String;
 use std::vec::Vec;
 
-use ascii;
-use ascii::AsAsciiStr;
 use rust_decimal::Decimal;
 
 use log::*;
 use num_derive::FromPrimitive;
 
 use crate::core::common::{
-    BarData, CommissionReport, DepthMktDataDescription, FaDataType, FamilyCode, HistogramData,
-    HistoricalTick, HistoricalTickBidAsk, HistoricalTickLast, NewsProvider, PriceIncrement,
-    RealTimeBar, SmartComponent, TickAttrib, TickAttribBidAsk, TickAttribLast, TickByTickType,
-    TickMsgType, TickType, UNSET_DOUBLE, UNSET_INTEGER,
+    Action, BarData, BarSize, ComboParam, CommissionReport, DepthMktDataDescription, DurationUnit,
+    ExerciseType, FaDataType, FamilyCode, FundamentalType, HedgeType, HistogramData,
+    HistoricalTick, HistoricalTickBidAsk, HistoricalTickLast, MarketDataTypeEnum, Method,
+    NewsProvider, OcaType, OrderType, PriceIncrement, RealTimeBar, ReferencePriceType, Right,
+    Rule80A, SecIdType, SecType, SmartComponent, TickAttrib, TickAttribBidAsk, TickAttribLast,
+    TickByTickType, TickMsgType, TickType, TimeInForce, TriggerMethod, UsePriceMgmtAlgo,
+    VolatilityType, WhatToShow, MAX_MSG_LEN, NO_VALID_ID, UNSET_DOUBLE, UNSET_INTEGER, UNSET_LONG,
 };
 use crate::core::contract::{Contract, ContractDescription, ContractPreamble, ContractDetails, DeltaNeutralContract};
-use crate::core::errors::IBKRApiLibError;
+use crate::core::errors::{IBKRApiLibError, TwsApiReportableError, TwsError};
 use crate::core::execution::{Execution,ExecutionFilter};
 use crate::core::scanner::ScannerSubscription;
 use crate::core::order::{Order, OrderState, SoftDollarTier};
@@ -42,7 +42,7 @@ pub enum FAMessageDataTypes {
     Aliases = 3,
 }
 
-#[derive(FromPrimitive)]
+#[derive(FromPrimitive, Debug, Clone, Copy)]
 #[repr(i32)]
 pub enum ServerRspMsgDiscriminants {
     TickPrice = 1,
@@ -420,7 +420,7 @@ pub enum ServerRspMsg {
     },
     TickNews {
         ticker_id: i32,
-        time_stamp: i32,
+        time_stamp: i64,
         provider_code: String,
         article_id: String,
         headline: String,
@@ -519,7 +519,7 @@ pub enum ServerRspMsg {
     },
 }
 
-#[derive(FromPrimitive)]
+#[derive(FromPrimitive, Debug, Clone, Copy)]
 #[repr(i32)]
 pub enum ServerReqMsgDiscriminants {
     ReqMktData = 1,
@@ -988,24 +988,45 @@ pub enum ServerReqMsg {
 
 
 //==================================================================================================
+/// Frames a message payload with its big endian length prefix.
+///
+/// The payload is sent as UTF-8 and the prefix counts bytes, not characters, so non-ASCII text
+/// such as local symbols or FA XML with accented names is framed correctly.
 pub fn make_message(msg: &str) -> Result<Vec<u8>, IBKRApiLibError> {
-    //let mut buffer = ByteBuffer::new();
-    let mut buffer: Vec<u8> = Vec::new();
+    let payload = msg.as_bytes();
+    check_msg_len(payload.len())?;
 
-    buffer.extend_from_slice(&i32::to_be_bytes(msg.len() as i32));
+    let mut buffer: Vec<u8> = Vec::with_capacity(4 + payload.len());
+    buffer.extend_from_slice(&i32::to_be_bytes(payload.len() as i32));
+    buffer.extend_from_slice(payload);
 
-    buffer.write(msg.as_ascii_str().unwrap().as_bytes())?;
-    let tmp = buffer.clone();
-    //debug!("Message after create: {:?}", buffer);
-
-    let (_size, _msg, _buf) = read_msg(tmp.as_slice())?;
-    //debug!("Message read: size:{}, msg:{}, bytes: {:?}", size, msg, buf);
+    Ok(buffer)
+}
 
-    Ok(tmp)
+//==================================================================================================
+fn check_msg_len(size: usize) -> Result<(), IBKRApiLibError> {
+    if size > MAX_MSG_LEN as usize {
+        return Err(IBKRApiLibError::ApiError(TwsApiReportableError::new(
+            NO_VALID_ID,
+            TwsError::BadLength.code().to_string(),
+            format!(
+                "{} message of {} bytes exceeds the maximum of {}",
+                TwsError::BadLength.message(),
+                size,
+                MAX_MSG_LEN
+            ),
+        )));
+    }
+    Ok(())
 }
 
 //==================================================================================================
-pub fn read_msg<'a>(buf: &[u8]) -> Result<(usize, String, Vec<u8>), IBKRApiLibError> {
+/// Reads one length prefixed message off the front of `buf`.
+///
+/// Returns the payload size, the payload text and the bytes following the message. If `buf` does
+/// not yet hold the whole message the text is empty and `buf` is returned unchanged. Invalid UTF-8
+/// from the server is replaced with U+FFFD rather than failing the whole message.
+pub fn read_msg(buf: &[u8]) -> Result<(usize, String, Vec<u8>), IBKRApiLibError> {
     // first the size prefix and then the corresponding msg payload ""
 
     if buf.len() < 4 {
@@ -1017,7 +1038,14 @@ pub fn read_msg<'a>(buf: &[u8]) -> Result<(usize, String, Vec<u8>), IBKRApiLibEr
     //debug!("read_msg: Message size: {:?}", size);
 
     if buf.len() - 4 >= size {
-        let text = String::from_utf8(buf[4..4 + size].to_vec()).unwrap();
+        let payload = &buf[4..4 + size];
+        let text = match String::from_utf8(payload.to_vec()) {
+            Ok(text) => text,
+            Err(_) => {
+                warn!("read_msg: message of {} bytes is not valid UTF-8", size);
+                String::from_utf8_lossy(payload).into_owned()
+            }
+        };
         //debug!("read_msg: text in read message: {:?}", text);
         Ok((size, text, buf[4 + size..].to_vec()))
     } else {
@@ -1041,47 +1069,301 @@ pub fn read_fields(buf: &str) -> Vec<String> {
 }
 
 //==================================================================================================
-pub fn make_field(val: &dyn Any) -> Result<String, IBKRApiLibError> {
-    // debug!("CALLING make_field!!");
-    // adds the NULL string terminator
-    let mut field = "\0".to_string();
-    // bool type is encoded as int
-    if let Some(boolval) = val.downcast_ref::<bool>() {
-        field = format!("{}\0", *boolval as i32);
-    } else if let Some(stringval) = val.downcast_ref::<usize>() {
-        field = format!("{}\0", *stringval as i32);
-    } else if let Some(stringval) = val.downcast_ref::<f64>() {
-        if UNSET_DOUBLE == *stringval {
-            field = format!("{}\0", "");
-        } else {
-            field = format!("{}\0", *stringval as f64);
+/// A value that can be written as a single protocol field.
+///
+/// Implementations append the value's wire representation to `out`, without the NUL terminator.
+/// Only types with a defined wire format implement it, so passing anything else to `make_field`
+/// or `FieldWriter::push` is a compile error rather than a silently empty field.
+pub trait ToField {
+    fn write_field(&self, out: &mut String);
+}
+
+impl<T: ToField + ?Sized> ToField for &T {
+    fn write_field(&self, out: &mut String) {
+        (**self).write_field(out)
+    }
+}
+
+impl<T: ToField + ?Sized> ToField for &mut T {
+    fn write_field(&self, out: &mut String) {
+        (**self).write_field(out)
+    }
+}
+
+impl ToField for str {
+    fn write_field(&self, out: &mut String) {
+        out.push_str(self)
+    }
+}
+
+impl ToField for String {
+    fn write_field(&self, out: &mut String) {
+        out.push_str(self)
+    }
+}
+
+impl ToField for char {
+    fn write_field(&self, out: &mut String) {
+        out.push(*self)
+    }
+}
+
+// bool type is encoded as int
+impl ToField for bool {
+    fn write_field(&self, out: &mut String) {
+        out.push(if *self { '1' } else { '0' })
+    }
+}
+
+macro_rules! impl_to_field_display {
+    ($($t:ty),*) => {
+        $(
+            impl ToField for $t {
+                fn write_field(&self, out: &mut String) {
+                    // Writing to a String cannot fail
+                    let _ = write!(out, "{}", self);
+                }
+            }
+        )*
+    };
+}
+
+impl_to_field_display!(i8, i16, u8, u16, u32, u64, usize, f32, Decimal);
+
+// Unset values are sent as empty fields
+macro_rules! impl_to_field_unset {
+    ($($t:ty => $unset:expr),*) => {
+        $(
+            impl ToField for $t {
+                fn write_field(&self, out: &mut String) {
+                    if *self != $unset {
+                        let _ = write!(out, "{}", self);
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_to_field_unset!(i32 => UNSET_INTEGER, i64 => UNSET_LONG, f64 => UNSET_DOUBLE);
+
+impl<T: ToField> ToField for Option<T> {
+    fn write_field(&self, out: &mut String) {
+        if let Some(val) = self {
+            val.write_field(out)
         }
-    } else if let Some(stringval) = val.downcast_ref::<i32>() {
-        if UNSET_INTEGER == *stringval {
-            field = format!("{}\0", "");
-        } else {
-            field = format!("{}\0", *stringval as i32);
+    }
+}
+
+// Enums whose Display output is their wire value
+macro_rules! impl_to_field_for_enum_display {
+    ($($t:ty),*) => {
+        $(
+            impl ToField for $t {
+                fn write_field(&self, out: &mut String) {
+                    let _ = write!(out, "{}", self);
+                }
+            }
+        )*
+    };
+}
+
+impl_to_field_for_enum_display!(
+    Action,
+    BarSize,
+    ComboParam,
+    Method,
+    OrderType,
+    Right,
+    Rule80A,
+    SecIdType,
+    SecType,
+    TimeInForce,
+    WhatToShow
+);
+
+// Enums sent as their integer code
+macro_rules! impl_to_field_for_enum_code {
+    ($($t:ty),*) => {
+        $(
+            impl ToField for $t {
+                fn write_field(&self, out: &mut String) {
+                    (self.clone() as i32).write_field(out)
+                }
+            }
+        )*
+    };
+}
+
+impl_to_field_for_enum_code!(FaDataType, MarketDataTypeEnum, OcaType);
+
+impl ToField for TickByTickType {
+    fn write_field(&self, out: &mut String) {
+        match self {
+            TickByTickType::NA => (),
+            _ => {
+                let _ = write!(out, "{}", self);
+            }
         }
-    } else if let Some(stringval) = val.downcast_ref::<String>() {
-        field = format!("{}\0", stringval);
-    } else if let Some(stringval) = val.downcast_ref::<&str>() {
-        field = format!("{}\0", stringval);
     }
+}
 
-    Ok(field)
+impl ToField for TriggerMethod {
+    fn write_field(&self, out: &mut String) {
+        let code = match self {
+            TriggerMethod::Default => 0,
+            TriggerMethod::DoubleBidAsk => 1,
+            TriggerMethod::Last => 2,
+            TriggerMethod::DoubleLast => 3,
+            TriggerMethod::BidAsk => 4,
+            TriggerMethod::LastOrBidAsk => 7,
+            TriggerMethod::Midpoint => 8,
+        };
+        code.write_field(out)
+    }
 }
 
-//==================================================================================================
-pub fn make_field_handle_empty(val: &dyn Any) -> Result<String, IBKRApiLibError> {
-    if let Some(stringval) = val.downcast_ref::<f64>() {
-        if UNSET_DOUBLE == *stringval {
-            return make_field(&"");
+impl ToField for VolatilityType {
+    fn write_field(&self, out: &mut String) {
+        match self {
+            VolatilityType::None => (),
+            VolatilityType::Daily => out.push('1'),
+            VolatilityType::Annual => out.push('2'),
+        }
+    }
+}
+
+impl ToField for ReferencePriceType {
+    fn write_field(&self, out: &mut String) {
+        match self {
+            ReferencePriceType::None => (),
+            ReferencePriceType::Midpoint => out.push('1'),
+            ReferencePriceType::BidOrAsk => out.push('2'),
+        }
+    }
+}
+
+impl ToField for ExerciseType {
+    fn write_field(&self, out: &mut String) {
+        match self {
+            ExerciseType::None => (),
+            ExerciseType::Exercise => out.push('1'),
+            ExerciseType::Lapse => out.push('2'),
         }
-    } else if let Some(stringval) = val.downcast_ref::<i32>() {
-        if UNSET_INTEGER == *stringval {
-            return make_field(&"");
+    }
+}
+
+impl ToField for HedgeType {
+    fn write_field(&self, out: &mut String) {
+        match self {
+            HedgeType::None => (),
+            HedgeType::Delta => out.push('D'),
+            HedgeType::Beta => out.push('B'),
+            HedgeType::Fx => out.push('F'),
+            HedgeType::Pair => out.push('P'),
         }
     }
+}
 
+impl ToField for DurationUnit {
+    fn write_field(&self, out: &mut String) {
+        out.push(match self {
+            DurationUnit::SECOND => 'S',
+            DurationUnit::DAY => 'D',
+            DurationUnit::WEEK => 'W',
+            DurationUnit::MONTH => 'M',
+            DurationUnit::YEAR => 'Y',
+        })
+    }
+}
+
+impl ToField for FundamentalType {
+    fn write_field(&self, out: &mut String) {
+        out.push_str(match self {
+            FundamentalType::ReportSnapshot => "ReportSnapshot",
+            FundamentalType::ReportsFinSummary => "ReportsFinSummary",
+            FundamentalType::ReportRatios => "ReportRatios",
+            FundamentalType::ReportsFinStatements => "ReportsFinStatements",
+            FundamentalType::RESC => "RESC",
+            FundamentalType::CalendarReport => "CalendarReport",
+            FundamentalType::ReportsOwnership => "ReportsOwnership",
+        })
+    }
+}
+
+impl ToField for UsePriceMgmtAlgo {
+    fn write_field(&self, out: &mut String) {
+        self.value().write_field(out)
+    }
+}
+
+//==================================================================================================
+/// Encodes a single NUL terminated field
+pub fn make_field<T: ToField + ?Sized>(val: &T) -> Result<String, IBKRApiLibError> {
+    let mut field = String::new();
+    val.write_field(&mut field);
+    // adds the NULL string terminator
+    field.push('\0');
+    Ok(field)
+}
+
+//==================================================================================================
+/// Same as `make_field`, kept for call sites that spell out that unset values are sent empty
+pub fn make_field_handle_empty<T: ToField + ?Sized>(val: &T) -> Result<String, IBKRApiLibError> {
     make_field(val)
 }
+
+//==================================================================================================
+/// Streaming message encoder.
+///
+/// Fields are written straight into one buffer behind a reserved length prefix, which `finish`
+/// fills in, so building a request does not allocate a `String` per field.
+pub struct FieldWriter {
+    buf: String,
+}
+
+impl FieldWriter {
+    pub fn new() -> Self {
+        FieldWriter {
+            buf: String::from("\0\0\0\0"),
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Appends `val` followed by the NUL terminator
+    pub fn push<T: ToField + ?Sized>(&mut self, val: &T) -> &mut Self {
+        val.write_field(&mut self.buf);
+        self.buf.push('\0');
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Appends a string that is already made up of NUL terminated fields
+    pub fn push_raw(&mut self, fields: &str) -> &mut Self {
+        self.buf.push_str(fields);
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// The fields written so far, without the length prefix
+    pub fn as_str(&self) -> &str {
+        &self.buf[4..]
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Fills in the length prefix and returns the framed message, ready to be sent
+    pub fn finish(self) -> Result<Vec<u8>, IBKRApiLibError> {
+        let size = self.buf.len() - 4;
+        check_msg_len(size)?;
+
+        let mut bytes = self.buf.into_bytes();
+        bytes[0..4].copy_from_slice(&i32::to_be_bytes(size as i32));
+        Ok(bytes)
+    }
+}
+
+impl Default for FieldWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,17 +1,35 @@
 //! Core structs, enums, and functions
+pub mod account_state;
+pub mod account_summary;
 pub mod account_summary_tags;
 pub mod algo_params;
 pub mod client;
 pub mod common;
 pub mod contract;
+pub mod datetime;
 pub mod decoder;
+pub mod dump;
+pub mod encoder;
 pub mod errors;
 pub mod execution;
+pub mod fa;
+pub mod fundamentals;
+pub mod historical_data;
 pub mod messages;
+pub mod news;
 pub mod order;
 pub mod order_condition;
 pub mod order_decoder;
+pub mod order_encoder;
+pub mod order_group;
+pub mod order_validation;
 pub mod reader;
 pub mod scanner;
+pub mod scanner_book;
+pub mod scanner_parameters;
 pub mod server_versions;
 pub mod streamer;
+pub mod tick_size;
+pub mod ticker;
+pub mod trading_hours;
+pub mod xml;
@@ -0,0 +1,475 @@
+//! Typed news headlines, bulletins and articles, an on-disk article cache and paginated
+//! historical headlines
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use chrono::{DateTime, FixedOffset, Utc};
+
+use crate::core::client::EClient;
+use crate::core::datetime::parse_tws_datetime;
+use crate::core::errors::{IBKRApiLibError, TwsApiReportableError};
+use crate::core::messages::ServerRspMsg;
+
+const BASE64_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+//==================================================================================================
+fn invalid(reason: String) -> IBKRApiLibError {
+    IBKRApiLibError::InvalidNewsArticle { reason }
+}
+
+//==================================================================================================
+/// A headline of `TickNews` or `HistoricalNews`.
+///
+/// TWS prefixes headlines with metadata between braces, e.g. "{A:800015:L:en:K:0.97:C:0.85}",
+/// made of key and value pairs. The prefix is removed from `headline` and its pairs are kept in
+/// `metadata`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NewsHeadline {
+    /// `None` if TWS sent a time that could not be read
+    pub time: Option<DateTime<Utc>>,
+    pub provider_code: String,
+    pub article_id: String,
+    pub headline: String,
+    pub metadata: Vec<(String, String)>,
+    /// Extra data of `TickNews`, empty for historical headlines
+    pub extra_data: String,
+}
+
+impl NewsHeadline {
+    /// Splits the metadata prefix off a headline, leaving `time` and `extra_data` empty
+    pub fn parse(provider_code: &str, article_id: &str, headline: &str) -> Self {
+        let mut metadata = vec![];
+        let mut text = headline;
+        if headline.starts_with('{') {
+            if let Some(end) = headline.find('}') {
+                let fields: Vec<&str> = headline[1..end].split(':').collect();
+                metadata = fields
+                    .chunks(2)
+                    .filter(|pair| pair.len() == 2)
+                    .map(|pair| (pair[0].to_string(), pair[1].to_string()))
+                    .collect();
+                text = &headline[end + 1..];
+            }
+        }
+        NewsHeadline {
+            time: None,
+            provider_code: provider_code.to_string(),
+            article_id: article_id.to_string(),
+            headline: text.trim().to_string(),
+            metadata,
+            extra_data: String::new(),
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// The headline of a `TickNews` or `HistoricalNews` event. Times of historical headlines are
+    /// read in UTC.
+    pub fn from_msg(msg: &ServerRspMsg) -> Option<Self> {
+        match msg {
+            ServerRspMsg::TickNews {
+                time_stamp,
+                provider_code,
+                article_id,
+                headline,
+                extra_data,
+                ..
+            } => Some(NewsHeadline {
+                time: DateTime::from_timestamp_millis(*time_stamp),
+                extra_data: extra_data.clone(),
+                ..NewsHeadline::parse(provider_code, article_id, headline)
+            }),
+            ServerRspMsg::HistoricalNews {
+                time,
+                provider_code,
+                article_id,
+                headline,
+                ..
+            } => Some(NewsHeadline {
+                time: parse_tws_datetime(time, FixedOffset::east_opt(0)?)
+                    .map(|time| time.with_timezone(&Utc)),
+                ..NewsHeadline::parse(provider_code, article_id, headline)
+            }),
+            _ => None,
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Value of a metadata key, e.g. "L" for the language
+    pub fn metadata(&self, key: &str) -> Option<&str> {
+        self.metadata
+            .iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Language code of the article, e.g. "en"
+    pub fn language(&self) -> Option<&str> {
+        self.metadata("L")
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Sentiment score from -1 to 1, `None` when the provider sends "n/a"
+    pub fn sentiment(&self) -> Option<f64> {
+        self.metadata("K")?.parse().ok()
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Confidence in the sentiment score from 0 to 1
+    pub fn confidence(&self) -> Option<f64> {
+        self.metadata("C")?.parse().ok()
+    }
+}
+
+//==================================================================================================
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BulletinType {
+    Regular,
+    /// The exchange in `origin_exchange` can no longer be traded
+    ExchangeUnavailable,
+    /// The exchange in `origin_exchange` can be traded again
+    ExchangeAvailable,
+}
+
+//==================================================================================================
+/// A message of `NewsBulletins`
+#[derive(Clone, Debug, PartialEq)]
+pub struct NewsBulletin {
+    pub msg_id: i32,
+    pub bulletin_type: BulletinType,
+    pub message: String,
+    pub origin_exchange: String,
+}
+
+impl NewsBulletin {
+    /// The bulletin of a `NewsBulletins` event, `None` for other events or an unknown type
+    pub fn from_msg(msg: &ServerRspMsg) -> Option<Self> {
+        match msg {
+            ServerRspMsg::NewsBulletins {
+                msg_id,
+                msg_type,
+                news_message,
+                origin_exch,
+            } => Some(NewsBulletin {
+                msg_id: *msg_id,
+                bulletin_type: match msg_type {
+                    1 => BulletinType::Regular,
+                    2 => BulletinType::ExchangeUnavailable,
+                    3 => BulletinType::ExchangeAvailable,
+                    _ => return None,
+                },
+                message: news_message.clone(),
+                origin_exchange: origin_exch.clone(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+//==================================================================================================
+#[derive(Clone, Debug, PartialEq)]
+pub enum ArticleBody {
+    /// Plain text or HTML, `article_type` 0
+    Text(String),
+    /// Content of a PDF, `article_type` 1
+    Pdf(Vec<u8>),
+}
+
+//==================================================================================================
+/// An article requested with `req_news_article`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Article {
+    pub provider_code: String,
+    pub article_id: String,
+    pub body: ArticleBody,
+}
+
+impl Article {
+    /// Reads the `article_type` and `article_text` of a `NewsArticle` event, decoding the base64
+    /// text of binary articles
+    pub fn from_response(
+        provider_code: &str,
+        article_id: &str,
+        article_type: i32,
+        article_text: &str,
+    ) -> Result<Self, IBKRApiLibError> {
+        let body = match article_type {
+            0 => ArticleBody::Text(article_text.to_string()),
+            1 => ArticleBody::Pdf(decode_base64(article_text)?),
+            _ => return Err(invalid(format!("unknown article type {}", article_type))),
+        };
+        Ok(Article {
+            provider_code: provider_code.to_string(),
+            article_id: article_id.to_string(),
+            body,
+        })
+    }
+}
+
+//==================================================================================================
+/// Decodes standard base64, ignoring whitespace and padding
+pub fn decode_base64(text: &str) -> Result<Vec<u8>, IBKRApiLibError> {
+    let mut bytes = Vec::with_capacity(text.len() * 3 / 4);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for c in text
+        .bytes()
+        .filter(|c| !c.is_ascii_whitespace() && *c != b'=')
+    {
+        let value = BASE64_ALPHABET
+            .iter()
+            .position(|&symbol| symbol == c)
+            .ok_or_else(|| invalid(format!("invalid base64 character {:?}", c as char)))?;
+        buffer = (buffer << 6) | value as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    if bits >= 6 {
+        return Err(invalid("truncated base64 text".to_string()));
+    }
+    Ok(bytes)
+}
+
+//==================================================================================================
+/// Articles stored as files named after their provider and id, so that each is requested once.
+///
+/// Text articles are stored as "<dir>/<provider>/<id>.txt" and PDF articles as
+/// "<dir>/<provider>/<id>.pdf". Bytes other than letters, digits, '-', '_' and '$' are written
+/// as "%XX" with their hexadecimal value, so text article "123:4" of provider "BRFG" is stored
+/// as "<dir>/BRFG/123%3A4.txt".
+#[derive(Clone, Debug)]
+pub struct NewsCache {
+    dir: PathBuf,
+}
+
+impl NewsCache {
+    pub fn new<P: AsRef<Path>>(dir: P) -> Self {
+        NewsCache {
+            dir: dir.as_ref().to_path_buf(),
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// The cached article, `None` if it was never stored
+    pub fn get(
+        &self,
+        provider_code: &str,
+        article_id: &str,
+    ) -> Result<Option<Article>, IBKRApiLibError> {
+        let path = self.path(provider_code, article_id, "txt");
+        let body = if path.is_file() {
+            ArticleBody::Text(fs::read_to_string(path)?)
+        } else {
+            let path = self.path(provider_code, article_id, "pdf");
+            if !path.is_file() {
+                return Ok(None);
+            }
+            ArticleBody::Pdf(fs::read(path)?)
+        };
+        Ok(Some(Article {
+            provider_code: provider_code.to_string(),
+            article_id: article_id.to_string(),
+            body,
+        }))
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Stores an article, replacing a previous copy, and returns the path of its file
+    pub fn put(&self, article: &Article) -> Result<PathBuf, IBKRApiLibError> {
+        let (extension, contents) = match &article.body {
+            ArticleBody::Text(text) => ("txt", text.as_bytes()),
+            ArticleBody::Pdf(bytes) => ("pdf", bytes.as_slice()),
+        };
+        let path = self.path(&article.provider_code, &article.article_id, extension);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, contents)?;
+        Ok(path)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Path of an article file. Bytes of the codes that are not safe in file names are
+    /// percent-encoded, so that distinct codes never share a file.
+    fn path(&self, provider_code: &str, article_id: &str, extension: &str) -> PathBuf {
+        let file_name = |name: &str| -> String {
+            name.bytes()
+                .map(|byte| {
+                    if byte.is_ascii_alphanumeric() || b"-_$".contains(&byte) {
+                        (byte as char).to_string()
+                    } else {
+                        format!("%{:02X}", byte)
+                    }
+                })
+                .collect()
+        };
+        self.dir.join(file_name(provider_code)).join(format!(
+            "{}.{}",
+            file_name(article_id),
+            extension
+        ))
+    }
+}
+
+//==================================================================================================
+/// Historical headlines of a contract, from the most recent, one page per `req_historical_news`.
+///
+/// Each call to `next` sends a request and waits for its headlines. When TWS reports more
+/// headlines, the next request ends at the time of the oldest headline received, and headlines
+/// already returned are skipped. Events of other requests received meanwhile are kept in
+/// `other_events`.
+///
+/// ```no_run
+/// use ibtwsapi::core::client::EClient;
+///
+/// # fn run(client: &mut EClient) -> Result<(), ibtwsapi::core::errors::IBKRApiLibError> {
+/// for page in client.historical_news(9001, 8314, "BRFG+DJNL").page_size(50) {
+///     for headline in page? {
+///         println!("{:?} {} {:?}", headline.time, headline.headline, headline.sentiment());
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct HistoricalNewsPages<'a> {
+    client: &'a mut EClient,
+    req_id: i32,
+    con_id: i32,
+    provider_codes: String,
+    start_date_time: Option<DateTime<Utc>>,
+    end_date_time: Option<DateTime<Utc>>,
+    page_size: i32,
+    timeout: Duration,
+    seen: HashSet<(String, String)>,
+    done: bool,
+    pub other_events: Vec<ServerRspMsg>,
+}
+
+impl<'a> HistoricalNewsPages<'a> {
+    /// Pages of 300 headlines, the most TWS sends per request, waiting up to 10 seconds for each
+    /// event
+    pub fn new(client: &'a mut EClient, req_id: i32, con_id: i32, provider_codes: &str) -> Self {
+        HistoricalNewsPages {
+            client,
+            req_id,
+            con_id,
+            provider_codes: provider_codes.to_string(),
+            start_date_time: None,
+            end_date_time: None,
+            page_size: 300,
+            timeout: Duration::from_secs(10),
+            seen: HashSet::new(),
+            done: false,
+            other_events: vec![],
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Stops at headlines older than this time
+    pub fn start_date_time(mut self, start_date_time: DateTime<Utc>) -> Self {
+        self.start_date_time = Some(start_date_time);
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Starts at headlines up to this time instead of the most recent ones
+    pub fn end_date_time(mut self, end_date_time: DateTime<Utc>) -> Self {
+        self.end_date_time = Some(end_date_time);
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn page_size(mut self, page_size: i32) -> Self {
+        self.page_size = page_size;
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Requests a page and reads events until its end, returning its headlines and whether TWS
+    /// has more
+    fn fetch(&mut self) -> Result<(Vec<NewsHeadline>, bool), IBKRApiLibError> {
+        self.client.req_historical_news(
+            self.req_id,
+            self.con_id,
+            &self.provider_codes,
+            self.start_date_time,
+            self.end_date_time,
+            self.page_size,
+            vec![],
+        )?;
+        let mut headlines = vec![];
+        loop {
+            let event = self.client.next_event(self.timeout)?.ok_or_else(|| {
+                IBKRApiLibError::ApiError(TwsApiReportableError::new(
+                    self.req_id,
+                    "-1".to_string(),
+                    "Timed out waiting for historical news".to_string(),
+                ))
+            })?;
+            match event {
+                ServerRspMsg::HistoricalNews { req_id, .. } if req_id == self.req_id => {
+                    headlines.extend(NewsHeadline::from_msg(&event));
+                }
+                ServerRspMsg::HistoricalNewsEnd { req_id, has_more } if req_id == self.req_id => {
+                    return Ok((headlines, has_more));
+                }
+                ServerRspMsg::ErrMsg {
+                    req_id,
+                    error_code,
+                    error_str,
+                } if req_id == self.req_id => {
+                    return Err(IBKRApiLibError::ApiError(TwsApiReportableError::new(
+                        req_id,
+                        error_code.to_string(),
+                        error_str,
+                    )));
+                }
+                event => self.other_events.push(event),
+            }
+        }
+    }
+}
+
+impl<'a> Iterator for HistoricalNewsPages<'a> {
+    type Item = Result<Vec<NewsHeadline>, IBKRApiLibError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let (headlines, has_more) = match self.fetch() {
+            Ok(page) => page,
+            Err(err) => {
+                self.done = true;
+                return Some(Err(err));
+            }
+        };
+        let oldest = headlines.iter().filter_map(|headline| headline.time).min();
+        let headlines: Vec<NewsHeadline> = headlines
+            .into_iter()
+            .filter(|headline| {
+                self.seen
+                    .insert((headline.provider_code.clone(), headline.article_id.clone()))
+            })
+            .collect();
+        // Without new headlines or times the next request would return the same page
+        self.done = !has_more || headlines.is_empty() || oldest.is_none();
+        self.end_date_time = oldest;
+        if headlines.is_empty() {
+            return None;
+        }
+        Some(Ok(headlines))
+    }
+}
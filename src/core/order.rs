@@ -1,19 +1,30 @@
 //! Types related to orders
 use std::fmt::{Display, Error, Formatter};
 
+use chrono::{DateTime, FixedOffset, TimeZone};
 use num_derive::FromPrimitive;
+use rust_decimal::Decimal;
+use strum_macros::EnumString;
 
 use serde::{Deserialize, Serialize};
 use serde::ser::{Serializer, SerializeStruct};
 use serde::de::{self, Deserializer, Visitor, SeqAccess};
-use crate::core::common::{TagValue, UNSET_DOUBLE, UNSET_INTEGER};
+use crate::core::common::{
+    parse_decimal, Action, HedgeType, OcaType, OrderType, ReferencePriceType, Rule80A, TagValue,
+    TimeInForce, TriggerMethod, VolatilityType, UNSET_DOUBLE, UNSET_INTEGER,
+};
+use crate::core::contract::Contract;
+use crate::core::datetime::{format_tws_datetime, parse_tws_datetime};
+use crate::core::errors::IBKRApiLibError;
+use crate::core::messages::ToField;
 use crate::core::order::AuctionStrategy::AuctionUnset;
 use crate::core::order::Origin::Customer;
 use crate::core::order_condition::{Condition, OrderConditionEnum};
+use crate::core::tick_size::{RoundDirection, TickSizeRules};
 
 //==================================================================================================
 #[repr(i32)]
-#[derive(Serialize, Deserialize, Clone, Debug, FromPrimitive, Copy)]
+#[derive(Serialize, Deserialize, Clone, Debug, FromPrimitive, Copy, PartialEq)]
 pub enum Origin {
     Customer = 0,
     Firm = 1,
@@ -29,7 +40,7 @@ impl Default for Origin {
 // enum AuctionStrategy
 //==================================================================================================
 #[repr(i32)]
-#[derive(Serialize, Deserialize, Clone, Debug, FromPrimitive, Copy)]
+#[derive(Serialize, Deserialize, Clone, Debug, FromPrimitive, Copy, PartialEq)]
 pub enum AuctionStrategy {
     AuctionUnset = 0,
     AuctionMatch = 1,
@@ -44,7 +55,7 @@ impl Default for AuctionStrategy {
 }
 
 //==================================================================================================
-#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 pub struct SoftDollarTier {
     pub name: String,
     pub val: String,
@@ -71,6 +82,68 @@ impl Display for SoftDollarTier {
     }
 }
 
+//==================================================================================================
+/// Status of an order as reported in `OrderStatus` messages and `OrderState::status`
+#[derive(EnumString, Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum OrderStatusKind {
+    /// The order has not yet been sent to IB, e.g. TWS is waiting for the order to be transmitted
+    ApiPending,
+    /// The order was transmitted but IB has not yet confirmed it
+    PendingSubmit,
+    /// A cancel request was sent but IB has not yet confirmed the cancellation
+    PendingCancel,
+    /// IB accepted a simulated order type or an order held until its conditions are met
+    PreSubmitted,
+    /// The order was accepted by the system and is working
+    Submitted,
+    /// The order was cancelled by the API client before it was submitted
+    ApiCancelled,
+    /// IB confirmed the cancellation, or rejected the order
+    Cancelled,
+    /// The order has been completely filled
+    Filled,
+    /// The order was received but is not working, e.g. because it was rejected or the trading
+    /// session is closed. TWS may still hold it and activate it later, so it is not terminal.
+    Inactive,
+}
+
+impl OrderStatusKind {
+    /// True once the order can no longer fill
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            OrderStatusKind::ApiCancelled | OrderStatusKind::Cancelled | OrderStatusKind::Filled
+        )
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// True while the order is accepted and can fill, including while a cancel is pending
+    pub fn is_working(&self) -> bool {
+        matches!(
+            self,
+            OrderStatusKind::PreSubmitted
+                | OrderStatusKind::Submitted
+                | OrderStatusKind::PendingCancel
+        )
+    }
+}
+
+impl Display for OrderStatusKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match *self {
+            OrderStatusKind::ApiPending => write!(f, "ApiPending"),
+            OrderStatusKind::PendingSubmit => write!(f, "PendingSubmit"),
+            OrderStatusKind::PendingCancel => write!(f, "PendingCancel"),
+            OrderStatusKind::PreSubmitted => write!(f, "PreSubmitted"),
+            OrderStatusKind::Submitted => write!(f, "Submitted"),
+            OrderStatusKind::ApiCancelled => write!(f, "ApiCancelled"),
+            OrderStatusKind::Cancelled => write!(f, "Cancelled"),
+            OrderStatusKind::Filled => write!(f, "Filled"),
+            OrderStatusKind::Inactive => write!(f, "Inactive"),
+        }
+    }
+}
+
 //==================================================================================================
 #[derive(Serialize, Deserialize, Clone, Debug, Default)]
 pub struct OrderState {
@@ -133,6 +206,87 @@ impl OrderState {
             completed_status,
         }
     }
+
+    //----------------------------------------------------------------------------------------------
+    /// `status` as an `OrderStatusKind`, `None` if it is empty or not a known status
+    pub fn status_kind(&self) -> Option<OrderStatusKind> {
+        self.status.parse().ok()
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// `completed_status` of a completed order as an `OrderStatusKind`
+    pub fn completed_status_kind(&self) -> Option<OrderStatusKind> {
+        self.completed_status.parse().ok()
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn init_margin_before_value(&self) -> Option<Decimal> {
+        parse_decimal(&self.init_margin_before)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn maint_margin_before_value(&self) -> Option<Decimal> {
+        parse_decimal(&self.maint_margin_before)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn equity_with_loan_before_value(&self) -> Option<Decimal> {
+        parse_decimal(&self.equity_with_loan_before)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn init_margin_change_value(&self) -> Option<Decimal> {
+        parse_decimal(&self.init_margin_change)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn maint_margin_change_value(&self) -> Option<Decimal> {
+        parse_decimal(&self.maint_margin_change)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn equity_with_loan_change_value(&self) -> Option<Decimal> {
+        parse_decimal(&self.equity_with_loan_change)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn init_margin_after_value(&self) -> Option<Decimal> {
+        parse_decimal(&self.init_margin_after)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn maint_margin_after_value(&self) -> Option<Decimal> {
+        parse_decimal(&self.maint_margin_after)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn equity_with_loan_after_value(&self) -> Option<Decimal> {
+        parse_decimal(&self.equity_with_loan_after)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn commission_value(&self) -> Option<f64> {
+        unset_to_none(self.commission)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn min_commission_value(&self) -> Option<f64> {
+        unset_to_none(self.min_commission)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn max_commission_value(&self) -> Option<f64> {
+        unset_to_none(self.max_commission)
+    }
+}
+
+//==================================================================================================
+fn unset_to_none(value: f64) -> Option<f64> {
+    if value == UNSET_DOUBLE || !value.is_finite() {
+        None
+    } else {
+        Some(value)
+    }
 }
 
 impl Display for OrderState {
@@ -190,7 +344,7 @@ impl Display for OrderState {
 }
 
 //==================================================================================================
-#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 pub struct OrderComboLeg {
     pub(crate) price: f64, // type: float
 }
@@ -261,7 +415,7 @@ impl serde::ser::Serialize for VolatilityOrder {
 }
 
 //==================================================================================================
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct Order {
 
     // order identifier
@@ -754,6 +908,120 @@ impl Order {
             use_price_mgmt_algo,
         }
     }
+
+    //----------------------------------------------------------------------------------------------
+    /// `good_till_date` parsed, see `parse_tws_datetime` for the formats and `default_tz`
+    pub fn good_till_timestamp(&self, default_tz: FixedOffset) -> Option<DateTime<FixedOffset>> {
+        parse_tws_datetime(&self.good_till_date, default_tz)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// `good_after_time` parsed, see `parse_tws_datetime` for the formats and `default_tz`
+    pub fn good_after_timestamp(&self, default_tz: FixedOffset) -> Option<DateTime<FixedOffset>> {
+        parse_tws_datetime(&self.good_after_time, default_tz)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Checks that the price fields set on the order make sense for its order type, e.g. a LMT
+    /// order needs a lmt_price and a TRAIL order takes either aux_price or trailing_percent.
+    /// Order types this library does not know are only checked for quantity and time in force.
+    pub fn validate(&self) -> Result<(), IBKRApiLibError> {
+        let has_cash_qty = self.cash_qty != UNSET_DOUBLE && self.cash_qty > 0.0;
+        if self.order_type.is_empty() {
+            return Err(self.invalid("requires an order_type"));
+        }
+        if self.total_quantity <= 0.0 && !has_cash_qty {
+            return Err(self.invalid("requires a positive total_quantity or cash_qty"));
+        }
+        if self.tif == TimeInForce::GTD.to_string() && self.good_till_date.is_empty() {
+            return Err(self.invalid("requires good_till_date with GTD time in force"));
+        }
+        self.validate_prices()
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// The order type and price field checks of `validate`, without the quantity and time in
+    /// force checks
+    pub(crate) fn validate_prices(&self) -> Result<(), IBKRApiLibError> {
+        let order_type = match self.order_type.parse::<OrderType>() {
+            Ok(order_type) => order_type,
+            Err(_) => return Ok(()),
+        };
+        let has_lmt_price = self.lmt_price != UNSET_DOUBLE;
+        let has_aux_price = self.aux_price != UNSET_DOUBLE;
+        let has_trailing_percent = self.trailing_percent != UNSET_DOUBLE;
+
+        let needs_lmt_price = matches!(
+            order_type,
+            OrderType::Limit
+                | OrderType::StopLimit
+                | OrderType::LimitIfTouched
+                | OrderType::LimitOnClose
+                | OrderType::RelativeLimit
+                | OrderType::LimitMarket
+        );
+        let takes_no_lmt_price = matches!(
+            order_type,
+            OrderType::Market
+                | OrderType::MarketOnClose
+                | OrderType::MarketToLimit
+                | OrderType::MarketProtect
+                | OrderType::Stop
+                | OrderType::StopProtect
+                | OrderType::MarketIfTouched
+                | OrderType::Trail
+        );
+        let needs_aux_price = matches!(
+            order_type,
+            OrderType::Stop
+                | OrderType::StopLimit
+                | OrderType::StopProtect
+                | OrderType::MarketIfTouched
+                | OrderType::LimitIfTouched
+        );
+        let is_trail = matches!(order_type, OrderType::Trail | OrderType::TrailLimit);
+
+        if needs_lmt_price && !has_lmt_price {
+            return Err(self.invalid("requires lmt_price"));
+        }
+        if takes_no_lmt_price && has_lmt_price {
+            return Err(self.invalid("does not take lmt_price"));
+        }
+        if needs_aux_price && !has_aux_price {
+            return Err(self.invalid("requires aux_price"));
+        }
+        if is_trail {
+            if has_aux_price && has_trailing_percent {
+                return Err(self.invalid("cannot set both aux_price and trailing_percent"));
+            }
+            if !has_aux_price && !has_trailing_percent {
+                return Err(self.invalid("requires aux_price or trailing_percent"));
+            }
+        } else if has_trailing_percent {
+            return Err(self.invalid("does not take trailing_percent"));
+        }
+        if order_type == OrderType::TrailLimit
+            && !has_lmt_price
+            && self.lmt_price_offset == UNSET_DOUBLE
+        {
+            return Err(self.invalid("requires lmt_price or lmt_price_offset"));
+        }
+        if order_type == OrderType::Volatility && self.volatility == UNSET_DOUBLE {
+            return Err(self.invalid("requires volatility"));
+        }
+        if order_type == OrderType::PeggedToBenchmark && self.reference_contract_id <= 0 {
+            return Err(self.invalid("requires reference_contract_id"));
+        }
+        Ok(())
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn invalid(&self, reason: &'static str) -> IBKRApiLibError {
+        IBKRApiLibError::InvalidOrder {
+            order_type: self.order_type.clone(),
+            reason,
+        }
+    }
 }
 
 impl Display for Order {
@@ -1006,3 +1274,307 @@ impl Default for Order {
         }
     }
 }
+
+//==================================================================================================
+// The wire value of an enum as stored in the untyped Order fields
+fn wire_value<T: ToField>(value: &T) -> String {
+    let mut out = String::new();
+    value.write_field(&mut out);
+    out
+}
+
+fn wire_code<T: ToField>(value: &T) -> i32 {
+    wire_value(value).parse().unwrap_or(UNSET_INTEGER)
+}
+
+//==================================================================================================
+/// Fluent builder producing a populated `Order` from the typed enums in `common`, rejecting
+/// combinations of order type and price fields that TWS would refuse.
+///
+/// ```
+/// use ibtwsapi::core::common::{Action, OrderType, TimeInForce};
+/// use ibtwsapi::core::order::OrderBuilder;
+///
+/// let order = OrderBuilder::new(Action::BUY, OrderType::Limit, 100.0)
+///     .lmt_price(50.25)
+///     .tif(TimeInForce::GTC)
+///     .build()
+///     .unwrap();
+/// assert_eq!("LMT", order.order_type);
+///
+/// assert!(OrderBuilder::new(Action::BUY, OrderType::Limit, 100.0).build().is_err());
+/// ```
+#[derive(Clone, Debug)]
+pub struct OrderBuilder {
+    order: Order,
+}
+
+impl OrderBuilder {
+    pub fn new(action: Action, order_type: OrderType, total_quantity: f64) -> Self {
+        OrderBuilder {
+            order: Order {
+                action: action.to_string(),
+                order_type: order_type.to_string(),
+                total_quantity,
+                ..Default::default()
+            },
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn order_id(mut self, order_id: i32) -> Self {
+        self.order.order_id = order_id;
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn lmt_price(mut self, lmt_price: f64) -> Self {
+        self.order.lmt_price = lmt_price;
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Stop price, or the trailing amount of TRAIL orders
+    pub fn aux_price(mut self, aux_price: f64) -> Self {
+        self.order.aux_price = aux_price;
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn trailing_percent(mut self, trailing_percent: f64) -> Self {
+        self.order.trailing_percent = trailing_percent;
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn trail_stop_price(mut self, trail_stop_price: f64) -> Self {
+        self.order.trail_stop_price = trail_stop_price;
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn lmt_price_offset(mut self, lmt_price_offset: f64) -> Self {
+        self.order.lmt_price_offset = lmt_price_offset;
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn cash_qty(mut self, cash_qty: f64) -> Self {
+        self.order.cash_qty = cash_qty;
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn tif(mut self, tif: TimeInForce) -> Self {
+        self.order.tif = tif.to_string();
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Sets GTD time in force, the date is formatted as "20060505 08:00:00 {time zone}"
+    pub fn good_till_date(mut self, good_till_date: &str) -> Self {
+        self.order.tif = TimeInForce::GTD.to_string();
+        self.order.good_till_date = good_till_date.to_string();
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn good_after_time(mut self, good_after_time: &str) -> Self {
+        self.order.good_after_time = good_after_time.to_string();
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Sets GTD time in force, expiring the order at `good_till`
+    pub fn good_till<Tz: TimeZone>(mut self, good_till: &DateTime<Tz>) -> Self {
+        self.order.tif = TimeInForce::GTD.to_string();
+        self.order.good_till_date = format_tws_datetime(good_till);
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Holds the order until `good_after`
+    pub fn good_after<Tz: TimeZone>(mut self, good_after: &DateTime<Tz>) -> Self {
+        self.order.good_after_time = format_tws_datetime(good_after);
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn account(mut self, account: &str) -> Self {
+        self.order.account = account.to_string();
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn order_ref(mut self, order_ref: &str) -> Self {
+        self.order.order_ref = order_ref.to_string();
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn oca_group(mut self, oca_group: &str, oca_type: OcaType) -> Self {
+        self.order.oca_group = oca_group.to_string();
+        self.order.oca_type = oca_type as i32;
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn parent_id(mut self, parent_id: i32) -> Self {
+        self.order.parent_id = parent_id;
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn transmit(mut self, transmit: bool) -> Self {
+        self.order.transmit = transmit;
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn outside_rth(mut self, outside_rth: bool) -> Self {
+        self.order.outside_rth = outside_rth;
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn hidden(mut self, hidden: bool) -> Self {
+        self.order.hidden = hidden;
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn all_or_none(mut self, all_or_none: bool) -> Self {
+        self.order.all_or_none = all_or_none;
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn display_size(mut self, display_size: i32) -> Self {
+        self.order.display_size = display_size;
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn min_qty(mut self, min_qty: i32) -> Self {
+        self.order.min_qty = min_qty;
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn percent_offset(mut self, percent_offset: f64) -> Self {
+        self.order.percent_offset = percent_offset;
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn discretionary_amt(mut self, discretionary_amt: f64) -> Self {
+        self.order.discretionary_amt = discretionary_amt;
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn trigger_method(mut self, trigger_method: TriggerMethod) -> Self {
+        self.order.trigger_method = wire_code(&trigger_method);
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn rule80a(mut self, rule80a: Rule80A) -> Self {
+        self.order.rule80a = rule80a.to_string();
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Attaches a hedge, e.g. `HedgeType::Beta` with "beta=X" or `HedgeType::Pair` with "ratio=Y"
+    pub fn hedge(mut self, hedge_type: HedgeType, hedge_param: &str) -> Self {
+        self.order.hedge_type = wire_value(&hedge_type);
+        self.order.hedge_param = hedge_param.to_string();
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn volatility(mut self, volatility: f64, volatility_type: VolatilityType) -> Self {
+        self.order.volatility = volatility;
+        self.order.volatility_type = wire_code(&volatility_type);
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn reference_price_type(mut self, reference_price_type: ReferencePriceType) -> Self {
+        self.order.reference_price_type = wire_code(&reference_price_type);
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn what_if(mut self, what_if: bool) -> Self {
+        self.order.what_if = what_if;
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn not_held(mut self, not_held: bool) -> Self {
+        self.order.not_held = not_held;
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Holds the order until `conditions` are met, see `ConditionBuilder`
+    pub fn conditions(mut self, conditions: Vec<OrderConditionEnum>) -> Self {
+        self.order.conditions = conditions;
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Cancels the order instead of submitting it when its conditions are met
+    pub fn conditions_cancel_order(mut self, conditions_cancel_order: bool) -> Self {
+        self.order.conditions_cancel_order = conditions_cancel_order;
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Also evaluates the conditions outside of regular trading hours
+    pub fn conditions_ignore_rth(mut self, conditions_ignore_rth: bool) -> Self {
+        self.order.conditions_ignore_rth = conditions_ignore_rth;
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Rounds the prices set so far to the tick size of `contract` on `exchange`, so TWS does not
+    /// reject them. The limit price is rounded away from the market, down for buys and up for
+    /// sells, stop and trigger prices to the nearest tick. Fails if the action is unknown or
+    /// `rules` lack the market rule.
+    pub fn round_prices(
+        mut self,
+        rules: &TickSizeRules,
+        contract: &Contract,
+        exchange: &str,
+    ) -> Result<Self, IBKRApiLibError> {
+        let action = self
+            .order
+            .action
+            .parse::<Action>()
+            .map_err(|_| self.order.invalid("requires a BUY, SELL or SSHORT action"))?;
+        let passive = RoundDirection::passive(action);
+        let stop = ["STP", "STP LMT", "MIT", "LIT"].contains(&self.order.order_type.as_str());
+        let order = &mut self.order;
+        let round = |price: &mut f64, direction| -> Result<(), IBKRApiLibError> {
+            if *price != UNSET_DOUBLE {
+                *price = rules.round_price(contract, exchange, *price, direction)?;
+            }
+            Ok(())
+        };
+        round(&mut order.lmt_price, passive)?;
+        if stop {
+            round(&mut order.aux_price, RoundDirection::Nearest)?;
+        }
+        round(&mut order.trail_stop_price, RoundDirection::Nearest)?;
+        Ok(self)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Returns the order if its price fields are consistent with the order type
+    pub fn build(self) -> Result<Order, IBKRApiLibError> {
+        self.order.validate()?;
+        Ok(self.order)
+    }
+}
@@ -2,18 +2,20 @@
 use std::fmt::{Debug, Display, Error, Formatter};
 use std::slice::Iter;
 
+use chrono::{DateTime, FixedOffset, TimeZone};
 use num_derive::FromPrimitive;
 use num_traits::FromPrimitive;
 
 use serde::{Deserialize, Serialize};
 
-use crate::core::decoder::{decode_bool, decode_f64, decode_i32, decode_string};
+use crate::core::datetime::{format_tws_datetime, parse_tws_datetime};
+use crate::core::decoder::{decode_bool, decode_i32, decode_string};
 use crate::core::errors::IBKRApiLibError;
 use crate::core::messages::make_field;
 
 //==================================================================================================
 #[repr(i32)]
-#[derive(Serialize, Deserialize, Clone, FromPrimitive, Copy)]
+#[derive(Serialize, Deserialize, Clone, FromPrimitive, Copy, PartialEq)]
 pub enum ConditionType {
     Price = 1,
     Time = 3,
@@ -50,7 +52,7 @@ impl Debug for ConditionType {
 
 //==================================================================================================
 #[repr(i32)]
-#[derive(Serialize, Deserialize, Clone, FromPrimitive, Copy)]
+#[derive(Serialize, Deserialize, Clone, FromPrimitive, Copy, PartialEq)]
 pub enum TriggerMethod {
     Default = 0,
     DoubleBidAsk = 1,
@@ -92,7 +94,7 @@ impl Debug for TriggerMethod {
 }
 
 //==================================================================================================
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
 pub enum OrderConditionEnum {
     Price(PriceCondition),
     Time(TimeCondition),
@@ -139,7 +141,7 @@ impl Condition for OrderConditionEnum {
     }
 
     //----------------------------------------------------------------------------------------------
-    fn set_value_from_string(&mut self, text: String) {
+    fn set_value_from_string(&mut self, text: String) -> Result<(), IBKRApiLibError> {
         match self {
             OrderConditionEnum::Execution(s) => s.set_value_from_string(text),
             OrderConditionEnum::Price(p) => p.set_value_from_string(text),
@@ -163,6 +165,50 @@ impl Condition for OrderConditionEnum {
     }
 }
 
+impl OrderConditionEnum {
+    /// The connection of this condition to the next one
+    pub fn order_condition(&self) -> &OrderCondition {
+        match self {
+            OrderConditionEnum::Execution(s) => &s.order_condition,
+            OrderConditionEnum::Price(p) => {
+                &p.contract_condition.operator_condition.order_condition
+            }
+            OrderConditionEnum::Margin(m) => &m.operator_condition.order_condition,
+            OrderConditionEnum::Time(t) => &t.operator_condition.order_condition,
+            OrderConditionEnum::Volume(v) => {
+                &v.contract_condition.operator_condition.order_condition
+            }
+            OrderConditionEnum::PercentChange(pch) => {
+                &pch.contract_condition.operator_condition.order_condition
+            }
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn order_condition_mut(&mut self) -> &mut OrderCondition {
+        match self {
+            OrderConditionEnum::Execution(s) => &mut s.order_condition,
+            OrderConditionEnum::Price(p) => {
+                &mut p.contract_condition.operator_condition.order_condition
+            }
+            OrderConditionEnum::Margin(m) => &mut m.operator_condition.order_condition,
+            OrderConditionEnum::Time(t) => &mut t.operator_condition.order_condition,
+            OrderConditionEnum::Volume(v) => {
+                &mut v.contract_condition.operator_condition.order_condition
+            }
+            OrderConditionEnum::PercentChange(pch) => {
+                &mut pch.contract_condition.operator_condition.order_condition
+            }
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// True if this condition and the next one must both be met, false if either one suffices
+    pub fn is_conjunction(&self) -> bool {
+        self.order_condition().is_conjunction_connection
+    }
+}
+
 impl Display for OrderConditionEnum {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
         write!(f, "{}", self.value_to_string())
@@ -191,12 +237,12 @@ pub trait Condition: Display + Debug + Serialize {
     fn decode(&mut self, fields_iter: &mut Iter<String>) -> Result<(), IBKRApiLibError>;
     fn make_fields(&self) -> Result<Vec<String>, IBKRApiLibError>;
     fn value_to_string(&self) -> String;
-    fn set_value_from_string(&mut self, text: String);
+    fn set_value_from_string(&mut self, text: String) -> Result<(), IBKRApiLibError>;
     fn get_type(&self) -> ConditionType;
 }
 
 //==================================================================================================
-#[derive(Serialize, Deserialize, Clone, Debug, Copy, Default)]
+#[derive(Serialize, Deserialize, Clone, Debug, Copy, Default, PartialEq)]
 pub struct OrderCondition {
     pub cond_type: ConditionType,
     pub is_conjunction_connection: bool,
@@ -247,7 +293,7 @@ impl OrderCondition {
     }
 }
 //==================================================================================================
-#[derive(Serialize, Deserialize, Clone, Default)]
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq)]
 pub struct ExecutionCondition {
     pub sec_type: String,
     pub exchange: String,
@@ -294,8 +340,9 @@ impl Condition for ExecutionCondition {
     }
 
     //----------------------------------------------------------------------------------------------
-    fn set_value_from_string(&mut self, _text: String) {
-        unimplemented!()
+    /// Execution conditions carry no value
+    fn set_value_from_string(&mut self, _text: String) -> Result<(), IBKRApiLibError> {
+        Ok(())
     }
 
     //----------------------------------------------------------------------------------------------
@@ -320,13 +367,24 @@ impl Debug for ExecutionCondition {
 
 impl From<OrderConditionEnum> for ExecutionCondition {
     //----------------------------------------------------------------------------------------------
-    fn from(_: OrderConditionEnum) -> Self {
-        ExecutionCondition::default()
+    /// The condition held by `condition`, or an empty one if it holds another type
+    fn from(condition: OrderConditionEnum) -> Self {
+        match condition {
+            OrderConditionEnum::Execution(condition) => condition,
+            _ => ExecutionCondition::new(String::new(), String::new(), String::new()),
+        }
+    }
+}
+
+impl From<ExecutionCondition> for OrderConditionEnum {
+    //----------------------------------------------------------------------------------------------
+    fn from(condition: ExecutionCondition) -> Self {
+        OrderConditionEnum::Execution(condition)
     }
 }
 
 //==================================================================================================
-#[derive(Serialize, Deserialize, Clone, Debug, Copy, Default)]
+#[derive(Serialize, Deserialize, Clone, Debug, Copy, Default, PartialEq)]
 pub struct OperatorCondition {
     pub order_condition: OrderCondition,
     pub is_more: bool,
@@ -341,32 +399,27 @@ impl OperatorCondition {
     }
 
     //----------------------------------------------------------------------------------------------
-    pub fn value_to_string(&self) -> String {
-        unimplemented!();
-    }
-
-    //----------------------------------------------------------------------------------------------
-    pub fn set_value_from_string(&self, _text: &str) {
-        unimplemented!();
-    }
-
-    //----------------------------------------------------------------------------------------------
-    pub fn decode(&mut self, fields_iter: &mut Iter<String>) -> Result<(), IBKRApiLibError> {
+    /// Decodes the connection and the operator, returning the text of the value that follows
+    /// them for the condition to parse with `set_value_from_string`
+    pub fn decode(&mut self, fields_iter: &mut Iter<String>) -> Result<String, IBKRApiLibError> {
         self.order_condition.decode(fields_iter)?;
         self.is_more = decode_bool(fields_iter)?;
-        Ok(())
+        decode_string(fields_iter)
     }
 
     //----------------------------------------------------------------------------------------------
-    fn make_fields(&self) -> Result<Vec<String>, IBKRApiLibError> {
+    /// Fields of the connection and the operator followed by `value`, the condition's
+    /// `value_to_string`
+    pub fn make_fields(&self, value: &str) -> Result<Vec<String>, IBKRApiLibError> {
         let mut flds = self.order_condition.make_fields()?;
         flds.push(make_field(&self.is_more)?);
+        flds.push(make_field(value)?);
         Ok(flds)
     }
 }
 
 //==================================================================================================
-#[derive(Serialize, Deserialize, Clone, Copy, Default)]
+#[derive(Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
 pub struct MarginCondition {
     pub operator_condition: OperatorCondition,
     pub percent: f64,
@@ -385,26 +438,27 @@ impl MarginCondition {
 impl Condition for MarginCondition {
     //----------------------------------------------------------------------------------------------
     fn decode(&mut self, fields_iter: &mut Iter<String>) -> Result<(), IBKRApiLibError> {
-        self.operator_condition.decode(fields_iter)?;
-        self.percent = decode_f64(fields_iter).unwrap();
-        Ok(())
+        let value = self.operator_condition.decode(fields_iter)?;
+        self.set_value_from_string(value)
     }
 
     //----------------------------------------------------------------------------------------------
     fn make_fields(&self) -> Result<Vec<String>, IBKRApiLibError> {
-        let mut flds = self.operator_condition.make_fields()?;
-        flds.push(make_field(&self.percent)?);
-        Ok(flds)
+        self.operator_condition.make_fields(&self.value_to_string())
     }
 
+    //----------------------------------------------------------------------------------------------
     fn value_to_string(&self) -> String {
         self.percent.to_string()
     }
 
-    fn set_value_from_string(&mut self, text: String) {
-        self.percent = text.parse().unwrap();
+    //----------------------------------------------------------------------------------------------
+    fn set_value_from_string(&mut self, text: String) -> Result<(), IBKRApiLibError> {
+        self.percent = text.parse()?;
+        Ok(())
     }
 
+    //----------------------------------------------------------------------------------------------
     fn get_type(&self) -> ConditionType {
         self.operator_condition.order_condition.cond_type
     }
@@ -426,12 +480,23 @@ impl Debug for MarginCondition {
 
 impl From<OrderConditionEnum> for MarginCondition {
     //----------------------------------------------------------------------------------------------
-    fn from(_: OrderConditionEnum) -> Self {
-        MarginCondition::default()
+    /// The condition held by `condition`, or an empty one if it holds another type
+    fn from(condition: OrderConditionEnum) -> Self {
+        match condition {
+            OrderConditionEnum::Margin(condition) => condition,
+            _ => MarginCondition::new(false, 0.0),
+        }
     }
 }
 
-#[derive(Serialize, Deserialize, Clone, Default)]
+impl From<MarginCondition> for OrderConditionEnum {
+    //----------------------------------------------------------------------------------------------
+    fn from(condition: MarginCondition) -> Self {
+        OrderConditionEnum::Margin(condition)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq)]
 pub struct ContractCondition {
     pub operator_condition: OperatorCondition,
     pub con_id: i32,
@@ -447,24 +512,40 @@ impl ContractCondition {
             exchange: exchange.to_string(),
         }
     }
-}
 
-impl Condition for ContractCondition {
     //----------------------------------------------------------------------------------------------
-    fn decode(&mut self, fields_iter: &mut Iter<String>) -> Result<(), IBKRApiLibError> {
-        self.operator_condition.decode(fields_iter)?;
+    /// Decodes the operator, value and contract fields, returning the text of the value
+    pub fn decode_with_value(
+        &mut self,
+        fields_iter: &mut Iter<String>,
+    ) -> Result<String, IBKRApiLibError> {
+        let value = self.operator_condition.decode(fields_iter)?;
         self.con_id = decode_i32(fields_iter)?;
         self.exchange = decode_string(fields_iter)?;
-        Ok(())
+        Ok(value)
     }
 
     //----------------------------------------------------------------------------------------------
-    fn make_fields(&self) -> Result<Vec<String>, IBKRApiLibError> {
-        let mut flds = self.operator_condition.make_fields()?;
+    /// Fields of the operator, `value` and the contract
+    pub fn make_fields_with_value(&self, value: &str) -> Result<Vec<String>, IBKRApiLibError> {
+        let mut flds = self.operator_condition.make_fields(value)?;
         flds.push(make_field(&self.con_id)?);
         flds.push(make_field(&self.exchange)?);
         Ok(flds)
     }
+}
+
+impl Condition for ContractCondition {
+    //----------------------------------------------------------------------------------------------
+    fn decode(&mut self, fields_iter: &mut Iter<String>) -> Result<(), IBKRApiLibError> {
+        self.decode_with_value(fields_iter)?;
+        Ok(())
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn make_fields(&self) -> Result<Vec<String>, IBKRApiLibError> {
+        self.make_fields_with_value("")
+    }
 
     //----------------------------------------------------------------------------------------------
     fn value_to_string(&self) -> String {
@@ -472,8 +553,9 @@ impl Condition for ContractCondition {
     }
 
     //----------------------------------------------------------------------------------------------
-    fn set_value_from_string(&mut self, _text: String) {
-        unimplemented!()
+    /// The value belongs to the condition built on this one
+    fn set_value_from_string(&mut self, _text: String) -> Result<(), IBKRApiLibError> {
+        Ok(())
     }
 
     //----------------------------------------------------------------------------------------------
@@ -503,7 +585,7 @@ impl From<OrderConditionEnum> for ContractCondition {
     }
 }
 //==================================================================================================
-#[derive(Serialize, Deserialize, Clone, Default)]
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq)]
 pub struct TimeCondition {
     pub operator_condition: OperatorCondition,
     pub time: String,
@@ -517,21 +599,30 @@ impl TimeCondition {
             time: time,
         }
     }
+
+    //----------------------------------------------------------------------------------------------
+    /// Condition on the time being after (`is_more`) or before `time`
+    pub fn at<Tz: TimeZone>(is_more: bool, time: &DateTime<Tz>) -> Self {
+        TimeCondition::new(is_more, format_tws_datetime(time))
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// `time` parsed, see `parse_tws_datetime` for the formats and `default_tz`
+    pub fn timestamp(&self, default_tz: FixedOffset) -> Option<DateTime<FixedOffset>> {
+        parse_tws_datetime(&self.time, default_tz)
+    }
 }
 
 impl Condition for TimeCondition {
     //----------------------------------------------------------------------------------------------
     fn decode(&mut self, fields_iter: &mut Iter<String>) -> Result<(), IBKRApiLibError> {
-        self.operator_condition.decode(fields_iter)?;
-        self.time = decode_string(fields_iter).unwrap();
-        Ok(())
+        let value = self.operator_condition.decode(fields_iter)?;
+        self.set_value_from_string(value)
     }
 
     //----------------------------------------------------------------------------------------------
     fn make_fields(&self) -> Result<Vec<String>, IBKRApiLibError> {
-        let mut flds = self.operator_condition.make_fields()?;
-        flds.push(make_field(&self.time)?);
-        Ok(flds)
+        self.operator_condition.make_fields(&self.value_to_string())
     }
 
     //----------------------------------------------------------------------------------------------
@@ -540,8 +631,9 @@ impl Condition for TimeCondition {
     }
 
     //----------------------------------------------------------------------------------------------
-    fn set_value_from_string(&mut self, text: String) {
-        self.time = text.to_string()
+    fn set_value_from_string(&mut self, text: String) -> Result<(), IBKRApiLibError> {
+        self.time = text;
+        Ok(())
     }
 
     //----------------------------------------------------------------------------------------------
@@ -568,13 +660,24 @@ impl Debug for TimeCondition {
 //==================================================================================================
 impl From<OrderConditionEnum> for TimeCondition {
     //----------------------------------------------------------------------------------------------
-    fn from(_: OrderConditionEnum) -> Self {
-        TimeCondition::default()
+    /// The condition held by `condition`, or an empty one if it holds another type
+    fn from(condition: OrderConditionEnum) -> Self {
+        match condition {
+            OrderConditionEnum::Time(condition) => condition,
+            _ => TimeCondition::new(false, String::new()),
+        }
+    }
+}
+
+impl From<TimeCondition> for OrderConditionEnum {
+    //----------------------------------------------------------------------------------------------
+    fn from(condition: TimeCondition) -> Self {
+        OrderConditionEnum::Time(condition)
     }
 }
 
 //==================================================================================================
-#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 pub struct PriceCondition {
     pub contract_condition: ContractCondition,
     pub price: f64,
@@ -606,9 +709,9 @@ impl PriceCondition {
 impl Condition for PriceCondition {
     //----------------------------------------------------------------------------------------------
     fn decode(&mut self, fields_iter: &mut Iter<String>) -> Result<(), IBKRApiLibError> {
-        self.price = decode_f64(fields_iter)?;
-        self.contract_condition.decode(fields_iter)?;
-        self.trigger_method = FromPrimitive::from_i32(decode_i32(fields_iter)?).unwrap();
+        let value = self.contract_condition.decode_with_value(fields_iter)?;
+        self.set_value_from_string(value)?;
+        self.trigger_method = FromPrimitive::from_i32(decode_i32(fields_iter)?).unwrap_or_default();
         Ok(())
     }
 
@@ -616,17 +719,8 @@ impl Condition for PriceCondition {
     fn make_fields(&self) -> Result<Vec<String>, IBKRApiLibError> {
         let mut flds = self
             .contract_condition
-            .operator_condition
-            .order_condition
-            .make_fields()?;
-        flds.push(make_field(
-            &(self.contract_condition.operator_condition.is_more as i32),
-        )?);
-        flds.push(make_field(&(self.price))?);
-        flds.push(make_field(&(self.contract_condition.con_id))?);
-        flds.push(make_field(&(self.contract_condition.exchange))?);
+            .make_fields_with_value(&self.value_to_string())?;
         flds.push(make_field(&(self.trigger_method as i32))?);
-
         Ok(flds)
     }
 
@@ -636,8 +730,9 @@ impl Condition for PriceCondition {
     }
 
     //----------------------------------------------------------------------------------------------
-    fn set_value_from_string(&mut self, text: String) {
-        self.price = text.parse().unwrap();
+    fn set_value_from_string(&mut self, text: String) -> Result<(), IBKRApiLibError> {
+        self.price = text.parse()?;
+        Ok(())
     }
 
     //----------------------------------------------------------------------------------------------
@@ -663,13 +758,24 @@ impl Display for PriceCondition {
 
 impl From<OrderConditionEnum> for PriceCondition {
     //----------------------------------------------------------------------------------------------
-    fn from(_: OrderConditionEnum) -> Self {
-        PriceCondition::default()
+    /// The condition held by `condition`, or an empty one if it holds another type
+    fn from(condition: OrderConditionEnum) -> Self {
+        match condition {
+            OrderConditionEnum::Price(condition) => condition,
+            _ => PriceCondition::new(TriggerMethod::Default, 0, "", false, 0.0),
+        }
+    }
+}
+
+impl From<PriceCondition> for OrderConditionEnum {
+    //----------------------------------------------------------------------------------------------
+    fn from(condition: PriceCondition) -> Self {
+        OrderConditionEnum::Price(condition)
     }
 }
 
 //==================================================================================================
-#[derive(Serialize, Deserialize, Clone, Default)]
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq)]
 pub struct PercentChangeCondition {
     pub contract_condition: ContractCondition,
     pub change_percent: f64,
@@ -693,26 +799,14 @@ impl PercentChangeCondition {
 impl Condition for PercentChangeCondition {
     //----------------------------------------------------------------------------------------------
     fn decode(&mut self, fields_iter: &mut Iter<String>) -> Result<(), IBKRApiLibError> {
-        self.change_percent = decode_f64(fields_iter)?;
-        self.contract_condition.decode(fields_iter)?;
-        Ok(())
+        let value = self.contract_condition.decode_with_value(fields_iter)?;
+        self.set_value_from_string(value)
     }
 
     //----------------------------------------------------------------------------------------------
     fn make_fields(&self) -> Result<Vec<String>, IBKRApiLibError> {
-        let mut flds = self
-            .contract_condition
-            .operator_condition
-            .order_condition
-            .make_fields()?;
-        flds.push(make_field(
-            &(self.contract_condition.operator_condition.is_more as i32),
-        )?);
-        flds.push(make_field(&(self.change_percent))?);
-        flds.push(make_field(&(self.contract_condition.con_id))?);
-        flds.push(make_field(&(self.contract_condition.exchange))?);
-
-        Ok(flds)
+        self.contract_condition
+            .make_fields_with_value(&self.value_to_string())
     }
 
     //----------------------------------------------------------------------------------------------
@@ -721,8 +815,9 @@ impl Condition for PercentChangeCondition {
     }
 
     //----------------------------------------------------------------------------------------------
-    fn set_value_from_string(&mut self, text: String) {
-        self.change_percent = text.parse().unwrap();
+    fn set_value_from_string(&mut self, text: String) -> Result<(), IBKRApiLibError> {
+        self.change_percent = text.parse()?;
+        Ok(())
     }
 
     //----------------------------------------------------------------------------------------------
@@ -749,13 +844,24 @@ impl Debug for PercentChangeCondition {
 
 impl From<OrderConditionEnum> for PercentChangeCondition {
     //----------------------------------------------------------------------------------------------
-    fn from(_: OrderConditionEnum) -> Self {
-        PercentChangeCondition::default()
+    /// The condition held by `condition`, or an empty one if it holds another type
+    fn from(condition: OrderConditionEnum) -> Self {
+        match condition {
+            OrderConditionEnum::PercentChange(condition) => condition,
+            _ => PercentChangeCondition::new(0, String::new(), false, 0.0),
+        }
+    }
+}
+
+impl From<PercentChangeCondition> for OrderConditionEnum {
+    //----------------------------------------------------------------------------------------------
+    fn from(condition: PercentChangeCondition) -> Self {
+        OrderConditionEnum::PercentChange(condition)
     }
 }
 
 //==================================================================================================
-#[derive(Serialize, Deserialize, Clone, Default)]
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq)]
 pub struct VolumeCondition {
     pub contract_condition: ContractCondition,
     pub volume: i32,
@@ -779,26 +885,14 @@ impl VolumeCondition {
 impl Condition for VolumeCondition {
     //----------------------------------------------------------------------------------------------
     fn decode(&mut self, fields_iter: &mut Iter<String>) -> Result<(), IBKRApiLibError> {
-        self.contract_condition.decode(fields_iter)?;
-        self.volume = decode_i32(fields_iter)?;
-
-        Ok(())
+        let value = self.contract_condition.decode_with_value(fields_iter)?;
+        self.set_value_from_string(value)
     }
 
     //----------------------------------------------------------------------------------------------
     fn make_fields(&self) -> Result<Vec<String>, IBKRApiLibError> {
-        let mut flds = self
-            .contract_condition
-            .operator_condition
-            .order_condition
-            .make_fields()?;
-        flds.push(make_field(
-            &(self.contract_condition.operator_condition.is_more as i32),
-        )?);
-        flds.push(make_field(&(self.volume))?);
-        flds.push(make_field(&(self.contract_condition.con_id))?);
-        flds.push(make_field(&(self.contract_condition.exchange))?);
-        Ok(flds)
+        self.contract_condition
+            .make_fields_with_value(&self.value_to_string())
     }
 
     //----------------------------------------------------------------------------------------------
@@ -807,8 +901,9 @@ impl Condition for VolumeCondition {
     }
 
     //----------------------------------------------------------------------------------------------
-    fn set_value_from_string(&mut self, text: String) {
-        self.volume = text.parse().unwrap();
+    fn set_value_from_string(&mut self, text: String) -> Result<(), IBKRApiLibError> {
+        self.volume = text.parse()?;
+        Ok(())
     }
 
     //----------------------------------------------------------------------------------------------
@@ -836,21 +931,181 @@ impl Debug for VolumeCondition {
 
 impl From<OrderConditionEnum> for VolumeCondition {
     //----------------------------------------------------------------------------------------------
-    fn from(_: OrderConditionEnum) -> Self {
-        VolumeCondition::default()
+    /// The condition held by `condition`, or an empty one if it holds another type
+    fn from(condition: OrderConditionEnum) -> Self {
+        match condition {
+            OrderConditionEnum::Volume(condition) => condition,
+            _ => VolumeCondition::new(0, "", false, 0),
+        }
+    }
+}
+
+impl From<VolumeCondition> for OrderConditionEnum {
+    //----------------------------------------------------------------------------------------------
+    fn from(condition: VolumeCondition) -> Self {
+        OrderConditionEnum::Volume(condition)
     }
 }
 
 //----------------------------------------------------------------------------------------------
 pub fn create_condition<'a>(cond_type: ConditionType) -> OrderConditionEnum {
     match cond_type {
-        ConditionType::Execution => OrderConditionEnum::Execution(ExecutionCondition::default()),
-        ConditionType::Margin => OrderConditionEnum::Margin(MarginCondition::default()),
+        ConditionType::Execution => {
+            let mut condition = ExecutionCondition::default();
+            condition.order_condition.cond_type = cond_type;
+            OrderConditionEnum::Execution(condition)
+        }
+        ConditionType::Margin => {
+            let mut condition = MarginCondition::default();
+            condition.operator_condition.order_condition.cond_type = cond_type;
+            OrderConditionEnum::Margin(condition)
+        }
         ConditionType::PercentChange => {
-            OrderConditionEnum::PercentChange(PercentChangeCondition::default())
+            let mut condition = PercentChangeCondition::default();
+            condition
+                .contract_condition
+                .operator_condition
+                .order_condition
+                .cond_type = cond_type;
+            OrderConditionEnum::PercentChange(condition)
+        }
+        ConditionType::Price => {
+            let mut condition = PriceCondition::default();
+            condition
+                .contract_condition
+                .operator_condition
+                .order_condition
+                .cond_type = cond_type;
+            OrderConditionEnum::Price(condition)
+        }
+        ConditionType::Time => {
+            let mut condition = TimeCondition::default();
+            condition.operator_condition.order_condition.cond_type = cond_type;
+            OrderConditionEnum::Time(condition)
+        }
+        ConditionType::Volume => {
+            let mut condition = VolumeCondition::default();
+            condition
+                .contract_condition
+                .operator_condition
+                .order_condition
+                .cond_type = cond_type;
+            OrderConditionEnum::Volume(condition)
+        }
+    }
+}
+
+//==================================================================================================
+/// Fluent builder for the conditions of an order. `and` and `or` connect the last added
+/// condition to the next one.
+///
+/// ```
+/// use ibtwsapi::core::order_condition::{Condition, ConditionBuilder, TriggerMethod};
+///
+/// let conditions = ConditionBuilder::new()
+///     .price(TriggerMethod::Default, 8314, "SMART", true, 150.0)
+///     .and()
+///     .volume(8314, "SMART", true, 1_000_000)
+///     .build();
+/// assert_eq!(2, conditions.len());
+/// assert!(conditions[0].is_conjunction());
+/// assert_eq!("1000000", conditions[1].value_to_string());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ConditionBuilder {
+    conditions: Vec<OrderConditionEnum>,
+}
+
+impl ConditionBuilder {
+    pub fn new() -> Self {
+        ConditionBuilder { conditions: vec![] }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Adds any condition
+    pub fn condition<T: Into<OrderConditionEnum>>(mut self, condition: T) -> Self {
+        self.conditions.push(condition.into());
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn price(
+        self,
+        trigger_method: TriggerMethod,
+        con_id: i32,
+        exchange: &str,
+        is_more: bool,
+        price: f64,
+    ) -> Self {
+        self.condition(PriceCondition::new(
+            trigger_method,
+            con_id,
+            exchange,
+            is_more,
+            price,
+        ))
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn time<Tz: TimeZone>(self, is_more: bool, time: &DateTime<Tz>) -> Self {
+        self.condition(TimeCondition::at(is_more, time))
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn margin(self, is_more: bool, percent: f64) -> Self {
+        self.condition(MarginCondition::new(is_more, percent))
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn execution(self, sec_type: &str, exchange: &str, symbol: &str) -> Self {
+        self.condition(ExecutionCondition::new(
+            sec_type.to_string(),
+            exchange.to_string(),
+            symbol.to_string(),
+        ))
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn volume(self, con_id: i32, exchange: &str, is_more: bool, volume: i32) -> Self {
+        self.condition(VolumeCondition::new(con_id, exchange, is_more, volume))
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn percent_change(
+        self,
+        con_id: i32,
+        exchange: &str,
+        is_more: bool,
+        change_percent: f64,
+    ) -> Self {
+        self.condition(PercentChangeCondition::new(
+            con_id,
+            exchange.to_string(),
+            is_more,
+            change_percent,
+        ))
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Both the last condition and the next one must be met
+    pub fn and(mut self) -> Self {
+        if let Some(last) = self.conditions.last_mut() {
+            last.order_condition_mut().and();
         }
-        ConditionType::Price => OrderConditionEnum::Price(PriceCondition::default()),
-        ConditionType::Time => OrderConditionEnum::Time(TimeCondition::default()),
-        ConditionType::Volume => OrderConditionEnum::Volume(VolumeCondition::default()),
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Either the last condition or the next one must be met
+    pub fn or(mut self) -> Self {
+        if let Some(last) = self.conditions.last_mut() {
+            last.order_condition_mut().or();
+        }
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn build(self) -> Vec<OrderConditionEnum> {
+        self.conditions
     }
 }
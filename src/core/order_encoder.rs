@@ -0,0 +1,773 @@
+//! Helper types and functions related to encoding order type messages
+use crate::core::common::UNSET_DOUBLE;
+use crate::core::contract::Contract;
+use crate::core::encoder::{
+    encode_bool, encode_condition, encode_f64, encode_f64_show_unset, encode_i32,
+    encode_i32_show_unset, encode_string,
+};
+use crate::core::errors::IBKRApiLibError;
+use crate::core::order::{Order, OrderState};
+use crate::core::order_condition::Condition;
+use crate::core::server_versions::{
+    MIN_SERVER_VER_AUTO_PRICE_FOR_HEDGE, MIN_SERVER_VER_CASH_QTY, MIN_SERVER_VER_D_PEG_ORDERS,
+    MIN_SERVER_VER_FRACTIONAL_POSITIONS, MIN_SERVER_VER_MODELS_SUPPORT,
+    MIN_SERVER_VER_ORDER_CONTAINER, MIN_SERVER_VER_PEGGED_TO_BENCHMARK,
+    MIN_SERVER_VER_PRICE_MGMT_ALGO, MIN_SERVER_VER_SOFT_DOLLAR_TIER, MIN_SERVER_VER_SSHORTX_OLD,
+    MIN_SERVER_VER_WHAT_IF_EXT_FIELDS,
+};
+
+//==================================================================================================
+/// Inverse of `OrderDecoder`: writes the fields of an open or completed order message in the
+/// order, and with the version gating, that `OrderDecoder` reads them.
+pub struct OrderEncoder<'a> {
+    contract: &'a Contract,
+    order: &'a Order,
+    order_state: &'a OrderState,
+    version: i32,
+    server_version: i32,
+}
+
+impl<'a> OrderEncoder<'a> {
+    pub fn new(
+        contract: &'a Contract,
+        order: &'a Order,
+        order_state: &'a OrderState,
+        version: i32,
+        server_version: i32,
+    ) -> Self {
+        OrderEncoder {
+            contract,
+            order,
+            order_state,
+            version,
+            server_version,
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub(crate) fn encode_completed(&self, fields: &mut Vec<String>) -> Result<(), IBKRApiLibError> {
+        // write contract fields
+        self.encode_contract_fields(fields);
+
+        // write order fields
+        self.encode_action(fields);
+        self.encode_total_quantity(fields);
+        self.encode_order_type(fields);
+        self.encode_lmt_price(fields);
+        self.encode_aux_price(fields);
+        self.encode_tif(fields);
+        self.encode_oca_group(fields);
+        self.encode_account(fields);
+        self.encode_open_close(fields);
+        self.encode_origin(fields);
+        self.encode_order_ref(fields);
+        self.encode_perm_id(fields);
+        self.encode_outside_rth(fields);
+        self.encode_hidden(fields);
+        self.encode_discretionary_amt(fields);
+        self.encode_good_after_time(fields);
+        self.encode_faparams(fields);
+        self.encode_model_code(fields);
+        self.encode_good_till_date(fields);
+        self.encode_rule80a(fields);
+        self.encode_percent_offset(fields);
+        self.encode_settling_firm(fields);
+        self.encode_short_sale_params(fields);
+        self.encode_box_order_params(fields);
+        self.encode_peg_to_stk_or_vol_order_params(fields);
+        self.encode_display_size(fields);
+        self.encode_sweep_to_fill(fields);
+        self.encode_all_or_none(fields);
+        self.encode_min_qty(fields);
+        self.encode_oca_type(fields);
+        self.encode_trigger_method(fields);
+        self.encode_vol_order_params(fields, false);
+        self.encode_trail_params(fields);
+        self.encode_combo_legs(fields);
+        self.encode_smart_combo_routing_params(fields);
+        self.encode_scale_order_params(fields);
+        self.encode_hedge_params(fields);
+        self.encode_clearing_params(fields);
+        self.encode_not_held(fields);
+        self.encode_delta_neutral(fields);
+        self.encode_algo_params(fields);
+        self.encode_solicited(fields);
+        self.encode_order_status(fields);
+        self.encode_vol_randomize_flags(fields);
+        self.encode_peg_to_bench_params(fields);
+        self.encode_conditions(fields)?;
+        self.encode_stop_price_and_lmt_price_offset(fields);
+        self.encode_cash_qty(fields);
+        self.encode_dont_use_auto_price_for_hedge(fields);
+        self.encode_is_oms_containers(fields);
+        self.encode_auto_cancel_date(fields);
+        self.encode_filled_quantity(fields);
+        self.encode_ref_futures_con_id(fields);
+        self.encode_auto_cancel_parent(fields);
+        self.encode_shareholder(fields);
+        self.encode_imbalance_only(fields);
+        self.encode_route_marketable_to_bbo(fields);
+        self.encode_parent_perm_id(fields);
+        self.encode_completed_time(fields);
+        self.encode_completed_status(fields);
+
+        Ok(())
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn encode_open(&self, fields: &mut Vec<String>) -> Result<(), IBKRApiLibError> {
+        self.encode_order_id(fields);
+
+        // write contract fields
+        self.encode_contract_fields(fields);
+
+        // write order fields
+        self.encode_action(fields);
+        self.encode_total_quantity(fields);
+        self.encode_order_type(fields);
+        self.encode_lmt_price(fields);
+        self.encode_aux_price(fields);
+        self.encode_tif(fields);
+        self.encode_oca_group(fields);
+        self.encode_account(fields);
+        self.encode_open_close(fields);
+        self.encode_origin(fields);
+        self.encode_order_ref(fields);
+        self.encode_client_id(fields);
+        self.encode_perm_id(fields);
+        self.encode_outside_rth(fields);
+        self.encode_hidden(fields);
+        self.encode_discretionary_amt(fields);
+        self.encode_good_after_time(fields);
+        self.skip_shares_allocation(fields);
+        self.encode_faparams(fields);
+        self.encode_model_code(fields);
+        self.encode_good_till_date(fields);
+        self.encode_rule80a(fields);
+        self.encode_percent_offset(fields);
+        self.encode_settling_firm(fields);
+        self.encode_short_sale_params(fields);
+        self.encode_auction_strategy(fields);
+        self.encode_box_order_params(fields);
+        self.encode_peg_to_stk_or_vol_order_params(fields);
+        self.encode_display_size(fields);
+        self.encode_block_order(fields);
+        self.encode_sweep_to_fill(fields);
+        self.encode_all_or_none(fields);
+        self.encode_min_qty(fields);
+        self.encode_oca_type(fields);
+        self.encode_etrade_only(fields);
+        self.encode_firm_quote_only(fields);
+        self.encode_nbbo_price_cap(fields);
+        self.encode_parent_id(fields);
+        self.encode_trigger_method(fields);
+        self.encode_vol_order_params(fields, true);
+        self.encode_trail_params(fields);
+        self.encode_basis_points(fields);
+        self.encode_combo_legs(fields);
+        self.encode_smart_combo_routing_params(fields);
+        self.encode_scale_order_params(fields);
+        self.encode_hedge_params(fields);
+        self.encode_opt_out_smart_routing(fields);
+        self.encode_clearing_params(fields);
+        self.encode_not_held(fields);
+        self.encode_delta_neutral(fields);
+        self.encode_algo_params(fields);
+        self.encode_solicited(fields);
+        self.encode_what_if_info_and_commission(fields);
+        self.encode_vol_randomize_flags(fields);
+        self.encode_peg_to_bench_params(fields);
+        self.encode_conditions(fields)?;
+        self.encode_adjusted_order_params(fields);
+        self.encode_soft_dollar_tier(fields);
+        self.encode_cash_qty(fields);
+        self.encode_dont_use_auto_price_for_hedge(fields);
+        self.encode_is_oms_containers(fields);
+        self.encode_discretionary_up_to_limit_price(fields);
+        self.encode_use_price_mgmt_algo(fields);
+
+        Ok(())
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_order_id(&self, fields: &mut Vec<String>) {
+        encode_i32(fields, self.order.order_id);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_contract_fields(&self, fields: &mut Vec<String>) {
+        encode_i32(fields, self.contract.con_id);
+        encode_string(fields, &self.contract.symbol);
+        encode_string(fields, &self.contract.sec_type);
+        encode_string(fields, &self.contract.last_trade_date_or_contract_month);
+        encode_f64(fields, self.contract.strike);
+        encode_string(fields, &self.contract.right);
+        if self.version >= 32 {
+            encode_string(fields, &self.contract.multiplier);
+        }
+        encode_string(fields, &self.contract.exchange);
+        encode_string(fields, &self.contract.currency);
+        encode_string(fields, &self.contract.local_symbol);
+        if self.version >= 32 {
+            encode_string(fields, &self.contract.trading_class);
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_action(&self, fields: &mut Vec<String>) {
+        encode_string(fields, &self.order.action);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_total_quantity(&self, fields: &mut Vec<String>) {
+        if self.server_version >= MIN_SERVER_VER_FRACTIONAL_POSITIONS {
+            encode_f64(fields, self.order.total_quantity);
+        } else {
+            encode_i32(fields, self.order.total_quantity as i32);
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_order_type(&self, fields: &mut Vec<String>) {
+        encode_string(fields, &self.order.order_type);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_lmt_price(&self, fields: &mut Vec<String>) {
+        if self.version < 29 {
+            encode_f64(fields, self.order.lmt_price);
+        } else {
+            encode_f64_show_unset(fields, self.order.lmt_price);
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_aux_price(&self, fields: &mut Vec<String>) {
+        if self.version < 30 {
+            encode_f64(fields, self.order.aux_price);
+        } else {
+            encode_f64_show_unset(fields, self.order.aux_price);
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_tif(&self, fields: &mut Vec<String>) {
+        encode_string(fields, &self.order.tif);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_oca_group(&self, fields: &mut Vec<String>) {
+        encode_string(fields, &self.order.oca_group);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_account(&self, fields: &mut Vec<String>) {
+        encode_string(fields, &self.order.account);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_open_close(&self, fields: &mut Vec<String>) {
+        encode_string(fields, &self.order.open_close);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_origin(&self, fields: &mut Vec<String>) {
+        encode_i32(fields, self.order.origin as i32);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_order_ref(&self, fields: &mut Vec<String>) {
+        encode_string(fields, &self.order.order_ref);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_client_id(&self, fields: &mut Vec<String>) {
+        encode_i32(fields, self.order.client_id);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_perm_id(&self, fields: &mut Vec<String>) {
+        encode_i32(fields, self.order.perm_id);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_outside_rth(&self, fields: &mut Vec<String>) {
+        encode_bool(fields, self.order.outside_rth);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_hidden(&self, fields: &mut Vec<String>) {
+        encode_bool(fields, self.order.hidden);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_discretionary_amt(&self, fields: &mut Vec<String>) {
+        encode_f64(fields, self.order.discretionary_amt);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_good_after_time(&self, fields: &mut Vec<String>) {
+        encode_string(fields, &self.order.good_after_time);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn skip_shares_allocation(&self, fields: &mut Vec<String>) {
+        encode_string(fields, ""); // deprecated
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_faparams(&self, fields: &mut Vec<String>) {
+        encode_string(fields, &self.order.fa_group);
+        encode_string(fields, &self.order.fa_method);
+        encode_string(fields, &self.order.fa_percentage);
+        encode_string(fields, &self.order.fa_profile);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_model_code(&self, fields: &mut Vec<String>) {
+        if self.server_version >= MIN_SERVER_VER_MODELS_SUPPORT {
+            encode_string(fields, &self.order.model_code);
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_good_till_date(&self, fields: &mut Vec<String>) {
+        encode_string(fields, &self.order.good_till_date);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_rule80a(&self, fields: &mut Vec<String>) {
+        encode_string(fields, &self.order.rule80a);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_percent_offset(&self, fields: &mut Vec<String>) {
+        encode_f64_show_unset(fields, self.order.percent_offset);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_settling_firm(&self, fields: &mut Vec<String>) {
+        encode_string(fields, &self.order.settling_firm);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_short_sale_params(&self, fields: &mut Vec<String>) {
+        encode_i32(fields, self.order.short_sale_slot);
+        encode_string(fields, &self.order.designated_location);
+        if self.server_version == MIN_SERVER_VER_SSHORTX_OLD || self.version >= 23 {
+            encode_i32(fields, self.order.exempt_code);
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_auction_strategy(&self, fields: &mut Vec<String>) {
+        encode_i32(fields, self.order.auction_strategy as i32);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_box_order_params(&self, fields: &mut Vec<String>) {
+        encode_f64_show_unset(fields, self.order.starting_price);
+        encode_f64_show_unset(fields, self.order.stock_ref_price);
+        encode_f64_show_unset(fields, self.order.delta);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_peg_to_stk_or_vol_order_params(&self, fields: &mut Vec<String>) {
+        encode_f64_show_unset(fields, self.order.stock_range_lower);
+        encode_f64_show_unset(fields, self.order.stock_range_upper);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_display_size(&self, fields: &mut Vec<String>) {
+        encode_i32(fields, self.order.display_size);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_block_order(&self, fields: &mut Vec<String>) {
+        encode_bool(fields, self.order.block_order);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_sweep_to_fill(&self, fields: &mut Vec<String>) {
+        encode_bool(fields, self.order.sweep_to_fill);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_all_or_none(&self, fields: &mut Vec<String>) {
+        encode_bool(fields, self.order.all_or_none);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_min_qty(&self, fields: &mut Vec<String>) {
+        encode_i32_show_unset(fields, self.order.min_qty);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_oca_type(&self, fields: &mut Vec<String>) {
+        encode_i32(fields, self.order.oca_type);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_etrade_only(&self, fields: &mut Vec<String>) {
+        encode_bool(fields, self.order.e_trade_only);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_firm_quote_only(&self, fields: &mut Vec<String>) {
+        encode_bool(fields, self.order.firm_quote_only);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_nbbo_price_cap(&self, fields: &mut Vec<String>) {
+        encode_f64_show_unset(fields, self.order.nbbo_price_cap);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_parent_id(&self, fields: &mut Vec<String>) {
+        encode_i32(fields, self.order.parent_id);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_trigger_method(&self, fields: &mut Vec<String>) {
+        encode_i32(fields, self.order.trigger_method);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_vol_order_params(&self, fields: &mut Vec<String>, write_open_order_attribs: bool) {
+        encode_f64_show_unset(fields, self.order.volatility);
+        encode_i32(fields, self.order.volatility_type);
+        encode_string(fields, &self.order.delta_neutral_order_type);
+        encode_f64_show_unset(fields, self.order.delta_neutral_aux_price);
+        if self.version >= 27 && !self.order.delta_neutral_order_type.is_empty() {
+            encode_i32(fields, self.order.delta_neutral_con_id);
+            if write_open_order_attribs {
+                encode_string(fields, &self.order.delta_neutral_settling_firm);
+                encode_string(fields, &self.order.delta_neutral_clearing_account);
+                encode_string(fields, &self.order.delta_neutral_clearing_intent);
+            }
+        }
+        if self.version >= 31 && !self.order.delta_neutral_order_type.is_empty() {
+            if write_open_order_attribs {
+                encode_string(fields, &self.order.delta_neutral_open_close);
+            }
+            encode_bool(fields, self.order.delta_neutral_short_sale);
+            encode_i32(fields, self.order.delta_neutral_short_sale_slot);
+            encode_string(fields, &self.order.delta_neutral_designated_location);
+        }
+        encode_bool(fields, self.order.continuous_update);
+        encode_i32(fields, self.order.reference_price_type);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_trail_params(&self, fields: &mut Vec<String>) {
+        encode_f64_show_unset(fields, self.order.trail_stop_price);
+        if self.version >= 30 {
+            encode_f64_show_unset(fields, self.order.trailing_percent);
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_basis_points(&self, fields: &mut Vec<String>) {
+        encode_f64_show_unset(fields, self.order.basis_points);
+        encode_i32_show_unset(fields, self.order.basis_points_type);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_combo_legs(&self, fields: &mut Vec<String>) {
+        encode_string(fields, &self.contract.combo_legs_descrip);
+        if self.version >= 29 {
+            encode_i32(fields, self.contract.combo_legs.len() as i32);
+            for combo_leg in &self.contract.combo_legs {
+                encode_i32(fields, combo_leg.con_id);
+                encode_f64(fields, combo_leg.ratio);
+                encode_string(fields, &combo_leg.action);
+                encode_string(fields, &combo_leg.exchange);
+                encode_i32(fields, combo_leg.open_close as i32);
+                encode_i32(fields, combo_leg.short_sale_slot);
+                encode_string(fields, &combo_leg.designated_location);
+                encode_i32(fields, combo_leg.exempt_code);
+            }
+        }
+        encode_i32(fields, self.order.order_combo_legs.len() as i32);
+        for order_combo_leg in &self.order.order_combo_legs {
+            encode_f64_show_unset(fields, order_combo_leg.price);
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_smart_combo_routing_params(&self, fields: &mut Vec<String>) {
+        if self.version >= 26 {
+            encode_i32(fields, self.order.smart_combo_routing_params.len() as i32);
+            for tag_value in &self.order.smart_combo_routing_params {
+                encode_string(fields, &tag_value.tag);
+                encode_string(fields, &tag_value.value);
+            }
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_scale_order_params(&self, fields: &mut Vec<String>) {
+        if self.version >= 20 {
+            encode_i32_show_unset(fields, self.order.scale_init_level_size);
+            encode_i32_show_unset(fields, self.order.scale_subs_level_size);
+        } else {
+            encode_i32_show_unset(fields, self.order.scale_init_level_size);
+        }
+        encode_f64_show_unset(fields, self.order.scale_price_increment);
+        if self.version >= 28
+            && self.order.scale_price_increment != UNSET_DOUBLE
+            && self.order.scale_price_increment > 0.0
+        {
+            encode_f64_show_unset(fields, self.order.scale_price_adjust_value);
+            encode_i32_show_unset(fields, self.order.scale_price_adjust_interval);
+            encode_f64_show_unset(fields, self.order.scale_profit_offset);
+            encode_bool(fields, self.order.scale_auto_reset);
+            encode_i32_show_unset(fields, self.order.scale_init_position);
+            encode_i32_show_unset(fields, self.order.scale_init_fill_qty);
+            encode_bool(fields, self.order.scale_random_percent);
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_hedge_params(&self, fields: &mut Vec<String>) {
+        if self.version >= 24 {
+            encode_string(fields, &self.order.hedge_type);
+        }
+        if !self.order.hedge_type.is_empty() {
+            encode_string(fields, &self.order.hedge_param);
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_opt_out_smart_routing(&self, fields: &mut Vec<String>) {
+        if self.version >= 25 {
+            encode_bool(fields, self.order.opt_out_smart_routing);
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_clearing_params(&self, fields: &mut Vec<String>) {
+        encode_string(fields, &self.order.clearing_account);
+        encode_string(fields, &self.order.clearing_intent);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_not_held(&self, fields: &mut Vec<String>) {
+        if self.version >= 22 {
+            encode_bool(fields, self.order.not_held);
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_delta_neutral(&self, fields: &mut Vec<String>) {
+        if self.version >= 20 {
+            match &self.contract.delta_neutral_contract {
+                Some(delta_neutral_contract) => {
+                    encode_bool(fields, true);
+                    encode_i32(fields, delta_neutral_contract.con_id);
+                    encode_f64(fields, delta_neutral_contract.delta);
+                    encode_f64(fields, delta_neutral_contract.price);
+                }
+                None => encode_bool(fields, false),
+            }
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_algo_params(&self, fields: &mut Vec<String>) {
+        if self.version >= 21 {
+            encode_string(fields, &self.order.algo_strategy);
+            if !self.order.algo_strategy.is_empty() {
+                encode_i32(fields, self.order.algo_params.len() as i32);
+                for tag_value in &self.order.algo_params {
+                    encode_string(fields, &tag_value.tag);
+                    encode_string(fields, &tag_value.value);
+                }
+            }
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_solicited(&self, fields: &mut Vec<String>) {
+        if self.version >= 33 {
+            encode_bool(fields, self.order.solicited);
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_order_status(&self, fields: &mut Vec<String>) {
+        encode_string(fields, &self.order_state.status);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_what_if_info_and_commission(&self, fields: &mut Vec<String>) {
+        encode_bool(fields, self.order.what_if);
+        self.encode_order_status(fields);
+        if self.server_version >= MIN_SERVER_VER_WHAT_IF_EXT_FIELDS {
+            encode_string(fields, &self.order_state.init_margin_before);
+            encode_string(fields, &self.order_state.maint_margin_before);
+            encode_string(fields, &self.order_state.equity_with_loan_before);
+            encode_string(fields, &self.order_state.init_margin_change);
+            encode_string(fields, &self.order_state.maint_margin_change);
+            encode_string(fields, &self.order_state.equity_with_loan_change);
+        }
+        encode_string(fields, &self.order_state.init_margin_after);
+        encode_string(fields, &self.order_state.maint_margin_after);
+        encode_string(fields, &self.order_state.equity_with_loan_after);
+        encode_f64_show_unset(fields, self.order_state.commission);
+        encode_f64_show_unset(fields, self.order_state.min_commission);
+        encode_f64_show_unset(fields, self.order_state.max_commission);
+        encode_string(fields, &self.order_state.commission_currency);
+        encode_string(fields, &self.order_state.warning_text);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_vol_randomize_flags(&self, fields: &mut Vec<String>) {
+        if self.version >= 34 {
+            encode_bool(fields, self.order.randomize_size);
+            encode_bool(fields, self.order.randomize_price);
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_peg_to_bench_params(&self, fields: &mut Vec<String>) {
+        if self.server_version >= MIN_SERVER_VER_PEGGED_TO_BENCHMARK
+            && self.order.order_type == "PEG BENCH"
+        {
+            encode_i32(fields, self.order.reference_contract_id);
+            encode_bool(fields, self.order.is_pegged_change_amount_decrease);
+            encode_f64(fields, self.order.pegged_change_amount);
+            encode_f64(fields, self.order.reference_change_amount);
+            encode_string(fields, &self.order.reference_exchange_id);
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_conditions(&self, fields: &mut Vec<String>) -> Result<(), IBKRApiLibError> {
+        if self.server_version >= MIN_SERVER_VER_PEGGED_TO_BENCHMARK {
+            encode_i32(fields, self.order.conditions.len() as i32);
+            if !self.order.conditions.is_empty() {
+                for condition in &self.order.conditions {
+                    encode_i32(fields, condition.get_type() as i32);
+                    encode_condition(fields, condition)?;
+                }
+                encode_bool(fields, self.order.conditions_ignore_rth);
+                encode_bool(fields, self.order.conditions_cancel_order);
+            }
+        }
+        Ok(())
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_adjusted_order_params(&self, fields: &mut Vec<String>) {
+        if self.server_version >= MIN_SERVER_VER_PEGGED_TO_BENCHMARK {
+            encode_string(fields, &self.order.adjusted_order_type);
+            encode_f64(fields, self.order.trigger_price);
+            self.encode_stop_price_and_lmt_price_offset(fields);
+            encode_f64(fields, self.order.adjusted_stop_price);
+            encode_f64(fields, self.order.adjusted_stop_limit_price);
+            encode_f64(fields, self.order.adjusted_trailing_amount);
+            encode_i32(fields, self.order.adjustable_trailing_unit);
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_stop_price_and_lmt_price_offset(&self, fields: &mut Vec<String>) {
+        encode_f64(fields, self.order.trail_stop_price);
+        encode_f64(fields, self.order.lmt_price_offset);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_soft_dollar_tier(&self, fields: &mut Vec<String>) {
+        if self.server_version >= MIN_SERVER_VER_SOFT_DOLLAR_TIER {
+            encode_string(fields, &self.order.soft_dollar_tier.name);
+            encode_string(fields, &self.order.soft_dollar_tier.val);
+            encode_string(fields, &self.order.soft_dollar_tier.display_name);
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_cash_qty(&self, fields: &mut Vec<String>) {
+        if self.server_version >= MIN_SERVER_VER_CASH_QTY {
+            encode_f64(fields, self.order.cash_qty);
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_dont_use_auto_price_for_hedge(&self, fields: &mut Vec<String>) {
+        if self.server_version >= MIN_SERVER_VER_AUTO_PRICE_FOR_HEDGE {
+            encode_bool(fields, self.order.dont_use_auto_price_for_hedge);
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_is_oms_containers(&self, fields: &mut Vec<String>) {
+        if self.server_version >= MIN_SERVER_VER_ORDER_CONTAINER {
+            encode_bool(fields, self.order.is_oms_container);
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_discretionary_up_to_limit_price(&self, fields: &mut Vec<String>) {
+        if self.server_version >= MIN_SERVER_VER_D_PEG_ORDERS {
+            encode_bool(fields, self.order.discretionary_up_to_limit_price);
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_auto_cancel_date(&self, fields: &mut Vec<String>) {
+        encode_string(fields, &self.order.auto_cancel_date);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_filled_quantity(&self, fields: &mut Vec<String>) {
+        encode_f64(fields, self.order.filled_quantity);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_ref_futures_con_id(&self, fields: &mut Vec<String>) {
+        encode_i32(fields, self.order.ref_futures_con_id);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_auto_cancel_parent(&self, fields: &mut Vec<String>) {
+        encode_bool(fields, self.order.auto_cancel_parent);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_shareholder(&self, fields: &mut Vec<String>) {
+        encode_string(fields, &self.order.shareholder);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_imbalance_only(&self, fields: &mut Vec<String>) {
+        encode_bool(fields, self.order.imbalance_only);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_route_marketable_to_bbo(&self, fields: &mut Vec<String>) {
+        encode_bool(fields, self.order.route_marketable_to_bbo);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_parent_perm_id(&self, fields: &mut Vec<String>) {
+        encode_i32(fields, self.order.parent_perm_id);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_completed_time(&self, fields: &mut Vec<String>) {
+        encode_string(fields, &self.order_state.completed_time);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_completed_status(&self, fields: &mut Vec<String>) {
+        encode_string(fields, &self.order_state.completed_status);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn encode_use_price_mgmt_algo(&self, fields: &mut Vec<String>) {
+        if self.server_version >= MIN_SERVER_VER_PRICE_MGMT_ALGO {
+            encode_bool(fields, self.order.use_price_mgmt_algo);
+        }
+    }
+}
@@ -0,0 +1,283 @@
+//! Bracket and OCA order groups, placed and managed as a single unit
+use std::fmt::{Display, Error, Formatter};
+
+use crate::core::client::EClient;
+use crate::core::contract::Contract;
+use crate::core::errors::IBKRApiLibError;
+use crate::core::messages::ServerRspMsg;
+use crate::core::order::{Order, OrderStatusKind};
+
+//==================================================================================================
+/// How the orders of a group are linked to each other
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OrderGroupKind {
+    /// The first order is the parent, the others are attached to it through `parent_id`
+    Bracket,
+    /// All orders share an `oca_group`, a fill on one cancels or reduces the others
+    Oca,
+}
+
+//==================================================================================================
+/// Combined status of all orders in a group
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GroupStatus {
+    /// No order of the group has been acknowledged by TWS yet
+    Pending,
+    /// At least one order is working and nothing has been filled
+    Working,
+    /// Some quantity has been filled but the group has not completed
+    PartiallyFilled,
+    /// The group completed: for a bracket the parent and one exit filled, for an OCA group one
+    /// of its orders filled
+    Filled,
+    /// Every order is cancelled without the group completing
+    Cancelled,
+}
+
+impl Display for GroupStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match *self {
+            GroupStatus::Pending => write!(f, "Pending"),
+            GroupStatus::Working => write!(f, "Working"),
+            GroupStatus::PartiallyFilled => write!(f, "PartiallyFilled"),
+            GroupStatus::Filled => write!(f, "Filled"),
+            GroupStatus::Cancelled => write!(f, "Cancelled"),
+        }
+    }
+}
+
+//==================================================================================================
+/// One order of a group together with the last status reported for it
+#[derive(Clone, Debug)]
+pub struct GroupMember {
+    pub contract: Contract,
+    pub order: Order,
+    /// Last `OrderStatus` status string, `None` until TWS reports one
+    pub status: Option<String>,
+    pub filled: f64,
+    pub remaining: f64,
+    pub avg_fill_price: f64,
+}
+
+impl GroupMember {
+    pub fn new(contract: Contract, order: Order) -> Self {
+        let remaining = order.total_quantity;
+        GroupMember {
+            contract,
+            order,
+            status: None,
+            filled: 0.0,
+            remaining,
+            avg_fill_price: 0.0,
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn status_kind(&self) -> Option<OrderStatusKind> {
+        self.status
+            .as_deref()
+            .and_then(|status| status.parse().ok())
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn is_filled(&self) -> bool {
+        self.status_kind() == Some(OrderStatusKind::Filled)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// True once the order can no longer fill or be modified
+    pub fn is_terminal(&self) -> bool {
+        self.status_kind().is_some_and(|kind| kind.is_terminal())
+    }
+}
+
+//==================================================================================================
+/// Handle returned by `EClient::place_bracket` and `EClient::place_oca_group`.
+///
+/// Feed it the events read from `EClient::get_event` through `update` to track the group, and use
+/// `cancel` or `modify` to act on all of its orders at once.
+#[derive(Clone, Debug)]
+pub struct OrderGroup {
+    kind: OrderGroupKind,
+    members: Vec<GroupMember>,
+}
+
+impl OrderGroup {
+    pub fn new(kind: OrderGroupKind, members: Vec<GroupMember>) -> Self {
+        OrderGroup { kind, members }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn kind(&self) -> OrderGroupKind {
+        self.kind
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Orders in the sequence they are transmitted, the bracket parent first
+    pub fn members(&self) -> &[GroupMember] {
+        &self.members
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn member(&self, order_id: i32) -> Option<&GroupMember> {
+        self.members
+            .iter()
+            .find(|member| member.order.order_id == order_id)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn order_ids(&self) -> Vec<i32> {
+        self.members
+            .iter()
+            .map(|member| member.order.order_id)
+            .collect()
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn contains(&self, order_id: i32) -> bool {
+        self.member(order_id).is_some()
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Folds an event into the group. Returns true if the event concerned one of its orders.
+    pub fn update(&mut self, msg: &ServerRspMsg) -> bool {
+        match msg {
+            ServerRspMsg::OrderStatus {
+                order_id,
+                status,
+                filled,
+                remaining,
+                avg_fill_price,
+                ..
+            } => match self.member_mut(*order_id) {
+                Some(member) => {
+                    member.status = Some(status.clone());
+                    member.filled = *filled;
+                    member.remaining = *remaining;
+                    member.avg_fill_price = *avg_fill_price;
+                    true
+                }
+                None => false,
+            },
+            ServerRspMsg::OpenOrder {
+                order_id,
+                order_state,
+                ..
+            } => match self.member_mut(*order_id) {
+                Some(member) => {
+                    if member.status.is_none() && !order_state.status.is_empty() {
+                        member.status = Some(order_state.status.clone());
+                    }
+                    true
+                }
+                None => false,
+            },
+            _ => false,
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn status(&self) -> GroupStatus {
+        let completed = match self.kind {
+            OrderGroupKind::Bracket => match self.members.split_first() {
+                Some((parent, exits)) => {
+                    parent.is_filled() && (exits.is_empty() || exits.iter().any(|m| m.is_filled()))
+                }
+                None => false,
+            },
+            OrderGroupKind::Oca => self.members.iter().any(|member| member.is_filled()),
+        };
+
+        if completed {
+            GroupStatus::Filled
+        } else if self.members.iter().all(|member| member.is_terminal()) {
+            GroupStatus::Cancelled
+        } else if self.members.iter().any(|member| member.filled > 0.0) {
+            GroupStatus::PartiallyFilled
+        } else if self.members.iter().all(|member| member.status.is_none()) {
+            GroupStatus::Pending
+        } else {
+            GroupStatus::Working
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn is_done(&self) -> bool {
+        matches!(self.status(), GroupStatus::Filled | GroupStatus::Cancelled)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Cancels every order of the group that is still working. A working bracket parent is
+    /// cancelled on its own since TWS cancels its attached orders along with it.
+    pub fn cancel(&self, client: &mut EClient) -> Result<(), IBKRApiLibError> {
+        if self.kind == OrderGroupKind::Bracket {
+            if let Some(parent) = self.members.first() {
+                if !parent.is_terminal() {
+                    return client.cancel_order(parent.order.order_id);
+                }
+            }
+        }
+        for member in self.members.iter().filter(|member| !member.is_terminal()) {
+            client.cancel_order(member.order.order_id)?;
+        }
+        Ok(())
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Applies `modify` to each order that is still working and transmits the orders it changed
+    /// again under their original ids. Unchanged orders are not sent.
+    pub fn modify<F>(&mut self, client: &mut EClient, mut modify: F) -> Result<(), IBKRApiLibError>
+    where
+        F: FnMut(&mut Order),
+    {
+        for member in self
+            .members
+            .iter_mut()
+            .filter(|member| !member.is_terminal())
+        {
+            let original = member.order.clone();
+            modify(&mut member.order);
+            if member.order != original {
+                member.order.transmit = true;
+                client.place_order(member.order.order_id, &member.contract, &member.order)?;
+            }
+        }
+        Ok(())
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Sends every working order, holding all of them in TWS until the last one is transmitted.
+    /// When an order cannot be sent, the orders already sent are cancelled so that no part of
+    /// the group is left behind in TWS.
+    pub(crate) fn transmit(&mut self, client: &mut EClient) -> Result<(), IBKRApiLibError> {
+        let last = self
+            .members
+            .iter()
+            .rposition(|member| !member.is_terminal());
+        let mut sent = Vec::with_capacity(self.members.len());
+        for (index, member) in self.members.iter_mut().enumerate() {
+            if member.is_terminal() {
+                continue;
+            }
+            member.order.transmit = Some(index) == last;
+            if let Err(err) =
+                client.place_order(member.order.order_id, &member.contract, &member.order)
+            {
+                for order_id in sent {
+                    // The send error is the one worth reporting
+                    let _ = client.cancel_order(order_id);
+                }
+                return Err(err);
+            }
+            sent.push(member.order.order_id);
+        }
+        Ok(())
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn member_mut(&mut self, order_id: i32) -> Option<&mut GroupMember> {
+        self.members
+            .iter_mut()
+            .find(|member| member.order.order_id == order_id)
+    }
+}
@@ -0,0 +1,460 @@
+//! Pre-flight checks of an order against its contract, contract details and server version
+use std::fmt::{Display, Error, Formatter};
+use std::str::FromStr;
+
+use crate::core::common::{OrderType, TimeInForce, UNSET_DOUBLE, UNSET_INTEGER};
+use crate::core::contract::{Contract, ContractDetails};
+use crate::core::errors::IBKRApiLibError;
+use crate::core::order::Order;
+use crate::core::server_versions::ServerFeature;
+
+//==================================================================================================
+/// A reason TWS would reject an order, found before the order is sent
+#[derive(Clone, Debug, PartialEq)]
+pub enum OrderViolation {
+    /// The contract is missing a field needed to trade it
+    Contract {
+        field: &'static str,
+    },
+    MissingOrderType,
+    /// Neither a positive `total_quantity` nor a positive `cash_qty` is set
+    NonPositiveQuantity {
+        total_quantity: f64,
+    },
+    /// The price fields do not fit the order type
+    OrderFields {
+        reason: &'static str,
+    },
+    UnknownTimeInForce {
+        tif: String,
+    },
+    /// `good_till_date` and the time in force disagree
+    GoodTillDate {
+        tif: String,
+        reason: &'static str,
+    },
+    /// The order type is not in `ContractDetails::order_types`
+    OrderTypeNotAllowed {
+        order_type: String,
+    },
+    /// The time in force is not in `ContractDetails::order_types`
+    TimeInForceNotAllowed {
+        tif: String,
+    },
+    /// The contract's exchange is not in `ContractDetails::valid_exchanges`
+    ExchangeNotValid {
+        exchange: String,
+    },
+    /// A price is not a multiple of `ContractDetails::min_tick`
+    PriceOffTick {
+        field: &'static str,
+        price: f64,
+        min_tick: f64,
+    },
+    /// The order uses a feature the connected server does not support
+    Unsupported {
+        feature: ServerFeature,
+        server_version: i32,
+        required: i32,
+    },
+}
+
+impl Display for OrderViolation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match self {
+            OrderViolation::Contract { field } => {
+                write!(f, "contract requires a valid {}", field)
+            }
+            OrderViolation::MissingOrderType => write!(f, "order requires an order_type"),
+            OrderViolation::NonPositiveQuantity { total_quantity } => write!(
+                f,
+                "total_quantity {} is not positive and no cash_qty is set",
+                total_quantity
+            ),
+            OrderViolation::OrderFields { reason } => write!(f, "order {}", reason),
+            OrderViolation::UnknownTimeInForce { tif } => {
+                write!(f, "unknown time in force '{}'", tif)
+            }
+            OrderViolation::GoodTillDate { tif, reason } => {
+                write!(f, "time in force '{}' {}", tif, reason)
+            }
+            OrderViolation::OrderTypeNotAllowed { order_type } => {
+                write!(
+                    f,
+                    "order type '{}' is not allowed for the contract",
+                    order_type
+                )
+            }
+            OrderViolation::TimeInForceNotAllowed { tif } => {
+                write!(f, "time in force '{}' is not allowed for the contract", tif)
+            }
+            OrderViolation::ExchangeNotValid { exchange } => {
+                write!(f, "exchange '{}' is not valid for the contract", exchange)
+            }
+            OrderViolation::PriceOffTick {
+                field,
+                price,
+                min_tick,
+            } => write!(
+                f,
+                "{} {} is not a multiple of the minimum tick {}",
+                field, price, min_tick
+            ),
+            OrderViolation::Unsupported {
+                feature,
+                server_version,
+                required,
+            } => write!(
+                f,
+                "{} requires server version {}, connected server version is {}",
+                feature, required, server_version
+            ),
+        }
+    }
+}
+
+//==================================================================================================
+/// Checks an order against its contract and, when available, the contract's details.
+///
+/// All violations are collected rather than stopping at the first one. Without `details` only
+/// the contract, quantity, order type price fields and time in force are checked. The tick check
+/// uses `min_tick`, the smallest increment of the contract; market rules with price dependent
+/// increments can still reject a price that passes it.
+pub fn validate_order(
+    contract: &Contract,
+    order: &Order,
+    details: Option<&ContractDetails>,
+) -> Vec<OrderViolation> {
+    let mut violations = Vec::new();
+
+    if let Err(IBKRApiLibError::InvalidContract { field, .. }) = contract.validate() {
+        violations.push(OrderViolation::Contract { field });
+    }
+
+    let has_cash_qty = order.cash_qty != UNSET_DOUBLE && order.cash_qty > 0.0;
+    if order.total_quantity <= 0.0 && !has_cash_qty {
+        violations.push(OrderViolation::NonPositiveQuantity {
+            total_quantity: order.total_quantity,
+        });
+    }
+
+    if order.order_type.is_empty() {
+        violations.push(OrderViolation::MissingOrderType);
+    } else if let Err(IBKRApiLibError::InvalidOrder { reason, .. }) = order.validate_prices() {
+        violations.push(OrderViolation::OrderFields { reason });
+    }
+
+    validate_time_in_force(order, &mut violations);
+
+    if let Some(details) = details {
+        validate_against_details(contract, order, details, &mut violations);
+    }
+    violations
+}
+
+//==================================================================================================
+/// `validate_order`, plus a check that the server supports every feature the order uses
+pub fn validate_order_for_server(
+    contract: &Contract,
+    order: &Order,
+    details: Option<&ContractDetails>,
+    server_version: i32,
+) -> Vec<OrderViolation> {
+    let mut violations = validate_order(contract, order, details);
+    violations.extend(
+        required_features(contract, order)
+            .into_iter()
+            .filter(|feature| !feature.is_supported_by(server_version))
+            .map(|feature| OrderViolation::Unsupported {
+                feature,
+                server_version,
+                required: feature.required_version(),
+            }),
+    );
+    violations
+}
+
+//==================================================================================================
+/// The server features `EClient::place_order` needs to send the fields set on `contract` and
+/// `order`
+pub fn required_features(contract: &Contract, order: &Order) -> Vec<ServerFeature> {
+    let mut features = Vec::new();
+
+    if contract.delta_neutral_contract.is_some() {
+        features.push(ServerFeature::DeltaNeutral);
+    }
+
+    if order.scale_subs_level_size != UNSET_INTEGER {
+        features.push(ServerFeature::ScaleOrders2);
+    }
+
+    if !order.algo_strategy.is_empty() {
+        features.push(ServerFeature::AlgoOrders);
+    }
+
+    if order.not_held {
+        features.push(ServerFeature::NotHeld);
+    }
+
+    if !contract.sec_id_type.is_empty() || !contract.sec_id.is_empty() {
+        features.push(ServerFeature::SecIdType);
+    }
+
+    if contract.con_id > 0 {
+        features.push(ServerFeature::PlaceOrderConid);
+    }
+
+    if order.exempt_code != -1 || contract.combo_legs.iter().any(|x| x.exempt_code != -1) {
+        features.push(ServerFeature::Sshortx);
+    }
+
+    if !order.hedge_type.is_empty() {
+        features.push(ServerFeature::HedgeOrders);
+    }
+
+    if order.opt_out_smart_routing {
+        features.push(ServerFeature::OptOutSmartRouting);
+    }
+
+    if order.delta_neutral_con_id > 0
+        || !order.delta_neutral_settling_firm.is_empty()
+        || !order.delta_neutral_clearing_account.is_empty()
+        || !order.delta_neutral_clearing_intent.is_empty()
+    {
+        features.push(ServerFeature::DeltaNeutralConid);
+    }
+
+    if !order.delta_neutral_open_close.is_empty()
+        || order.delta_neutral_short_sale
+        || order.delta_neutral_short_sale_slot > 0
+        || !order.delta_neutral_designated_location.is_empty()
+    {
+        features.push(ServerFeature::DeltaNeutralOpenClose);
+    }
+
+    if order.scale_price_increment > 0.0
+        && order.scale_price_increment != UNSET_DOUBLE
+        && (order.scale_price_adjust_value != UNSET_DOUBLE
+            || order.scale_price_adjust_interval != UNSET_INTEGER
+            || order.scale_profit_offset != UNSET_DOUBLE
+            || order.scale_auto_reset
+            || order.scale_init_position != UNSET_INTEGER
+            || order.scale_init_fill_qty != UNSET_INTEGER
+            || order.scale_random_percent)
+    {
+        features.push(ServerFeature::ScaleOrders3);
+    }
+
+    if contract.sec_type == "BAG"
+        && order
+            .order_combo_legs
+            .iter()
+            .any(|x| x.price != UNSET_DOUBLE)
+    {
+        features.push(ServerFeature::OrderComboLegsPrice);
+    }
+
+    if order.trailing_percent != UNSET_DOUBLE {
+        features.push(ServerFeature::TrailingPercent);
+    }
+
+    if !contract.trading_class.is_empty() {
+        features.push(ServerFeature::TradingClass);
+    }
+
+    if !order.scale_table.is_empty()
+        || !order.active_start_time.is_empty()
+        || !order.active_stop_time.is_empty()
+    {
+        features.push(ServerFeature::ScaleTable);
+    }
+
+    if !order.algo_id.is_empty() {
+        features.push(ServerFeature::AlgoId);
+    }
+
+    if order.solicited {
+        features.push(ServerFeature::OrderSolicited);
+    }
+
+    if !order.model_code.is_empty() {
+        features.push(ServerFeature::ModelsSupport);
+    }
+
+    if !order.ext_operator.is_empty() {
+        features.push(ServerFeature::ExtOperator);
+    }
+
+    if !order.soft_dollar_tier.name.is_empty() || !order.soft_dollar_tier.val.is_empty() {
+        features.push(ServerFeature::SoftDollarTier);
+    }
+
+    if order.cash_qty != UNSET_DOUBLE && order.cash_qty > 0.0 {
+        features.push(ServerFeature::CashQty);
+    }
+
+    if !order.mifid2decision_maker.is_empty() || !order.mifid2decision_algo.is_empty() {
+        features.push(ServerFeature::DecisionMaker);
+    }
+
+    if !order.mifid2execution_trader.is_empty() || !order.mifid2execution_algo.is_empty() {
+        features.push(ServerFeature::MifidExecution);
+    }
+
+    if order.dont_use_auto_price_for_hedge {
+        features.push(ServerFeature::AutoPriceForHedge);
+    }
+
+    if order.is_oms_container {
+        features.push(ServerFeature::OrderContainer);
+    }
+
+    if order.use_price_mgmt_algo {
+        features.push(ServerFeature::PriceMgmtAlgo);
+    }
+
+    features
+}
+
+//==================================================================================================
+fn validate_time_in_force(order: &Order, violations: &mut Vec<OrderViolation>) {
+    if order.tif.is_empty() {
+        if !order.good_till_date.is_empty() {
+            violations.push(OrderViolation::GoodTillDate {
+                tif: order.tif.clone(),
+                reason: "does not take good_till_date",
+            });
+        }
+        return;
+    }
+
+    match order.tif.parse::<TimeInForce>() {
+        Ok(TimeInForce::GTD) if order.good_till_date.is_empty() => {
+            violations.push(OrderViolation::GoodTillDate {
+                tif: order.tif.clone(),
+                reason: "requires good_till_date",
+            })
+        }
+        Ok(TimeInForce::GTD) => (),
+        Ok(_) if !order.good_till_date.is_empty() => {
+            violations.push(OrderViolation::GoodTillDate {
+                tif: order.tif.clone(),
+                reason: "does not take good_till_date",
+            })
+        }
+        Ok(_) => (),
+        Err(_) => violations.push(OrderViolation::UnknownTimeInForce {
+            tif: order.tif.clone(),
+        }),
+    }
+}
+
+//==================================================================================================
+fn validate_against_details(
+    contract: &Contract,
+    order: &Order,
+    details: &ContractDetails,
+    violations: &mut Vec<OrderViolation>,
+) {
+    let allowed: Vec<&str> = split_list(&details.order_types);
+    if !allowed.is_empty() {
+        // Order types this crate does not know are looked up as they are
+        let code = match OrderType::from_str(&order.order_type) {
+            Ok(order_type) => order_type_code(order_type).map(str::to_string),
+            Err(_) => Some(order.order_type.to_uppercase()),
+        };
+        if code.is_some_and(|code| !code.is_empty() && !allowed.contains(&code.as_str())) {
+            violations.push(OrderViolation::OrderTypeNotAllowed {
+                order_type: order.order_type.clone(),
+            });
+        }
+        // DAY is the default and not always listed
+        let tif = order.tif.to_uppercase();
+        if !tif.is_empty() && tif != "DAY" && !allowed.contains(&tif.as_str()) {
+            violations.push(OrderViolation::TimeInForceNotAllowed {
+                tif: order.tif.clone(),
+            });
+        }
+    }
+
+    let exchanges = split_list(&details.valid_exchanges);
+    if !contract.exchange.is_empty()
+        && !exchanges.is_empty()
+        && !exchanges
+            .iter()
+            .any(|exchange| exchange.eq_ignore_ascii_case(&contract.exchange))
+    {
+        violations.push(OrderViolation::ExchangeNotValid {
+            exchange: contract.exchange.clone(),
+        });
+    }
+
+    let min_tick = details.min_tick;
+    if min_tick > 0.0 && min_tick != UNSET_DOUBLE {
+        let prices = [
+            ("lmt_price", order.lmt_price),
+            ("aux_price", order.aux_price),
+            ("trail_stop_price", order.trail_stop_price),
+        ];
+        for &(field, price) in prices.iter() {
+            if price != UNSET_DOUBLE && !is_on_tick(price, min_tick) {
+                violations.push(OrderViolation::PriceOffTick {
+                    field,
+                    price,
+                    min_tick,
+                });
+            }
+        }
+    }
+}
+
+//==================================================================================================
+fn split_list(list: &str) -> Vec<&str> {
+    list.split(',')
+        .map(|item| item.trim())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+//==================================================================================================
+/// Code of an order type in `ContractDetails::order_types`, `None` for the combo order types which
+/// contract details do not list
+pub fn order_type_code(order_type: OrderType) -> Option<&'static str> {
+    let code = match order_type {
+        OrderType::Market => "MKT",
+        OrderType::Limit => "LMT",
+        OrderType::Stop => "STP",
+        OrderType::StopLimit => "STPLMT",
+        OrderType::StopProtect => "STPPRT",
+        OrderType::Trail => "TRAIL",
+        OrderType::TrailLimit => "TRAILLMT",
+        OrderType::MarketIfTouched => "MIT",
+        OrderType::LimitIfTouched => "LIT",
+        OrderType::MarketToLimit => "MTL",
+        OrderType::MarketProtect => "MKTPRT",
+        OrderType::MarketOnClose => "MOC",
+        OrderType::LimitOnClose => "LOC",
+        OrderType::Relative => "REL",
+        OrderType::PassiveRelative => "PASSVREL",
+        OrderType::RelativeLimit | OrderType::RelativeMarket | OrderType::LimitMarket => {
+            return None
+        }
+        OrderType::PeggedToMidpoint => "PEGMID",
+        OrderType::PeggedToMarket => "PEGMKT",
+        OrderType::PeggedToStock => "PEGSTK",
+        OrderType::PeggedToBenchmark => "PEGBENCH",
+        OrderType::Midprice => "MIDPX",
+        OrderType::BoxTop => "BOXTOP",
+        OrderType::SnapMarket => "SNAPMKT",
+        OrderType::SnapMidpoint => "SNAPMID",
+        OrderType::SnapPrimary => "SNAPPRIM",
+        OrderType::Volatility => "VOL",
+    };
+    Some(code)
+}
+
+//==================================================================================================
+fn is_on_tick(price: f64, min_tick: f64) -> bool {
+    let ticks = price / min_tick;
+    (ticks - ticks.round()).abs() < 1e-6
+}
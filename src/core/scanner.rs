@@ -3,7 +3,10 @@ use std::fmt::{Display, Error, Formatter};
 
 use serde::{Deserialize, Serialize};
 
+use crate::core::common::TagValue;
 use crate::core::contract::ContractDetails;
+use crate::core::errors::IBKRApiLibError;
+use crate::core::scanner_parameters::ScannerCatalog;
 
 //==================================================================================================
 
@@ -119,6 +122,57 @@ impl ScannerSubscription {
             stock_type_filter,
         }
     }
+
+    //----------------------------------------------------------------------------------------------
+    /// Checks the instrument, location code, scan code and `filter_options`, the
+    /// `scanner_subscription_filter_options` of `EClient::req_scanner_subscription`, against the
+    /// scanner parameters
+    pub fn validate(
+        &self,
+        catalog: &ScannerCatalog,
+        filter_options: &[TagValue],
+    ) -> Result<(), IBKRApiLibError> {
+        let invalid = |reason: String| IBKRApiLibError::InvalidScannerSubscription { reason };
+        let instrument = catalog
+            .instrument(&self.instrument)
+            .ok_or_else(|| invalid(format!("unknown instrument {}", self.instrument)))?;
+        let location = catalog
+            .location(&self.location_code)
+            .ok_or_else(|| invalid(format!("unknown location code {}", self.location_code)))?;
+        if !location.instruments.contains(&self.instrument) {
+            return Err(invalid(format!(
+                "location code {} does not list instrument {}",
+                self.location_code, self.instrument
+            )));
+        }
+        let scan_type = catalog
+            .scan_type(&self.scan_code)
+            .ok_or_else(|| invalid(format!("unknown scan code {}", self.scan_code)))?;
+        if !scan_type.instruments.contains(&self.instrument) {
+            return Err(invalid(format!(
+                "scan code {} is not available for instrument {}",
+                self.scan_code, self.instrument
+            )));
+        }
+        for option in filter_options {
+            let (filter, field) = catalog
+                .filter_field(&option.tag)
+                .ok_or_else(|| invalid(format!("unknown filter {}", option.tag)))?;
+            if !instrument.filters.contains(&filter.id) {
+                return Err(invalid(format!(
+                    "filter {} is not valid for instrument {}",
+                    option.tag, self.instrument
+                )));
+            }
+            if !field.values.is_empty() && !field.values.contains(&option.value) {
+                return Err(invalid(format!(
+                    "{} is not a value of filter {}",
+                    option.value, option.tag
+                )));
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Display for ScannerSubscription {
@@ -130,3 +184,296 @@ impl Display for ScannerSubscription {
         )
     }
 }
+
+//==================================================================================================
+/// A scanner subscription filter option, sent as a `TagValue` in the
+/// `scanner_subscription_filter_options` of `EClient::req_scanner_subscription`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum ScannerFilter {
+    PriceAbove(f64),
+    PriceBelow(f64),
+    VolumeAbove(i64),
+    AvgVolumeAbove(i64),
+    /// Market cap in millions of the reporting currency
+    MarketCapAbove(f64),
+    MarketCapBelow(f64),
+    /// Market cap in US dollars
+    UsdMarketCapAbove(f64),
+    UsdMarketCapBelow(f64),
+    ChangePercentAbove(f64),
+    ChangePercentBelow(f64),
+    OptionVolumeAbove(i64),
+    AvgOptionVolumeAbove(i64),
+    /// Moody's rating, e.g. "AAA" or "BAA"
+    MoodyRatingAbove(String),
+    MoodyRatingBelow(String),
+    /// S&P rating, e.g. "AAA" or "BBB"
+    SpRatingAbove(String),
+    SpRatingBelow(String),
+    DividendYieldAbove(f64),
+    DividendYieldBelow(f64),
+    /// Industry name, e.g. "Technology"
+    Industry(String),
+    /// Any other filter of the scanner parameters
+    Other {
+        tag: String,
+        value: String,
+    },
+}
+
+impl ScannerFilter {
+    /// Tag of the filter option
+    pub fn tag(&self) -> &str {
+        match self {
+            ScannerFilter::PriceAbove(_) => "priceAbove",
+            ScannerFilter::PriceBelow(_) => "priceBelow",
+            ScannerFilter::VolumeAbove(_) => "volumeAbove",
+            ScannerFilter::AvgVolumeAbove(_) => "avgVolumeAbove",
+            ScannerFilter::MarketCapAbove(_) => "marketCapAbove1e6",
+            ScannerFilter::MarketCapBelow(_) => "marketCapBelow1e6",
+            ScannerFilter::UsdMarketCapAbove(_) => "usdMarketCapAbove",
+            ScannerFilter::UsdMarketCapBelow(_) => "usdMarketCapBelow",
+            ScannerFilter::ChangePercentAbove(_) => "changePercAbove",
+            ScannerFilter::ChangePercentBelow(_) => "changePercBelow",
+            ScannerFilter::OptionVolumeAbove(_) => "optVolumeAbove",
+            ScannerFilter::AvgOptionVolumeAbove(_) => "avgOptVolumeAbove",
+            ScannerFilter::MoodyRatingAbove(_) => "moodyRatingAbove",
+            ScannerFilter::MoodyRatingBelow(_) => "moodyRatingBelow",
+            ScannerFilter::SpRatingAbove(_) => "spRatingAbove",
+            ScannerFilter::SpRatingBelow(_) => "spRatingBelow",
+            ScannerFilter::DividendYieldAbove(_) => "dividendYieldFrdAbove",
+            ScannerFilter::DividendYieldBelow(_) => "dividendYieldFrdBelow",
+            ScannerFilter::Industry(_) => "industryLike",
+            ScannerFilter::Other { tag, .. } => tag,
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Value of the filter option
+    pub fn value(&self) -> String {
+        match self {
+            ScannerFilter::PriceAbove(value)
+            | ScannerFilter::PriceBelow(value)
+            | ScannerFilter::MarketCapAbove(value)
+            | ScannerFilter::MarketCapBelow(value)
+            | ScannerFilter::UsdMarketCapAbove(value)
+            | ScannerFilter::UsdMarketCapBelow(value)
+            | ScannerFilter::ChangePercentAbove(value)
+            | ScannerFilter::ChangePercentBelow(value)
+            | ScannerFilter::DividendYieldAbove(value)
+            | ScannerFilter::DividendYieldBelow(value) => value.to_string(),
+            ScannerFilter::VolumeAbove(value)
+            | ScannerFilter::AvgVolumeAbove(value)
+            | ScannerFilter::OptionVolumeAbove(value)
+            | ScannerFilter::AvgOptionVolumeAbove(value) => value.to_string(),
+            ScannerFilter::MoodyRatingAbove(value)
+            | ScannerFilter::MoodyRatingBelow(value)
+            | ScannerFilter::SpRatingAbove(value)
+            | ScannerFilter::SpRatingBelow(value)
+            | ScannerFilter::Industry(value)
+            | ScannerFilter::Other { value, .. } => value.clone(),
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn to_tag_value(&self) -> TagValue {
+        TagValue::new(self.tag().to_string(), self.value())
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Reads a filter option, keeping unknown tags as `Other`. Fails if the value of a known tag
+    /// is not a number where one is expected.
+    pub fn from_tag_value(tag_value: &TagValue) -> Result<Self, IBKRApiLibError> {
+        let value = tag_value.value.as_str();
+        let invalid = || IBKRApiLibError::InvalidScannerSubscription {
+            reason: format!("{} is not a valid value of filter {}", value, tag_value.tag),
+        };
+        let float = || value.trim().parse::<f64>().map_err(|_| invalid());
+        let int = || value.trim().parse::<i64>().map_err(|_| invalid());
+        let filter = match tag_value.tag.as_str() {
+            "priceAbove" => ScannerFilter::PriceAbove(float()?),
+            "priceBelow" => ScannerFilter::PriceBelow(float()?),
+            "volumeAbove" => ScannerFilter::VolumeAbove(int()?),
+            "avgVolumeAbove" => ScannerFilter::AvgVolumeAbove(int()?),
+            "marketCapAbove1e6" => ScannerFilter::MarketCapAbove(float()?),
+            "marketCapBelow1e6" => ScannerFilter::MarketCapBelow(float()?),
+            "usdMarketCapAbove" => ScannerFilter::UsdMarketCapAbove(float()?),
+            "usdMarketCapBelow" => ScannerFilter::UsdMarketCapBelow(float()?),
+            "changePercAbove" => ScannerFilter::ChangePercentAbove(float()?),
+            "changePercBelow" => ScannerFilter::ChangePercentBelow(float()?),
+            "optVolumeAbove" => ScannerFilter::OptionVolumeAbove(int()?),
+            "avgOptVolumeAbove" => ScannerFilter::AvgOptionVolumeAbove(int()?),
+            "moodyRatingAbove" => ScannerFilter::MoodyRatingAbove(value.to_string()),
+            "moodyRatingBelow" => ScannerFilter::MoodyRatingBelow(value.to_string()),
+            "spRatingAbove" => ScannerFilter::SpRatingAbove(value.to_string()),
+            "spRatingBelow" => ScannerFilter::SpRatingBelow(value.to_string()),
+            "dividendYieldFrdAbove" => ScannerFilter::DividendYieldAbove(float()?),
+            "dividendYieldFrdBelow" => ScannerFilter::DividendYieldBelow(float()?),
+            "industryLike" => ScannerFilter::Industry(value.to_string()),
+            _ => ScannerFilter::Other {
+                tag: tag_value.tag.clone(),
+                value: value.to_string(),
+            },
+        };
+        Ok(filter)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn to_tag_values(filters: &[ScannerFilter]) -> Vec<TagValue> {
+        filters.iter().map(ScannerFilter::to_tag_value).collect()
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn from_tag_values(tag_values: &[TagValue]) -> Result<Vec<Self>, IBKRApiLibError> {
+        tag_values
+            .iter()
+            .map(ScannerFilter::from_tag_value)
+            .collect()
+    }
+}
+
+impl Display for ScannerFilter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        write!(f, "{}={}", self.tag(), self.value())
+    }
+}
+
+//==================================================================================================
+/// Fluent builder for scanner subscription filter options.
+///
+/// ```
+/// use ibtwsapi::core::scanner::ScannerFilterBuilder;
+///
+/// let filter_options = ScannerFilterBuilder::new()
+///     .price_above(5.0)
+///     .market_cap_above(100.0)
+///     .change_percent_above(2.5)
+///     .build();
+/// assert_eq!("marketCapAbove1e6", filter_options[1].tag);
+/// assert_eq!("2.5", filter_options[2].value);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ScannerFilterBuilder {
+    filters: Vec<ScannerFilter>,
+}
+
+impl ScannerFilterBuilder {
+    pub fn new() -> Self {
+        ScannerFilterBuilder { filters: vec![] }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn filter(mut self, filter: ScannerFilter) -> Self {
+        self.filters.push(filter);
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn price_above(self, price: f64) -> Self {
+        self.filter(ScannerFilter::PriceAbove(price))
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn price_below(self, price: f64) -> Self {
+        self.filter(ScannerFilter::PriceBelow(price))
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn volume_above(self, volume: i64) -> Self {
+        self.filter(ScannerFilter::VolumeAbove(volume))
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn avg_volume_above(self, volume: i64) -> Self {
+        self.filter(ScannerFilter::AvgVolumeAbove(volume))
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Market cap in millions
+    pub fn market_cap_above(self, market_cap: f64) -> Self {
+        self.filter(ScannerFilter::MarketCapAbove(market_cap))
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Market cap in millions
+    pub fn market_cap_below(self, market_cap: f64) -> Self {
+        self.filter(ScannerFilter::MarketCapBelow(market_cap))
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn usd_market_cap_above(self, market_cap: f64) -> Self {
+        self.filter(ScannerFilter::UsdMarketCapAbove(market_cap))
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn usd_market_cap_below(self, market_cap: f64) -> Self {
+        self.filter(ScannerFilter::UsdMarketCapBelow(market_cap))
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn change_percent_above(self, percent: f64) -> Self {
+        self.filter(ScannerFilter::ChangePercentAbove(percent))
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn change_percent_below(self, percent: f64) -> Self {
+        self.filter(ScannerFilter::ChangePercentBelow(percent))
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn option_volume_above(self, volume: i64) -> Self {
+        self.filter(ScannerFilter::OptionVolumeAbove(volume))
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn avg_option_volume_above(self, volume: i64) -> Self {
+        self.filter(ScannerFilter::AvgOptionVolumeAbove(volume))
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn moody_rating_above(self, rating: &str) -> Self {
+        self.filter(ScannerFilter::MoodyRatingAbove(rating.to_string()))
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn moody_rating_below(self, rating: &str) -> Self {
+        self.filter(ScannerFilter::MoodyRatingBelow(rating.to_string()))
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn sp_rating_above(self, rating: &str) -> Self {
+        self.filter(ScannerFilter::SpRatingAbove(rating.to_string()))
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn sp_rating_below(self, rating: &str) -> Self {
+        self.filter(ScannerFilter::SpRatingBelow(rating.to_string()))
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn dividend_yield_above(self, percent: f64) -> Self {
+        self.filter(ScannerFilter::DividendYieldAbove(percent))
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn dividend_yield_below(self, percent: f64) -> Self {
+        self.filter(ScannerFilter::DividendYieldBelow(percent))
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn industry(self, industry: &str) -> Self {
+        self.filter(ScannerFilter::Industry(industry.to_string()))
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// The typed filters
+    pub fn filters(&self) -> &[ScannerFilter] {
+        &self.filters
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// The filter options to pass to `EClient::req_scanner_subscription`
+    pub fn build(self) -> Vec<TagValue> {
+        ScannerFilter::to_tag_values(&self.filters)
+    }
+}
@@ -0,0 +1,174 @@
+//! Ranked snapshots of scanner subscriptions maintained from `ScannerData` events, with the
+//! changes between refreshes
+use std::collections::BTreeMap;
+use std::sync::mpsc::{channel, Receiver, Sender};
+
+use crate::core::messages::ServerRspMsg;
+use crate::core::scanner::ScanData;
+
+//==================================================================================================
+/// A difference between two refreshes of a scanner subscription. Ranks start at 0 for the top
+/// row and contracts are identified by con_id.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ScannerChange {
+    /// A contract that was not in the previous refresh
+    Entered { req_id: i32, con_id: i32, rank: i32 },
+    /// A contract of the previous refresh that is no longer listed
+    Exited { req_id: i32, con_id: i32, rank: i32 },
+    /// A contract ranked higher than in the previous refresh, `to` is lower than `from`
+    MovedUp {
+        req_id: i32,
+        con_id: i32,
+        from: i32,
+        to: i32,
+    },
+    MovedDown {
+        req_id: i32,
+        con_id: i32,
+        from: i32,
+        to: i32,
+    },
+    /// A refresh completed, sent after its other changes
+    Refreshed { req_id: i32, rows: usize },
+}
+
+//==================================================================================================
+/// Changes from the rows of `previous` to the rows of `current`: entries and moves in the order
+/// of `current`, followed by the exits in the order of `previous`
+pub fn diff_scans(req_id: i32, previous: &[ScanData], current: &[ScanData]) -> Vec<ScannerChange> {
+    let con_id = |row: &ScanData| row.contract.contract.con_id;
+    let mut changes = vec![];
+    for row in current {
+        let rank = row.rank;
+        let change = match previous.iter().find(|old| con_id(old) == con_id(row)) {
+            None => Some(ScannerChange::Entered {
+                req_id,
+                con_id: con_id(row),
+                rank,
+            }),
+            Some(old) if rank < old.rank => Some(ScannerChange::MovedUp {
+                req_id,
+                con_id: con_id(row),
+                from: old.rank,
+                to: rank,
+            }),
+            Some(old) if rank > old.rank => Some(ScannerChange::MovedDown {
+                req_id,
+                con_id: con_id(row),
+                from: old.rank,
+                to: rank,
+            }),
+            Some(_) => None,
+        };
+        changes.extend(change);
+    }
+    for old in previous {
+        if !current.iter().any(|row| con_id(row) == con_id(old)) {
+            changes.push(ScannerChange::Exited {
+                req_id,
+                con_id: con_id(old),
+                rank: old.rank,
+            });
+        }
+    }
+    changes
+}
+
+//==================================================================================================
+/// The latest complete refresh of each scanner subscription.
+///
+/// Rows of a refresh are collected until its `ScannerDataEnd`, which replaces the snapshot of the
+/// subscription and reports the changes from the previous one. Feed every event read from
+/// `EClient::get_event` to `update`, events that do not concern scanners are ignored.
+#[derive(Debug, Default)]
+pub struct ScannerBook {
+    pending: BTreeMap<i32, Vec<ScanData>>,
+    snapshots: BTreeMap<i32, Vec<ScanData>>,
+    observers: Vec<Sender<ScannerChange>>,
+}
+
+impl ScannerBook {
+    pub fn new() -> Self {
+        ScannerBook::default()
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Applies an event, returning the changes of the refresh it completed, if any
+    pub fn update(&mut self, msg: &ServerRspMsg) -> Vec<ScannerChange> {
+        match msg {
+            ServerRspMsg::ScannerData {
+                req_id,
+                rank,
+                contract_details,
+                distance,
+                benchmark,
+                projection,
+                legs_str,
+            } => {
+                self.pending.entry(*req_id).or_default().push(ScanData::new(
+                    contract_details.clone(),
+                    *rank,
+                    distance.clone(),
+                    benchmark.clone(),
+                    projection.clone(),
+                    legs_str.clone(),
+                ));
+                vec![]
+            }
+            ServerRspMsg::ScannerDataEnd { req_id } => self.refresh(*req_id),
+            _ => vec![],
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Receives every change from now on
+    pub fn subscribe(&mut self) -> Receiver<ScannerChange> {
+        let (sender, receiver) = channel();
+        self.observers.push(sender);
+        receiver
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Rows of the latest refresh of a subscription, by rank
+    pub fn snapshot(&self, req_id: i32) -> Option<&[ScanData]> {
+        self.snapshots.get(&req_id).map(|rows| rows.as_slice())
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Rank of a contract in the latest refresh of a subscription
+    pub fn rank(&self, req_id: i32, con_id: i32) -> Option<i32> {
+        self.snapshot(req_id)?
+            .iter()
+            .find(|row| row.contract.contract.con_id == con_id)
+            .map(|row| row.rank)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Forgets a subscription, e.g. after `cancel_scanner_subscription`
+    pub fn remove(&mut self, req_id: i32) -> Option<Vec<ScanData>> {
+        self.pending.remove(&req_id);
+        self.snapshots.remove(&req_id)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn refresh(&mut self, req_id: i32) -> Vec<ScannerChange> {
+        let mut rows = self.pending.remove(&req_id).unwrap_or_default();
+        rows.sort_by_key(|row| row.rank);
+        let previous = self
+            .snapshots
+            .get(&req_id)
+            .map(Vec::as_slice)
+            .unwrap_or(&[]);
+        let mut changes = diff_scans(req_id, previous, &rows);
+        changes.push(ScannerChange::Refreshed {
+            req_id,
+            rows: rows.len(),
+        });
+        self.snapshots.insert(req_id, rows);
+        for change in &changes {
+            self.observers
+                .retain(|observer| observer.send(change.clone()).is_ok());
+        }
+        changes
+    }
+}
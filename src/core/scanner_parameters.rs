@@ -0,0 +1,251 @@
+//! Catalog of the instruments, locations, scan codes and filters described by the XML of
+//! `EWrapper::scanner_parameters`
+use crate::core::errors::IBKRApiLibError;
+use crate::core::xml::XmlElement;
+
+//==================================================================================================
+fn invalid(reason: String) -> IBKRApiLibError {
+    IBKRApiLibError::InvalidXml { reason }
+}
+
+//==================================================================================================
+fn child_string(element: &XmlElement, name: &str) -> String {
+    element.child_text(name).unwrap_or_default().to_string()
+}
+
+//==================================================================================================
+/// Splits a comma separated list, e.g. the instruments of a scan type
+fn list(element: &XmlElement, name: &str) -> Vec<String> {
+    element
+        .child_text(name)
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+//==================================================================================================
+/// An instrument a scan can run on, e.g. "STK" for US stocks
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScannerInstrument {
+    pub name: String,
+    /// Value of `ScannerSubscription::instrument`
+    pub instrument_type: String,
+    /// Ids of the filters valid for the instrument
+    pub filters: Vec<String>,
+}
+
+//==================================================================================================
+/// A location a scan can run at, e.g. "STK.US.MAJOR"
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScannerLocation {
+    pub display_name: String,
+    /// Value of `ScannerSubscription::location_code`
+    pub location_code: String,
+    pub instruments: Vec<String>,
+    /// Code of the enclosing location, `None` at the top of the tree
+    pub parent: Option<String>,
+}
+
+//==================================================================================================
+/// A scan, e.g. "TOP_PERC_GAIN" for the top % gainers
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScanType {
+    pub display_name: String,
+    /// Value of `ScannerSubscription::scan_code`
+    pub scan_code: String,
+    pub instruments: Vec<String>,
+}
+
+//==================================================================================================
+/// A field of a filter, whose code is the tag of a scanner subscription filter option
+#[derive(Clone, Debug, PartialEq)]
+pub struct FilterField {
+    /// Tag of the filter option, e.g. "priceAbove"
+    pub code: String,
+    pub display_name: String,
+    /// Kind of value, e.g. "DoubleField", "IntField" or "ComboField"
+    pub field_type: String,
+    /// Codes of the values a combo field accepts, empty for other fields
+    pub values: Vec<String>,
+}
+
+//==================================================================================================
+/// A filter, e.g. "PRICE" with the fields "priceAbove" and "priceBelow"
+#[derive(Clone, Debug, PartialEq)]
+pub struct FilterDefinition {
+    pub id: String,
+    pub category: String,
+    pub fields: Vec<FilterField>,
+}
+
+//==================================================================================================
+/// The parsed scanner parameters
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ScannerCatalog {
+    pub instruments: Vec<ScannerInstrument>,
+    /// All locations of the tree, each followed by the locations it encloses
+    pub locations: Vec<ScannerLocation>,
+    pub scan_types: Vec<ScanType>,
+    pub filters: Vec<FilterDefinition>,
+}
+
+impl ScannerCatalog {
+    /// Parses the XML of `EWrapper::scanner_parameters`
+    pub fn parse(xml: &str) -> Result<Self, IBKRApiLibError> {
+        let root = XmlElement::parse(xml)?;
+        if root.name != "ScanParameterResponse" {
+            return Err(invalid(format!(
+                "expected ScanParameterResponse but found {}",
+                root.name
+            )));
+        }
+
+        let mut catalog = ScannerCatalog::default();
+        if let Some(instruments) = root.child("InstrumentList") {
+            catalog.instruments = instruments
+                .children_named("Instrument")
+                .map(|instrument| ScannerInstrument {
+                    name: child_string(instrument, "name"),
+                    instrument_type: child_string(instrument, "type"),
+                    filters: list(instrument, "filters"),
+                })
+                .collect();
+        }
+        if let Some(tree) = root.child("LocationTree") {
+            add_locations(&mut catalog.locations, tree, None);
+        }
+        if let Some(scan_types) = root.child("ScanTypeList") {
+            catalog.scan_types = scan_types
+                .children_named("ScanType")
+                .map(|scan_type| ScanType {
+                    display_name: child_string(scan_type, "displayName"),
+                    scan_code: child_string(scan_type, "scanCode"),
+                    instruments: list(scan_type, "instruments"),
+                })
+                .collect();
+        }
+        if let Some(filters) = root.child("FilterList") {
+            catalog.filters = filters
+                .children
+                .iter()
+                .map(|filter| FilterDefinition {
+                    id: child_string(filter, "id"),
+                    category: child_string(filter, "category"),
+                    fields: filter
+                        .children_named("AbstractField")
+                        .map(|field| FilterField {
+                            code: child_string(field, "code"),
+                            display_name: child_string(field, "displayName"),
+                            field_type: field.attribute("type").unwrap_or_default().to_string(),
+                            values: field
+                                .child("ComboValues")
+                                .map(|values| {
+                                    values
+                                        .children_named("ComboValue")
+                                        .map(|value| child_string(value, "code"))
+                                        .collect()
+                                })
+                                .unwrap_or_default(),
+                        })
+                        .collect(),
+                })
+                .collect();
+        }
+        Ok(catalog)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn instrument(&self, instrument_type: &str) -> Option<&ScannerInstrument> {
+        self.instruments
+            .iter()
+            .find(|instrument| instrument.instrument_type == instrument_type)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn location(&self, location_code: &str) -> Option<&ScannerLocation> {
+        self.locations
+            .iter()
+            .find(|location| location.location_code == location_code)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn scan_type(&self, scan_code: &str) -> Option<&ScanType> {
+        self.scan_types
+            .iter()
+            .find(|scan_type| scan_type.scan_code == scan_code)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// The filter with a field called `code`, and that field
+    pub fn filter_field(&self, code: &str) -> Option<(&FilterDefinition, &FilterField)> {
+        self.filters.iter().find_map(|filter| {
+            filter
+                .fields
+                .iter()
+                .find(|field| field.code == code)
+                .map(|field| (filter, field))
+        })
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Scans available for `instrument_type`
+    pub fn scans_for<'a>(&'a self, instrument_type: &'a str) -> impl Iterator<Item = &'a ScanType> {
+        self.scan_types.iter().filter(move |scan_type| {
+            scan_type
+                .instruments
+                .iter()
+                .any(|instrument| instrument == instrument_type)
+        })
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Filters valid for `instrument_type`
+    pub fn filters_for(&self, instrument_type: &str) -> Vec<&FilterDefinition> {
+        match self.instrument(instrument_type) {
+            Some(instrument) => self
+                .filters
+                .iter()
+                .filter(|filter| instrument.filters.contains(&filter.id))
+                .collect(),
+            None => vec![],
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Scans whose code or display name contains every word of `query`, ignoring case, e.g.
+    /// "perc gain" finds "TOP_PERC_GAIN"
+    pub fn search_scans(&self, query: &str) -> Vec<&ScanType> {
+        let words: Vec<String> = query
+            .split_whitespace()
+            .map(|word| word.to_lowercase())
+            .collect();
+        self.scan_types
+            .iter()
+            .filter(|scan_type| {
+                let text =
+                    format!("{} {}", scan_type.scan_code, scan_type.display_name).to_lowercase();
+                words.iter().all(|word| text.contains(word.as_str()))
+            })
+            .collect()
+    }
+}
+
+//==================================================================================================
+/// Adds the locations of a `LocationTree` and of the trees nested in them
+fn add_locations(locations: &mut Vec<ScannerLocation>, tree: &XmlElement, parent: Option<&str>) {
+    for location in tree.children_named("Location") {
+        let location_code = child_string(location, "locationCode");
+        locations.push(ScannerLocation {
+            display_name: child_string(location, "displayName"),
+            location_code: location_code.clone(),
+            instruments: list(location, "instruments"),
+            parent: parent.map(str::to_string),
+        });
+        for subtree in location.children_named("LocationTree") {
+            add_locations(locations, subtree, Some(&location_code));
+        }
+    }
+}
@@ -1,4 +1,8 @@
 //! The known server versions.
+use std::fmt;
+
+use strum::IntoEnumIterator;
+use strum_macros::{Display, EnumIter};
 
 //MIN_SERVER_VER_REAL_TIME_BARS       = 34
 //MIN_SERVER_VER_SCALE_ORDERS         = 35
@@ -98,3 +102,243 @@ pub const MIN_SERVER_VER_PRICE_MGMT_ALGO: i32 = 151;
 
 pub const MIN_CLIENT_VER: i32 = 100;
 pub const MAX_CLIENT_VER: i32 = MIN_SERVER_VER_PRICE_MGMT_ALGO;
+
+//==================================================================================================
+/// Features of the API that are gated on the server version reported by TWS during the handshake.
+/// Each variant maps to one of the `MIN_SERVER_VER_*` constants above.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Display, EnumIter)]
+pub enum ServerFeature {
+    PtaOrders,
+    FundamentalData,
+    DeltaNeutral,
+    ContractDataChain,
+    ScaleOrders2,
+    AlgoOrders,
+    ExecutionDataChain,
+    NotHeld,
+    SecIdType,
+    PlaceOrderConid,
+    ReqMktDataConid,
+    ReqCalcImpliedVolat,
+    ReqCalcOptionPrice,
+    SshortxOld,
+    Sshortx,
+    ReqGlobalCancel,
+    HedgeOrders,
+    ReqMarketDataType,
+    OptOutSmartRouting,
+    SmartComboRoutingParams,
+    DeltaNeutralConid,
+    ScaleOrders3,
+    OrderComboLegsPrice,
+    TrailingPercent,
+    DeltaNeutralOpenClose,
+    Positions,
+    AccountSummary,
+    TradingClass,
+    ScaleTable,
+    Linking,
+    AlgoId,
+    OptionalCapabilities,
+    OrderSolicited,
+    LinkingAuth,
+    Primaryexch,
+    RandomizeSizeAndPrice,
+    FractionalPositions,
+    PeggedToBenchmark,
+    ModelsSupport,
+    SecDefOptParamsReq,
+    ExtOperator,
+    SoftDollarTier,
+    ReqFamilyCodes,
+    ReqMatchingSymbols,
+    PastLimit,
+    MdSizeMultiplier,
+    CashQty,
+    ReqMktDepthExchanges,
+    TickNews,
+    ReqSmartComponents,
+    ReqNewsProviders,
+    ReqNewsArticle,
+    ReqHistoricalNews,
+    ReqHeadTimestamp,
+    ReqHistogram,
+    ServiceDataType,
+    AggGroup,
+    UnderlyingInfo,
+    CancelHeadtimestamp,
+    SyntRealtimeBars,
+    CfdReroute,
+    MarketRules,
+    Pnl,
+    NewsQueryOrigins,
+    UnrealizedPnl,
+    HistoricalTicks,
+    MarketCapPrice,
+    PreOpenBidAsk,
+    RealExpirationDate,
+    RealizedPnl,
+    LastLiquidity,
+    TickByTick,
+    DecisionMaker,
+    MifidExecution,
+    TickByTickIgnoreSize,
+    AutoPriceForHedge,
+    WhatIfExtFields,
+    ScannerGenericOpts,
+    ApiBindOrder,
+    OrderContainer,
+    SmartDepth,
+    RemoveNullAllCasting,
+    DPegOrders,
+    MktDepthPrimExchange,
+    CompletedOrders,
+    PriceMgmtAlgo,
+}
+
+impl ServerFeature {
+    /// The minimum server version that supports this feature
+    pub fn required_version(&self) -> i32 {
+        match self {
+            ServerFeature::PtaOrders => MIN_SERVER_VER_PTA_ORDERS,
+            ServerFeature::FundamentalData => MIN_SERVER_VER_FUNDAMENTAL_DATA,
+            ServerFeature::DeltaNeutral => MIN_SERVER_VER_DELTA_NEUTRAL,
+            ServerFeature::ContractDataChain => MIN_SERVER_VER_CONTRACT_DATA_CHAIN,
+            ServerFeature::ScaleOrders2 => MIN_SERVER_VER_SCALE_ORDERS2,
+            ServerFeature::AlgoOrders => MIN_SERVER_VER_ALGO_ORDERS,
+            ServerFeature::ExecutionDataChain => MIN_SERVER_VER_EXECUTION_DATA_CHAIN,
+            ServerFeature::NotHeld => MIN_SERVER_VER_NOT_HELD,
+            ServerFeature::SecIdType => MIN_SERVER_VER_SEC_ID_TYPE,
+            ServerFeature::PlaceOrderConid => MIN_SERVER_VER_PLACE_ORDER_CONID,
+            ServerFeature::ReqMktDataConid => MIN_SERVER_VER_REQ_MKT_DATA_CONID,
+            ServerFeature::ReqCalcImpliedVolat => MIN_SERVER_VER_REQ_CALC_IMPLIED_VOLAT,
+            ServerFeature::ReqCalcOptionPrice => MIN_SERVER_VER_REQ_CALC_OPTION_PRICE,
+            ServerFeature::SshortxOld => MIN_SERVER_VER_SSHORTX_OLD,
+            ServerFeature::Sshortx => MIN_SERVER_VER_SSHORTX,
+            ServerFeature::ReqGlobalCancel => MIN_SERVER_VER_REQ_GLOBAL_CANCEL,
+            ServerFeature::HedgeOrders => MIN_SERVER_VER_HEDGE_ORDERS,
+            ServerFeature::ReqMarketDataType => MIN_SERVER_VER_REQ_MARKET_DATA_TYPE,
+            ServerFeature::OptOutSmartRouting => MIN_SERVER_VER_OPT_OUT_SMART_ROUTING,
+            ServerFeature::SmartComboRoutingParams => MIN_SERVER_VER_SMART_COMBO_ROUTING_PARAMS,
+            ServerFeature::DeltaNeutralConid => MIN_SERVER_VER_DELTA_NEUTRAL_CONID,
+            ServerFeature::ScaleOrders3 => MIN_SERVER_VER_SCALE_ORDERS3,
+            ServerFeature::OrderComboLegsPrice => MIN_SERVER_VER_ORDER_COMBO_LEGS_PRICE,
+            ServerFeature::TrailingPercent => MIN_SERVER_VER_TRAILING_PERCENT,
+            ServerFeature::DeltaNeutralOpenClose => MIN_SERVER_VER_DELTA_NEUTRAL_OPEN_CLOSE,
+            ServerFeature::Positions => MIN_SERVER_VER_POSITIONS,
+            ServerFeature::AccountSummary => MIN_SERVER_VER_ACCOUNT_SUMMARY,
+            ServerFeature::TradingClass => MIN_SERVER_VER_TRADING_CLASS,
+            ServerFeature::ScaleTable => MIN_SERVER_VER_SCALE_TABLE,
+            ServerFeature::Linking => MIN_SERVER_VER_LINKING,
+            ServerFeature::AlgoId => MIN_SERVER_VER_ALGO_ID,
+            ServerFeature::OptionalCapabilities => MIN_SERVER_VER_OPTIONAL_CAPABILITIES,
+            ServerFeature::OrderSolicited => MIN_SERVER_VER_ORDER_SOLICITED,
+            ServerFeature::LinkingAuth => MIN_SERVER_VER_LINKING_AUTH,
+            ServerFeature::Primaryexch => MIN_SERVER_VER_PRIMARYEXCH,
+            ServerFeature::RandomizeSizeAndPrice => MIN_SERVER_VER_RANDOMIZE_SIZE_AND_PRICE,
+            ServerFeature::FractionalPositions => MIN_SERVER_VER_FRACTIONAL_POSITIONS,
+            ServerFeature::PeggedToBenchmark => MIN_SERVER_VER_PEGGED_TO_BENCHMARK,
+            ServerFeature::ModelsSupport => MIN_SERVER_VER_MODELS_SUPPORT,
+            ServerFeature::SecDefOptParamsReq => MIN_SERVER_VER_SEC_DEF_OPT_PARAMS_REQ,
+            ServerFeature::ExtOperator => MIN_SERVER_VER_EXT_OPERATOR,
+            ServerFeature::SoftDollarTier => MIN_SERVER_VER_SOFT_DOLLAR_TIER,
+            ServerFeature::ReqFamilyCodes => MIN_SERVER_VER_REQ_FAMILY_CODES,
+            ServerFeature::ReqMatchingSymbols => MIN_SERVER_VER_REQ_MATCHING_SYMBOLS,
+            ServerFeature::PastLimit => MIN_SERVER_VER_PAST_LIMIT,
+            ServerFeature::MdSizeMultiplier => MIN_SERVER_VER_MD_SIZE_MULTIPLIER,
+            ServerFeature::CashQty => MIN_SERVER_VER_CASH_QTY,
+            ServerFeature::ReqMktDepthExchanges => MIN_SERVER_VER_REQ_MKT_DEPTH_EXCHANGES,
+            ServerFeature::TickNews => MIN_SERVER_VER_TICK_NEWS,
+            ServerFeature::ReqSmartComponents => MIN_SERVER_VER_REQ_SMART_COMPONENTS,
+            ServerFeature::ReqNewsProviders => MIN_SERVER_VER_REQ_NEWS_PROVIDERS,
+            ServerFeature::ReqNewsArticle => MIN_SERVER_VER_REQ_NEWS_ARTICLE,
+            ServerFeature::ReqHistoricalNews => MIN_SERVER_VER_REQ_HISTORICAL_NEWS,
+            ServerFeature::ReqHeadTimestamp => MIN_SERVER_VER_REQ_HEAD_TIMESTAMP,
+            ServerFeature::ReqHistogram => MIN_SERVER_VER_REQ_HISTOGRAM,
+            ServerFeature::ServiceDataType => MIN_SERVER_VER_SERVICE_DATA_TYPE,
+            ServerFeature::AggGroup => MIN_SERVER_VER_AGG_GROUP,
+            ServerFeature::UnderlyingInfo => MIN_SERVER_VER_UNDERLYING_INFO,
+            ServerFeature::CancelHeadtimestamp => MIN_SERVER_VER_CANCEL_HEADTIMESTAMP,
+            ServerFeature::SyntRealtimeBars => MIN_SERVER_VER_SYNT_REALTIME_BARS,
+            ServerFeature::CfdReroute => MIN_SERVER_VER_CFD_REROUTE,
+            ServerFeature::MarketRules => MIN_SERVER_VER_MARKET_RULES,
+            ServerFeature::Pnl => MIN_SERVER_VER_PNL,
+            ServerFeature::NewsQueryOrigins => MIN_SERVER_VER_NEWS_QUERY_ORIGINS,
+            ServerFeature::UnrealizedPnl => MIN_SERVER_VER_UNREALIZED_PNL,
+            ServerFeature::HistoricalTicks => MIN_SERVER_VER_HISTORICAL_TICKS,
+            ServerFeature::MarketCapPrice => MIN_SERVER_VER_MARKET_CAP_PRICE,
+            ServerFeature::PreOpenBidAsk => MIN_SERVER_VER_PRE_OPEN_BID_ASK,
+            ServerFeature::RealExpirationDate => MIN_SERVER_VER_REAL_EXPIRATION_DATE,
+            ServerFeature::RealizedPnl => MIN_SERVER_VER_REALIZED_PNL,
+            ServerFeature::LastLiquidity => MIN_SERVER_VER_LAST_LIQUIDITY,
+            ServerFeature::TickByTick => MIN_SERVER_VER_TICK_BY_TICK,
+            ServerFeature::DecisionMaker => MIN_SERVER_VER_DECISION_MAKER,
+            ServerFeature::MifidExecution => MIN_SERVER_VER_MIFID_EXECUTION,
+            ServerFeature::TickByTickIgnoreSize => MIN_SERVER_VER_TICK_BY_TICK_IGNORE_SIZE,
+            ServerFeature::AutoPriceForHedge => MIN_SERVER_VER_AUTO_PRICE_FOR_HEDGE,
+            ServerFeature::WhatIfExtFields => MIN_SERVER_VER_WHAT_IF_EXT_FIELDS,
+            ServerFeature::ScannerGenericOpts => MIN_SERVER_VER_SCANNER_GENERIC_OPTS,
+            ServerFeature::ApiBindOrder => MIN_SERVER_VER_API_BIND_ORDER,
+            ServerFeature::OrderContainer => MIN_SERVER_VER_ORDER_CONTAINER,
+            ServerFeature::SmartDepth => MIN_SERVER_VER_SMART_DEPTH,
+            ServerFeature::RemoveNullAllCasting => MIN_SERVER_VER_REMOVE_NULL_ALL_CASTING,
+            ServerFeature::DPegOrders => MIN_SERVER_VER_D_PEG_ORDERS,
+            ServerFeature::MktDepthPrimExchange => MIN_SERVER_VER_MKT_DEPTH_PRIM_EXCHANGE,
+            ServerFeature::CompletedOrders => MIN_SERVER_VER_COMPLETED_ORDERS,
+            ServerFeature::PriceMgmtAlgo => MIN_SERVER_VER_PRICE_MGMT_ALGO,
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Checks whether a server reporting `server_version` supports this feature
+    pub fn is_supported_by(&self, server_version: i32) -> bool {
+        server_version >= self.required_version()
+    }
+}
+
+//==================================================================================================
+/// Report of the features supported by a server, as returned by `EClient::capabilities`
+#[derive(Clone, Debug)]
+pub struct ServerCapabilities {
+    pub server_version: i32,
+    pub supported: Vec<ServerFeature>,
+    pub unsupported: Vec<ServerFeature>,
+}
+
+impl ServerCapabilities {
+    pub fn new(server_version: i32) -> Self {
+        let (supported, unsupported) =
+            ServerFeature::iter().partition(|feature| feature.is_supported_by(server_version));
+        ServerCapabilities {
+            server_version,
+            supported,
+            unsupported,
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Checks whether the feature is supported
+    pub fn supports(&self, feature: ServerFeature) -> bool {
+        feature.is_supported_by(self.server_version)
+    }
+}
+
+impl fmt::Display for ServerCapabilities {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Server version: {}", self.server_version)?;
+        for feature in ServerFeature::iter() {
+            writeln!(
+                f,
+                "  {} (>= {}): {}",
+                feature,
+                feature.required_version(),
+                if self.supports(feature) {
+                    "supported"
+                } else {
+                    "unsupported"
+                }
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,238 @@
+//! Market rules of contracts and rounding of prices to the tick size they allow
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+use crate::core::client::EClient;
+use crate::core::common::{Action, PriceIncrement};
+use crate::core::contract::{Contract, ContractDetails};
+use crate::core::errors::IBKRApiLibError;
+use crate::core::messages::ServerRspMsg;
+
+/// Tolerance for prices that are already on a tick but not exactly representable
+const EPSILON: f64 = 1e-9;
+/// Decimal precision of rounded prices, which removes the binary noise of computing them
+const PRECISION: f64 = 1e9;
+
+//==================================================================================================
+fn unknown(reason: String) -> IBKRApiLibError {
+    IBKRApiLibError::UnknownMarketRule { reason }
+}
+
+//==================================================================================================
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RoundDirection {
+    Nearest,
+    /// Towards the next higher tick
+    Up,
+    /// Towards the next lower tick
+    Down,
+}
+
+impl RoundDirection {
+    /// The direction that never makes a limit price more aggressive: down for buys, up for sells
+    pub fn passive(action: Action) -> Self {
+        match action {
+            Action::BUY => RoundDirection::Down,
+            Action::SELL | Action::SSHORT => RoundDirection::Up,
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn reversed(self) -> Self {
+        match self {
+            RoundDirection::Nearest => RoundDirection::Nearest,
+            RoundDirection::Up => RoundDirection::Down,
+            RoundDirection::Down => RoundDirection::Up,
+        }
+    }
+}
+
+//==================================================================================================
+/// The price increments of a `MarketRule` event, by increasing low edge
+#[derive(Clone, Debug, PartialEq)]
+pub struct MarketRule {
+    pub market_rule_id: i32,
+    pub price_increments: Vec<PriceIncrement>,
+}
+
+impl MarketRule {
+    pub fn new(market_rule_id: i32, mut price_increments: Vec<PriceIncrement>) -> Self {
+        price_increments.sort_by(|a, b| {
+            a.low_edge
+                .partial_cmp(&b.low_edge)
+                .unwrap_or(Ordering::Equal)
+        });
+        MarketRule {
+            market_rule_id,
+            price_increments,
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// The band of increments a positive price falls in
+    pub fn price_increment(&self, price: f64) -> Option<&PriceIncrement> {
+        self.price_increments
+            .iter()
+            .rev()
+            .find(|band| price >= band.low_edge - EPSILON)
+            .or_else(|| self.price_increments.first())
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Rounds a price to a tick of its band. Negative prices, e.g. of combos, are rounded by their
+    /// absolute value, so `Down` still means towards the lower price.
+    pub fn round(&self, price: f64, direction: RoundDirection) -> f64 {
+        if price < 0.0 {
+            return -self.round(-price, direction.reversed());
+        }
+        let band = match self.price_increment(price) {
+            Some(band) if band.increment > 0.0 => band,
+            _ => return price,
+        };
+        let ticks = (price - band.low_edge) / band.increment;
+        let ticks = match direction {
+            RoundDirection::Nearest => ticks.round(),
+            RoundDirection::Up => (ticks - EPSILON).ceil(),
+            RoundDirection::Down => (ticks + EPSILON).floor(),
+        };
+        let rounded = band.low_edge + ticks * band.increment;
+        (rounded * PRECISION).round() / PRECISION
+    }
+}
+
+//==================================================================================================
+/// Market rules and the rule of each contract on each of its exchanges.
+///
+/// Feed every event read from `EClient::get_event` to `update`: `ContractData` events tell which
+/// rule applies on which exchange and `MarketRule` events fill the cache. `request_missing` asks
+/// TWS for the rules contracts refer to that are not cached yet, each once.
+#[derive(Clone, Debug, Default)]
+pub struct TickSizeRules {
+    rules: HashMap<i32, MarketRule>,
+    /// Rule ids by exchange, for each con_id
+    contracts: HashMap<i32, HashMap<String, i32>>,
+    requested: HashSet<i32>,
+}
+
+impl TickSizeRules {
+    pub fn new() -> Self {
+        TickSizeRules::default()
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Applies an event, returning the id of the market rule it cached, if any
+    pub fn update(&mut self, msg: &ServerRspMsg) -> Option<i32> {
+        match msg {
+            ServerRspMsg::ContractData {
+                contract_details, ..
+            }
+            | ServerRspMsg::BondContractData {
+                contract_details, ..
+            } => {
+                self.add_contract_details(contract_details);
+                None
+            }
+            ServerRspMsg::MarketRule {
+                market_rule_id,
+                price_increments,
+            } => {
+                self.add_rule(MarketRule::new(*market_rule_id, price_increments.clone()));
+                Some(*market_rule_id)
+            }
+            _ => None,
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Records the rule ids of a contract, which come in the order of its valid exchanges
+    pub fn add_contract_details(&mut self, contract_details: &ContractDetails) {
+        let exchanges = contract_details.valid_exchanges.split(',');
+        let rule_ids = contract_details.market_rule_ids.split(',');
+        let rules = self
+            .contracts
+            .entry(contract_details.contract.con_id)
+            .or_default();
+        for (exchange, rule_id) in exchanges.zip(rule_ids) {
+            if let Ok(rule_id) = rule_id.trim().parse() {
+                rules.insert(exchange.trim().to_string(), rule_id);
+            }
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn add_rule(&mut self, rule: MarketRule) {
+        self.requested.remove(&rule.market_rule_id);
+        self.rules.insert(rule.market_rule_id, rule);
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn market_rule(&self, market_rule_id: i32) -> Option<&MarketRule> {
+        self.rules.get(&market_rule_id)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Ids of the rules contracts refer to that are neither cached nor requested, in order
+    pub fn missing_rules(&self) -> Vec<i32> {
+        let mut missing: Vec<i32> = self
+            .contracts
+            .values()
+            .flat_map(|rules| rules.values().copied())
+            .filter(|id| !self.rules.contains_key(id) && !self.requested.contains(id))
+            .collect::<HashSet<i32>>()
+            .into_iter()
+            .collect();
+        missing.sort_unstable();
+        missing
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Sends `req_market_rule` for each missing rule, returning the number of requests
+    pub fn request_missing(&mut self, client: &mut EClient) -> Result<usize, IBKRApiLibError> {
+        let missing = self.missing_rules();
+        for market_rule_id in &missing {
+            client.req_market_rule(*market_rule_id)?;
+            self.requested.insert(*market_rule_id);
+        }
+        Ok(missing.len())
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Id of the rule of a contract on an exchange, the exchange of the contract when `exchange`
+    /// is empty
+    pub fn rule_id(&self, contract: &Contract, exchange: &str) -> Option<i32> {
+        let exchange = if exchange.is_empty() {
+            contract.exchange.as_str()
+        } else {
+            exchange
+        };
+        self.contracts.get(&contract.con_id)?.get(exchange).copied()
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// The cached rule of a contract on an exchange
+    pub fn rule(&self, contract: &Contract, exchange: &str) -> Option<&MarketRule> {
+        self.market_rule(self.rule_id(contract, exchange)?)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Rounds a price to the tick size of a contract on an exchange. Fails if the contract details
+    /// or the market rule were not received.
+    pub fn round_price(
+        &self,
+        contract: &Contract,
+        exchange: &str,
+        price: f64,
+        direction: RoundDirection,
+    ) -> Result<f64, IBKRApiLibError> {
+        let rule_id = self.rule_id(contract, exchange).ok_or_else(|| {
+            unknown(format!(
+                "no market rule of con_id {} on {:?}",
+                contract.con_id, exchange
+            ))
+        })?;
+        let rule = self
+            .market_rule(rule_id)
+            .ok_or_else(|| unknown(format!("market rule {} not received", rule_id)))?;
+        Ok(rule.round(price, direction))
+    }
+}
@@ -0,0 +1,640 @@
+//! Latest market data of `req_mkt_data` requests, folded from the tick events into one snapshot
+//! per request
+use std::collections::HashMap;
+use std::sync::mpsc::{channel, Receiver, Sender};
+
+use chrono::{DateTime, Utc};
+
+use crate::core::common::{TickAttrib, TickType, UNSET_DOUBLE};
+use crate::core::messages::ServerRspMsg;
+
+//==================================================================================================
+/// Option model values of a `TickOptionComputation` event. Values TWS has not computed are `None`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct OptionGreeks {
+    pub implied_vol: Option<f64>,
+    pub delta: Option<f64>,
+    pub opt_price: Option<f64>,
+    pub pv_dividend: Option<f64>,
+    pub gamma: Option<f64>,
+    pub vega: Option<f64>,
+    pub theta: Option<f64>,
+    pub und_price: Option<f64>,
+}
+
+//==================================================================================================
+/// Exchange for Physical values of a `TickEfp` event
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Efp {
+    pub basis_points: f64,
+    pub formatted_basis_points: String,
+    pub implied_futures_price: f64,
+    pub hold_days: i32,
+    pub future_last_trade_date: String,
+    pub dividend_impact: f64,
+    pub dividends_to_last_trade_date: f64,
+}
+
+//==================================================================================================
+/// A trade reported by the RT Volume (generic tick 233) or RT Trade Volume (generic tick 375)
+/// string, formatted as `price;size;time;total volume;vwap;single trade flag`
+#[derive(Clone, Debug, PartialEq)]
+pub struct RtVolume {
+    /// Empty in the reports that only update the volume
+    pub price: Option<f64>,
+    pub size: Option<f64>,
+    pub time: Option<DateTime<Utc>>,
+    pub total_volume: f64,
+    pub vwap: Option<f64>,
+    /// True if the trade was filled by a single market maker
+    pub single_trade: bool,
+}
+
+impl RtVolume {
+    /// Parses the value of a `RtVolume` or `RtTrdVolume` tick string
+    pub fn parse(value: &str) -> Option<Self> {
+        let fields: Vec<&str> = value.split(';').map(|field| field.trim()).collect();
+        if fields.len() < 6 {
+            return None;
+        }
+        let number = |field: &str| -> Option<f64> {
+            if field.is_empty() {
+                None
+            } else {
+                field.parse().ok()
+            }
+        };
+        Some(RtVolume {
+            price: number(fields[0]),
+            size: number(fields[1]),
+            time: fields[2]
+                .parse::<i64>()
+                .ok()
+                .and_then(DateTime::from_timestamp_millis),
+            total_volume: number(fields[3])?,
+            vwap: number(fields[4]),
+            single_trade: fields[5].eq_ignore_ascii_case("true"),
+        })
+    }
+}
+
+//==================================================================================================
+/// Snapshot of one market data request.
+///
+/// Delayed ticks update the same fields as their live counterparts, e.g. `DelayedBid` is returned
+/// by `bid()` and recorded under `TickType::Bid`; `is_delayed` tells which kind of data was
+/// received last.
+#[derive(Clone, Debug)]
+pub struct Ticker {
+    req_id: i32,
+    market_data_type: i32,
+    values: HashMap<TickType, f64>,
+    sizes: HashMap<TickType, i32>,
+    strings: HashMap<TickType, String>,
+    attribs: HashMap<TickType, TickAttrib>,
+    greeks: HashMap<TickType, OptionGreeks>,
+    efps: HashMap<TickType, Efp>,
+    rt_volume: Option<RtVolume>,
+    rt_trade_volume: Option<RtVolume>,
+    updated: HashMap<TickType, DateTime<Utc>>,
+    delayed: bool,
+    min_tick: Option<f64>,
+    bbo_exchange: Option<String>,
+    snapshot_permissions: Option<i32>,
+    snapshot_end: bool,
+}
+
+impl Ticker {
+    pub fn new(req_id: i32) -> Self {
+        Ticker {
+            req_id,
+            market_data_type: 1,
+            values: HashMap::new(),
+            sizes: HashMap::new(),
+            strings: HashMap::new(),
+            attribs: HashMap::new(),
+            greeks: HashMap::new(),
+            efps: HashMap::new(),
+            rt_volume: None,
+            rt_trade_volume: None,
+            updated: HashMap::new(),
+            delayed: false,
+            min_tick: None,
+            bbo_exchange: None,
+            snapshot_permissions: None,
+            snapshot_end: false,
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn req_id(&self) -> i32 {
+        self.req_id
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Folds an event of this request into the snapshot, returning the fields whose value changed
+    pub fn update(&mut self, msg: &ServerRspMsg) -> Vec<TickType> {
+        self.update_at(msg, Utc::now())
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// `update`, recording `time` as the update time of the changed fields
+    pub fn update_at(&mut self, msg: &ServerRspMsg, time: DateTime<Utc>) -> Vec<TickType> {
+        if tick_req_id(msg) != Some(self.req_id) {
+            return vec![];
+        }
+
+        let mut changed = vec![];
+        match msg {
+            ServerRspMsg::TickPrice {
+                tick_type,
+                price,
+                tick_attr,
+                ..
+            } => {
+                let field = self.field(*tick_type);
+                self.attribs.insert(field, tick_attr.clone());
+                // -1 is sent for a bid or ask when there is no quote
+                let no_quote = *price == -1.0 && matches!(field, TickType::Bid | TickType::Ask);
+                let is_changed = if no_quote {
+                    self.values.remove(&field).is_some()
+                } else {
+                    self.set_value(field, *price)
+                };
+                if is_changed {
+                    changed.push(field);
+                }
+            }
+            ServerRspMsg::TickSize {
+                tick_type, size, ..
+            } => {
+                let field = self.field(*tick_type);
+                if self.sizes.insert(field, *size) != Some(*size) {
+                    changed.push(field);
+                }
+            }
+            ServerRspMsg::TickGeneric {
+                tick_type, value, ..
+            } => {
+                let field = self.field(*tick_type);
+                if self.set_value(field, *value) {
+                    changed.push(field);
+                }
+            }
+            ServerRspMsg::TickString {
+                tick_type, value, ..
+            } => {
+                let field = self.field(*tick_type);
+                match field {
+                    TickType::RtVolume => self.rt_volume = RtVolume::parse(value),
+                    TickType::RtTrdVolume => self.rt_trade_volume = RtVolume::parse(value),
+                    _ => (),
+                }
+                if self.strings.get(&field) != Some(value) {
+                    self.strings.insert(field, value.clone());
+                    changed.push(field);
+                }
+            }
+            ServerRspMsg::TickOptionComputation {
+                tick_type,
+                implied_vol,
+                delta,
+                opt_price,
+                pv_dividend,
+                gamma,
+                vega,
+                theta,
+                und_price,
+                ..
+            } => {
+                let field = self.field(*tick_type);
+                let greeks = OptionGreeks {
+                    implied_vol: unset_to_none(*implied_vol),
+                    delta: unset_to_none(*delta),
+                    opt_price: unset_to_none(*opt_price),
+                    pv_dividend: unset_to_none(*pv_dividend),
+                    gamma: unset_to_none(*gamma),
+                    vega: unset_to_none(*vega),
+                    theta: unset_to_none(*theta),
+                    und_price: unset_to_none(*und_price),
+                };
+                if self.greeks.get(&field) != Some(&greeks) {
+                    self.greeks.insert(field, greeks);
+                    changed.push(field);
+                }
+            }
+            ServerRspMsg::TickEfp {
+                tick_type,
+                basis_points,
+                formatted_basis_points,
+                implied_futures_price,
+                hold_days,
+                future_last_trade_date,
+                dividend_impact,
+                dividends_to_last_trade_date,
+                ..
+            } => {
+                let efp = Efp {
+                    basis_points: *basis_points,
+                    formatted_basis_points: formatted_basis_points.clone(),
+                    implied_futures_price: *implied_futures_price,
+                    hold_days: *hold_days,
+                    future_last_trade_date: future_last_trade_date.clone(),
+                    dividend_impact: *dividend_impact,
+                    dividends_to_last_trade_date: *dividends_to_last_trade_date,
+                };
+                if self.efps.get(tick_type) != Some(&efp) {
+                    self.efps.insert(*tick_type, efp);
+                    changed.push(*tick_type);
+                }
+            }
+            ServerRspMsg::TickReqParams {
+                min_tick,
+                bbo_exchange,
+                snapshot_permissions,
+                ..
+            } => {
+                self.min_tick = unset_to_none(*min_tick);
+                self.bbo_exchange = Some(bbo_exchange.clone());
+                self.snapshot_permissions = Some(*snapshot_permissions);
+            }
+            ServerRspMsg::MarketDataType {
+                market_data_type, ..
+            } => {
+                self.market_data_type = *market_data_type;
+            }
+            ServerRspMsg::TickSnapshotEnd { .. } => self.snapshot_end = true,
+            _ => (),
+        }
+
+        for field in changed.iter() {
+            self.updated.insert(*field, time);
+        }
+        changed
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Latest price or generic value of a tick type
+    pub fn value(&self, tick_type: TickType) -> Option<f64> {
+        self.values.get(&tick_type).copied()
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn size(&self, tick_type: TickType) -> Option<i32> {
+        self.sizes.get(&tick_type).copied()
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn string(&self, tick_type: TickType) -> Option<&str> {
+        self.strings.get(&tick_type).map(|value| value.as_str())
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Attributes sent with the latest price of a tick type
+    pub fn attrib(&self, tick_type: TickType) -> Option<&TickAttrib> {
+        self.attribs.get(&tick_type)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn efp(&self, tick_type: TickType) -> Option<&Efp> {
+        self.efps.get(&tick_type)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// When the field of a tick type last changed
+    pub fn updated_at(&self, tick_type: TickType) -> Option<DateTime<Utc>> {
+        self.updated.get(&tick_type).copied()
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// When any field last changed
+    pub fn last_update(&self) -> Option<DateTime<Utc>> {
+        self.updated.values().max().copied()
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn bid(&self) -> Option<f64> {
+        self.value(TickType::Bid)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn ask(&self) -> Option<f64> {
+        self.value(TickType::Ask)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn last(&self) -> Option<f64> {
+        self.value(TickType::Last)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn midpoint(&self) -> Option<f64> {
+        Some((self.bid()? + self.ask()?) / 2.0)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn bid_size(&self) -> Option<i32> {
+        self.size(TickType::BidSize)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn ask_size(&self) -> Option<i32> {
+        self.size(TickType::AskSize)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn last_size(&self) -> Option<i32> {
+        self.size(TickType::LastSize)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn open(&self) -> Option<f64> {
+        self.value(TickType::Open)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn high(&self) -> Option<f64> {
+        self.value(TickType::High)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn low(&self) -> Option<f64> {
+        self.value(TickType::Low)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn close(&self) -> Option<f64> {
+        self.value(TickType::Close)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn volume(&self) -> Option<i32> {
+        self.size(TickType::Volume)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn mark_price(&self) -> Option<f64> {
+        self.value(TickType::MarkPrice)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// True while trading is halted, generic tick 49 is 1 for a general and 2 for a volatility
+    /// halt
+    pub fn halted(&self) -> Option<bool> {
+        self.value(TickType::Halted).map(|value| value > 0.0)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Shortable indicator, above 2.5 at least 1000 shares can be borrowed, above 1.5 shares can
+    /// be located, otherwise the contract is not available for short sale
+    pub fn shortable(&self) -> Option<f64> {
+        self.value(TickType::Shortable)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn shortable_shares(&self) -> Option<i32> {
+        self.size(TickType::ShortableShares)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Time of the last trade
+    pub fn last_timestamp(&self) -> Option<DateTime<Utc>> {
+        self.string(TickType::LastTimestamp)
+            .and_then(|value| value.parse::<i64>().ok())
+            .and_then(|seconds| DateTime::from_timestamp(seconds, 0))
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn rt_volume(&self) -> Option<&RtVolume> {
+        self.rt_volume.as_ref()
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn rt_trade_volume(&self) -> Option<&RtVolume> {
+        self.rt_trade_volume.as_ref()
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn bid_greeks(&self) -> Option<&OptionGreeks> {
+        self.greeks.get(&TickType::BidOptionComputation)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn ask_greeks(&self) -> Option<&OptionGreeks> {
+        self.greeks.get(&TickType::AskOptionComputation)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn last_greeks(&self) -> Option<&OptionGreeks> {
+        self.greeks.get(&TickType::LastOptionComputation)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn model_greeks(&self) -> Option<&OptionGreeks> {
+        self.greeks.get(&TickType::ModelOption)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Type of the data last announced by a `MarketDataType` event: 1 live, 2 frozen, 3 delayed,
+    /// 4 delayed frozen
+    pub fn market_data_type(&self) -> i32 {
+        self.market_data_type
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// True if the last tick received was a delayed one
+    pub fn is_delayed(&self) -> bool {
+        self.delayed
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Minimum tick from the `TickReqParams` event
+    pub fn min_tick(&self) -> Option<f64> {
+        self.min_tick
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn bbo_exchange(&self) -> Option<&str> {
+        self.bbo_exchange.as_deref()
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn snapshot_permissions(&self) -> Option<i32> {
+        self.snapshot_permissions
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// True once a snapshot request has delivered all of its ticks
+    pub fn is_snapshot_complete(&self) -> bool {
+        self.snapshot_end
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Maps a delayed tick type to the field it updates and records whether data is delayed
+    fn field(&mut self, tick_type: TickType) -> TickType {
+        let live = live_tick_type(tick_type);
+        if live != tick_type {
+            self.delayed = true;
+        } else if is_delayable(tick_type) {
+            self.delayed = false;
+        }
+        live
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn set_value(&mut self, field: TickType, value: f64) -> bool {
+        self.values.insert(field, value) != Some(value)
+    }
+}
+
+//==================================================================================================
+/// Fields of a ticker changed by one event, as observed through `Tickers::subscribe`
+#[derive(Clone, Debug, PartialEq)]
+pub struct TickerUpdate {
+    pub req_id: i32,
+    pub fields: Vec<TickType>,
+}
+
+//==================================================================================================
+/// The tickers of all market data requests, created on their first event.
+///
+/// ```no_run
+/// use ibtwsapi::core::client::EClient;
+/// use ibtwsapi::core::ticker::Tickers;
+///
+/// # fn run(client: &mut EClient) -> Result<(), ibtwsapi::core::errors::IBKRApiLibError> {
+/// let mut tickers = Tickers::new();
+/// let changes = tickers.subscribe();
+/// while let Some(event) = client.get_event()? {
+///     tickers.update(&event);
+/// }
+/// for update in changes.try_iter() {
+///     let ticker = tickers.get(update.req_id).unwrap();
+///     println!("{}: bid {:?} ask {:?}", update.req_id, ticker.bid(), ticker.ask());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct Tickers {
+    tickers: HashMap<i32, Ticker>,
+    observers: Vec<Sender<TickerUpdate>>,
+}
+
+impl Tickers {
+    pub fn new() -> Self {
+        Tickers::default()
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Folds a market data event into the ticker of its request. Returns the changed fields, or
+    /// `None` if the event is not market data or changed nothing.
+    pub fn update(&mut self, msg: &ServerRspMsg) -> Option<TickerUpdate> {
+        let req_id = tick_req_id(msg)?;
+        let fields = self
+            .tickers
+            .entry(req_id)
+            .or_insert_with(|| Ticker::new(req_id))
+            .update(msg);
+        if fields.is_empty() {
+            return None;
+        }
+
+        let update = TickerUpdate { req_id, fields };
+        self.observers
+            .retain(|observer| observer.send(update.clone()).is_ok());
+        Some(update)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Receives a `TickerUpdate` for every change to any ticker
+    pub fn subscribe(&mut self) -> Receiver<TickerUpdate> {
+        let (sender, receiver) = channel();
+        self.observers.push(sender);
+        receiver
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn get(&self, req_id: i32) -> Option<&Ticker> {
+        self.tickers.get(&req_id)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Drops the ticker of a cancelled request
+    pub fn remove(&mut self, req_id: i32) -> Option<Ticker> {
+        self.tickers.remove(&req_id)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn iter(&self) -> impl Iterator<Item = &Ticker> {
+        self.tickers.values()
+    }
+}
+
+//==================================================================================================
+/// Request id of a market data event
+fn tick_req_id(msg: &ServerRspMsg) -> Option<i32> {
+    match msg {
+        ServerRspMsg::TickPrice { req_id, .. }
+        | ServerRspMsg::TickSize { req_id, .. }
+        | ServerRspMsg::TickString { req_id, .. }
+        | ServerRspMsg::TickSnapshotEnd { req_id }
+        | ServerRspMsg::MarketDataType { req_id, .. } => Some(*req_id),
+        ServerRspMsg::TickGeneric { ticker_id, .. }
+        | ServerRspMsg::TickOptionComputation { ticker_id, .. }
+        | ServerRspMsg::TickEfp { ticker_id, .. }
+        | ServerRspMsg::TickReqParams { ticker_id, .. } => Some(*ticker_id),
+        _ => None,
+    }
+}
+
+//==================================================================================================
+fn live_tick_type(tick_type: TickType) -> TickType {
+    match tick_type {
+        TickType::DelayedBid => TickType::Bid,
+        TickType::DelayedAsk => TickType::Ask,
+        TickType::DelayedLast => TickType::Last,
+        TickType::DelayedBidSize => TickType::BidSize,
+        TickType::DelayedAskSize => TickType::AskSize,
+        TickType::DelayedLastSize => TickType::LastSize,
+        TickType::DelayedHigh => TickType::High,
+        TickType::DelayedLow => TickType::Low,
+        TickType::DelayedVolume => TickType::Volume,
+        TickType::DelayedClose => TickType::Close,
+        TickType::DelayedOpen => TickType::Open,
+        TickType::DelayedBidOption => TickType::BidOptionComputation,
+        TickType::DelayedAskOption => TickType::AskOptionComputation,
+        TickType::DelayedLastOption => TickType::LastOptionComputation,
+        TickType::DelayedModelOption => TickType::ModelOption,
+        TickType::DelayedLastTimestamp => TickType::LastTimestamp,
+        other => other,
+    }
+}
+
+//==================================================================================================
+/// Live tick types that have a delayed counterpart
+fn is_delayable(tick_type: TickType) -> bool {
+    matches!(
+        tick_type,
+        TickType::Bid
+            | TickType::Ask
+            | TickType::Last
+            | TickType::BidSize
+            | TickType::AskSize
+            | TickType::LastSize
+            | TickType::High
+            | TickType::Low
+            | TickType::Volume
+            | TickType::Close
+            | TickType::Open
+    )
+}
+
+//==================================================================================================
+fn unset_to_none(value: f64) -> Option<f64> {
+    if value == UNSET_DOUBLE {
+        None
+    } else {
+        Some(value)
+    }
+}
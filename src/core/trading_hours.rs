@@ -0,0 +1,293 @@
+//! Sessions of the `trading_hours` and `liquid_hours` of `ContractDetails`
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+
+use crate::core::contract::ContractDetails;
+use crate::core::datetime::zone_offset;
+use crate::core::errors::IBKRApiLibError;
+
+/// Start and end of a period of trading or liquid hours
+type Period = (DateTime<FixedOffset>, DateTime<FixedOffset>);
+
+//==================================================================================================
+fn invalid(reason: String) -> IBKRApiLibError {
+    IBKRApiLibError::InvalidTradingHours { reason }
+}
+
+//==================================================================================================
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SessionKind {
+    /// Within the liquid hours
+    Regular,
+    /// Within the trading hours but outside the liquid hours, e.g. pre-market
+    Extended,
+}
+
+//==================================================================================================
+/// A period the contract trades, from `start` inclusive to `end` exclusive
+#[derive(Clone, Debug, PartialEq)]
+pub struct Session {
+    pub start: DateTime<FixedOffset>,
+    pub end: DateTime<FixedOffset>,
+    pub kind: SessionKind,
+}
+
+impl Session {
+    pub fn contains<Tz: TimeZone>(&self, at: &DateTime<Tz>) -> bool {
+        self.start <= *at && *at < self.end
+    }
+}
+
+//==================================================================================================
+/// The sessions of a trading date, in order. A holiday has no sessions.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TradingDay {
+    pub date: NaiveDate,
+    pub sessions: Vec<Session>,
+}
+
+impl TradingDay {
+    pub fn is_holiday(&self) -> bool {
+        self.sessions.is_empty()
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn regular_sessions(&self) -> impl Iterator<Item = &Session> {
+        self.sessions
+            .iter()
+            .filter(|session| session.kind == SessionKind::Regular)
+    }
+}
+
+//==================================================================================================
+/// The trading days of a contract in the time zone of its `time_zone_id`.
+///
+/// Sessions of the trading hours are split into regular sessions, where they overlap the liquid
+/// hours, and extended sessions. Without liquid hours every session is regular.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TradingCalendar {
+    pub time_zone_id: String,
+    pub days: Vec<TradingDay>,
+}
+
+impl TradingCalendar {
+    /// Parses hours such as "20240101:CLOSED;20240102:0930-20240102:1600", or the older
+    /// "20240102:0930-1600,1700-1800", read in the time zone `time_zone_id`
+    pub fn parse(
+        trading_hours: &str,
+        liquid_hours: &str,
+        time_zone_id: &str,
+    ) -> Result<Self, IBKRApiLibError> {
+        let trading = parse_hours(trading_hours, time_zone_id)?;
+        let liquid: Vec<Period> = parse_hours(liquid_hours, time_zone_id)?
+            .into_iter()
+            .flat_map(|(_, periods)| periods)
+            .collect();
+        let days = trading
+            .into_iter()
+            .map(|(date, periods)| TradingDay {
+                date,
+                sessions: periods
+                    .into_iter()
+                    .flat_map(|(start, end)| {
+                        if liquid_hours.trim().is_empty() {
+                            vec![Session {
+                                start,
+                                end,
+                                kind: SessionKind::Regular,
+                            }]
+                        } else {
+                            split(start, end, &liquid)
+                        }
+                    })
+                    .collect(),
+            })
+            .collect();
+        Ok(TradingCalendar {
+            time_zone_id: time_zone_id.to_string(),
+            days,
+        })
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn from_contract_details(details: &ContractDetails) -> Result<Self, IBKRApiLibError> {
+        TradingCalendar::parse(
+            &details.trading_hours,
+            &details.liquid_hours,
+            &details.time_zone_id,
+        )
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// All sessions, in order
+    pub fn sessions(&self) -> impl Iterator<Item = &Session> {
+        self.days.iter().flat_map(|day| day.sessions.iter())
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// The trading day of a date, `None` if the hours do not cover it
+    pub fn session_for(&self, date: NaiveDate) -> Option<&TradingDay> {
+        self.days.iter().find(|day| day.date == date)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// True if the hours list the date as closed
+    pub fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.session_for(date).is_some_and(TradingDay::is_holiday)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn holidays(&self) -> Vec<NaiveDate> {
+        self.days
+            .iter()
+            .filter(|day| day.is_holiday())
+            .map(|day| day.date)
+            .collect()
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// The session in progress at a time
+    pub fn session_at<Tz: TimeZone>(&self, at: &DateTime<Tz>) -> Option<&Session> {
+        self.sessions().find(|session| session.contains(at))
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// True during regular and extended sessions
+    pub fn is_open<Tz: TimeZone>(&self, at: &DateTime<Tz>) -> bool {
+        self.session_at(at).is_some()
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn is_regular_open<Tz: TimeZone>(&self, at: &DateTime<Tz>) -> bool {
+        self.session_at(at)
+            .is_some_and(|session| session.kind == SessionKind::Regular)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Time the market next opens after `after`, counting extended sessions. Sessions following
+    /// another without a break, such as the regular session after the pre-market, do not open it.
+    pub fn next_open<Tz: TimeZone>(&self, after: &DateTime<Tz>) -> Option<DateTime<FixedOffset>> {
+        let mut previous_end = None;
+        for session in self.sessions() {
+            if session.start > *after && previous_end != Some(session.start) {
+                return Some(session.start);
+            }
+            previous_end = Some(session.end);
+        }
+        None
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Start of the next regular session after `after`
+    pub fn next_regular_open<Tz: TimeZone>(
+        &self,
+        after: &DateTime<Tz>,
+    ) -> Option<DateTime<FixedOffset>> {
+        self.sessions()
+            .find(|session| session.kind == SessionKind::Regular && session.start > *after)
+            .map(|session| session.start)
+    }
+}
+
+//==================================================================================================
+/// Regular parts of a trading session where it overlaps liquid periods, extended parts elsewhere
+fn split(
+    start: DateTime<FixedOffset>,
+    end: DateTime<FixedOffset>,
+    liquid: &[Period],
+) -> Vec<Session> {
+    let mut sessions = vec![];
+    let mut cursor = start;
+    let mut push = |start, end, kind| {
+        if start < end {
+            sessions.push(Session { start, end, kind });
+        }
+    };
+    for (liquid_start, liquid_end) in liquid {
+        if *liquid_end <= cursor || *liquid_start >= end {
+            continue;
+        }
+        let regular_start = (*liquid_start).max(cursor);
+        let regular_end = (*liquid_end).min(end);
+        push(cursor, regular_start, SessionKind::Extended);
+        push(regular_start, regular_end, SessionKind::Regular);
+        cursor = regular_end;
+    }
+    push(cursor, end, SessionKind::Extended);
+    sessions
+}
+
+//==================================================================================================
+/// Periods of each date of a trading or liquid hours string, in order of date. Closed dates have
+/// no periods.
+fn parse_hours(
+    hours: &str,
+    time_zone_id: &str,
+) -> Result<Vec<(NaiveDate, Vec<Period>)>, IBKRApiLibError> {
+    let zone = time_zone_id.trim();
+    let at = |local: NaiveDateTime| -> Result<DateTime<FixedOffset>, IBKRApiLibError> {
+        zone_offset(zone, &local)
+            .and_then(|offset| offset.from_local_datetime(&local).single())
+            .ok_or_else(|| invalid(format!("unknown time zone {:?}", time_zone_id)))
+    };
+
+    let mut days: BTreeMap<NaiveDate, Vec<_>> = BTreeMap::new();
+    for entry in hours.split(';').map(str::trim).filter(|e| !e.is_empty()) {
+        let (date, periods) = entry
+            .split_once(':')
+            .ok_or_else(|| invalid(format!("missing date in {:?}", entry)))?;
+        let date = parse_date(date)?;
+        let day = days.entry(date).or_default();
+        if periods == "CLOSED" {
+            continue;
+        }
+        for period in periods.split(',') {
+            let (start, end) = period
+                .split_once('-')
+                .ok_or_else(|| invalid(format!("missing end in {:?}", period)))?;
+            let start = match start.split_once(':') {
+                Some((start_date, start_time)) => parse_local(parse_date(start_date)?, start_time)?,
+                None => parse_local(date, start)?,
+            };
+            let end = match end.split_once(':') {
+                Some((end_date, end_time)) => parse_local(parse_date(end_date)?, end_time)?,
+                None => {
+                    let end = parse_local(date, end)?;
+                    // "2200-0200" ends on the next day
+                    if end <= start {
+                        end + chrono::Duration::days(1)
+                    } else {
+                        end
+                    }
+                }
+            };
+            if end <= start {
+                return Err(invalid(format!(
+                    "period {:?} ends before it starts",
+                    period
+                )));
+            }
+            day.push((at(start)?, at(end)?));
+        }
+    }
+    Ok(days.into_iter().collect())
+}
+
+//==================================================================================================
+fn parse_date(date: &str) -> Result<NaiveDate, IBKRApiLibError> {
+    NaiveDate::parse_from_str(date.trim(), "%Y%m%d")
+        .map_err(|_| invalid(format!("invalid date {:?}", date)))
+}
+
+//==================================================================================================
+/// A date and an "hhmm" time, where "2400" is the midnight ending the date
+fn parse_local(date: NaiveDate, time: &str) -> Result<NaiveDateTime, IBKRApiLibError> {
+    let time = time.trim();
+    if time == "2400" {
+        return Ok(date.and_time(NaiveTime::MIN) + chrono::Duration::days(1));
+    }
+    NaiveTime::parse_from_str(time, "%H%M")
+        .map(|time| date.and_time(time))
+        .map_err(|_| invalid(format!("invalid time {:?}", time)))
+}
@@ -0,0 +1,310 @@
+//! Minimal XML reader and writer for the documents TWS exchanges as strings, such as the
+//! Financial Advisor configuration
+use crate::core::errors::IBKRApiLibError;
+
+const DECLARATION: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>";
+
+//==================================================================================================
+/// An element with its attributes, child elements and trimmed text content
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct XmlElement {
+    pub name: String,
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<XmlElement>,
+    pub text: String,
+}
+
+impl XmlElement {
+    pub fn new(name: &str) -> Self {
+        XmlElement {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Parses the root element of a document, skipping the declaration, comments and doctype
+    pub fn parse(xml: &str) -> Result<Self, IBKRApiLibError> {
+        let mut reader = Reader { xml, pos: 0 };
+        reader.skip_misc()?;
+        let root = reader.element()?;
+        reader.skip_misc()?;
+        if reader.pos < xml.len() {
+            return Err(reader.error("content after the root element"));
+        }
+        Ok(root)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn with_attribute(mut self, name: &str, value: &str) -> Self {
+        self.attributes.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn with_text(mut self, text: &str) -> Self {
+        self.text = text.to_string();
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn with_child(mut self, child: XmlElement) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    //----------------------------------------------------------------------------------------------
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(attribute, _)| attribute == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// First child element called `name`
+    pub fn child(&self, name: &str) -> Option<&XmlElement> {
+        self.children.iter().find(|child| child.name == name)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Child elements called `name`, in document order
+    pub fn children_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a XmlElement> {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Text of the first child element called `name`
+    pub fn child_text(&self, name: &str) -> Option<&str> {
+        self.child(name).map(|child| child.text.as_str())
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Writes the element and its children without indentation
+    pub fn to_xml(&self) -> String {
+        let mut xml = String::new();
+        self.write(&mut xml);
+        xml
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Writes the element as a document, preceded by the XML declaration
+    pub fn to_document(&self) -> String {
+        format!("{}{}", DECLARATION, self.to_xml())
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn write(&self, xml: &mut String) {
+        xml.push('<');
+        xml.push_str(&self.name);
+        for (name, value) in &self.attributes {
+            xml.push_str(&format!(" {}=\"{}\"", name, escape(value)));
+        }
+        xml.push('>');
+        xml.push_str(&escape(&self.text));
+        for child in &self.children {
+            child.write(xml);
+        }
+        xml.push_str("</");
+        xml.push_str(&self.name);
+        xml.push('>');
+    }
+}
+
+//==================================================================================================
+/// Replaces the characters XML reserves with entity references
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '&' => escaped.push_str("&amp;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+//==================================================================================================
+/// Replaces entity and character references, leaving unknown ones as they are
+pub fn unescape(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        unescaped.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let end = match rest.find(';') {
+            Some(end) => end,
+            None => break,
+        };
+        let reference = &rest[1..end];
+        let c = match reference {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ if reference.starts_with("#x") => u32::from_str_radix(&reference[2..], 16)
+                .ok()
+                .and_then(std::char::from_u32),
+            _ if reference.starts_with('#') => {
+                reference[1..].parse().ok().and_then(std::char::from_u32)
+            }
+            _ => None,
+        };
+        match c {
+            Some(c) => {
+                unescaped.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                unescaped.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    unescaped.push_str(rest);
+    unescaped
+}
+
+//==================================================================================================
+struct Reader<'a> {
+    xml: &'a str,
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn rest(&self) -> &'a str {
+        &self.xml[self.pos..]
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn error(&self, reason: &str) -> IBKRApiLibError {
+        IBKRApiLibError::InvalidXml {
+            reason: format!("{} at byte {}", reason, self.pos),
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Moves past the next occurrence of `end`
+    fn skip_past(&mut self, end: &str) -> Result<&'a str, IBKRApiLibError> {
+        let rest = self.rest();
+        match rest.find(end) {
+            Some(index) => {
+                self.pos += index + end.len();
+                Ok(&rest[..index])
+            }
+            None => Err(self.error(&format!("missing {}", end))),
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    /// Skips whitespace, processing instructions, comments and doctype declarations
+    fn skip_misc(&mut self) -> Result<(), IBKRApiLibError> {
+        loop {
+            self.skip_whitespace();
+            let rest = self.rest();
+            if rest.starts_with("<?") {
+                self.skip_past("?>")?;
+            } else if rest.starts_with("<!--") {
+                self.skip_past("-->")?;
+            } else if rest.starts_with("<!") && !rest.starts_with("<![CDATA[") {
+                self.skip_past(">")?;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn name(&mut self) -> Result<&'a str, IBKRApiLibError> {
+        let rest = self.rest();
+        let end = rest
+            .find(|c: char| c.is_whitespace() || c == '/' || c == '>' || c == '=')
+            .unwrap_or(rest.len());
+        if end == 0 {
+            return Err(self.error("missing name"));
+        }
+        self.pos += end;
+        Ok(&rest[..end])
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn expect(&mut self, token: &str) -> Result<(), IBKRApiLibError> {
+        if !self.rest().starts_with(token) {
+            return Err(self.error(&format!("expected {}", token)));
+        }
+        self.pos += token.len();
+        Ok(())
+    }
+
+    //----------------------------------------------------------------------------------------------
+    fn element(&mut self) -> Result<XmlElement, IBKRApiLibError> {
+        self.expect("<")?;
+        let mut element = XmlElement::new(self.name()?);
+        loop {
+            self.skip_whitespace();
+            if self.rest().starts_with("/>") {
+                self.pos += 2;
+                return Ok(element);
+            }
+            if self.rest().starts_with('>') {
+                self.pos += 1;
+                break;
+            }
+            let name = self.name()?;
+            self.skip_whitespace();
+            self.expect("=")?;
+            self.skip_whitespace();
+            let quote = if self.rest().starts_with('\'') {
+                "'"
+            } else {
+                "\""
+            };
+            self.expect(quote)?;
+            let value = unescape(self.skip_past(quote)?);
+            element.attributes.push((name.to_string(), value));
+        }
+
+        let mut text = String::new();
+        loop {
+            let rest = self.rest();
+            if rest.starts_with("</") {
+                self.pos += 2;
+                let name = self.name()?;
+                if name != element.name {
+                    return Err(self.error(&format!(
+                        "closing tag {} does not match {}",
+                        name, element.name
+                    )));
+                }
+                self.skip_whitespace();
+                self.expect(">")?;
+                element.text = text.trim().to_string();
+                return Ok(element);
+            } else if rest.starts_with("<![CDATA[") {
+                self.pos += "<![CDATA[".len();
+                text.push_str(self.skip_past("]]>")?);
+            } else if rest.starts_with("<!--") || rest.starts_with("<?") {
+                self.skip_misc()?;
+            } else if rest.starts_with('<') {
+                element.children.push(self.element()?);
+            } else if rest.is_empty() {
+                return Err(self.error(&format!("unclosed element {}", element.name)));
+            } else {
+                let end = rest.find('<').unwrap_or(rest.len());
+                text.push_str(&unescape(&rest[..end]));
+                self.pos += end;
+            }
+        }
+    }
+}
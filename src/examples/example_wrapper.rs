@@ -1,14 +1,16 @@
 use crate::{
     core::client::EClient,
     core::common::{
-        BarData, CommissionReport, DepthMktDataDescription, FaDataType, FamilyCode, HistogramData,
-        HistoricalTick, HistoricalTickBidAsk, HistoricalTickLast, MarketDataTypeEnum, NewsProvider,
-        PriceIncrement, RealTimeBar, SmartComponent, TagValue, TickAttrib, TickAttribBidAsk,
-        TickAttribLast, TickByTickType, TickType,
+        self, Action, BarData, BarSize, CommissionReport, DepthMktDataDescription, DurationUnit,
+        FaDataType, FamilyCode, HistogramData, HistoricalTick, HistoricalTickBidAsk,
+        HistoricalTickLast, MarketDataTypeEnum, NewsProvider, OrderType, PriceIncrement,
+        RealTimeBar, SmartComponent, TagValue, TickAttrib, TickAttribBidAsk, TickAttribLast,
+        TickByTickType, TickType, WhatToShow,
     },
     core::contract::{Contract, ContractDescription, ContractDetails, DeltaNeutralContract},
     core::errors::IBKRApiLibError,
     core::execution::{Execution, ExecutionFilter},
+    core::historical_data::HistoricalDataRequest,
     core::{
         account_summary_tags::AccountSummaryTags,
         algo_params::{
@@ -17,18 +19,19 @@ use crate::{
             fill_dark_ice_params, fill_jefferies_vwapparams, fill_min_impact_params,
             fill_pct_vol_params, fill_price_variant_pct_vol_params, fill_qbalgo_in_line_params,
             fill_scale_params, fill_size_variant_pct_vol_params, fill_time_variant_pct_vol_params,
-            fill_twap_params, fill_vwap_params,
+            fill_twap_params, fill_vwap_params, RiskAversion,
         },
         messages::{ServerRspMsg, ServerRspMsgDiscriminants},
-        order::{Order, OrderState, SoftDollarTier},
-        order_condition::TriggerMethod,
+        order::{Order, OrderBuilder, OrderState, SoftDollarTier},
+        order_condition::{ConditionBuilder, TriggerMethod},
+        scanner::ScannerFilterBuilder,
     },
     examples::{
         contract_samples, fa_allocation_samples, order_samples, scanner_subscription_samples,
     },
 };
 use chrono;
-use chrono::Utc;
+use chrono::{TimeZone, Utc};
 use log::*;
 use std::borrow::Borrow;
 use std::string::ToString;
@@ -138,17 +141,20 @@ impl ExampleWrapper {
         self.client.req_family_codes()?;
 
         // Requesting accounts' summary
-        let all_tags = AccountSummaryTags::AllTags.to_string();
         self.client
-            .req_account_summary(9001, "All", all_tags.as_str())?;
-
-        self.client.req_account_summary(9002, "All", "$LEDGER")?;
+            .req_account_summary(9001, "All", &[AccountSummaryTags::AllTags])?;
 
         self.client
-            .req_account_summary(9003, "All", "$LEDGER:EUR")?;
+            .req_account_summary(9002, "All", &[AccountSummaryTags::Ledger])?;
+
+        self.client.req_account_summary(
+            9003,
+            "All",
+            &[AccountSummaryTags::LedgerCurrency("EUR".to_string())],
+        )?;
 
         self.client
-            .req_account_summary(9004, "All", "$LEDGER:ALL")?;
+            .req_account_summary(9004, "All", &[AccountSummaryTags::LedgerAll])?;
 
         self.client
             .req_account_updates(true, self.account.as_str())?;
@@ -171,8 +177,8 @@ impl ExampleWrapper {
         self.client.req_real_time_bars(
             3001,
             contract_samples::us_stock_at_smart().borrow(),
-            1,
-            "TRADES",
+            BarSize::_5Secs,
+            WhatToShow::Trades,
             true,
             vec![],
         )?;
@@ -586,20 +592,16 @@ impl ExampleWrapper {
         )?;
 
         // Conditions can make the order active or cancel it. Only LMT orders can be conditionally canceled.
-        let mut lmt = order_samples::limit_order("BUY", 100.0, 20.0);
-        // The active order will be cancelled if conditioning criteria is met
-        lmt.conditions_cancel_order = true;
-        lmt.conditions
-            .push(crate::core::order_condition::OrderConditionEnum::Price(
-                order_samples::price_condition(
-                    TriggerMethod::Last as i32,
-                    208813720,
-                    "SMART",
-                    600.0,
-                    false,
-                    false,
-                ),
-            ));
+        let lmt = OrderBuilder::new(Action::BUY, OrderType::Limit, 100.0)
+            .lmt_price(20.0)
+            .conditions(
+                ConditionBuilder::new()
+                    .price(TriggerMethod::Last, 208813720, "SMART", false, 600.0)
+                    .build(),
+            )
+            // The active order will be cancelled if conditioning criteria is met
+            .conditions_cancel_order(true)
+            .build()?;
 
         next_id = self.next_order_id();
         self.client.place_order(
@@ -770,7 +772,7 @@ impl ExampleWrapper {
         fill_arrival_price_params(
             order,
             0.1,
-            "Aggressive",
+            RiskAversion::Aggressive,
             "09:00:00 CET",
             "16:00:00 CET",
             true,
@@ -820,7 +822,7 @@ impl ExampleWrapper {
         // let order = &mut base_order.clone();
         // fill_twap_params(
         //     order,
-        //     "Marketable",
+        //     TwapStrategyType::Marketable,
         //     "09:00:00 CET",
         //     "16:00:00 CET",
         //     true,
@@ -847,7 +849,7 @@ impl ExampleWrapper {
 
         //
         // let order = &mut base_order.clone();
-        // fill_balance_impact_risk_params(order, 0.1, "Aggressive", true);
+        // fill_balance_impact_risk_params(order, 0.1, RiskAversion::Aggressive, true);
         //         self.client.ontract_samples::us_option_contract().borrow(),
         //     order,
         // )?;
@@ -862,7 +864,7 @@ impl ExampleWrapper {
         //
 
         // let order = &mut base_order.clone();
-        // fill_adaptive_params(order, "Normal");
+        // fill_adaptive_params(order, AdaptivePriority::Normal);
         //         self.client.ontract_samples::us_stock_at_smart().borrow(),
         //     order,
         // )?;
@@ -872,7 +874,7 @@ impl ExampleWrapper {
         // fill_close_price_params(
         //     order,
         //     0.4,
-        //     "Neutral",
+        //     RiskAversion::Neutral,
         //     "20180926-06:06:49",
         //     true,
         //     100000.0,
@@ -1066,50 +1068,43 @@ impl ExampleWrapper {
         self.client.req_head_time_stamp(
             4101,
             contract_samples::simple_future().borrow(),
-            "TRADES",
+            WhatToShow::Trades,
             0,
             1,
         )?;
 
-        let dt = Utc::now();
-        let query_time = dt.format("%Y%m%d %H:%M:%S").to_string();
+        let query_time = Utc::now();
         info!("Request Time:  {}", query_time);
-        self.client.req_historical_data(
-            4102,
+        let request = HistoricalDataRequest::builder(
             contract_samples::simple_future().borrow(),
-            query_time.as_str(),
-            "1 M",
-            "1 day",
-            "MIDPOINT",
-            1,
-            1,
-            false,
-            vec![],
-        )?;
-        self.client.req_historical_data(
-            4103,
+            common::Duration::new(1, DurationUnit::MONTH),
+            BarSize::_1Day,
+            WhatToShow::Midpoint,
+        )
+        .end_date_time(&query_time)
+        .use_rth(true)
+        .build()?;
+        self.client.req_historical_data(4102, &request)?;
+        let request = HistoricalDataRequest::builder(
             contract_samples::simple_future().borrow(),
-            query_time.as_str(),
-            "10 D",
-            "1 min",
-            "TRADES",
-            1,
-            1,
-            false,
-            vec![],
-        )?;
-        self.client.req_historical_data(
-            4104,
+            common::Duration::new(1, DurationUnit::DAY),
+            BarSize::_1Min,
+            WhatToShow::Trades,
+        )
+        .end_date_time(&query_time)
+        .use_rth(true)
+        .build()?;
+        self.client.req_historical_data(4103, &request)?;
+        let request = HistoricalDataRequest::builder(
             contract_samples::eur_gbp_fx().borrow(),
-            "",
-            "1 M",
-            "1 day",
-            "MIDPOINT",
-            1,
-            1,
-            true,
-            vec![],
-        )?;
+            common::Duration::new(1, DurationUnit::MONTH),
+            BarSize::_1Day,
+            WhatToShow::Midpoint,
+        )
+        .use_rth(true)
+        .keep_up_to_date(true)
+        .build()?;
+        self.client.req_historical_data(4104, &request)?;
 
         Ok(())
     }
@@ -1373,19 +1368,11 @@ impl ExampleWrapper {
         )?;
 
         // Generic Filters
-        let mut tagvalues = vec![];
-        tagvalues.push(TagValue::new(
-            "usdMarketCapAbove".to_string(),
-            "10000".to_string(),
-        ));
-        tagvalues.push(TagValue::new(
-            "optVolumeAbove".to_string(),
-            "1000".to_string(),
-        ));
-        tagvalues.push(TagValue::new(
-            "avgVolumeAbove".to_string(),
-            "10000".to_string(),
-        ));
+        let tagvalues = ScannerFilterBuilder::new()
+            .usd_market_cap_above(10000.0)
+            .option_volume_above(1000)
+            .avg_volume_above(10000)
+            .build();
 
         let result = self.client.req_scanner_subscription(
             7002,
@@ -1555,8 +1542,8 @@ impl ExampleWrapper {
         self.client.req_historical_ticks(
             18001,
             contract_samples::usstock().borrow(),
-            "20170712 21:39:33",
-            "",
+            Utc.with_ymd_and_hms(2017, 7, 12, 21, 39, 33).single(),
+            None,
             10,
             "TRADES",
             1,
@@ -1566,8 +1553,8 @@ impl ExampleWrapper {
         self.client.req_historical_ticks(
             18002,
             contract_samples::us_stock_at_smart().borrow(),
-            "20170712 21:39:33",
-            "",
+            Utc.with_ymd_and_hms(2017, 7, 12, 21, 39, 33).single(),
+            None,
             10,
             "BID_ASK",
             1,
@@ -1577,8 +1564,8 @@ impl ExampleWrapper {
         self.client.req_historical_ticks(
             18003,
             contract_samples::us_stock_at_smart().borrow(),
-            "20170712 21:39:33",
-            "",
+            Utc.with_ymd_and_hms(2017, 7, 12, 21, 39, 33).single(),
+            None,
             10,
             "MIDPOINT",
             1,
@@ -1596,7 +1583,7 @@ impl ExampleWrapper {
             4002,
             contract_samples::usstock().borrow(),
             false,
-            "3 days",
+            common::Duration::new(3, DurationUnit::DAY),
         )?;
 
         Ok(())
@@ -1614,19 +1601,15 @@ impl ExampleWrapper {
         self.client
             .req_contract_details(18001, contract_samples::cont_fut().borrow())?;
 
-        let time_str = Utc::now().format("%Y%m%d %H:%M:%S");
-        self.client.req_historical_data(
-            18002,
+        let request = HistoricalDataRequest::builder(
             contract_samples::cont_fut().borrow(),
-            time_str.to_string().as_str(),
-            "1 Y",
-            "1 month",
-            "TRADES",
-            0,
-            1,
-            false,
-            vec![],
-        )?;
+            common::Duration::new(1, DurationUnit::YEAR),
+            BarSize::_1Month,
+            WhatToShow::Trades,
+        )
+        .end_date_time(&Utc::now())
+        .build()?;
+        self.client.req_historical_data(18002, &request)?;
 
         Ok(())
     }
@@ -1743,6 +1726,13 @@ impl ExampleWrapper {
         self.client
             .replace_fa(FaDataType::PROFILES, fa_allocation_samples::FA_TWO_PROFILES)?;
 
+        // The same configuration built from typed groups and profiles
+        self.client
+            .replace_fa_config(&fa_allocation_samples::two_groups())?;
+
+        self.client
+            .replace_fa_config(&fa_allocation_samples::two_profiles())?;
+
         self.client.req_soft_dollar_tiers(14001)?;
 
         Ok(())
@@ -1770,7 +1760,7 @@ impl ExampleWrapper {
 
         // Returns list of historical news headlines with IDs
         self.client
-            .req_historical_news(10003, 8314, "BRFG", "", "", 10, vec![])?;
+            .req_historical_news(10003, 8314, "BRFG", None, None, 10, vec![])?;
 
         self.client
             .req_contract_details(10004, contract_samples::news_feed_for_query().borrow())?;
@@ -2584,7 +2574,7 @@ impl ExampleWrapper {
 
             &mut self,
             ticker_id: i32,
-            time_stamp: i32,
+            time_stamp: i64,
             provider_code: &str,
             article_id: &str,
             headline: &str,
@@ -1,4 +1,5 @@
 //! Example FA allocation XML request data
+use crate::core::fa::{FaGroup, FaGroupMethod, FaProfile, FaProfileType};
 
 ///Replace all with your own accountIds
 
@@ -87,3 +88,29 @@ pub const FA_TWO_PROFILES: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\"?> \
                                 </ListOfAllocations> \
                                 </AllocationProfile> \
                                 </ListOfAllocationProfiles>";
+
+//==================================================================================================
+/// The groups of FA_TWO_GROUPS, for `EClient::replace_fa_config`
+pub fn two_groups() -> Vec<FaGroup> {
+    vec![
+        FaGroup::new("Equal_Quantity", FaGroupMethod::EqualQuantity)
+            .with_account("DU119915")
+            .with_account("DU119916"),
+        FaGroup::new("Pct_Change", FaGroupMethod::PctChange)
+            .with_account("DU119915")
+            .with_account("DU119916"),
+    ]
+}
+
+//==================================================================================================
+/// The profiles of FA_TWO_PROFILES, for `EClient::replace_fa_config`
+pub fn two_profiles() -> Vec<FaProfile> {
+    vec![
+        FaProfile::new("Percent_60_40", FaProfileType::Percentages)
+            .with_allocation("DU119915", 60.0)
+            .with_allocation("DU119916", 40.0),
+        FaProfile::new("Ratios_2_1", FaProfileType::Percentages)
+            .with_allocation("DU119915", 2.0)
+            .with_allocation("DU119916", 1.0),
+    ]
+}
@@ -1023,7 +1023,7 @@ pub fn percentage_change_condition(
     is_conjunction: bool,
 ) -> PercentChangeCondition {
     let mut pct_change_condition: PercentChangeCondition =
-        create_condition(ConditionType::Execution).into();
+        create_condition(ConditionType::PercentChange).into();
     // If there is a price percent change measured against last close price above or below...
     pct_change_condition
         .contract_condition
@@ -1,2 +1,24 @@
+pub(crate) mod test_account_state;
+pub(crate) mod test_account_summary;
+pub(crate) mod test_algo_params;
+pub(crate) mod test_contract;
+pub(crate) mod test_datetime;
+pub(crate) mod test_dump;
 pub(crate) mod test_eclient;
+pub(crate) mod test_encoder;
+pub(crate) mod test_fa;
+pub(crate) mod test_fundamentals;
+pub(crate) mod test_historical_data;
 pub(crate) mod test_messages;
+pub(crate) mod test_news;
+pub(crate) mod test_order;
+pub(crate) mod test_order_condition;
+pub(crate) mod test_order_group;
+pub(crate) mod test_order_validation;
+pub(crate) mod test_scanner;
+pub(crate) mod test_scanner_book;
+pub(crate) mod test_scanner_parameters;
+pub(crate) mod test_server_versions;
+pub(crate) mod test_tick_size;
+pub(crate) mod test_ticker;
+pub(crate) mod test_trading_hours;
@@ -0,0 +1,123 @@
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveTime;
+    use rust_decimal_macros::dec;
+
+    use crate::core::account_state::{AccountChange, AccountState};
+    use crate::core::contract::Contract;
+    use crate::core::messages::ServerRspMsg;
+
+    fn acct_value(key: &str, val: &str, currency: &str) -> ServerRspMsg {
+        ServerRspMsg::AcctValue {
+            key: key.to_string(),
+            val: val.to_string(),
+            currency: currency.to_string(),
+            account_name: "DU123".to_string(),
+        }
+    }
+
+    fn portfolio_value(con_id: i32, position: f64, unrealized_pnl: f64) -> ServerRspMsg {
+        let mut contract = Contract::stock("AMZN", "NASDAQ", "USD");
+        contract.con_id = con_id;
+        ServerRspMsg::PortfolioValue {
+            contract,
+            position,
+            market_price: 130.0,
+            market_value: position * 130.0,
+            average_cost: 120.0,
+            unrealized_pnl,
+            realized_pnl: 15.0,
+            account_name: "DU123".to_string(),
+        }
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_account_state_updates() {
+        let mut state = AccountState::new();
+        let changes = state.subscribe();
+
+        state.update(&acct_value("NetLiquidation", "100250.37", "USD"));
+        state.update(&acct_value("CashBalance", "2500", "EUR"));
+        state.update(&acct_value("AccountType", "INDIVIDUAL", ""));
+        assert!(state
+            .update(&acct_value("NetLiquidation", "100250.37", "USD"))
+            .is_none());
+        state.update(&portfolio_value(3691937, 100.0, 1000.0));
+        state.update(&portfolio_value(265598, 10.0, -50.0));
+        assert!(state
+            .update(&portfolio_value(265598, 10.0, -50.0))
+            .is_none());
+        state.update(&ServerRspMsg::AcctUpdateTime {
+            time_stamp: "15:42".to_string(),
+        });
+        state.update(&ServerRspMsg::AcctDownloadEnd {
+            account_name: "DU123".to_string(),
+        });
+
+        assert_eq!(
+            Some(dec!(100250.37)),
+            state.value("DU123", "NetLiquidation", "USD")
+        );
+        assert_eq!(Some(dec!(2500)), state.value("DU123", "CashBalance", "EUR"));
+        assert_eq!(
+            Some("INDIVIDUAL"),
+            state.values("DU123").unwrap().text("AccountType")
+        );
+        assert_eq!(vec!["DU123"], state.accounts());
+        assert_eq!(2, state.portfolio("DU123").unwrap().len());
+        assert_eq!(100.0, state.position("DU123", 3691937).unwrap().position);
+        assert_eq!(Some((950.0, 30.0)), state.pnl("DU123"));
+        assert_eq!(
+            Some(NaiveTime::from_hms_opt(15, 42, 0).unwrap()),
+            state.last_update()
+        );
+        assert!(state.is_downloaded("DU123"));
+
+        let received: Vec<AccountChange> = changes.try_iter().collect();
+        assert_eq!(7, received.len());
+        assert_eq!(
+            AccountChange::Portfolio {
+                account: "DU123".to_string(),
+                con_id: 265598
+            },
+            received[4]
+        );
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_account_state_multi() {
+        let mut state = AccountState::new();
+        let change = state.update(&ServerRspMsg::AccountUpdateMulti {
+            req_id: 7,
+            account: "DU123".to_string(),
+            model_code: "GROWTH".to_string(),
+            key: "NetLiquidation".to_string(),
+            value: "5000.5".to_string(),
+            currency: "USD".to_string(),
+        });
+
+        assert_eq!(
+            Some(AccountChange::Value {
+                account: "DU123".to_string(),
+                model_code: "GROWTH".to_string(),
+                key: "NetLiquidation".to_string(),
+                currency: "USD".to_string(),
+            }),
+            change
+        );
+        assert_eq!(None, state.value("DU123", "NetLiquidation", "USD"));
+        assert_eq!(
+            Some(dec!(5000.5)),
+            state
+                .model_values("DU123", "GROWTH")
+                .unwrap()
+                .value("NetLiquidation", "USD")
+        );
+        assert_eq!(
+            Some(AccountChange::MultiEnd { req_id: 7 }),
+            state.update(&ServerRspMsg::AccountUpdateMultiEnd { req_id: 7 })
+        );
+    }
+}
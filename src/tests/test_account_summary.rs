@@ -0,0 +1,112 @@
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use crate::core::account_summary::AccountSummary;
+    use crate::core::account_summary_tags::AccountSummaryTags;
+    use crate::core::messages::ServerRspMsg;
+
+    fn summary_event(
+        req_id: i32,
+        account: &str,
+        tag: &str,
+        value: &str,
+        currency: &str,
+    ) -> ServerRspMsg {
+        ServerRspMsg::AccountSummary {
+            req_id,
+            account: account.to_string(),
+            tag: tag.to_string(),
+            value: value.to_string(),
+            currency: currency.to_string(),
+        }
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_account_summary_tags() {
+        assert_eq!("RegTEquity", AccountSummaryTags::RegTEquity.to_string());
+        assert_eq!(
+            "$LEDGER:EUR",
+            AccountSummaryTags::LedgerCurrency("EUR".to_string()).to_string()
+        );
+        let all_tags = AccountSummaryTags::AllTags.to_string();
+        assert_eq!(29, all_tags.split(',').count());
+        assert!(all_tags.starts_with("AccountType,NetLiquidation,"));
+        assert_eq!(
+            "NetLiquidation,$LEDGER:ALL",
+            AccountSummaryTags::join(&[
+                AccountSummaryTags::NetLiquidation,
+                AccountSummaryTags::LedgerAll
+            ])
+        );
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_account_summary_collects_events() {
+        let mut summary = AccountSummary::new(9001);
+
+        assert!(summary.update(&summary_event(
+            9001,
+            "DU123",
+            "AccountType",
+            "INDIVIDUAL",
+            ""
+        )));
+        summary.update(&summary_event(
+            9001,
+            "DU123",
+            "NetLiquidation",
+            "100250.37",
+            "USD",
+        ));
+        summary.update(&summary_event(
+            9001,
+            "DU123",
+            "CashBalance",
+            "-1500.5",
+            "EUR",
+        ));
+        summary.update(&summary_event(
+            9001,
+            "DU456",
+            "NetLiquidation",
+            "5000",
+            "USD",
+        ));
+        assert!(!summary.update(&summary_event(9002, "DU123", "NetLiquidation", "1", "USD")));
+        assert!(!summary.is_complete());
+        assert!(summary.update(&ServerRspMsg::AccountSummaryEnd { req_id: 9001 }));
+        assert!(summary.is_complete());
+
+        assert_eq!(
+            vec!["DU123", "DU456"],
+            summary.accounts().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Some(dec!(100250.37)),
+            summary.value("DU123", "NetLiquidation", "USD")
+        );
+        let account = summary.account("DU123").unwrap();
+        assert_eq!(Some("INDIVIDUAL"), account.text("AccountType"));
+        assert_eq!(Some(dec!(-1500.5)), account.value("CashBalance", "EUR"));
+        assert_eq!(
+            Some(dec!(100250.37)),
+            account.get(&AccountSummaryTags::NetLiquidation)
+        );
+        assert_eq!(vec!["EUR", "USD"], account.currencies().collect::<Vec<_>>());
+
+        summary.update(&summary_event(
+            9001,
+            "DU123",
+            "NetLiquidation",
+            "100300",
+            "USD",
+        ));
+        assert_eq!(
+            Some(dec!(100300)),
+            summary.value("DU123", "NetLiquidation", "USD")
+        );
+    }
+}
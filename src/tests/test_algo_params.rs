@@ -0,0 +1,183 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::algo_params::{
+        fill_arrival_price_params, AdaptivePriority, AlgoStrategy, RiskAversion, TwapStrategyType,
+    };
+    use crate::core::common::TagValue;
+    use crate::core::errors::IBKRApiLibError;
+    use crate::core::order::Order;
+
+    fn tag_values(pairs: &[(&str, &str)]) -> Vec<TagValue> {
+        pairs
+            .iter()
+            .map(|(tag, value)| TagValue::new(tag.to_string(), value.to_string()))
+            .collect()
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_algo_strategy_round_trip() -> Result<(), IBKRApiLibError> {
+        let strategies = vec![
+            AlgoStrategy::Adaptive {
+                priority: AdaptivePriority::Urgent,
+            },
+            AlgoStrategy::Twap {
+                strategy_type: TwapStrategyType::MatchingMidpoint,
+                start_time: "09:00:00 US/Eastern".to_string(),
+                end_time: "16:00:00 US/Eastern".to_string(),
+                allow_past_end_time: true,
+                monetary_value: None,
+            },
+            AlgoStrategy::PctVolPx {
+                pct_vol: 0.1,
+                delta_pct_vol: 0.05,
+                min_pct_vol_4px: 0.01,
+                max_pct_vol_4px: 0.2,
+                start_time: "12:00:00 EST".to_string(),
+                end_time: "14:00:00 EST".to_string(),
+                no_take_liq: true,
+                monetary_value: Some(100000.0),
+            },
+            AlgoStrategy::AccumDistr {
+                component_size: 10,
+                time_between_orders: 60,
+                randomize_time_20: true,
+                randomize_size_55: false,
+                give_up: 1,
+                catch_up: true,
+                wait_for_fill: false,
+                active_time_start: "20161010-12:00:00 GMT".to_string(),
+                active_time_end: "20161010-16:00:00 GMT".to_string(),
+            },
+        ];
+
+        for strategy in strategies {
+            let mut order = Order::default();
+            strategy.apply_to(&mut order)?;
+            assert_eq!(strategy.name(), order.algo_strategy);
+            assert_eq!(Some(strategy), AlgoStrategy::from_order(&order)?);
+        }
+
+        let mut order = Order::default();
+        AlgoStrategy::Vwap {
+            max_pct_vol: 0.2,
+            start_time: "".to_string(),
+            end_time: "".to_string(),
+            allow_past_end_time: true,
+            no_take_liq: false,
+            monetary_value: None,
+        }
+        .apply_to(&mut order)?;
+        assert_eq!(
+            tag_values(&[
+                ("maxPctVol", "0.2"),
+                ("startTime", ""),
+                ("endTime", ""),
+                ("allowPastEndTime", "1"),
+                ("noTakeLiq", "0"),
+            ]),
+            order.algo_params
+        );
+
+        Ok(())
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_algo_strategy_ranges() {
+        let strategy = AlgoStrategy::MinImpact { max_pct_vol: 0.7 };
+        let mut order = Order::default();
+        match strategy.apply_to(&mut order) {
+            Err(IBKRApiLibError::InvalidAlgoParam {
+                strategy, param, ..
+            }) => {
+                assert_eq!("MinImpact", strategy);
+                assert_eq!("maxPctVol", param);
+            }
+            _ => panic!("expected an invalid algo parameter error"),
+        }
+        assert!(order.algo_strategy.is_empty());
+
+        let dark_ice = AlgoStrategy::DarkIce {
+            display_size: 0,
+            start_time: "".to_string(),
+            end_time: "".to_string(),
+            allow_past_end_time: false,
+            monetary_value: None,
+        };
+        assert!(dark_ice.validate().is_err());
+
+        let close = AlgoStrategy::ClosePx {
+            max_pct_vol: 0.4,
+            risk_aversion: RiskAversion::GetDone,
+            start_time: "".to_string(),
+            force_completion: true,
+            monetary_value: Some(-1.0),
+        };
+        assert!(close.validate().is_err());
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_algo_strategy_from_tag_values() -> Result<(), IBKRApiLibError> {
+        assert_eq!(None, AlgoStrategy::from_tag_values("", &[])?);
+
+        // Older samples sent flags as true/false and TWS may leave optional params out
+        let params = tag_values(&[
+            ("maxPctVol", "0.1"),
+            ("riskAversion", "Get Done"),
+            ("forceCompletion", "true"),
+        ]);
+        assert_eq!(
+            Some(AlgoStrategy::BalanceImpactRisk {
+                max_pct_vol: 0.1,
+                risk_aversion: RiskAversion::GetDone,
+                force_completion: true,
+            }),
+            AlgoStrategy::from_tag_values("BalanceImpactRisk", &params)?
+        );
+
+        let params = tag_values(&[("someParam", "1")]);
+        assert_eq!(
+            Some(AlgoStrategy::Other {
+                strategy: "NewAlgo".to_string(),
+                params: params.clone(),
+            }),
+            AlgoStrategy::from_tag_values("NewAlgo", &params)?
+        );
+
+        assert!(AlgoStrategy::from_tag_values("MinImpact", &[]).is_err());
+        let params = tag_values(&[("adaptivePriority", "Fast")]);
+        assert!(AlgoStrategy::from_tag_values("Adaptive", &params).is_err());
+
+        // The sample helpers write the same params as the typed strategy
+        let mut order = Order::default();
+        fill_arrival_price_params(
+            &mut order,
+            0.1,
+            RiskAversion::Aggressive,
+            "09:00:00 CET",
+            "16:00:00 CET",
+            true,
+            true,
+            100000,
+        );
+        assert_eq!("ArrivalPx", order.algo_strategy);
+        assert_eq!(
+            Some("100000"),
+            order
+                .algo_params
+                .iter()
+                .find(|param| param.tag == "monetaryValue")
+                .map(|param| param.value.as_str())
+        );
+        assert_eq!(
+            Some("ArrivalPx"),
+            AlgoStrategy::from_order(&order)?
+                .as_ref()
+                .map(AlgoStrategy::name)
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,151 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::common::{Right, SecIdType, SecType};
+    use crate::core::contract::{ComboLeg, Contract, ContractBuilder};
+    use crate::core::errors::IBKRApiLibError;
+
+    fn invalid_field(result: Result<(), IBKRApiLibError>) -> Option<&'static str> {
+        match result {
+            Err(IBKRApiLibError::InvalidContract { field, .. }) => Some(field),
+            _ => None,
+        }
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_contract_constructors() -> Result<(), IBKRApiLibError> {
+        let stock = Contract::stock("IBM", "SMART", "USD");
+        assert_eq!("STK", stock.sec_type);
+        assert_eq!("IBM", stock.symbol);
+        stock.validate()?;
+
+        let option = Contract::option("GOOG", "20261218", 150.0, Right::Put, "SMART", "USD");
+        assert_eq!("OPT", option.sec_type);
+        assert_eq!("P", option.right);
+        assert_eq!("20261218", option.last_trade_date_or_contract_month);
+        option.validate()?;
+
+        let future = Contract::future("ES", "202612", "CME", "USD");
+        assert_eq!("FUT", future.sec_type);
+        future.validate()?;
+
+        let forex = Contract::forex("EUR", "GBP");
+        assert_eq!(
+            ("CASH", "EUR", "IDEALPRO", "GBP"),
+            (
+                forex.sec_type.as_str(),
+                forex.symbol.as_str(),
+                forex.exchange.as_str(),
+                forex.currency.as_str()
+            )
+        );
+        forex.validate()?;
+
+        let bond = Contract::bond_by_cusip("912828C57", "SMART", "USD");
+        assert_eq!("BOND", bond.sec_type);
+        assert_eq!("CUSIP", bond.sec_id_type);
+        assert_eq!("912828C57", bond.sec_id);
+        bond.validate()?;
+
+        assert_eq!("IND", Contract::index("SPX", "CBOE", "USD").sec_type);
+        assert_eq!("CFD", Contract::cfd("IBDE30", "SMART", "EUR").sec_type);
+        assert_eq!("CRYPTO", Contract::crypto("ETH", "PAXOS", "USD").sec_type);
+
+        Ok(())
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_contract_builder() -> Result<(), IBKRApiLibError> {
+        let contract = ContractBuilder::new()
+            .sec_type(SecType::FOP)
+            .symbol("ES")
+            .last_trade_date_or_contract_month("202612")
+            .strike(4500.0)
+            .right(Right::Call)
+            .multiplier("50")
+            .exchange("CME")
+            .currency("USD")
+            .trading_class("ES")
+            .build()?;
+        assert_eq!("FOP", contract.sec_type);
+        assert_eq!("C", contract.right);
+        assert_eq!("50", contract.multiplier);
+
+        let by_isin = Contract::builder()
+            .sec_type(SecType::STK)
+            .sec_id(SecIdType::ISIN, "US45841N1072")
+            .exchange("SMART")
+            .currency("USD")
+            .build()?;
+        assert_eq!("ISIN", by_isin.sec_id_type);
+
+        let missing_strike = ContractBuilder::new()
+            .sec_type(SecType::OPT)
+            .symbol("GOOG")
+            .last_trade_date_or_contract_month("20261218")
+            .right(Right::Call)
+            .exchange("SMART")
+            .build();
+        match missing_strike {
+            Err(IBKRApiLibError::InvalidContract { sec_type, field }) => {
+                assert_eq!("OPT", sec_type);
+                assert_eq!("strike", field);
+            }
+            _ => panic!("expected an invalid contract error"),
+        }
+
+        Ok(())
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_contract_validate() {
+        assert_eq!(
+            Some("sec_type"),
+            invalid_field(Contract::default().validate())
+        );
+        assert_eq!(
+            Some("symbol"),
+            invalid_field(Contract::stock("", "SMART", "USD").validate())
+        );
+        assert_eq!(
+            Some("exchange"),
+            invalid_field(Contract::stock("IBM", "", "USD").validate())
+        );
+        assert_eq!(
+            Some("right"),
+            invalid_field(
+                Contract::option("GOOG", "20261218", 150.0, Right::None, "SMART", "USD").validate()
+            )
+        );
+        assert_eq!(
+            Some("last_trade_date_or_contract_month"),
+            invalid_field(Contract::future("ES", "", "CME", "USD").validate())
+        );
+        assert_eq!(
+            Some("currency"),
+            invalid_field(Contract::forex("EUR", "").validate())
+        );
+
+        let mut bag = Contract::default();
+        bag.sec_type = SecType::BAG.to_string();
+        bag.exchange = "SMART".to_string();
+        assert_eq!(Some("combo_legs"), invalid_field(bag.validate()));
+        bag.combo_legs.push(ComboLeg::default());
+        assert!(bag.validate().is_ok());
+
+        // A local symbol identifies a derivative without expiry, strike and right
+        let mut future = Contract::future("ES", "", "CME", "USD");
+        future.local_symbol = "ESZ6".to_string();
+        assert!(future.validate().is_ok());
+
+        // A con_id alone is enough, and lookups may leave everything but the symbol empty
+        let mut by_con_id = Contract::default();
+        by_con_id.con_id = 265598;
+        assert!(by_con_id.validate().is_ok());
+        let chain = Contract::option("GOOG", "", 0.0, Right::None, "", "USD");
+        assert!(chain.validate_for_lookup().is_ok());
+        assert!(chain.validate().is_err());
+    }
+}
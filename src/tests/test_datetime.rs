@@ -0,0 +1,93 @@
+#[cfg(test)]
+mod tests {
+    use chrono::{FixedOffset, NaiveDate, TimeZone, Utc};
+
+    use crate::core::common::{Action, BarData, OrderType};
+    use crate::core::datetime::{
+        format_news_datetime, format_tws_datetime, parse_tws_datetime, zone_offset,
+    };
+    use crate::core::order::OrderBuilder;
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_parse_tws_datetime() {
+        let utc = FixedOffset::east_opt(0).unwrap();
+        let login_tz = FixedOffset::west_opt(5 * 3600).unwrap();
+        let parse = |text| parse_tws_datetime(text, login_tz).map(|time| time.with_timezone(&Utc));
+        let expected = Utc.with_ymd_and_hms(2023, 7, 10, 13, 30, 0).unwrap();
+
+        assert_eq!(Some(expected), parse("20230710 09:30:00 US/Eastern"));
+        assert_eq!(Some(expected), parse("20230710 09:30:00 EDT"));
+        assert_eq!(Some(expected), parse("20230710 14:30:00 Europe/London"));
+        assert_eq!(Some(expected), parse("20230710 08:30:00"));
+        assert_eq!(Some(expected), parse("20230710  08:30:00"));
+        assert_eq!(Some(expected), parse("20230710-13:30:00"));
+        assert_eq!(Some(expected), parse("1688995800"));
+        assert_eq!(Some(expected), parse("2023-07-10 08:30:00.0"));
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2023, 1, 10, 14, 30, 0).unwrap()),
+            parse("20230110 09:30:00 US/Eastern")
+        );
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2023, 7, 10, 5, 0, 0).unwrap()),
+            parse("20230710")
+        );
+        assert_eq!(None, parse("20230710 09:30:00 Mars/Olympus"));
+        assert_eq!(None, parse("yesterday"));
+
+        let bar = BarData {
+            date: "20230710 09:30:00 US/Eastern".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            Some(expected),
+            bar.timestamp(utc).map(|time| time.with_timezone(&Utc))
+        );
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_format_tws_datetime() {
+        let new_york = FixedOffset::west_opt(4 * 3600).unwrap();
+        let time = new_york.with_ymd_and_hms(2023, 7, 10, 9, 30, 0).unwrap();
+
+        assert_eq!("20230710 13:30:00 UTC", format_tws_datetime(&time));
+        assert_eq!("2023-07-10 13:30:00.0", format_news_datetime(&time));
+
+        let order = OrderBuilder::new(Action::BUY, OrderType::Limit, 100.0)
+            .lmt_price(150.0)
+            .good_till(&time)
+            .build()
+            .unwrap();
+        assert_eq!("GTD", order.tif);
+        assert_eq!("20230710 13:30:00 UTC", order.good_till_date);
+        assert_eq!(Some(time), order.good_till_timestamp(new_york));
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_zone_offset() {
+        let offset = |name, y, m, d| {
+            let local = NaiveDate::from_ymd_opt(y, m, d)?.and_hms_opt(12, 0, 0)?;
+            zone_offset(name, &local).map(|offset| offset.local_minus_utc() / 3600)
+        };
+
+        assert_eq!(Some(1), offset("CET", 2024, 1, 15));
+        assert_eq!(Some(2), offset("CET", 2024, 7, 15));
+        assert_eq!(Some(2), offset("MET", 2024, 7, 15));
+        assert_eq!(Some(1), offset("WET", 2024, 7, 15));
+        assert_eq!(Some(3), offset("EET", 2024, 7, 15));
+        assert_eq!(Some(2), offset("CEST", 2024, 1, 15));
+        assert_eq!(Some(-5), offset("EST", 2024, 7, 15));
+        assert_eq!(None, offset("IST", 2024, 7, 15));
+
+        // 2007 moved the start from April to March and the end from October to November
+        assert_eq!(Some(-4), offset("US/Eastern", 2007, 3, 20));
+        assert_eq!(Some(-5), offset("US/Eastern", 2006, 3, 20));
+        assert_eq!(Some(-4), offset("US/Eastern", 2006, 4, 3));
+        assert_eq!(Some(-5), offset("US/Eastern", 2006, 11, 3));
+        assert_eq!(Some(-4), offset("US/Eastern", 2007, 11, 3));
+        assert_eq!(Some(-5), offset("US/Eastern", 1985, 4, 20));
+        assert_eq!(Some(-4), offset("US/Eastern", 1985, 4, 29));
+    }
+}
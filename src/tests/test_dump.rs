@@ -0,0 +1,280 @@
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use crate::core::account_summary_tags::AccountSummaryTags;
+    use crate::core::client::{ConnStatus, EClient, POISONED_MUTEX};
+    use crate::core::common::{BarSize, TickAttrib, TickByTickType, TickType, WhatToShow};
+    use crate::core::dump::{dump_bytes, dump_fields, parse_hex_dump, Direction, DumpedMessage};
+    use crate::core::encoder::Encoder;
+    use crate::core::errors::IBKRApiLibError;
+    use crate::core::messages::{make_message, ServerRspMsg};
+    use crate::core::server_versions::MIN_SERVER_VER_PRICE_MGMT_ALGO;
+    use crate::core::streamer::{Streamer, TestStreamer};
+    use crate::examples::contract_samples::us_stock_at_smart;
+
+    const SERVER_VERSION: i32 = MIN_SERVER_VER_PRICE_MGMT_ALGO;
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_parse_hex_dump_formats() -> Result<(), IBKRApiLibError> {
+        let expected = vec![0, 0, 0, 13, 54, 0, 50, 0, 49, 0, 68, 49, 50, 51, 52, 53, 0];
+
+        let decimal = "let expected: [u8; 17] = [0, 0, 0, 13, 54, 0, 50, 0, 49, 0, 68, 49, 50, 51,
+                       52, 53, 0];";
+        let decimal = &decimal[decimal.find('=').unwrap() + 1..];
+        assert_eq!(expected, parse_hex_dump(decimal)?);
+
+        let pairs = "00 00 00 0d 36 00 32 00 31 00 44 31 32 33 34 35 00 # ReqAcctData";
+        assert_eq!(expected, parse_hex_dump(pairs)?);
+
+        let prefixed = "0x00 0x00 0x00 0x0d 0x36 0x00 0x32 0x00 0x31 0x00 0x44 0x31 0x32 0x33 \
+                        0x34 0x35 0x00";
+        assert_eq!(expected, parse_hex_dump(prefixed)?);
+
+        let contiguous = "0000000d3600320031004431323334350a00";
+        assert!(parse_hex_dump(contiguous).is_ok());
+        assert!(parse_hex_dump("0000000d3").is_err());
+        assert!(parse_hex_dump("[0, 256]").is_err());
+
+        Ok(())
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_dump_outgoing_request() -> Result<(), IBKRApiLibError> {
+        let mut bytes = b"API\0".to_vec();
+        bytes.extend(make_message("v100..151")?);
+        bytes.extend(make_message("62\x001\x00100\x00All\x00NetLiquidation\x00")?);
+
+        let messages = dump_bytes(&bytes, Direction::Outgoing, SERVER_VERSION)?;
+        assert_eq!(2, messages.len());
+        assert_eq!("Handshake", messages[0].name);
+        assert_eq!(Some("v100..151"), messages[0].field("client_versions"));
+
+        let summary = &messages[1];
+        assert_eq!("ReqAccountSummary", summary.name);
+        assert_eq!(Some(62), summary.msg_id);
+        assert_eq!(Some("100"), summary.field("req_id"));
+        assert_eq!(Some("All"), summary.field("group_name"));
+        assert_eq!(Some("NetLiquidation"), summary.field("tags"));
+        assert!(summary.note.is_none());
+
+        let printed = summary.to_string();
+        assert!(printed.starts_with("-> ReqAccountSummary (62) [5 fields]"));
+        assert!(printed.contains("group_name = \"All\""));
+
+        Ok(())
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_dump_outgoing_past_layout() {
+        let fields: Vec<String> = vec!["92", "7", "DU123", "", "extra"]
+            .iter()
+            .map(|field| field.to_string())
+            .collect();
+
+        let dumped = dump_fields(&fields, Direction::Outgoing, SERVER_VERSION);
+        assert_eq!("ReqPnl", dumped.name);
+        assert_eq!(Some("DU123"), dumped.field("account"));
+        assert_eq!(Some("extra"), dumped.field("field[4]"));
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_dump_incoming_messages() -> Result<(), IBKRApiLibError> {
+        let encoder = Encoder::new(SERVER_VERSION);
+        let mut bytes = make_message("151\x0020240102 09:30:00 EST\x00")?;
+        bytes.extend(encoder.encode(&ServerRspMsg::TickPrice {
+            req_id: 7,
+            tick_type: TickType::Last,
+            price: 101.25,
+            tick_attr: TickAttrib::new(false, false, false),
+        })?);
+        bytes.extend(encoder.encode(&ServerRspMsg::NextValidId { order_id: 42 })?);
+
+        let messages = dump_bytes(&bytes, Direction::Incoming, SERVER_VERSION)?;
+        assert_eq!(3, messages.len());
+        assert_eq!("ServerVersion", messages[0].name);
+        assert_eq!(Some("151"), messages[0].field("server_version"));
+
+        // A last price carries its size, which the decoder splits into a second message
+        let tick = &messages[1];
+        assert_eq!("TickPrice", tick.name);
+        assert_eq!(Some("7"), tick.field("TickPrice[0].req_id"));
+        assert_eq!(Some("Last"), tick.field("TickPrice[0].tick_type"));
+        assert_eq!(Some("101.25"), tick.field("TickPrice[0].price"));
+        assert_eq!(
+            Some("false"),
+            tick.field("TickPrice[0].tick_attr.past_limit")
+        );
+        assert_eq!(Some("LastSize"), tick.field("TickSize[1].tick_type"));
+
+        assert_eq!("NextValidId", messages[2].name);
+        assert_eq!(Some("42"), messages[2].field("order_id"));
+
+        Ok(())
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_dump_malformed_input() -> Result<(), IBKRApiLibError> {
+        let fields: Vec<String> = vec!["1".to_string(), "6".to_string()];
+        let dumped = dump_fields(&fields, Direction::Incoming, SERVER_VERSION);
+        assert_eq!("TickPrice", dumped.name);
+        assert!(dumped.note.is_some());
+        assert_eq!(Some("6"), dumped.field("field[1]"));
+
+        let unknown: Vec<String> = vec!["999".to_string(), "x".to_string(), "y".to_string()];
+        let dumped = dump_fields(&unknown, Direction::Incoming, SERVER_VERSION);
+        assert_eq!("Unknown", dumped.name);
+        assert_eq!(Some("unknown message id"), dumped.note.as_deref());
+
+        let mut truncated = make_message("9\x001\x0042\x00")?;
+        truncated.truncate(truncated.len() - 2);
+        assert!(dump_bytes(&truncated, Direction::Incoming, SERVER_VERSION).is_err());
+
+        Ok(())
+    }
+
+    //------------------------------------------------------------------------------------------------
+    /// Dumps the requests written by `send` through a connected `EClient`
+    fn dump_sent(
+        send: impl FnOnce(&mut EClient) -> Result<(), IBKRApiLibError>,
+    ) -> Result<Vec<DumpedMessage>, IBKRApiLibError> {
+        let mut client = EClient::new();
+        *client.conn_state.lock().expect(POISONED_MUTEX) = ConnStatus::CONNECTED;
+        client.set_streamer(Option::from(
+            Box::new(TestStreamer::new()) as Box<dyn Streamer>
+        ));
+        client.server_version = SERVER_VERSION;
+
+        send(&mut client)?;
+        let mut buf = Vec::<u8>::new();
+        client.stream.as_mut().unwrap().read_to_end(&mut buf)?;
+        dump_bytes(&buf, Direction::Outgoing, SERVER_VERSION)
+    }
+
+    //------------------------------------------------------------------------------------------------
+    /// Every field `EClient` writes is labeled by the layout, which ends with the last field written
+    fn assert_layout(dumped: &DumpedMessage, last_label: &str) {
+        assert!(
+            dumped
+                .fields
+                .iter()
+                .all(|field| !field.label.starts_with("field[")),
+            "{} has fields past its layout",
+            dumped.name
+        );
+        assert_eq!(
+            dumped.raw_fields.len(),
+            dumped.fields.len(),
+            "{}",
+            dumped.name
+        );
+        assert_eq!(
+            Some(last_label),
+            dumped.fields.last().map(|field| field.label.as_str()),
+            "{}",
+            dumped.name
+        );
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_request_layouts_match_client() -> Result<(), IBKRApiLibError> {
+        let contract = us_stock_at_smart();
+        let messages = dump_sent(|client| {
+            client.req_mkt_data(1, &contract, "233", false, false, vec![])?;
+            client.cancel_mkt_data(1)?;
+            client.req_contract_details(2, &contract)?;
+            client.req_mkt_depth(3, &contract, 5, true, vec![])?;
+            client.req_real_time_bars(
+                4,
+                &contract,
+                BarSize::_5Secs,
+                WhatToShow::Trades,
+                true,
+                vec![],
+            )?;
+            client.req_head_time_stamp(5, &contract, WhatToShow::Trades, 1, 2)?;
+            client.req_tick_by_tick_data(6, &contract, TickByTickType::Last, 10, true)?;
+            client.req_account_summary(7, "All", &[AccountSummaryTags::NetLiquidation])?;
+            client.req_pnl_single(8, "DU123", "", 265598)?;
+            client.req_sec_def_opt_params(9, "MSFT", "", "STK", 272093)?;
+            client.req_news_article(10, "BRFG", "BRFG$0a1b", vec![])?;
+            client.req_matching_symbols(11, "MSF")?;
+            client.req_market_rule(26)
+        })?;
+
+        let names: Vec<&str> = messages.iter().map(|msg| msg.name.as_str()).collect();
+        assert_eq!(
+            vec![
+                "ReqMktData",
+                "CancelMktData",
+                "ReqContractData",
+                "ReqMktDepth",
+                "ReqRealTimeBars",
+                "ReqHeadTimestamp",
+                "ReqTickByTickData",
+                "ReqAccountSummary",
+                "ReqPnlSingle",
+                "ReqSecDefOptParams",
+                "ReqNewsArticle",
+                "ReqMatchingSymbols",
+                "ReqMarketRule",
+            ],
+            names
+        );
+
+        let mkt_data = &messages[0];
+        assert_eq!(Some("1"), mkt_data.field("req_id"));
+        assert_eq!(Some("MSFT"), mkt_data.field("contract.symbol"));
+        assert_eq!(Some("SMART"), mkt_data.field("contract.exchange"));
+
+        assert_layout(&messages[1], "req_id");
+
+        let contract_data = &messages[2];
+        assert_layout(contract_data, "contract.sec_id");
+        assert_eq!(Some("MSFT"), contract_data.field("contract.symbol"));
+        assert_eq!(Some("0"), contract_data.field("contract.include_expired"));
+
+        let depth = &messages[3];
+        assert_layout(depth, "mkt_depth_options");
+        assert_eq!(Some("5"), depth.field("num_rows"));
+        assert_eq!(Some("1"), depth.field("is_smart_depth"));
+
+        let bars = &messages[4];
+        assert_layout(bars, "real_time_bars_options");
+        assert_eq!(Some("TRADES"), bars.field("what_to_show"));
+        assert_eq!(Some("1"), bars.field("use_rth"));
+
+        let head = &messages[5];
+        assert_layout(head, "format_date");
+        assert_eq!(Some("TRADES"), head.field("what_to_show"));
+        assert_eq!(Some("2"), head.field("format_date"));
+
+        let tick_by_tick = &messages[6];
+        assert_layout(tick_by_tick, "ignore_size");
+        assert_eq!(Some("Last"), tick_by_tick.field("tick_type"));
+        assert_eq!(Some("10"), tick_by_tick.field("number_of_ticks"));
+
+        assert_layout(&messages[7], "tags");
+        assert_eq!(Some("NetLiquidation"), messages[7].field("tags"));
+
+        assert_layout(&messages[8], "con_id");
+        assert_eq!(Some("265598"), messages[8].field("con_id"));
+
+        assert_layout(&messages[9], "underlying_con_id");
+        assert_eq!(Some("STK"), messages[9].field("underlying_sec_type"));
+
+        assert_layout(&messages[10], "news_article_options");
+        assert_eq!(Some("BRFG$0a1b"), messages[10].field("article_id"));
+
+        assert_layout(&messages[11], "pattern");
+        assert_layout(&messages[12], "market_rule_id");
+
+        Ok(())
+    }
+}
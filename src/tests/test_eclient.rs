@@ -1,26 +1,35 @@
 #[cfg(test)]
 mod tests {
+    use crate::core::account_summary_tags::AccountSummaryTags;
     use crate::core::client::{ConnStatus, EClient, POISONED_MUTEX};
+    use chrono::{FixedOffset, TimeZone, Utc};
 
     use crate::core::{
         common::{
-            BarData, CommissionReport, DepthMktDataDescription, FaDataType, FamilyCode,
-            HistogramData, HistoricalTick, HistoricalTickBidAsk, HistoricalTickLast, NewsProvider,
-            PriceIncrement, RealTimeBar, SmartComponent, TickAttrib, TickAttribBidAsk,
-            TickAttribLast, TickByTickType, TickType,
+            BarData, BarSize, CommissionReport, DepthMktDataDescription, Duration, DurationUnit,
+            FaDataType, FamilyCode, HistogramData, HistoricalTick, HistoricalTickBidAsk,
+            HistoricalTickLast, NewsProvider, OcaType, PriceIncrement, RealTimeBar, Right,
+            SmartComponent, TickAttrib, TickAttribBidAsk, TickAttribLast, TickByTickType, TickType,
+            WhatToShow,
         },
         contract::{Contract, ContractDescription, ContractDetails, DeltaNeutralContract},
         execution::{Execution, ExecutionFilter},
+        historical_data::HistoricalDataRequest,
         order::{Order, SoftDollarTier},
         streamer::{Streamer, TestStreamer},
     };
     use crate::{
         core::{
+            dump::{dump_bytes, Direction},
             errors::IBKRApiLibError,
             messages::{read_fields, read_msg, ServerReqMsgDiscriminants},
             order::OrderState,
+            server_versions::{
+                ServerFeature, MIN_SERVER_VER_CASH_QTY, MIN_SERVER_VER_MARKET_RULES,
+            },
         },
         examples::contract_samples::simple_future,
+        examples::order_samples::{bracket_order, limit_order},
     };
     use std::sync::{Arc, Mutex};
 
@@ -38,6 +47,41 @@ mod tests {
         }
     }
 
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_replace_fa() -> Result<(), IBKRApiLibError> {
+        let app = Arc::new(Mutex::new(EClient::new()));
+
+        let version = 1;
+        let cxml = "<ListOfGroups/>";
+        let mut buf = Vec::<u8>::new();
+
+        let mut locked_app = app.lock().expect("EClient mutex was poisoned");
+
+        locked_app.connect_test();
+        locked_app.replace_fa(FaDataType::GROUPS, cxml)?;
+        locked_app.stream.as_mut().unwrap().read_to_end(&mut buf)?;
+
+        let expected: [u8; 27] = [
+            0, 0, 0, 23, 49, 57, 0, 49, 0, 49, 0, 60, 76, 105, 115, 116, 79, 102, 71, 114, 111,
+            117, 112, 115, 47, 62, 0,
+        ];
+
+        let msg_data = read_msg(buf.as_slice())?;
+        let fields = read_fields(&msg_data.1);
+
+        assert_eq!(expected.as_ref(), buf.as_slice());
+        assert_eq!(
+            ServerReqMsgDiscriminants::ReplaceFa as u8,
+            fields[0].parse::<u8>().unwrap()
+        );
+        assert_eq!(version, fields[1].parse::<i32>().unwrap());
+        assert_eq!(FaDataType::GROUPS as i32, fields[2].parse::<i32>().unwrap());
+        assert_eq!(cxml, fields[3]);
+
+        Ok(())
+    }
+
     //------------------------------------------------------------------------------------------------
     #[test]
     fn test_req_account_summary() -> Result<(), IBKRApiLibError> {
@@ -46,19 +90,22 @@ mod tests {
         let version = 2;
         let req_id = 100;
         let group_name = "MyGroup";
-        let tags = "tag1:tag_value1, tag2:tag_value2";
+        let tags = [
+            AccountSummaryTags::NetLiquidation,
+            AccountSummaryTags::LedgerCurrency("EUR".to_string()),
+        ];
         let mut buf = Vec::<u8>::new();
 
         let mut locked_app = app.lock().expect("EClient mutex was poisoned");
 
         locked_app.connect_test();
-        locked_app.req_account_summary(req_id, group_name, tags)?;
+        locked_app.req_account_summary(req_id, group_name, &tags)?;
         locked_app.stream.as_mut().unwrap().read_to_end(&mut buf)?;
 
-        let expected: [u8; 54] = [
-            0, 0, 0, 50, 54, 50, 0, 50, 0, 49, 48, 48, 0, 77, 121, 71, 114, 111, 117, 112, 0, 116,
-            97, 103, 49, 58, 116, 97, 103, 95, 118, 97, 108, 117, 101, 49, 44, 32, 116, 97, 103,
-            50, 58, 116, 97, 103, 95, 118, 97, 108, 117, 101, 50, 0,
+        let expected: [u8; 48] = [
+            0, 0, 0, 44, 54, 50, 0, 50, 0, 49, 48, 48, 0, 77, 121, 71, 114, 111, 117, 112, 0, 78,
+            101, 116, 76, 105, 113, 117, 105, 100, 97, 116, 105, 111, 110, 44, 36, 76, 69, 68, 71,
+            69, 82, 58, 69, 85, 82, 0,
         ];
 
         let msg_data = read_msg(buf.as_slice())?;
@@ -72,7 +119,7 @@ mod tests {
         assert_eq!(version, fields[1].parse::<i32>().unwrap());
         assert_eq!(req_id, fields[2].parse::<i32>().unwrap());
         assert_eq!(group_name, fields[3]);
-        assert_eq!(tags, fields[4]);
+        assert_eq!("NetLiquidation,$LEDGER:EUR", fields[4]);
 
         Ok(())
     }
@@ -391,4 +438,367 @@ mod tests {
 
         Ok(())
     }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_req_historical_data() -> Result<(), IBKRApiLibError> {
+        let app = Arc::new(Mutex::new(EClient::new()));
+
+        let mut buf = Vec::<u8>::new();
+
+        let mut locked_app = app.lock().expect("EClient mutex was poisoned");
+
+        locked_app.connect_test();
+        let contract = Contract::stock("AMZN", "SMART", "USD");
+        let request = HistoricalDataRequest::builder(
+            &contract,
+            Duration::new(1, DurationUnit::WEEK),
+            BarSize::_1Hour,
+            WhatToShow::Midpoint,
+        )
+        .use_rth(true)
+        .build()?;
+        locked_app.req_historical_data(4102, &request)?;
+        locked_app.stream.as_mut().unwrap().read_to_end(&mut buf)?;
+
+        let messages = dump_bytes(&buf, Direction::Outgoing, 151)?;
+        assert_eq!(1, messages.len());
+        let fields: Vec<Option<&str>> = [
+            "end_date_time",
+            "bar_size_setting",
+            "duration_str",
+            "use_rth",
+            "what_to_show",
+        ]
+        .iter()
+        .map(|name| messages[0].field(name))
+        .collect();
+        assert_eq!(
+            vec![
+                Some(""),
+                Some("1 hour"),
+                Some("1 W"),
+                Some("1"),
+                Some("MIDPOINT")
+            ],
+            fields
+        );
+
+        let result = locked_app.req_real_time_bars(
+            3001,
+            &contract,
+            BarSize::_1Min,
+            WhatToShow::Trades,
+            true,
+            vec![],
+        );
+        assert!(matches!(
+            result,
+            Err(IBKRApiLibError::InvalidHistoricalRequest { .. })
+        ));
+
+        Ok(())
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_req_historical_ticks() -> Result<(), IBKRApiLibError> {
+        let app = Arc::new(Mutex::new(EClient::new()));
+
+        let mut buf = Vec::<u8>::new();
+
+        let mut locked_app = app.lock().expect("EClient mutex was poisoned");
+
+        locked_app.connect_test();
+        let new_york = FixedOffset::west_opt(4 * 3600).unwrap();
+        let start = new_york.with_ymd_and_hms(2017, 7, 12, 17, 39, 33).unwrap();
+        locked_app.req_historical_ticks(
+            18001,
+            &Contract::stock("AMZN", "SMART", "USD"),
+            Some(start.with_timezone(&Utc)),
+            None,
+            10,
+            "TRADES",
+            1,
+            true,
+            vec![],
+        )?;
+        locked_app.stream.as_mut().unwrap().read_to_end(&mut buf)?;
+
+        let messages = dump_bytes(&buf, Direction::Outgoing, 151)?;
+        assert_eq!(1, messages.len());
+        assert_eq!(
+            Some("20170712 21:39:33 UTC"),
+            messages[0].field("start_date_time")
+        );
+        assert_eq!(Some(""), messages[0].field("end_date_time"));
+
+        Ok(())
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_req_matching_symbols_utf8() -> Result<(), IBKRApiLibError> {
+        let app = Arc::new(Mutex::new(EClient::new()));
+
+        let req_id = 103;
+        let pattern = "トヨタ";
+        let mut buf = Vec::<u8>::new();
+
+        let mut locked_app = app.lock().expect("EClient mutex was poisoned");
+
+        locked_app.connect_test();
+        locked_app.req_matching_symbols(req_id, pattern)?;
+        locked_app.stream.as_mut().unwrap().read_to_end(&mut buf)?;
+
+        // The length prefix counts the 3 bytes of each katakana character
+        let expected: [u8; 21] = [
+            0, 0, 0, 17, 56, 49, 0, 49, 48, 51, 0, 227, 131, 136, 227, 131, 168, 227, 130, 191, 0,
+        ];
+
+        let msg_data = read_msg(buf.as_slice())?;
+        let fields = read_fields(&msg_data.1);
+
+        assert_eq!(expected.as_ref(), buf.as_slice());
+        assert_eq!(
+            ServerReqMsgDiscriminants::ReqMatchingSymbols as u8,
+            fields[0].parse::<u8>().unwrap()
+        );
+        assert_eq!(req_id, fields[1].parse::<i32>().unwrap());
+        assert_eq!(pattern, fields[2]);
+
+        Ok(())
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_req_mkt_data_invalid_contract() -> Result<(), IBKRApiLibError> {
+        let app = Arc::new(Mutex::new(EClient::new()));
+
+        let mut buf = Vec::<u8>::new();
+
+        let mut locked_app = app.lock().expect("EClient mutex was poisoned");
+
+        locked_app.connect_test();
+        let contract = Contract::option("GOOG", "20261218", 150.0, Right::None, "SMART", "USD");
+        let result = locked_app.req_mkt_data(103, &contract, "", false, false, vec![]);
+        locked_app.stream.as_mut().unwrap().read_to_end(&mut buf)?;
+
+        match result {
+            Err(IBKRApiLibError::InvalidContract { sec_type, field }) => {
+                assert_eq!("OPT", sec_type);
+                assert_eq!("right", field);
+            }
+            _ => panic!("expected an invalid contract error"),
+        }
+        assert!(buf.is_empty());
+
+        Ok(())
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_place_bracket() -> Result<(), IBKRApiLibError> {
+        let app = Arc::new(Mutex::new(EClient::new()));
+
+        let mut buf = Vec::<u8>::new();
+
+        let mut locked_app = app.lock().expect("EClient mutex was poisoned");
+
+        locked_app.connect_test();
+        let contract = Contract::stock("AMZN", "SMART", "USD");
+        let (parent, take_profit, stop_loss) = bracket_order(0, "BUY", 100.0, 30.0, 40.0, 20.0);
+
+        let result = locked_app.place_bracket(
+            &contract,
+            parent.clone(),
+            take_profit.clone(),
+            stop_loss.clone(),
+        );
+        assert!(matches!(result, Err(IBKRApiLibError::NoValidOrderId)));
+
+        locked_app.set_next_order_id(10);
+        let group = locked_app.place_bracket(&contract, parent, take_profit, stop_loss)?;
+        locked_app.stream.as_mut().unwrap().read_to_end(&mut buf)?;
+
+        assert_eq!(vec![10, 11, 12], group.order_ids());
+        assert_eq!(Some(13), locked_app.next_order_id());
+
+        let messages = dump_bytes(&buf, Direction::Outgoing, 151)?;
+        assert_eq!(3, messages.len());
+        let fields: Vec<(&str, &str, &str)> = messages
+            .iter()
+            .map(|msg| {
+                (
+                    msg.field("order_id").unwrap(),
+                    msg.field("order.parent_id").unwrap(),
+                    msg.field("order.transmit").unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            vec![("10", "0", "0"), ("11", "10", "0"), ("12", "10", "1")],
+            fields
+        );
+
+        Ok(())
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_place_oca_group() -> Result<(), IBKRApiLibError> {
+        let app = Arc::new(Mutex::new(EClient::new()));
+
+        let mut buf = Vec::<u8>::new();
+
+        let mut locked_app = app.lock().expect("EClient mutex was poisoned");
+
+        locked_app.connect_test();
+        locked_app.set_next_order_id(20);
+        let orders = vec![
+            (
+                Contract::stock("AMZN", "SMART", "USD"),
+                limit_order("BUY", 100.0, 30.0),
+            ),
+            (
+                Contract::stock("GOOG", "SMART", "USD"),
+                limit_order("BUY", 50.0, 120.0),
+            ),
+        ];
+        let mut group = locked_app.place_oca_group("OCA_1", OcaType::CancelWithBlocking, orders)?;
+        locked_app.stream.as_mut().unwrap().read_to_end(&mut buf)?;
+
+        let messages = dump_bytes(&buf, Direction::Outgoing, 151)?;
+        assert_eq!(2, messages.len());
+        assert_eq!(Some("20"), messages[0].field("order_id"));
+        assert_eq!(Some("OCA_1"), messages[0].field("order.oca_group"));
+        assert_eq!(Some("0"), messages[0].field("order.transmit"));
+        assert_eq!(Some("GOOG"), messages[1].field("contract.symbol"));
+        assert_eq!(Some("1"), messages[1].field("order.transmit"));
+        assert_eq!(1, group.members()[1].order.oca_type);
+
+        buf.clear();
+        group.modify(&mut locked_app, |order| order.lmt_price += 1.0)?;
+        group.cancel(&mut locked_app)?;
+        locked_app.stream.as_mut().unwrap().read_to_end(&mut buf)?;
+
+        let messages = dump_bytes(&buf, Direction::Outgoing, 151)?;
+        let names: Vec<&str> = messages.iter().map(|msg| msg.name.as_str()).collect();
+        assert_eq!(
+            vec!["PlaceOrder", "PlaceOrder", "CancelOrder", "CancelOrder"],
+            names
+        );
+        assert_eq!(Some("31"), messages[0].field("order.lmt_price"));
+
+        Ok(())
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_place_oca_group_rejects_small_groups() -> Result<(), IBKRApiLibError> {
+        let app = Arc::new(Mutex::new(EClient::new()));
+
+        let mut buf = Vec::<u8>::new();
+
+        let mut locked_app = app.lock().expect("EClient mutex was poisoned");
+
+        locked_app.connect_test();
+        locked_app.set_next_order_id(20);
+
+        let result = locked_app.place_oca_group("OCA_1", OcaType::CancelWithBlocking, vec![]);
+        assert!(matches!(result, Err(IBKRApiLibError::InvalidOrder { .. })));
+
+        let orders = vec![(
+            Contract::stock("AMZN", "SMART", "USD"),
+            limit_order("BUY", 100.0, 30.0),
+        )];
+        let result = locked_app.place_oca_group("OCA_1", OcaType::CancelWithBlocking, orders);
+        assert!(matches!(result, Err(IBKRApiLibError::InvalidOrder { .. })));
+
+        assert_eq!(Some(20), locked_app.next_order_id());
+        locked_app.stream.as_mut().unwrap().read_to_end(&mut buf)?;
+        assert!(buf.is_empty());
+
+        Ok(())
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_place_oca_group_cancels_sent_orders_on_failure() -> Result<(), IBKRApiLibError> {
+        let app = Arc::new(Mutex::new(EClient::new()));
+
+        let mut buf = Vec::<u8>::new();
+
+        let mut locked_app = app.lock().expect("EClient mutex was poisoned");
+
+        locked_app.connect_test();
+        locked_app.server_version = MIN_SERVER_VER_CASH_QTY - 1;
+        locked_app.set_next_order_id(20);
+
+        let mut cash_order = limit_order("BUY", 0.0, 120.0);
+        cash_order.cash_qty = 5000.0;
+        let orders = vec![
+            (
+                Contract::stock("AMZN", "SMART", "USD"),
+                limit_order("BUY", 100.0, 30.0),
+            ),
+            (Contract::stock("GOOG", "SMART", "USD"), cash_order),
+        ];
+        let result = locked_app.place_oca_group("OCA_1", OcaType::CancelWithBlocking, orders);
+        assert!(matches!(
+            result,
+            Err(IBKRApiLibError::Unsupported {
+                feature: ServerFeature::CashQty,
+                ..
+            })
+        ));
+
+        locked_app.stream.as_mut().unwrap().read_to_end(&mut buf)?;
+        let messages = dump_bytes(&buf, Direction::Outgoing, MIN_SERVER_VER_CASH_QTY - 1)?;
+        let sent: Vec<(&str, &str)> = messages
+            .iter()
+            .map(|msg| (msg.name.as_str(), msg.field("order_id").unwrap()))
+            .collect();
+        assert_eq!(vec![("PlaceOrder", "20"), ("CancelOrder", "20")], sent);
+
+        Ok(())
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_unsupported_feature() -> Result<(), IBKRApiLibError> {
+        let app = Arc::new(Mutex::new(EClient::new()));
+
+        let mut locked_app = app.lock().expect("EClient mutex was poisoned");
+
+        locked_app.connect_test();
+        assert!(locked_app.supports(ServerFeature::PriceMgmtAlgo));
+        assert!(locked_app.capabilities().unsupported.is_empty());
+
+        locked_app.server_version = MIN_SERVER_VER_MARKET_RULES - 1;
+        assert!(!locked_app.supports(ServerFeature::MarketRules));
+        assert!(locked_app
+            .capabilities()
+            .unsupported
+            .contains(&ServerFeature::MarketRules));
+
+        match locked_app.req_market_rule(26) {
+            Err(IBKRApiLibError::Unsupported {
+                feature,
+                server_version,
+                required,
+            }) => {
+                assert_eq!(ServerFeature::MarketRules, feature);
+                assert_eq!(MIN_SERVER_VER_MARKET_RULES - 1, server_version);
+                assert_eq!(MIN_SERVER_VER_MARKET_RULES, required);
+            }
+            other => panic!("expected Unsupported error, got {:?}", other),
+        }
+
+        let mut buf = Vec::<u8>::new();
+        locked_app.stream.as_mut().unwrap().read_to_end(&mut buf)?;
+        assert!(buf.is_empty());
+
+        Ok(())
+    }
 }
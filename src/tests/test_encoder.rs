@@ -0,0 +1,624 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc::channel;
+    use std::sync::{Arc, Mutex};
+
+    use crate::core::algo_params::{AdaptivePriority, AlgoStrategy};
+    use crate::core::client::ConnStatus;
+    use crate::core::common::{
+        BarData, CommissionReport, HistoricalTickLast, TagValue, TickAttrib, TickAttribBidAsk,
+        TickAttribLast, TickMsgType, TickType, UNSET_DOUBLE,
+    };
+    use crate::core::contract::{ContractDetails, DeltaNeutralContract};
+    use crate::core::decoder::Decoder;
+    use crate::core::encoder::Encoder;
+    use crate::core::errors::IBKRApiLibError;
+    use crate::core::execution::Execution;
+    use crate::core::messages::{read_fields, read_msg, ServerRspMsg};
+    use crate::core::order::OrderState;
+    use crate::core::order_condition::{ExecutionCondition, OrderConditionEnum};
+    use crate::core::server_versions::{
+        MIN_SERVER_VER_LAST_LIQUIDITY, MIN_SERVER_VER_MARKET_CAP_PRICE,
+        MIN_SERVER_VER_PRICE_MGMT_ALGO,
+    };
+    use crate::examples::{contract_samples, order_samples};
+
+    const SERVER_VERSION: i32 = MIN_SERVER_VER_PRICE_MGMT_ALGO;
+
+    fn decode(
+        server_version: i32,
+        fields: &[String],
+    ) -> Result<Vec<ServerRspMsg>, IBKRApiLibError> {
+        let (_msg_sender, msg_queue) = channel::<String>();
+        let (send_queue, received) = channel::<ServerRspMsg>();
+        let mut decoder = Decoder::new(
+            msg_queue,
+            send_queue,
+            server_version,
+            Arc::new(Mutex::new(ConnStatus::CONNECTED)),
+        );
+        decoder.interpret(fields)?;
+        Ok(received.try_iter().collect())
+    }
+
+    fn fields_of(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    fn assert_decodes_to(fields: &[&str], expected: ServerRspMsg) -> Result<(), IBKRApiLibError> {
+        let decoded = decode(SERVER_VERSION, &fields_of(fields))?;
+        assert_eq!(1, decoded.len());
+        assert_eq!(format!("{:?}", expected), format!("{:?}", decoded[0]));
+        Ok(())
+    }
+
+    // Encode to a framed message, read it back and run it through the decoder
+    fn round_trip(
+        server_version: i32,
+        msg: &ServerRspMsg,
+    ) -> Result<Vec<ServerRspMsg>, IBKRApiLibError> {
+        let encoded = Encoder::new(server_version).encode(msg)?;
+        let (size, text, remaining) = read_msg(&encoded)?;
+        assert_eq!(encoded.len(), size + 4);
+        assert!(remaining.is_empty());
+        decode(server_version, &read_fields(&text))
+    }
+
+    fn assert_round_trip(server_version: i32, msg: ServerRspMsg) -> Result<(), IBKRApiLibError> {
+        let decoded = round_trip(server_version, &msg)?;
+        assert_eq!(1, decoded.len());
+        assert_eq!(format!("{:?}", msg), format!("{:?}", decoded[0]));
+        Ok(())
+    }
+
+    // Re-encoding the decoded message must produce identical fields
+    fn assert_stable_encoding(
+        server_version: i32,
+        msg: &ServerRspMsg,
+    ) -> Result<ServerRspMsg, IBKRApiLibError> {
+        let encoder = Encoder::new(server_version);
+        let fields = encoder.encode_fields(msg)?;
+        let mut decoded = decode(server_version, &fields)?;
+        assert_eq!(1, decoded.len());
+        let decoded = decoded.remove(0);
+        assert_eq!(fields, encoder.encode_fields(&decoded)?);
+        Ok(decoded)
+    }
+
+    #[test]
+    fn test_simple_messages_round_trip() -> Result<(), IBKRApiLibError> {
+        let messages = vec![
+            ServerRspMsg::TickSize {
+                req_id: 1,
+                tick_type: TickType::BidSize,
+                size: 300,
+            },
+            ServerRspMsg::TickString {
+                req_id: 1,
+                tick_type: TickType::LastTimestamp,
+                value: "1600000000".to_string(),
+            },
+            ServerRspMsg::TickGeneric {
+                ticker_id: 2,
+                tick_type: TickType::Halted,
+                value: 1.0,
+            },
+            ServerRspMsg::TickEfp {
+                ticker_id: 3,
+                tick_type: TickType::BidEfpComputation,
+                basis_points: 1.5,
+                formatted_basis_points: "1.50".to_string(),
+                implied_futures_price: 3000.25,
+                hold_days: 30,
+                future_last_trade_date: "20211217".to_string(),
+                dividend_impact: 0.5,
+                dividends_to_last_trade_date: 0.75,
+            },
+            ServerRspMsg::ErrMsg {
+                req_id: -1,
+                error_code: 2104,
+                error_str: "Market data farm connection is OK:usfarm".to_string(),
+            },
+            ServerRspMsg::AcctValue {
+                key: "NetLiquidation".to_string(),
+                val: "100000.00".to_string(),
+                currency: "USD".to_string(),
+                account_name: "DU123456".to_string(),
+            },
+            ServerRspMsg::NextValidId { order_id: 42 },
+            ServerRspMsg::MarketDepthL2 {
+                req_id: 4,
+                position: 0,
+                market_maker: "NSDQ".to_string(),
+                operation: 1,
+                side: 0,
+                price: 101.5,
+                size: 200,
+                is_smart_depth: true,
+            },
+            ServerRspMsg::CommissionReport {
+                commission_report: CommissionReport::new(
+                    "0001f4e8.5f8d1d7b.01.01".to_string(),
+                    1.0,
+                    "USD".to_string(),
+                    UNSET_DOUBLE,
+                    UNSET_DOUBLE,
+                    "".to_string(),
+                ),
+            },
+            ServerRspMsg::HeadTimestamp {
+                req_id: 5,
+                head_timestamp: "19801212  14:30:00".to_string(),
+            },
+            ServerRspMsg::VerifyMessageApi {
+                api_data: "data".to_string(),
+            },
+            ServerRspMsg::VerifyCompleted {
+                is_successful: true,
+                error_text: "".to_string(),
+            },
+            ServerRspMsg::DeltaNeutralValidation {
+                req_id: 6,
+                delta_neutral_contract: DeltaNeutralContract::new(12087792, 0.5, 150.0),
+            },
+            ServerRspMsg::HistoricalTicksLast {
+                req_id: 7,
+                ticks: vec![HistoricalTickLast::new(
+                    1600000000,
+                    TickAttribLast::new(true, false),
+                    100.25,
+                    100,
+                    "ARCA".to_string(),
+                    "  T".to_string(),
+                )],
+                done: true,
+            },
+            ServerRspMsg::TickByTick {
+                req_id: 8,
+                tick_type: 3,
+                time: 1600000000,
+                tick_msg: TickMsgType::BidAsk {
+                    bid_price: 100.0,
+                    ask_price: 100.5,
+                    bid_size: 10,
+                    ask_size: 20,
+                    tick_attrib_bid_ask: TickAttribBidAsk::new(false, true),
+                },
+            },
+            ServerRspMsg::OpenOrderEnd,
+        ];
+
+        for msg in messages {
+            assert_round_trip(SERVER_VERSION, msg)?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_tick_price_round_trip() -> Result<(), IBKRApiLibError> {
+        let encoder = Encoder::new(SERVER_VERSION);
+        let fields = encoder.encode_tick_price_fields(9, TickType::Bid, 101.25, 500, 1 | 4);
+        let decoded = decode(SERVER_VERSION, &fields)?;
+
+        assert_eq!(2, decoded.len());
+        let expected_price = ServerRspMsg::TickPrice {
+            req_id: 9,
+            tick_type: TickType::Bid,
+            price: 101.25,
+            tick_attr: TickAttrib::new(true, false, true),
+        };
+        let expected_size = ServerRspMsg::TickSize {
+            req_id: 9,
+            tick_type: TickType::BidSize,
+            size: 500,
+        };
+        assert_eq!(format!("{:?}", expected_price), format!("{:?}", decoded[0]));
+        assert_eq!(format!("{:?}", expected_size), format!("{:?}", decoded[1]));
+        assert_eq!(
+            fields,
+            encoder.encode_tick_price_fields(9, TickType::Bid, 101.25, 500, 5)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_order_status_round_trip() -> Result<(), IBKRApiLibError> {
+        let msg = ServerRspMsg::OrderStatus {
+            order_id: 10,
+            status: "Submitted".to_string(),
+            filled: 0.0,
+            remaining: 100.0,
+            avg_fill_price: 0.0,
+            perm_id: 123456,
+            parent_id: 0,
+            last_fill_price: 0.0,
+            client_id: 0,
+            why_held: "".to_string(),
+            mkt_cap_price: 0.0,
+        };
+        assert_round_trip(SERVER_VERSION, msg.clone())?;
+
+        // older servers send a version field and no market cap price
+        let old_version = MIN_SERVER_VER_MARKET_CAP_PRICE - 1;
+        let fields = Encoder::new(old_version).encode_fields(&msg)?;
+        assert_eq!(
+            Encoder::new(SERVER_VERSION).encode_fields(&msg)?.len(),
+            fields.len()
+        );
+        assert_round_trip(old_version, msg)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_open_order_round_trip() -> Result<(), IBKRApiLibError> {
+        let mut contract = contract_samples::stock_combo_contract();
+        contract.con_id = 28812380;
+        contract.delta_neutral_contract = Some(DeltaNeutralContract::new(12087792, 0.5, 150.0));
+
+        let mut order = order_samples::limit_order("BUY", 100.0, 150.25);
+        order.perm_id = 1234567;
+        order.account = "DU123456".to_string();
+        order.algo_strategy = "Adaptive".to_string();
+        order.algo_params = vec![TagValue::new(
+            "adaptivePriority".to_string(),
+            "Normal".to_string(),
+        )];
+        order.smart_combo_routing_params =
+            vec![TagValue::new("NonGuaranteed".to_string(), "1".to_string())];
+        order.conditions = vec![OrderConditionEnum::Execution(ExecutionCondition::new(
+            "STK".to_string(),
+            "SMART".to_string(),
+            "IBM".to_string(),
+        ))];
+        order.conditions_cancel_order = true;
+
+        let mut order_state = OrderState::default();
+        order_state.status = "PreSubmitted".to_string();
+        order_state.init_margin_after = "1000.5".to_string();
+        order_state.commission = 1.25;
+        order_state.min_commission = UNSET_DOUBLE;
+        order_state.max_commission = UNSET_DOUBLE;
+
+        let msg = ServerRspMsg::OpenOrder {
+            order_id: 11,
+            contract,
+            order,
+            order_state,
+        };
+
+        for server_version in &[MIN_SERVER_VER_LAST_LIQUIDITY, SERVER_VERSION] {
+            let decoded = assert_stable_encoding(*server_version, &msg)?;
+            if let ServerRspMsg::OpenOrder {
+                order_id,
+                contract,
+                order,
+                order_state,
+            } = decoded
+            {
+                assert_eq!(11, order_id);
+                assert_eq!(11, order.order_id);
+                assert_eq!(28812380, contract.con_id);
+                assert_eq!(2, contract.combo_legs.len());
+                assert_eq!(0.5, contract.delta_neutral_contract.unwrap().delta);
+                assert_eq!(150.25, order.lmt_price);
+                assert_eq!(UNSET_DOUBLE, order.aux_price);
+                assert_eq!("Adaptive", order.algo_strategy);
+                assert_eq!("Normal", order.algo_params[0].value);
+                assert_eq!(
+                    Some(AlgoStrategy::Adaptive {
+                        priority: AdaptivePriority::Normal
+                    }),
+                    AlgoStrategy::from_order(&order)?
+                );
+                assert_eq!(1, order.conditions.len());
+                assert!(order.conditions_cancel_order);
+                assert_eq!("PreSubmitted", order_state.status);
+                assert_eq!("1000.5", order_state.init_margin_after);
+                assert_eq!(1.25, order_state.commission);
+                assert_eq!(UNSET_DOUBLE, order_state.min_commission);
+            } else {
+                panic!("Expected OpenOrder, got {:?}", decoded);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_completed_order_round_trip() -> Result<(), IBKRApiLibError> {
+        let mut order = order_samples::stop("SELL", 50.0, 99.5);
+        order.perm_id = 7654321;
+        order.filled_quantity = 50.0;
+        let mut order_state = OrderState::default();
+        order_state.status = "Filled".to_string();
+        order_state.completed_time = "20210101 10:00:00 EST".to_string();
+        order_state.completed_status = "Filled Size: 50".to_string();
+
+        let msg = ServerRspMsg::CompletedOrder {
+            contract: contract_samples::usstock(),
+            order,
+            order_state,
+        };
+
+        let decoded = assert_stable_encoding(SERVER_VERSION, &msg)?;
+        if let ServerRspMsg::CompletedOrder {
+            contract,
+            order,
+            order_state,
+        } = decoded
+        {
+            assert_eq!("AMZN", contract.symbol);
+            assert_eq!(99.5, order.aux_price);
+            assert_eq!(50.0, order.filled_quantity);
+            assert_eq!("Filled Size: 50", order_state.completed_status);
+        } else {
+            panic!("Expected CompletedOrder, got {:?}", decoded);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_contract_data_round_trip() -> Result<(), IBKRApiLibError> {
+        let mut contract_details = ContractDetails::default();
+        contract_details.contract = contract_samples::us_option_contract();
+        contract_details.contract.con_id = 12345;
+        contract_details.contract.primary_exchange = "CBOE".to_string();
+        contract_details.contract.local_symbol = "GOOG  170120C00775000".to_string();
+        contract_details.contract.trading_class = "GOOG".to_string();
+        contract_details.last_trade_time = "15:00".to_string();
+        contract_details.market_name = "GOOG".to_string();
+        contract_details.min_tick = 0.01;
+        contract_details.md_size_multiplier = 1;
+        contract_details.order_types = "ACTIVETIM,AD,ADJUST,ALERT".to_string();
+        contract_details.valid_exchanges = "SMART,AMEX,CBOE".to_string();
+        contract_details.price_magnifier = 1;
+        contract_details.under_con_id = 208813719;
+        contract_details.long_name = "ALPHABET INC-CL C".to_string();
+        contract_details.contract_month = "201701".to_string();
+        contract_details.time_zone_id = "US/Eastern".to_string();
+        contract_details.trading_hours = "20170119:0930-1600;20170120:0930-1600".to_string();
+        contract_details.liquid_hours = "20170119:0930-1600;20170120:0930-1600".to_string();
+        contract_details.ev_multiplier = 1.0;
+        contract_details.sec_id_list = vec![TagValue::new(
+            "ISIN".to_string(),
+            "US38259P5089".to_string(),
+        )];
+        contract_details.agg_group = 2;
+        contract_details.under_symbol = "GOOG".to_string();
+        contract_details.under_sec_type = "STK".to_string();
+        contract_details.market_rule_ids = "32,32,32".to_string();
+        contract_details.real_expiration_date = "20170120".to_string();
+
+        assert_round_trip(
+            SERVER_VERSION,
+            ServerRspMsg::ContractData {
+                req_id: 12,
+                contract_details,
+            },
+        )
+    }
+
+    #[test]
+    fn test_bond_contract_data_round_trip() -> Result<(), IBKRApiLibError> {
+        let mut contract_details = ContractDetails::default();
+        contract_details.contract.symbol = "IBCID12345".to_string();
+        contract_details.contract.sec_type = "BOND".to_string();
+        contract_details.contract.exchange = "SMART".to_string();
+        contract_details.contract.currency = "USD".to_string();
+        contract_details.contract.con_id = 147554578;
+        contract_details.cusip = "912828C57".to_string();
+        contract_details.coupon = 2.25;
+        contract_details.maturity = "20240331".to_string();
+        contract_details.last_trade_time = "16:00".to_string();
+        contract_details.time_zone_id = "EST".to_string();
+        contract_details.callable = true;
+        contract_details.next_option_partial = true;
+        contract_details.ev_multiplier = 1.0;
+        contract_details.market_rule_ids = "26".to_string();
+
+        assert_round_trip(
+            SERVER_VERSION,
+            ServerRspMsg::BondContractData {
+                req_id: 13,
+                contract_details,
+            },
+        )
+    }
+
+    #[test]
+    fn test_execution_data_round_trip() -> Result<(), IBKRApiLibError> {
+        let mut contract = contract_samples::usstock();
+        contract.con_id = 756733;
+        contract.exchange = "ARCA".to_string();
+        let mut execution = Execution::default();
+        execution.order_id = 14;
+        execution.exec_id = "0000e0d5.5f8d1d7b.01.01".to_string();
+        execution.time = "20201019  09:30:00".to_string();
+        execution.acct_number = "DU123456".to_string();
+        execution.exchange = "ARCA".to_string();
+        execution.side = "BOT".to_string();
+        execution.shares = 100.0;
+        execution.price = 345.67;
+        execution.cum_qty = 100.0;
+        execution.avg_price = 345.67;
+        execution.ev_multiplier = 1.0;
+        execution.last_liquidity = 2;
+
+        let msg = ServerRspMsg::ExecutionData {
+            req_id: 15,
+            contract,
+            execution,
+        };
+        assert_round_trip(SERVER_VERSION, msg.clone())?;
+        assert_stable_encoding(MIN_SERVER_VER_LAST_LIQUIDITY - 1, &msg)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_historical_data_round_trip() -> Result<(), IBKRApiLibError> {
+        let bars = vec![
+            BarData::new("20201019".to_string(), 1.0, 2.0, 0.5, 1.5, 1000, 10, 1.25),
+            BarData::new("20201020".to_string(), 1.5, 2.5, 1.0, 2.0, 2000, 20, 1.75),
+        ];
+
+        let encoder = Encoder::new(SERVER_VERSION);
+        let fields = encoder.encode_historical_data_fields(
+            16,
+            "20201019 00:00:00",
+            "20201021 00:00:00",
+            &bars,
+        );
+        let decoded = decode(SERVER_VERSION, &fields)?;
+        assert_eq!(3, decoded.len());
+        for (bar, msg) in bars.iter().zip(decoded.iter()) {
+            let expected = ServerRspMsg::HistoricalData {
+                req_id: 16,
+                bar: bar.clone(),
+            };
+            assert_eq!(format!("{:?}", expected), format!("{:?}", msg));
+        }
+        let expected_end = ServerRspMsg::HistoricalDataEnd {
+            req_id: 16,
+            start: "20201019 00:00:00".to_string(),
+            end: "20201021 00:00:00".to_string(),
+        };
+        assert_eq!(format!("{:?}", expected_end), format!("{:?}", decoded[2]));
+        Ok(())
+    }
+
+    #[test]
+    fn test_missing_trailing_flag_decodes_as_false() -> Result<(), IBKRApiLibError> {
+        // A market depth message without its smart depth flag
+        let fields: Vec<String> = vec!["13", "1", "4", "0", "NSDQ", "0", "1", "101.5", "200"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let decoded = decode(SERVER_VERSION, &fields)?;
+        let expected = ServerRspMsg::MarketDepthL2 {
+            req_id: 4,
+            position: 0,
+            market_maker: "NSDQ".to_string(),
+            operation: 0,
+            side: 1,
+            price: 101.5,
+            size: 200,
+            is_smart_depth: false,
+        };
+        assert_eq!(1, decoded.len());
+        assert_eq!(format!("{:?}", expected), format!("{:?}", decoded[0]));
+        Ok(())
+    }
+
+    #[test]
+    fn test_unknown_message_id_is_an_error() {
+        let fields = vec!["999".to_string(), "1".to_string()];
+        let result = decode(SERVER_VERSION, &fields);
+        assert!(matches!(result, Err(IBKRApiLibError::ApiError(_))));
+    }
+
+    #[test]
+    fn test_tick_string_decodes_numeric_tick_type() -> Result<(), IBKRApiLibError> {
+        assert_decodes_to(
+            &["46", "6", "1", "45", "1600000000"],
+            ServerRspMsg::TickString {
+                req_id: 1,
+                tick_type: TickType::LastTimestamp,
+                value: "1600000000".to_string(),
+            },
+        )
+    }
+
+    #[test]
+    fn test_tick_efp_decodes_as_tick_efp() -> Result<(), IBKRApiLibError> {
+        assert_decodes_to(
+            &[
+                "47", "6", "3", "38", "1.5", "1.50", "3000.25", "30", "20211217", "0.5", "0.75",
+            ],
+            ServerRspMsg::TickEfp {
+                ticker_id: 3,
+                tick_type: TickType::BidEfpComputation,
+                basis_points: 1.5,
+                formatted_basis_points: "1.50".to_string(),
+                implied_futures_price: 3000.25,
+                hold_days: 30,
+                future_last_trade_date: "20211217".to_string(),
+                dividend_impact: 0.5,
+                dividends_to_last_trade_date: 0.75,
+            },
+        )
+    }
+
+    #[test]
+    fn test_head_timestamp_decodes_as_head_timestamp() -> Result<(), IBKRApiLibError> {
+        assert_decodes_to(
+            &["88", "12", "20040123  14:30:00"],
+            ServerRspMsg::HeadTimestamp {
+                req_id: 12,
+                head_timestamp: "20040123  14:30:00".to_string(),
+            },
+        )
+    }
+
+    #[test]
+    fn test_verify_messages_read_each_field_once() -> Result<(), IBKRApiLibError> {
+        assert_decodes_to(
+            &["65", "1", "api data"],
+            ServerRspMsg::VerifyMessageApi {
+                api_data: "api data".to_string(),
+            },
+        )?;
+        assert_decodes_to(
+            &["66", "1", "false", "verification failed"],
+            ServerRspMsg::VerifyCompleted {
+                is_successful: false,
+                error_text: "verification failed".to_string(),
+            },
+        )?;
+        assert_decodes_to(
+            &["70", "1", "true", ""],
+            ServerRspMsg::VerifyAndAuthCompleted {
+                is_successful: true,
+                error_text: "".to_string(),
+            },
+        )
+    }
+
+    #[test]
+    fn test_execution_data_reads_ev_multiplier() -> Result<(), IBKRApiLibError> {
+        let mut execution = Execution::default();
+        execution.order_id = 14;
+        execution.ev_rule = "aussieBond:YearsToExpiration=3".to_string();
+        execution.ev_multiplier = 2.5;
+        execution.model_code = "Model".to_string();
+        execution.last_liquidity = 2;
+        let msg = ServerRspMsg::ExecutionData {
+            req_id: 15,
+            contract: contract_samples::usstock(),
+            execution,
+        };
+        let mut fields = Encoder::new(SERVER_VERSION).encode_fields(&msg)?;
+        let ev_rule = fields
+            .iter()
+            .position(|field| field.as_str() == "aussieBond:YearsToExpiration=3")
+            .unwrap();
+        assert_eq!("2.5", fields[ev_rule + 1]);
+
+        let read_execution = |fields: &[String]| -> Result<Execution, IBKRApiLibError> {
+            match decode(SERVER_VERSION, fields)?.remove(0) {
+                ServerRspMsg::ExecutionData { execution, .. } => Ok(execution),
+                other => panic!("expected ExecutionData, got {:?}", other),
+            }
+        };
+        let execution = read_execution(&fields)?;
+        assert_eq!(2.5, execution.ev_multiplier);
+        assert_eq!("Model", execution.model_code);
+        assert_eq!(2, execution.last_liquidity);
+
+        // An empty multiplier reads as 1
+        fields[ev_rule + 1] = "".to_string();
+        let execution = read_execution(&fields)?;
+        assert_eq!(1.0, execution.ev_multiplier);
+        assert_eq!("Model", execution.model_code);
+        assert_eq!(2, execution.last_liquidity);
+        Ok(())
+    }
+}
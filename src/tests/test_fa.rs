@@ -0,0 +1,79 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::fa::{
+        diff_fa, fa_to_xml, parse_fa, FaAlias, FaChange, FaGroup, FaGroupMethod, FaProfile,
+        FaProfileType,
+    };
+    use crate::core::xml::XmlElement;
+    use crate::examples::fa_allocation_samples::{
+        two_groups, two_profiles, FA_TWO_GROUPS, FA_TWO_PROFILES,
+    };
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_parse_fa() {
+        let groups: Vec<FaGroup> = parse_fa(FA_TWO_GROUPS).unwrap();
+        assert_eq!(two_groups(), groups);
+        let profiles: Vec<FaProfile> = parse_fa(FA_TWO_PROFILES).unwrap();
+        assert_eq!(two_profiles(), profiles);
+
+        let xml = fa_to_xml(&profiles);
+        assert!(xml.contains("<amount>60.0</amount>"));
+        assert_eq!(profiles, parse_fa::<FaProfile>(&xml).unwrap());
+
+        let aliases = vec![FaAlias::new("DU119915", "Growth & Income")];
+        let xml = fa_to_xml(&aliases);
+        assert!(xml.contains("<alias>Growth &amp; Income</alias>"));
+        assert_eq!(aliases, parse_fa::<FaAlias>(&xml).unwrap());
+
+        assert!(parse_fa::<FaGroup>(FA_TWO_PROFILES).is_err());
+        assert!(parse_fa::<FaGroup>("<ListOfGroups><Group>").is_err());
+
+        let root = XmlElement::parse(
+            "<?xml version=\"1.0\"?><!-- c --><a x='1'><b/><b>&#65;<![CDATA[<c>]]></b></a>",
+        )
+        .unwrap();
+        assert_eq!(Some("1"), root.attribute("x"));
+        assert_eq!(2, root.children_named("b").count());
+        assert_eq!("A<c>", root.children[1].text);
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_diff_fa() {
+        let current = two_groups();
+        let replacement = vec![
+            current[0].clone().with_account("DU119917"),
+            FaGroup::new("Net_Liq", FaGroupMethod::NetLiq).with_account("DU119915"),
+        ];
+        let changes = diff_fa(&current, &replacement);
+        assert_eq!(
+            vec![
+                FaChange::Modified {
+                    current: current[0].clone(),
+                    replacement: replacement[0].clone(),
+                },
+                FaChange::Removed(current[1].clone()),
+                FaChange::Added(replacement[1].clone()),
+            ],
+            changes
+        );
+        assert_eq!(
+            "- group Pct_Change (PctChange): DU119915,DU119916",
+            changes[1].to_string()
+        );
+
+        assert!(diff_fa(&current, &current).is_empty());
+
+        let ratios = FaProfile::new("Ratios", FaProfileType::FinancialRatios)
+            .with_allocation("DU119915", 3.0)
+            .with_allocation("DU119916", 1.0);
+        assert_eq!(
+            vec![
+                ("DU119915".to_string(), 75.0),
+                ("DU119916".to_string(), 25.0)
+            ],
+            ratios.allocate(100.0)
+        );
+    }
+}
@@ -0,0 +1,96 @@
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use crate::core::common::FundamentalType;
+    use crate::core::fundamentals::{
+        parse_calendar, parse_fundamental_data, parse_ratios, Estimates, FinStatements, FinSummary,
+        FundamentalReport, Snapshot, StatementType,
+    };
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_parse_snapshot_and_summary() {
+        let snapshot = Snapshot::parse(include_str!("fixtures/report_snapshot.xml")).unwrap();
+        assert_eq!(
+            "International Business Machines Corp.",
+            snapshot.company_name
+        );
+        assert_eq!("IBM", snapshot.ticker);
+        assert_eq!("NYSE", snapshot.exchange);
+        assert_eq!(Some(352600.0), snapshot.employees);
+        assert_eq!("USD", snapshot.reporting_currency);
+        assert_eq!("IT Services & Consulting - NEC", snapshot.industries[0]);
+        assert_eq!(4, snapshot.ratios.len());
+        assert_eq!("Valuation", snapshot.ratios[3].group);
+        assert_eq!(Some(14.207), snapshot.ratios[3].value);
+        assert_eq!(None, snapshot.ratios[2].value);
+        assert_eq!("TargetPrice", snapshot.forecasts[1].field);
+        assert_eq!(Some(158.88235), snapshot.forecasts[1].value);
+
+        let summary = FinSummary::parse(include_str!("fixtures/reports_fin_summary.xml")).unwrap();
+        assert_eq!("USD", summary.currency);
+        assert_eq!(3, summary.eps.len());
+        assert_eq!("TTM", summary.eps[0].report_type);
+        assert_eq!(10.56, summary.eps[0].value);
+        assert_eq!(77147000000.0, summary.total_revenue[0].value);
+        assert_eq!(2, summary.dividends.len());
+        assert_eq!(
+            NaiveDate::from_ymd_opt(2020, 2, 7),
+            summary.dividends[0].ex_date
+        );
+
+        let ratios = parse_ratios(include_str!("fixtures/report_ratios.xml")).unwrap();
+        assert_eq!(5, ratios.len());
+        assert_eq!(Some(47.3), ratios[2].value);
+        assert_eq!("USD", ratios[4].text);
+
+        assert!(Snapshot::parse(include_str!("fixtures/resc.xml")).is_err());
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_parse_statements_estimates_and_calendar() {
+        let statements =
+            FinStatements::parse(include_str!("fixtures/reports_fin_statements.xml")).unwrap();
+        assert_eq!(Some("Net Income"), statements.description("NINC"));
+        assert_eq!(2, statements.annual.len());
+        let latest = &statements.annual[0];
+        assert_eq!(2019, latest.fiscal_year);
+        assert_eq!(None, latest.period_number);
+        let income = latest.statement(StatementType::Income).unwrap();
+        assert_eq!(Some(77147.0), income.line_item("SREV"));
+        assert_eq!(
+            Some(152186.0),
+            latest
+                .statement(StatementType::BalanceSheet)
+                .and_then(|balance| balance.line_item("ATOT"))
+        );
+        assert_eq!(Some(4), statements.interim[0].period_number);
+
+        let estimates = Estimates::parse(include_str!("fixtures/resc.xml")).unwrap();
+        assert_eq!(3, estimates.actuals.len());
+        assert_eq!("Q", estimates.actuals[1].period_type);
+        assert_eq!(77147.0, estimates.actuals[2].value);
+        let eps = &estimates.estimates[0];
+        assert_eq!(("EPS", 2020), (eps.measure.as_str(), eps.fiscal_year));
+        assert_eq!(Some(13.71), eps.mean);
+        assert_eq!(Some(13.4), eps.low);
+        assert_eq!(Some(16.0), eps.num_of_estimates);
+
+        let events = parse_calendar(include_str!("fixtures/calendar_report.xml")).unwrap();
+        assert_eq!(2, events.len());
+        assert_eq!("Earnings", events[0].event_type);
+        assert_eq!(NaiveDate::from_ymd_opt(2020, 4, 20), events[0].date);
+        assert_eq!(NaiveDate::from_ymd_opt(2020, 7, 20), events[1].date);
+
+        match parse_fundamental_data(
+            &FundamentalType::CalendarReport,
+            include_str!("fixtures/calendar_report.xml"),
+        ) {
+            Ok(FundamentalReport::Calendar(calendar)) => assert_eq!(events, calendar),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(parse_fundamental_data(&FundamentalType::ReportsOwnership, "<a/>").is_err());
+    }
+}
@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use crate::core::common::{BarSize, Duration, DurationUnit, WhatToShow};
+    use crate::core::contract::Contract;
+    use crate::core::errors::IBKRApiLibError;
+    use crate::core::historical_data::{validate_bar_size, HistoricalDataRequest};
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_validate_bar_size() {
+        let valid = [
+            (Duration::new(60, DurationUnit::SECOND), BarSize::_1Secs),
+            (Duration::new(3600, DurationUnit::SECOND), BarSize::_5Secs),
+            (Duration::new(1, DurationUnit::DAY), BarSize::_1Min),
+            (Duration::new(1, DurationUnit::DAY), BarSize::_1Day),
+            (Duration::new(1, DurationUnit::WEEK), BarSize::_3Mins),
+            (Duration::new(1, DurationUnit::MONTH), BarSize::_30Mins),
+            (Duration::new(5, DurationUnit::YEAR), BarSize::_1Month),
+        ];
+        for (duration, bar_size) in valid.iter() {
+            assert!(
+                validate_bar_size(duration, *bar_size).is_ok(),
+                "{} {}",
+                duration,
+                bar_size
+            );
+        }
+
+        let invalid = [
+            (Duration::new(0, DurationUnit::DAY), BarSize::_1Min),
+            (Duration::new(90000, DurationUnit::SECOND), BarSize::_1Min),
+            (Duration::new(3600, DurationUnit::SECOND), BarSize::_1Secs),
+            (Duration::new(1, DurationUnit::DAY), BarSize::_30Secs),
+            (Duration::new(1, DurationUnit::DAY), BarSize::_1Week),
+            (Duration::new(1, DurationUnit::YEAR), BarSize::_1Hour),
+        ];
+        for (duration, bar_size) in invalid.iter() {
+            assert!(
+                validate_bar_size(duration, *bar_size).is_err(),
+                "{} {}",
+                duration,
+                bar_size
+            );
+        }
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_historical_data_request_builder() {
+        let contract = Contract::stock("IBM", "SMART", "USD");
+        let end = Utc.with_ymd_and_hms(2023, 7, 10, 20, 0, 0).unwrap();
+        let request = HistoricalDataRequest::builder(
+            &contract,
+            Duration::new(2, DurationUnit::DAY),
+            BarSize::_5Mins,
+            WhatToShow::Trades,
+        )
+        .end_date_time(&end)
+        .build()
+        .unwrap();
+        assert_eq!(Some(end), request.end_date_time);
+        assert_eq!("2 D", request.duration.to_string());
+        assert_eq!("3 days", Duration::new(3, DurationUnit::DAY).period());
+
+        let result = HistoricalDataRequest::builder(
+            &contract,
+            Duration::new(2, DurationUnit::DAY),
+            BarSize::_5Mins,
+            WhatToShow::Trades,
+        )
+        .end_date_time(&end)
+        .keep_up_to_date(true)
+        .build();
+        assert!(matches!(
+            result,
+            Err(IBKRApiLibError::InvalidHistoricalRequest { .. })
+        ));
+    }
+}
@@ -1,13 +1,18 @@
 #[cfg(test)]
 mod tests {
 
-    use crate::core::common::{TickByTickType, UNSET_DOUBLE, UNSET_INTEGER};
+    use crate::core::common::{
+        Action, BarSize, DurationUnit, FaDataType, FundamentalType, HedgeType, OcaType, Right,
+        SecType, TickByTickType, TimeInForce, TriggerMethod, UsePriceMgmtAlgo, VolatilityType,
+        WhatToShow, UNSET_DOUBLE, UNSET_INTEGER, UNSET_LONG,
+    };
     use crate::core::errors::IBKRApiLibError;
     use crate::core::messages::{
-        make_field, make_field_handle_empty, make_message, read_fields, read_msg,
+        make_field, make_field_handle_empty, make_message, read_fields, read_msg, FieldWriter,
         ServerReqMsgDiscriminants,
     };
     use crate::examples::contract_samples;
+    use rust_decimal_macros::dec;
     #[test]
     fn test_make_field() -> Result<(), IBKRApiLibError> {
         assert_eq!("1\u{0}", make_field(&true)?);
@@ -22,6 +27,36 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_make_field_typed() -> Result<(), IBKRApiLibError> {
+        assert_eq!("1234567890123\u{0}", make_field(&1_234_567_890_123_i64)?);
+        assert_eq!("\u{0}", make_field(&UNSET_LONG)?);
+        assert_eq!("7\u{0}", make_field(&7_u32)?);
+        assert_eq!("0.0001\u{0}", make_field(&dec!(0.0001))?);
+        assert_eq!("100\u{0}", make_field(&Some(100))?);
+        assert_eq!("\u{0}", make_field(&Option::<f64>::None)?);
+        assert_eq!("\u{0}", make_field(&Some(UNSET_DOUBLE))?);
+
+        assert_eq!("SELL\u{0}", make_field(&Action::SELL)?);
+        assert_eq!("GTD\u{0}", make_field(&TimeInForce::GTD)?);
+        assert_eq!("FUT\u{0}", make_field(&SecType::FUT)?);
+        assert_eq!("C\u{0}", make_field(&Right::Call)?);
+        assert_eq!("YIELD_BID\u{0}", make_field(&WhatToShow::YieldBid)?);
+        assert_eq!("30 mins\u{0}", make_field(&BarSize::_30Mins)?);
+        assert_eq!("W\u{0}", make_field(&DurationUnit::WEEK)?);
+        assert_eq!("2\u{0}", make_field(&OcaType::ReduceWithBlocking)?);
+        assert_eq!("8\u{0}", make_field(&TriggerMethod::Midpoint)?);
+        assert_eq!("2\u{0}", make_field(&VolatilityType::Annual)?);
+        assert_eq!("\u{0}", make_field(&VolatilityType::None)?);
+        assert_eq!("B\u{0}", make_field(&HedgeType::Beta)?);
+        assert_eq!("3\u{0}", make_field(&FaDataType::ALIASES)?);
+        assert_eq!("RESC\u{0}", make_field(&FundamentalType::RESC)?);
+        assert_eq!("AllLast\u{0}", make_field(&TickByTickType::AllLast)?);
+        assert_eq!("\u{0}", make_field(&UsePriceMgmtAlgo::Default)?);
+        assert_eq!("0\u{0}", make_field(&UsePriceMgmtAlgo::NotUse)?);
+        Ok(())
+    }
+
     #[test]
     fn test_make_field_handle_empty() -> Result<(), IBKRApiLibError> {
         assert_eq!("1\u{0}", make_field_handle_empty(&true)?);
@@ -79,6 +114,50 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_field_writer() -> Result<(), IBKRApiLibError> {
+        let contract = contract_samples::usstock();
+        let mut msg = FieldWriter::new();
+
+        msg.push(&(ServerReqMsgDiscriminants::ReqTickByTickData as i32))
+            .push(&1009)
+            .push(&contract.con_id)
+            .push(&contract.symbol)
+            .push(&contract.sec_type)
+            .push(&contract.last_trade_date_or_contract_month)
+            .push(&contract.strike)
+            .push(&contract.right)
+            .push(&contract.multiplier)
+            .push(&contract.exchange)
+            .push(&contract.primary_exchange)
+            .push(&contract.currency)
+            .push(&contract.local_symbol)
+            .push(&contract.trading_class)
+            .push(&TickByTickType::AllLast)
+            .push(&0)
+            .push(&false);
+
+        assert!(msg
+            .as_str()
+            .starts_with("97\u{0}1009\u{0}0\u{0}AMZN\u{0}STK"));
+
+        let expected: Vec<u8> = vec![
+            0, 0, 0, 50, 57, 55, 0, 49, 48, 48, 57, 0, 48, 0, 65, 77, 90, 78, 0, 83, 84, 75, 0, 0,
+            48, 0, 0, 0, 73, 83, 76, 65, 78, 68, 0, 0, 85, 83, 68, 0, 0, 0, 65, 108, 108, 76, 97,
+            115, 116, 0, 48, 0, 48, 0,
+        ];
+        assert_eq!(expected, msg.finish()?);
+
+        // Multi-byte text is counted in bytes, and pre-encoded fields are copied as they are
+        let mut msg = FieldWriter::new();
+        msg.push("Zürich").push_raw("1\u{0}2\u{0}");
+        let framed = msg.finish()?;
+        assert_eq!(vec![0, 0, 0, 12], framed[0..4].to_vec());
+        assert_eq!(make_message("Zürich\u{0}1\u{0}2\u{0}")?, framed);
+
+        Ok(())
+    }
+
     #[test]
     fn test_read_msg() -> Result<(), IBKRApiLibError> {
         let msg_bytes: Vec<u8> = vec![
@@ -92,4 +171,72 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_make_msg_utf8() -> Result<(), IBKRApiLibError> {
+        // Toyota's local symbol on TSE, and an order ref with accents and the euro sign
+        let local_symbol = "トヨタ自動車";
+        let order_ref = "Société Générale €";
+
+        let mut msg = "".to_string();
+        msg.push_str(&make_field(&"7203".to_string())?);
+        msg.push_str(&make_field(&local_symbol.to_string())?);
+        msg.push_str(&make_field(&order_ref.to_string())?);
+
+        let actual = make_message(msg.as_str())?;
+
+        // 5 + 19 + 25 bytes for 5 + 7 + 19 characters
+        assert_eq!(49, msg.len());
+        assert_eq!(31, msg.chars().count());
+        assert_eq!(vec![0, 0, 0, 49], actual[0..4].to_vec());
+        assert_eq!(msg.as_bytes(), &actual[4..]);
+        assert_eq!(
+            vec![0xE3, 0x83, 0x88],
+            actual[9..12].to_vec(),
+            "ト is three bytes"
+        );
+
+        let (size, text, remaining) = read_msg(&actual)?;
+        assert_eq!(49, size);
+        assert!(remaining.is_empty());
+        assert_eq!(vec!["7203", local_symbol, order_ref], read_fields(&text));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_msg_split_utf8() -> Result<(), IBKRApiLibError> {
+        let mut stream = make_message("1\u{0}Zürich\u{0}")?;
+        stream.extend(make_message("2\u{0}東京\u{0}")?);
+
+        // A partial message is left in the buffer, even when cut inside a multi-byte character
+        let (_, text, remaining) = read_msg(&stream[0..8])?;
+        assert_eq!("", text);
+        assert_eq!(stream[0..8].to_vec(), remaining);
+
+        let (_, first, remaining) = read_msg(&stream)?;
+        assert_eq!(vec!["1", "Zürich"], read_fields(&first));
+        let (_, second, remaining) = read_msg(&remaining)?;
+        assert_eq!(vec!["2", "東京"], read_fields(&second));
+        assert!(remaining.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_msg_invalid_utf8() -> Result<(), IBKRApiLibError> {
+        // Latin-1 encoded "Zürich" followed by a well formed message
+        let mut msg_bytes: Vec<u8> = vec![0, 0, 0, 9, 49, 0, 90, 0xFC, 114, 105, 99, 104, 0];
+        msg_bytes.extend(make_message("2\u{0}ok\u{0}")?);
+
+        let (size, text, remaining) = read_msg(&msg_bytes)?;
+        assert_eq!(9, size);
+        assert_eq!(vec!["1", "Z\u{FFFD}rich"], read_fields(&text));
+
+        let (_, text, remaining) = read_msg(&remaining)?;
+        assert_eq!(vec!["2", "ok"], read_fields(&text));
+        assert!(remaining.is_empty());
+
+        Ok(())
+    }
 }
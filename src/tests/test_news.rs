@@ -0,0 +1,143 @@
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use crate::core::client::{ConnStatus, EClient, POISONED_MUTEX};
+    use crate::core::errors::IBKRApiLibError;
+    use crate::core::messages::{read_fields, read_msg, ServerRspMsg};
+    use crate::core::news::{
+        decode_base64, Article, ArticleBody, BulletinType, NewsBulletin, NewsCache, NewsHeadline,
+    };
+    use crate::core::streamer::{Streamer, TestStreamer};
+
+    fn historical_news(time: &str, article_id: &str) -> ServerRspMsg {
+        ServerRspMsg::HistoricalNews {
+            req_id: 9001,
+            time: time.to_string(),
+            provider_code: "BRFG".to_string(),
+            article_id: article_id.to_string(),
+            headline: format!("{{A:800015:L:en:K:n/a:C:0.5}}Headline {}", article_id),
+        }
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_news_headlines() {
+        let tick = ServerRspMsg::TickNews {
+            ticker_id: 1,
+            time_stamp: 1553797225000,
+            provider_code: "BZ".to_string(),
+            article_id: "BZ$12345".to_string(),
+            headline: "{A:800015,800016:L:de:K:-0.97:C:0.8561}  Shares rise".to_string(),
+            extra_data: "A:800015".to_string(),
+        };
+        let headline = NewsHeadline::from_msg(&tick).unwrap();
+        assert_eq!("Shares rise", headline.headline);
+        assert_eq!(Some("800015,800016"), headline.metadata("A"));
+        assert_eq!(Some("de"), headline.language());
+        assert_eq!(Some(-0.97), headline.sentiment());
+        assert_eq!(Some(0.8561), headline.confidence());
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2019, 3, 28, 18, 20, 25).unwrap()),
+            headline.time
+        );
+
+        let headline = NewsHeadline::from_msg(&historical_news("2019-03-12 11:45:00.0", "a1"));
+        let headline = headline.unwrap();
+        assert_eq!("Headline a1", headline.headline);
+        assert_eq!(None, headline.sentiment());
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2019, 3, 12, 11, 45, 0).unwrap()),
+            headline.time
+        );
+
+        let plain = NewsHeadline::parse("DJNL", "1", "No metadata {here}");
+        assert_eq!("No metadata {here}", plain.headline);
+        assert!(plain.metadata.is_empty());
+
+        let bulletin = NewsBulletin::from_msg(&ServerRspMsg::NewsBulletins {
+            msg_id: 3,
+            msg_type: 2,
+            news_message: "Trading halted".to_string(),
+            origin_exch: "ISLAND".to_string(),
+        })
+        .unwrap();
+        assert_eq!(BulletinType::ExchangeUnavailable, bulletin.bulletin_type);
+        assert_eq!("ISLAND", bulletin.origin_exchange);
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_news_articles() -> Result<(), IBKRApiLibError> {
+        assert_eq!(b"%PDF-1.4".to_vec(), decode_base64("JVBERi0x\nLjQ=")?);
+        assert_eq!(b"ab".to_vec(), decode_base64("YWI=")?);
+        assert!(decode_base64("JVB*").is_err());
+        assert!(Article::from_response("BRFG", "a1", 2, "").is_err());
+
+        let pdf = Article::from_response("BRFG", "BRFG$0a1b", 1, "JVBERi0xLjQ=")?;
+        assert_eq!(ArticleBody::Pdf(b"%PDF-1.4".to_vec()), pdf.body);
+        let text = Article::from_response("DJ-N", "DJ-N$1/2", 0, "<p>Text</p>")?;
+
+        let dir = std::env::temp_dir().join(format!("ibtwsapi_news_{}", std::process::id()));
+        let cache = NewsCache::new(&dir);
+        assert_eq!(None, cache.get("BRFG", "BRFG$0a1b")?);
+        let path = cache.put(&pdf)?;
+        assert!(path.ends_with("BRFG/BRFG$0a1b.pdf"));
+        assert!(cache.put(&text)?.ends_with("DJ-N/DJ-N$1%2F2.txt"));
+        assert_eq!(Some(pdf), cache.get("BRFG", "BRFG$0a1b")?);
+        assert_eq!(Some(text), cache.get("DJ-N", "DJ-N$1/2")?);
+        assert_eq!(None, cache.get("DJ-N", "DJ-N$1_2")?);
+        std::fs::remove_dir_all(dir)?;
+        Ok(())
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_historical_news_pages() -> Result<(), IBKRApiLibError> {
+        let mut client = EClient::new();
+        *client.conn_state.lock().expect(POISONED_MUTEX) = ConnStatus::CONNECTED;
+        client.set_streamer(Some(Box::new(TestStreamer::new()) as Box<dyn Streamer>));
+        client.server_version = 151;
+
+        let events = client.evt_chan.0.clone();
+        let other = ServerRspMsg::CurrentTime { time: 1 };
+        for event in vec![
+            historical_news("2019-03-12 11:45:00.0", "a3"),
+            other.clone(),
+            historical_news("2019-03-12 10:00:00.0", "a2"),
+            ServerRspMsg::HistoricalNewsEnd {
+                req_id: 9001,
+                has_more: true,
+            },
+            historical_news("2019-03-12 10:00:00.0", "a2"),
+            historical_news("2019-03-11 09:30:00.0", "a1"),
+            ServerRspMsg::HistoricalNewsEnd {
+                req_id: 9001,
+                has_more: false,
+            },
+        ] {
+            events.send(event).unwrap();
+        }
+
+        let mut pages = client.historical_news(9001, 8314, "BRFG").page_size(2);
+        let ids = |page: Vec<NewsHeadline>| -> Vec<String> {
+            page.into_iter()
+                .map(|headline| headline.article_id)
+                .collect()
+        };
+        assert_eq!(vec!["a3", "a2"], ids(pages.next().unwrap()?));
+        assert_eq!(vec!["a1"], ids(pages.next().unwrap()?));
+        assert!(pages.next().is_none());
+        assert_eq!(1, pages.other_events.len());
+        drop(pages);
+
+        let mut buf = Vec::new();
+        client.stream.as_mut().unwrap().read_to_end(&mut buf)?;
+        let (_, first, rest) = read_msg(&buf)?;
+        let (_, second, _) = read_msg(&rest)?;
+        assert_eq!("", read_fields(&first)[5]);
+        assert_eq!("2019-03-12 10:00:00.0", read_fields(&second)[5]);
+        assert_eq!("2", read_fields(&second)[6]);
+        Ok(())
+    }
+}
@@ -0,0 +1,173 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::common::{
+        Action, HedgeType, OcaType, OrderType, ReferencePriceType, Rule80A, TimeInForce,
+        TriggerMethod, VolatilityType, UNSET_DOUBLE, UNSET_INTEGER,
+    };
+    use crate::core::errors::IBKRApiLibError;
+    use crate::core::order::{Order, OrderBuilder, OrderState, OrderStatusKind};
+    use rust_decimal_macros::dec;
+
+    fn invalid_reason(result: Result<Order, IBKRApiLibError>) -> Option<&'static str> {
+        match result {
+            Err(IBKRApiLibError::InvalidOrder { reason, .. }) => Some(reason),
+            _ => None,
+        }
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_order_type_strings() {
+        for order_type in &[
+            OrderType::Market,
+            OrderType::StopLimit,
+            OrderType::TrailLimit,
+            OrderType::PeggedToMidpoint,
+            OrderType::RelativeLimit,
+            OrderType::MarketOnClose,
+        ] {
+            assert_eq!(
+                *order_type,
+                order_type.to_string().parse::<OrderType>().unwrap()
+            );
+        }
+        assert_eq!("STP LMT", OrderType::StopLimit.to_string());
+        assert_eq!("PEG MID", OrderType::PeggedToMidpoint.to_string());
+        assert!("LIMIT".parse::<OrderType>().is_err());
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_order_builder() -> Result<(), IBKRApiLibError> {
+        let order = OrderBuilder::new(Action::SELL, OrderType::StopLimit, 200.0)
+            .lmt_price(99.5)
+            .aux_price(100.0)
+            .good_till_date("20261231 16:00:00 US/Eastern")
+            .oca_group("exit", OcaType::ReduceWithBlocking)
+            .trigger_method(TriggerMethod::Midpoint)
+            .rule80a(Rule80A::Agency)
+            .hedge(HedgeType::Beta, "beta=1")
+            .outside_rth(true)
+            .transmit(false)
+            .build()?;
+
+        assert_eq!("SELL", order.action);
+        assert_eq!("STP LMT", order.order_type);
+        assert_eq!(200.0, order.total_quantity);
+        assert_eq!("GTD", order.tif);
+        assert_eq!("exit", order.oca_group);
+        assert_eq!(2, order.oca_type);
+        assert_eq!(8, order.trigger_method);
+        assert_eq!("A", order.rule80a);
+        assert_eq!("B", order.hedge_type);
+        assert_eq!("beta=1", order.hedge_param);
+        assert!(order.outside_rth);
+        assert!(!order.transmit);
+
+        let vol = OrderBuilder::new(Action::BUY, OrderType::Volatility, 1.0)
+            .volatility(0.3, VolatilityType::Annual)
+            .reference_price_type(ReferencePriceType::BidOrAsk)
+            .build()?;
+        assert_eq!(2, vol.volatility_type);
+        assert_eq!(2, vol.reference_price_type);
+
+        let unset = OrderBuilder::new(Action::BUY, OrderType::Market, 1.0)
+            .reference_price_type(ReferencePriceType::None)
+            .tif(TimeInForce::IOC)
+            .build()?;
+        assert_eq!(UNSET_INTEGER, unset.reference_price_type);
+        assert_eq!("IOC", unset.tif);
+
+        Ok(())
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_order_builder_rejects() -> Result<(), IBKRApiLibError> {
+        assert_eq!(
+            Some("requires lmt_price"),
+            invalid_reason(OrderBuilder::new(Action::BUY, OrderType::Limit, 100.0).build())
+        );
+        assert_eq!(
+            Some("cannot set both aux_price and trailing_percent"),
+            invalid_reason(
+                OrderBuilder::new(Action::SELL, OrderType::Trail, 100.0)
+                    .aux_price(0.5)
+                    .trailing_percent(1.0)
+                    .build()
+            )
+        );
+        assert_eq!(
+            Some("requires aux_price or trailing_percent"),
+            invalid_reason(OrderBuilder::new(Action::SELL, OrderType::Trail, 100.0).build())
+        );
+        assert_eq!(
+            Some("does not take lmt_price"),
+            invalid_reason(
+                OrderBuilder::new(Action::BUY, OrderType::Market, 100.0)
+                    .lmt_price(10.0)
+                    .build()
+            )
+        );
+        assert_eq!(
+            Some("requires aux_price"),
+            invalid_reason(
+                OrderBuilder::new(Action::BUY, OrderType::StopLimit, 100.0)
+                    .lmt_price(10.0)
+                    .build()
+            )
+        );
+        assert_eq!(
+            Some("requires a positive total_quantity or cash_qty"),
+            invalid_reason(OrderBuilder::new(Action::BUY, OrderType::Market, 0.0).build())
+        );
+        assert_eq!(
+            Some("requires good_till_date with GTD time in force"),
+            invalid_reason(
+                OrderBuilder::new(Action::BUY, OrderType::Market, 10.0)
+                    .tif(TimeInForce::GTD)
+                    .build()
+            )
+        );
+
+        // Cash quantity stands in for the share quantity, trailing percent alone is fine
+        OrderBuilder::new(Action::BUY, OrderType::Market, 0.0)
+            .cash_qty(5000.0)
+            .build()?;
+        OrderBuilder::new(Action::SELL, OrderType::Trail, 100.0)
+            .trailing_percent(1.5)
+            .build()?;
+
+        Ok(())
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_order_state_values() {
+        let mut state = OrderState::default();
+        state.status = "PreSubmitted".to_string();
+        state.completed_status = "Cancelled".to_string();
+        state.init_margin_before = "".to_string();
+        state.maint_margin_before = "1.7976931348623157E308".to_string();
+        state.init_margin_change = "1250.75".to_string();
+        state.equity_with_loan_after = "-3.5e2".to_string();
+        state.commission = UNSET_DOUBLE;
+        state.min_commission = 1.0;
+
+        let status = state.status_kind().unwrap();
+        assert_eq!(OrderStatusKind::PreSubmitted, status);
+        assert!(status.is_working() && !status.is_terminal());
+        let completed = state.completed_status_kind().unwrap();
+        assert!(completed.is_terminal() && !completed.is_working());
+        assert!(!OrderStatusKind::Inactive.is_terminal());
+        assert_eq!(Some(OrderStatusKind::ApiPending), "ApiPending".parse().ok());
+        assert!("Unknown".parse::<OrderStatusKind>().is_err());
+
+        assert_eq!(None, state.init_margin_before_value());
+        assert_eq!(None, state.maint_margin_before_value());
+        assert_eq!(Some(dec!(1250.75)), state.init_margin_change_value());
+        assert_eq!(Some(dec!(-350)), state.equity_with_loan_after_value());
+        assert_eq!(None, state.commission_value());
+        assert_eq!(Some(1.0), state.min_commission_value());
+    }
+}
@@ -0,0 +1,88 @@
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use crate::core::common::{Action, OrderType};
+    use crate::core::order::{Order, OrderBuilder};
+    use crate::core::order_condition::{
+        create_condition, Condition, ConditionBuilder, ConditionType, TriggerMethod,
+    };
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_condition_round_trip() {
+        let cases: [(ConditionType, &[&str]); 6] = [
+            (
+                ConditionType::Price,
+                &["a", "1", "150.25", "8314", "SMART", "2"],
+            ),
+            (ConditionType::Time, &["o", "0", "20160118 23:59:59 UTC"]),
+            (ConditionType::Margin, &["a", "1", "30"]),
+            (
+                ConditionType::Execution,
+                &["o", "CASH", "IDEALPRO", "EUR.USD"],
+            ),
+            (
+                ConditionType::Volume,
+                &["a", "0", "100000", "208813720", "SMART"],
+            ),
+            (
+                ConditionType::PercentChange,
+                &["o", "1", "15.5", "208813720", "SMART"],
+            ),
+        ];
+        for (cond_type, wire) in cases.iter() {
+            let fields: Vec<String> = wire.iter().map(|field| field.to_string()).collect();
+            let mut condition = create_condition(*cond_type);
+            let mut fields_iter = fields.iter();
+            condition.decode(&mut fields_iter).unwrap();
+            assert!(fields_iter.next().is_none(), "{:?}", cond_type);
+
+            let encoded: Vec<String> = condition
+                .make_fields()
+                .unwrap()
+                .iter()
+                .map(|field| field.trim_end_matches('\0').to_string())
+                .collect();
+            assert_eq!(fields, encoded, "{:?}", cond_type);
+            assert_eq!(*cond_type as i32, condition.get_type() as i32);
+        }
+
+        let mut volume = create_condition(ConditionType::Volume);
+        assert!(volume.set_value_from_string("lots".to_string()).is_err());
+        volume.set_value_from_string("250".to_string()).unwrap();
+        assert_eq!("250", volume.value_to_string());
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_condition_builder() {
+        let time = Utc.with_ymd_and_hms(2016, 1, 18, 23, 59, 59).unwrap();
+        let conditions = ConditionBuilder::new()
+            .price(TriggerMethod::Last, 8314, "SMART", true, 150.0)
+            .and()
+            .time(false, &time)
+            .or()
+            .margin(true, 30.0)
+            .build();
+        assert_eq!(3, conditions.len());
+        assert!(conditions[0].is_conjunction());
+        assert!(!conditions[1].is_conjunction());
+        assert_eq!("20160118 23:59:59 UTC", conditions[1].value_to_string());
+        assert_eq!(
+            vec!["a\0", "1\0", "150\0", "8314\0", "SMART\0", "2\0"],
+            conditions[0].make_fields().unwrap()
+        );
+
+        let order: Order = OrderBuilder::new(Action::BUY, OrderType::Limit, 100.0)
+            .lmt_price(20.0)
+            .conditions(conditions)
+            .conditions_cancel_order(true)
+            .conditions_ignore_rth(true)
+            .build()
+            .unwrap();
+        assert_eq!(3, order.conditions.len());
+        assert!(order.conditions_cancel_order);
+        assert!(order.conditions_ignore_rth);
+    }
+}
@@ -0,0 +1,131 @@
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use crate::core::client::{ConnStatus, EClient, POISONED_MUTEX};
+    use crate::core::contract::Contract;
+    use crate::core::dump::{dump_bytes, Direction};
+    use crate::core::errors::IBKRApiLibError;
+    use crate::core::messages::ServerRspMsg;
+    use crate::core::order_group::{GroupMember, GroupStatus, OrderGroup, OrderGroupKind};
+    use crate::core::streamer::{Streamer, TestStreamer};
+    use crate::examples::order_samples::{bracket_order, limit_order};
+
+    fn order_status(order_id: i32, status: &str, filled: f64, remaining: f64) -> ServerRspMsg {
+        ServerRspMsg::OrderStatus {
+            order_id,
+            status: status.to_string(),
+            filled,
+            remaining,
+            avg_fill_price: 0.0,
+            perm_id: 0,
+            parent_id: 0,
+            last_fill_price: 0.0,
+            client_id: 0,
+            why_held: "".to_string(),
+            mkt_cap_price: 0.0,
+        }
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_bracket_status() {
+        let contract = Contract::stock("AMZN", "SMART", "USD");
+        let (parent, take_profit, stop_loss) = bracket_order(1, "BUY", 100.0, 30.0, 40.0, 20.0);
+        let mut group = OrderGroup::new(
+            OrderGroupKind::Bracket,
+            vec![parent, take_profit, stop_loss]
+                .into_iter()
+                .map(|order| GroupMember::new(contract.clone(), order))
+                .collect(),
+        );
+        assert_eq!(GroupStatus::Pending, group.status());
+
+        assert!(!group.update(&order_status(9, "Submitted", 0.0, 100.0)));
+        assert!(group.update(&order_status(1, "Submitted", 0.0, 100.0)));
+        assert!(group.update(&order_status(2, "PreSubmitted", 0.0, 100.0)));
+        assert_eq!(GroupStatus::Working, group.status());
+
+        group.update(&order_status(1, "Submitted", 40.0, 60.0));
+        assert_eq!(GroupStatus::PartiallyFilled, group.status());
+
+        group.update(&order_status(1, "Filled", 100.0, 0.0));
+        assert_eq!(GroupStatus::PartiallyFilled, group.status());
+        assert!(!group.is_done());
+
+        group.update(&order_status(3, "Filled", 100.0, 0.0));
+        group.update(&order_status(2, "Cancelled", 0.0, 100.0));
+        assert_eq!(GroupStatus::Filled, group.status());
+        assert!(group.is_done());
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_oca_status() {
+        let members = vec![
+            limit_order("BUY", 100.0, 30.0),
+            limit_order("BUY", 100.0, 29.0),
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(index, mut order)| {
+            order.order_id = 5 + index as i32;
+            GroupMember::new(Contract::stock("AMZN", "SMART", "USD"), order)
+        })
+        .collect();
+        let mut group = OrderGroup::new(OrderGroupKind::Oca, members);
+
+        group.update(&order_status(5, "Submitted", 0.0, 100.0));
+        assert_eq!(GroupStatus::Working, group.status());
+        assert!(group.contains(6));
+
+        group.update(&order_status(5, "Cancelled", 0.0, 100.0));
+        group.update(&order_status(6, "ApiCancelled", 0.0, 100.0));
+        assert_eq!(GroupStatus::Cancelled, group.status());
+        assert_eq!(
+            Some("ApiCancelled"),
+            group.member(6).unwrap().status.as_deref()
+        );
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_modify_sends_changed_orders() -> Result<(), IBKRApiLibError> {
+        let mut client = EClient::new();
+        *client.conn_state.lock().expect(POISONED_MUTEX) = ConnStatus::CONNECTED;
+        client.set_streamer(Option::from(
+            Box::new(TestStreamer::new()) as Box<dyn Streamer>
+        ));
+        client.server_version = 151;
+
+        let contract = Contract::stock("AMZN", "SMART", "USD");
+        let (parent, take_profit, stop_loss) = bracket_order(1, "BUY", 100.0, 30.0, 40.0, 20.0);
+        let mut group = OrderGroup::new(
+            OrderGroupKind::Bracket,
+            vec![parent, take_profit, stop_loss]
+                .into_iter()
+                .map(|order| GroupMember::new(contract.clone(), order))
+                .collect(),
+        );
+        group.update(&order_status(1, "Filled", 100.0, 0.0));
+
+        group.modify(&mut client, |order| {
+            if order.order_type == "STP" {
+                order.aux_price = 22.0;
+            }
+        })?;
+
+        let mut buf = Vec::<u8>::new();
+        client.stream.as_mut().unwrap().read_to_end(&mut buf)?;
+        let sent = dump_bytes(&buf, Direction::Outgoing, 151)?;
+        assert_eq!(1, sent.len());
+        assert_eq!("PlaceOrder", sent[0].name);
+        assert_eq!(Some("3"), sent[0].field("order_id"));
+        assert_eq!(Some("22"), sent[0].field("order.aux_price"));
+        assert_eq!(Some("1"), sent[0].field("order.transmit"));
+        assert!(group.member(3).unwrap().order.transmit);
+        assert!(!group.member(2).unwrap().order.transmit);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,194 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::common::{Action, OrderType, UNSET_DOUBLE};
+    use crate::core::contract::{Contract, ContractDetails};
+    use crate::core::order::{Order, OrderBuilder};
+    use crate::core::order_validation::{
+        order_type_code, required_features, validate_order, validate_order_for_server,
+        OrderViolation,
+    };
+    use crate::core::server_versions::{ServerFeature, MIN_SERVER_VER_CASH_QTY};
+
+    fn details() -> ContractDetails {
+        let mut details = ContractDetails::default();
+        details.min_tick = 0.01;
+        details.order_types =
+            "ACTIVETIM,DAY,GTC,GTD,IOC,LMT,MKT,STP,STPLMT,TRAIL,TRAILLMT".to_string();
+        details.valid_exchanges = "SMART,AMEX,NYSE,ARCA,ISLAND".to_string();
+        details
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_validate_order_passes() {
+        let contract = Contract::stock("AMZN", "SMART", "USD");
+        let order = OrderBuilder::new(Action::BUY, OrderType::StopLimit, 100.0)
+            .lmt_price(30.05)
+            .aux_price(30.1)
+            .good_till_date("20261231 16:00:00 US/Eastern")
+            .build()
+            .unwrap();
+
+        assert!(validate_order(&contract, &order, Some(&details())).is_empty());
+        assert!(validate_order_for_server(&contract, &order, Some(&details()), 151).is_empty());
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_validate_order_collects_violations() {
+        let mut contract = Contract::stock("AMZN", "BATS", "USD");
+        contract.symbol = "".to_string();
+
+        let mut order = Order::default();
+        order.order_type = "MIT".to_string();
+        order.total_quantity = 0.0;
+        order.aux_price = UNSET_DOUBLE;
+        order.lmt_price = 30.005;
+        order.tif = "GTD".to_string();
+
+        let violations = validate_order(&contract, &order, Some(&details()));
+        assert_eq!(
+            vec![
+                OrderViolation::Contract { field: "symbol" },
+                OrderViolation::NonPositiveQuantity {
+                    total_quantity: 0.0
+                },
+                OrderViolation::OrderFields {
+                    reason: "does not take lmt_price"
+                },
+                OrderViolation::GoodTillDate {
+                    tif: "GTD".to_string(),
+                    reason: "requires good_till_date"
+                },
+                OrderViolation::OrderTypeNotAllowed {
+                    order_type: "MIT".to_string()
+                },
+                OrderViolation::ExchangeNotValid {
+                    exchange: "BATS".to_string()
+                },
+                OrderViolation::PriceOffTick {
+                    field: "lmt_price",
+                    price: 30.005,
+                    min_tick: 0.01
+                },
+            ],
+            violations
+        );
+
+        order.tif = "FOK".to_string();
+        order.good_till_date = "20261231 16:00:00".to_string();
+        let violations = validate_order(&contract, &order, Some(&details()));
+        assert!(violations.contains(&OrderViolation::TimeInForceNotAllowed {
+            tif: "FOK".to_string()
+        }));
+        assert!(violations.contains(&OrderViolation::GoodTillDate {
+            tif: "FOK".to_string(),
+            reason: "does not take good_till_date"
+        }));
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_validate_order_for_server() {
+        let contract = Contract::stock("AMZN", "SMART", "USD");
+        let order = OrderBuilder::new(Action::BUY, OrderType::Market, 0.0)
+            .cash_qty(5000.0)
+            .not_held(true)
+            .build()
+            .unwrap();
+
+        let features = required_features(&contract, &order);
+        assert!(features.contains(&ServerFeature::CashQty));
+        assert!(features.contains(&ServerFeature::NotHeld));
+
+        let violations = validate_order_for_server(&contract, &order, None, 100);
+        assert_eq!(
+            vec![OrderViolation::Unsupported {
+                feature: ServerFeature::CashQty,
+                server_version: 100,
+                required: MIN_SERVER_VER_CASH_QTY,
+            }],
+            violations
+        );
+        assert!(
+            validate_order_for_server(&contract, &order, None, MIN_SERVER_VER_CASH_QTY).is_empty()
+        );
+
+        // A plain order leaves cash_qty unset and needs no recent server
+        let order = OrderBuilder::new(Action::BUY, OrderType::Limit, 100.0)
+            .lmt_price(30.05)
+            .build()
+            .unwrap();
+        assert!(required_features(&contract, &order).is_empty());
+        assert!(validate_order_for_server(&contract, &order, None, 110).is_empty());
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_order_type_codes() {
+        let contract = Contract::stock("AMZN", "SMART", "USD");
+        let mut details = details();
+        details.order_types = "ACTIVETIM,AD,ADJUST,ALERT,ALGO,ALLOC,AON,AVGCOST,BASKET,BENCHPX,\
+                               BOXTOP,COND,CONDORDER,DAY,DEACT,DEACTDIS,DEACTEOD,GAT,GTC,GTD,GTT,\
+                               HID,ICE,IOC,LIT,LMT,LOC,MIDPX,MIT,MKT,MKTPRT,MOC,MTL,NONALGO,OCA,\
+                               OPG,PASSVREL,PEGBENCH,PEGMID,PEGMKT,PEGSTK,REL,RELPCTOFS,RTH,SCALE,\
+                               SNAPMID,SNAPMKT,SNAPPRIM,STP,STPLMT,STPPRT,TRAIL,TRAILLIT,TRAILLMT,\
+                               TRAILMIT,VOL,WHATIF"
+            .to_string();
+
+        let order_types = vec![
+            OrderType::Market,
+            OrderType::Limit,
+            OrderType::Stop,
+            OrderType::StopLimit,
+            OrderType::StopProtect,
+            OrderType::Trail,
+            OrderType::TrailLimit,
+            OrderType::MarketIfTouched,
+            OrderType::LimitIfTouched,
+            OrderType::MarketToLimit,
+            OrderType::MarketProtect,
+            OrderType::MarketOnClose,
+            OrderType::LimitOnClose,
+            OrderType::Relative,
+            OrderType::PassiveRelative,
+            OrderType::RelativeLimit,
+            OrderType::RelativeMarket,
+            OrderType::LimitMarket,
+            OrderType::PeggedToMidpoint,
+            OrderType::PeggedToMarket,
+            OrderType::PeggedToStock,
+            OrderType::PeggedToBenchmark,
+            OrderType::Midprice,
+            OrderType::BoxTop,
+            OrderType::SnapMarket,
+            OrderType::SnapMidpoint,
+            OrderType::SnapPrimary,
+            OrderType::Volatility,
+        ];
+        for order_type in order_types {
+            let mut order = Order::default();
+            order.order_type = order_type.to_string();
+            let violations = validate_order(&contract, &order, Some(&details));
+            assert!(
+                !violations.contains(&OrderViolation::OrderTypeNotAllowed {
+                    order_type: order.order_type.clone()
+                }),
+                "{} is reported as not allowed",
+                order_type
+            );
+        }
+
+        assert_eq!(Some("MIDPX"), order_type_code(OrderType::Midprice));
+        assert_eq!(None, order_type_code(OrderType::RelativeLimit));
+
+        details.order_types = details.order_types.replace("MIDPX,", "");
+        let mut order = Order::default();
+        order.order_type = OrderType::Midprice.to_string();
+        assert!(validate_order(&contract, &order, Some(&details)).contains(
+            &OrderViolation::OrderTypeNotAllowed {
+                order_type: "MIDPRICE".to_string()
+            }
+        ));
+    }
+}
@@ -0,0 +1,69 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::common::TagValue;
+    use crate::core::errors::IBKRApiLibError;
+    use crate::core::scanner::{ScannerFilter, ScannerFilterBuilder};
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_scanner_filter_round_trip() {
+        let tag_values: Vec<TagValue> = [
+            ("priceAbove", "5.5"),
+            ("avgVolumeAbove", "10000"),
+            ("marketCapBelow1e6", "2000"),
+            ("changePercAbove", "-3"),
+            ("avgOptVolumeAbove", "500"),
+            ("moodyRatingAbove", "BAA"),
+            ("dividendYieldFrdAbove", "2.25"),
+            ("industryLike", "Technology"),
+            ("stkTypes", "inc:CORP"),
+        ]
+        .iter()
+        .map(|(tag, value)| TagValue::new(tag.to_string(), value.to_string()))
+        .collect();
+
+        let filters = ScannerFilter::from_tag_values(&tag_values).unwrap();
+        assert_eq!(ScannerFilter::PriceAbove(5.5), filters[0]);
+        assert_eq!(ScannerFilter::ChangePercentAbove(-3.0), filters[3]);
+        assert_eq!(
+            ScannerFilter::Industry("Technology".to_string()),
+            filters[7]
+        );
+        assert_eq!(
+            ScannerFilter::Other {
+                tag: "stkTypes".to_string(),
+                value: "inc:CORP".to_string()
+            },
+            filters[8]
+        );
+        assert_eq!(tag_values, ScannerFilter::to_tag_values(&filters));
+
+        let invalid = TagValue::new("volumeAbove".to_string(), "lots".to_string());
+        match ScannerFilter::from_tag_value(&invalid) {
+            Err(IBKRApiLibError::InvalidScannerSubscription { .. }) => (),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_scanner_filter_builder() {
+        let builder = ScannerFilterBuilder::new()
+            .price_above(1.0)
+            .price_below(50.0)
+            .volume_above(100000)
+            .usd_market_cap_above(10000.0)
+            .option_volume_above(1000)
+            .sp_rating_below("BBB")
+            .dividend_yield_below(8.0);
+        assert_eq!(7, builder.filters().len());
+        assert_eq!("priceBelow=50", builder.filters()[1].to_string());
+
+        let tag_values = builder.build();
+        assert_eq!(
+            ScannerFilter::SpRatingBelow("BBB".to_string()),
+            ScannerFilter::from_tag_value(&tag_values[5]).unwrap()
+        );
+        assert_eq!("dividendYieldFrdBelow", tag_values[6].tag);
+    }
+}
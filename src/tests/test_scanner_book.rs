@@ -0,0 +1,112 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::contract::ContractDetails;
+    use crate::core::messages::ServerRspMsg;
+    use crate::core::scanner_book::{ScannerBook, ScannerChange};
+
+    fn scanner_data(req_id: i32, rank: i32, con_id: i32) -> ServerRspMsg {
+        let mut contract_details = ContractDetails::default();
+        contract_details.contract.con_id = con_id;
+        ServerRspMsg::ScannerData {
+            req_id,
+            rank,
+            contract_details,
+            distance: "".to_string(),
+            benchmark: "".to_string(),
+            projection: "".to_string(),
+            legs_str: "".to_string(),
+        }
+    }
+
+    fn refresh(book: &mut ScannerBook, req_id: i32, con_ids: &[i32]) -> Vec<ScannerChange> {
+        // Rows are sent in reverse so the book has to sort them by rank
+        for (rank, con_id) in con_ids.iter().enumerate().rev() {
+            assert!(book
+                .update(&scanner_data(req_id, rank as i32, *con_id))
+                .is_empty());
+        }
+        book.update(&ServerRspMsg::ScannerDataEnd { req_id })
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_scanner_book_snapshots() {
+        let mut book = ScannerBook::new();
+        let changes = refresh(&mut book, 7, &[101, 102]);
+        assert_eq!(
+            vec![
+                ScannerChange::Entered {
+                    req_id: 7,
+                    con_id: 101,
+                    rank: 0
+                },
+                ScannerChange::Entered {
+                    req_id: 7,
+                    con_id: 102,
+                    rank: 1
+                },
+                ScannerChange::Refreshed { req_id: 7, rows: 2 },
+            ],
+            changes
+        );
+        let con_ids: Vec<i32> = book
+            .snapshot(7)
+            .unwrap()
+            .iter()
+            .map(|row| row.contract.contract.con_id)
+            .collect();
+        assert_eq!(vec![101, 102], con_ids);
+        assert_eq!(Some(1), book.rank(7, 102));
+        assert!(book.snapshot(8).is_none());
+
+        // Rows of an unfinished refresh do not replace the snapshot
+        book.update(&scanner_data(7, 0, 103));
+        assert_eq!(None, book.rank(7, 103));
+        assert_eq!(2, book.remove(7).unwrap().len());
+        assert!(book.snapshot(7).is_none());
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_scanner_book_changes() {
+        let mut book = ScannerBook::new();
+        refresh(&mut book, 7, &[101, 102, 103]);
+        let receiver = book.subscribe();
+        let changes = refresh(&mut book, 7, &[103, 101, 104]);
+        assert_eq!(
+            vec![
+                ScannerChange::MovedUp {
+                    req_id: 7,
+                    con_id: 103,
+                    from: 2,
+                    to: 0
+                },
+                ScannerChange::MovedDown {
+                    req_id: 7,
+                    con_id: 101,
+                    from: 0,
+                    to: 1
+                },
+                ScannerChange::Entered {
+                    req_id: 7,
+                    con_id: 104,
+                    rank: 2
+                },
+                ScannerChange::Exited {
+                    req_id: 7,
+                    con_id: 102,
+                    rank: 1
+                },
+                ScannerChange::Refreshed { req_id: 7, rows: 3 },
+            ],
+            changes
+        );
+        assert_eq!(changes, receiver.try_iter().collect::<Vec<_>>());
+
+        // An unchanged refresh only reports its completion
+        assert_eq!(
+            vec![ScannerChange::Refreshed { req_id: 7, rows: 3 }],
+            refresh(&mut book, 7, &[103, 101, 104])
+        );
+    }
+}
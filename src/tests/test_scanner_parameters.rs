@@ -0,0 +1,80 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::common::TagValue;
+    use crate::core::errors::IBKRApiLibError;
+    use crate::core::scanner_parameters::ScannerCatalog;
+    use crate::examples::scanner_subscription_samples::hot_usstk_by_volume;
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_parse_scanner_parameters() {
+        let catalog =
+            ScannerCatalog::parse(include_str!("fixtures/scanner_parameters.xml")).unwrap();
+        assert_eq!(2, catalog.instruments.len());
+        assert_eq!(
+            vec!["STK.US", "STK.US.MAJOR", "STK.US.MINOR", "FUT.US"],
+            catalog
+                .locations
+                .iter()
+                .map(|location| location.location_code.as_str())
+                .collect::<Vec<&str>>()
+        );
+        assert_eq!(
+            Some("STK.US"),
+            catalog
+                .location("STK.US.MAJOR")
+                .and_then(|location| location.parent.as_deref())
+        );
+        assert_eq!(1, catalog.scans_for("FUT.US").count());
+        assert_eq!(1, catalog.filters_for("FUT.US").len());
+        let (filter, field) = catalog.filter_field("moodyRatingAbove").unwrap();
+        assert_eq!("MOODY", filter.id);
+        assert_eq!(vec!["AAA", "BAA"], field.values);
+
+        let gainers = catalog.search_scans("perc GAIN");
+        assert_eq!(1, gainers.len());
+        assert_eq!("Top % Gainers", gainers[0].display_name);
+        assert_eq!(2, catalog.search_scans("top").len());
+
+        assert!(ScannerCatalog::parse("<FinancialSummary/>").is_err());
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_validate_scanner_subscription() {
+        let catalog =
+            ScannerCatalog::parse(include_str!("fixtures/scanner_parameters.xml")).unwrap();
+        let filter = |tag: &str, value: &str| TagValue::new(tag.to_string(), value.to_string());
+        let mut subscription = hot_usstk_by_volume();
+        subscription
+            .validate(
+                &catalog,
+                &[filter("priceAbove", "5"), filter("moodyRatingAbove", "AAA")],
+            )
+            .unwrap();
+
+        let invalid_filters = [
+            filter("unknownAbove", "1"),
+            filter("moodyRatingAbove", "ZZZ"),
+        ];
+        for option in invalid_filters.iter() {
+            match subscription.validate(&catalog, &[option.clone()]) {
+                Err(IBKRApiLibError::InvalidScannerSubscription { .. }) => (),
+                other => panic!("{}: {:?}", option.tag, other),
+            }
+        }
+
+        subscription.instrument = "FUT.US".to_string();
+        subscription.location_code = "FUT.US".to_string();
+        subscription.validate(&catalog, &[]).unwrap();
+        assert!(subscription
+            .validate(&catalog, &[filter("marketCapAbove1e6", "100")])
+            .is_err());
+
+        subscription.scan_code = "TOP_PERC_GAIN".to_string();
+        assert!(subscription.validate(&catalog, &[]).is_err());
+        subscription.scan_code = "HOT_BY_VOLUME".to_string();
+        subscription.location_code = "STK.US.MAJOR".to_string();
+        assert!(subscription.validate(&catalog, &[]).is_err());
+    }
+}
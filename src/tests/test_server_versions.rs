@@ -0,0 +1,44 @@
+#[cfg(test)]
+mod tests {
+    use strum::IntoEnumIterator;
+
+    use crate::core::server_versions::*;
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_server_feature_required_version() {
+        assert_eq!(
+            MIN_SERVER_VER_PTA_ORDERS,
+            ServerFeature::PtaOrders.required_version()
+        );
+        assert_eq!(
+            MIN_SERVER_VER_TICK_BY_TICK,
+            ServerFeature::TickByTick.required_version()
+        );
+        assert_eq!(
+            MAX_CLIENT_VER,
+            ServerFeature::iter()
+                .map(|feature| feature.required_version())
+                .max()
+                .unwrap()
+        );
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_server_capabilities() {
+        let capabilities = ServerCapabilities::new(MIN_SERVER_VER_PNL);
+
+        assert!(capabilities.supports(ServerFeature::Pnl));
+        assert!(capabilities.supports(ServerFeature::MarketRules));
+        assert!(!capabilities.supports(ServerFeature::UnrealizedPnl));
+        assert_eq!(
+            ServerFeature::iter().count(),
+            capabilities.supported.len() + capabilities.unsupported.len()
+        );
+        assert!(capabilities
+            .unsupported
+            .iter()
+            .all(|feature| feature.required_version() > MIN_SERVER_VER_PNL));
+    }
+}
@@ -0,0 +1,115 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::client::{ConnStatus, EClient, POISONED_MUTEX};
+    use crate::core::common::{Action, OrderType, PriceIncrement};
+    use crate::core::contract::{Contract, ContractDetails};
+    use crate::core::errors::IBKRApiLibError;
+    use crate::core::messages::{read_fields, read_msg, ServerRspMsg};
+    use crate::core::order::OrderBuilder;
+    use crate::core::streamer::{Streamer, TestStreamer};
+    use crate::core::tick_size::{MarketRule, RoundDirection, TickSizeRules};
+
+    fn penny_rule(market_rule_id: i32) -> ServerRspMsg {
+        ServerRspMsg::MarketRule {
+            market_rule_id,
+            price_increments: vec![
+                PriceIncrement::new(1.0, 0.01),
+                PriceIncrement::new(0.0, 0.0001),
+            ],
+        }
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_market_rule_round() {
+        let rule = MarketRule::new(
+            239,
+            vec![
+                PriceIncrement::new(0.0, 0.01),
+                PriceIncrement::new(5.0, 0.05),
+                PriceIncrement::new(10.0, 0.1),
+            ],
+        );
+        assert_eq!(0.05, rule.price_increment(7.3).unwrap().increment);
+        assert_eq!(0.3, rule.round(0.304, RoundDirection::Nearest));
+        assert_eq!(0.31, rule.round(0.301, RoundDirection::Up));
+        assert_eq!(7.25, rule.round(7.29, RoundDirection::Down));
+        assert_eq!(7.3, rule.round(7.26, RoundDirection::Up));
+        assert_eq!(12.3, rule.round(12.3, RoundDirection::Up));
+        assert_eq!(12.3, rule.round(12.3, RoundDirection::Down));
+        assert_eq!(-0.31, rule.round(-0.304, RoundDirection::Down));
+        assert_eq!(RoundDirection::Down, RoundDirection::passive(Action::BUY));
+        assert_eq!(RoundDirection::Up, RoundDirection::passive(Action::SELL));
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_tick_size_rules() -> Result<(), IBKRApiLibError> {
+        let mut contract_details = ContractDetails::default();
+        contract_details.contract = Contract::stock("AAPL", "SMART", "USD");
+        contract_details.contract.con_id = 265598;
+        contract_details.valid_exchanges = "SMART,AMEX,ISLAND".to_string();
+        contract_details.market_rule_ids = "26,26,239".to_string();
+        let contract = contract_details.contract.clone();
+
+        let mut rules = TickSizeRules::new();
+        assert_eq!(
+            None,
+            rules.update(&ServerRspMsg::ContractData {
+                req_id: 1,
+                contract_details,
+            })
+        );
+        assert_eq!(Some(26), rules.rule_id(&contract, ""));
+        assert_eq!(Some(239), rules.rule_id(&contract, "ISLAND"));
+        assert!(rules
+            .round_price(&contract, "", 150.123, RoundDirection::Nearest)
+            .is_err());
+
+        let mut client = EClient::new();
+        *client.conn_state.lock().expect(POISONED_MUTEX) = ConnStatus::CONNECTED;
+        client.set_streamer(Some(Box::new(TestStreamer::new()) as Box<dyn Streamer>));
+        client.server_version = 151;
+        assert_eq!(2, rules.request_missing(&mut client)?);
+        assert_eq!(0, rules.request_missing(&mut client)?);
+        let mut buf = Vec::new();
+        client.stream.as_mut().unwrap().read_to_end(&mut buf)?;
+        let (_, first, rest) = read_msg(&buf)?;
+        let (_, second, _) = read_msg(&rest)?;
+        assert_eq!("26", read_fields(&first)[1]);
+        assert_eq!("239", read_fields(&second)[1]);
+
+        assert_eq!(Some(26), rules.update(&penny_rule(26)));
+        assert!(rules.missing_rules().is_empty());
+        assert_eq!(
+            150.12,
+            rules.round_price(&contract, "SMART", 150.123, RoundDirection::Nearest)?
+        );
+        assert_eq!(
+            0.5124,
+            rules.round_price(&contract, "AMEX", 0.51237, RoundDirection::Up)?
+        );
+        assert!(rules
+            .round_price(&contract, "ISLAND", 1.0, RoundDirection::Up)
+            .is_err());
+
+        let order = OrderBuilder::new(Action::BUY, OrderType::StopLimit, 100.0)
+            .lmt_price(150.129)
+            .aux_price(150.205)
+            .round_prices(&rules, &contract, "")?
+            .build()?;
+        assert_eq!(150.12, order.lmt_price);
+        assert_eq!(150.21, order.aux_price);
+        let order = OrderBuilder::new(Action::SELL, OrderType::Limit, 100.0)
+            .lmt_price(150.121)
+            .round_prices(&rules, &contract, "SMART")?
+            .build()?;
+        assert_eq!(150.13, order.lmt_price);
+        let order = OrderBuilder::new(Action::SSHORT, OrderType::Limit, 100.0)
+            .lmt_price(150.121)
+            .round_prices(&rules, &contract, "SMART")?
+            .build()?;
+        assert_eq!(150.13, order.lmt_price);
+        Ok(())
+    }
+}
@@ -0,0 +1,152 @@
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Duration, Utc};
+
+    use crate::core::common::{TickAttrib, TickType, UNSET_DOUBLE};
+    use crate::core::messages::ServerRspMsg;
+    use crate::core::ticker::{RtVolume, Ticker, TickerUpdate, Tickers};
+
+    fn tick_price(req_id: i32, tick_type: TickType, price: f64) -> ServerRspMsg {
+        ServerRspMsg::TickPrice {
+            req_id,
+            tick_type,
+            price,
+            tick_attr: TickAttrib::new(false, false, false),
+        }
+    }
+
+    fn tick_size(req_id: i32, tick_type: TickType, size: i32) -> ServerRspMsg {
+        ServerRspMsg::TickSize {
+            req_id,
+            tick_type,
+            size,
+        }
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_ticker_fields() {
+        let start = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let later = start + Duration::seconds(5);
+        let mut ticker = Ticker::new(7);
+
+        assert_eq!(
+            vec![TickType::Bid],
+            ticker.update_at(&tick_price(7, TickType::Bid, 101.25), start)
+        );
+        ticker.update_at(&tick_price(7, TickType::Ask, 101.5), start);
+        ticker.update_at(&tick_size(7, TickType::BidSize, 300), start);
+        assert!(ticker
+            .update_at(&tick_price(8, TickType::Bid, 99.0), later)
+            .is_empty());
+        assert!(ticker
+            .update_at(&tick_price(7, TickType::Bid, 101.25), later)
+            .is_empty());
+        ticker.update_at(&tick_price(7, TickType::Ask, 101.75), later);
+
+        assert_eq!(Some(101.25), ticker.bid());
+        assert_eq!(Some(101.5), ticker.midpoint());
+        assert_eq!(Some(300), ticker.bid_size());
+        assert_eq!(Some(start), ticker.updated_at(TickType::Bid));
+        assert_eq!(Some(later), ticker.updated_at(TickType::Ask));
+        assert_eq!(Some(later), ticker.last_update());
+
+        ticker.update_at(&tick_price(7, TickType::Bid, -1.0), later);
+        assert_eq!(None, ticker.bid());
+
+        ticker.update_at(
+            &ServerRspMsg::TickGeneric {
+                ticker_id: 7,
+                tick_type: TickType::Halted,
+                value: 2.0,
+            },
+            later,
+        );
+        assert_eq!(Some(true), ticker.halted());
+
+        ticker.update_at(
+            &ServerRspMsg::TickString {
+                req_id: 7,
+                tick_type: TickType::RtVolume,
+                value: "701.28;1;1348075471534;67854;701.46918464;true".to_string(),
+            },
+            later,
+        );
+        let rt_volume = ticker.rt_volume().unwrap();
+        assert_eq!(Some(701.28), rt_volume.price);
+        assert_eq!(67854.0, rt_volume.total_volume);
+        assert_eq!(1348075471534, rt_volume.time.unwrap().timestamp_millis());
+        assert!(rt_volume.single_trade);
+
+        let volume_only = RtVolume::parse(";0;1348075471534;67900;701.4;false").unwrap();
+        assert_eq!(None, volume_only.price);
+        assert!(RtVolume::parse("701.28;1").is_none());
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_ticker_delayed_and_greeks() {
+        let mut ticker = Ticker::new(3);
+        ticker.update(&tick_price(3, TickType::DelayedLast, 12.5));
+        ticker.update(&tick_size(3, TickType::DelayedVolume, 4200));
+        assert!(ticker.is_delayed());
+        assert_eq!(Some(12.5), ticker.last());
+        assert_eq!(Some(4200), ticker.volume());
+
+        let changed = ticker.update(&ServerRspMsg::TickOptionComputation {
+            ticker_id: 3,
+            tick_type: TickType::DelayedModelOption,
+            implied_vol: 0.25,
+            delta: 0.5,
+            opt_price: 1.2,
+            pv_dividend: 0.0,
+            gamma: 0.04,
+            vega: 0.1,
+            theta: -0.02,
+            und_price: UNSET_DOUBLE,
+        });
+        assert_eq!(vec![TickType::ModelOption], changed);
+        let greeks = ticker.model_greeks().unwrap();
+        assert_eq!(Some(0.5), greeks.delta);
+        assert_eq!(None, greeks.und_price);
+
+        ticker.update(&tick_price(3, TickType::Last, 12.75));
+        assert!(!ticker.is_delayed());
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_tickers_observe_changes() {
+        let mut tickers = Tickers::new();
+        let changes = tickers.subscribe();
+
+        tickers.update(&tick_price(1, TickType::Last, 50.0));
+        tickers.update(&tick_price(1, TickType::Last, 50.0));
+        tickers.update(&tick_size(2, TickType::AskSize, 10));
+        assert!(tickers
+            .update(&ServerRspMsg::NextValidId { order_id: 1 })
+            .is_none());
+        tickers.update(&ServerRspMsg::TickSnapshotEnd { req_id: 2 });
+
+        assert_eq!(
+            vec![
+                TickerUpdate {
+                    req_id: 1,
+                    fields: vec![TickType::Last]
+                },
+                TickerUpdate {
+                    req_id: 2,
+                    fields: vec![TickType::AskSize]
+                },
+            ],
+            changes.try_iter().collect::<Vec<_>>()
+        );
+        assert!(tickers.get(2).unwrap().is_snapshot_complete());
+        assert!(tickers.get(1).unwrap().updated_at(TickType::Last).unwrap() <= Utc::now());
+
+        drop(changes);
+        tickers.update(&tick_price(1, TickType::Last, 51.0));
+        assert!(tickers.remove(1).is_some());
+        assert_eq!(1, tickers.iter().count());
+    }
+}
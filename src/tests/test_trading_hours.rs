@@ -0,0 +1,126 @@
+#[cfg(test)]
+mod tests {
+    use chrono::{FixedOffset, NaiveDate, TimeZone, Utc};
+
+    use crate::core::contract::ContractDetails;
+    use crate::core::errors::IBKRApiLibError;
+    use crate::core::trading_hours::{SessionKind, TradingCalendar};
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_trading_calendar() -> Result<(), IBKRApiLibError> {
+        let mut details = ContractDetails::default();
+        details.time_zone_id = "US/Eastern".to_string();
+        details.trading_hours = "20240101:CLOSED;20240102:0400-20240102:2000;\
+                                 20240103:0400-20240103:2000"
+            .to_string();
+        details.liquid_hours =
+            "20240101:CLOSED;20240102:0930-20240102:1600;20240103:0930-20240103:1600".to_string();
+        let calendar = TradingCalendar::from_contract_details(&details)?;
+
+        let new_year = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        assert!(calendar.is_holiday(new_year));
+        assert_eq!(vec![new_year], calendar.holidays());
+
+        let day = calendar
+            .session_for(NaiveDate::from_ymd_opt(2024, 1, 2).unwrap())
+            .unwrap();
+        let kinds: Vec<SessionKind> = day.sessions.iter().map(|s| s.kind).collect();
+        assert_eq!(
+            vec![
+                SessionKind::Extended,
+                SessionKind::Regular,
+                SessionKind::Extended
+            ],
+            kinds
+        );
+        let eastern = FixedOffset::west_opt(5 * 3600).unwrap();
+        let regular = day.regular_sessions().next().unwrap();
+        assert_eq!(
+            eastern.with_ymd_and_hms(2024, 1, 2, 9, 30, 0).unwrap(),
+            regular.start
+        );
+        assert_eq!(
+            Utc.with_ymd_and_hms(2024, 1, 2, 21, 0, 0).unwrap(),
+            regular.end
+        );
+
+        let pre_market = eastern.with_ymd_and_hms(2024, 1, 2, 8, 0, 0).unwrap();
+        assert!(calendar.is_open(&pre_market));
+        assert!(!calendar.is_regular_open(&pre_market));
+        assert!(calendar.is_regular_open(&Utc.with_ymd_and_hms(2024, 1, 2, 15, 0, 0).unwrap()));
+        let closing = eastern.with_ymd_and_hms(2024, 1, 2, 20, 0, 0).unwrap();
+        assert!(!calendar.is_open(&closing));
+
+        // The regular session continues the pre-market and does not open the market again
+        let next_day = eastern.with_ymd_and_hms(2024, 1, 3, 4, 0, 0).unwrap();
+        assert_eq!(Some(next_day), calendar.next_open(&pre_market));
+        assert_eq!(
+            Some(eastern.with_ymd_and_hms(2024, 1, 2, 9, 30, 0).unwrap()),
+            calendar.next_regular_open(&pre_market)
+        );
+        assert_eq!(
+            Some(eastern.with_ymd_and_hms(2024, 1, 2, 4, 0, 0).unwrap()),
+            calendar.next_open(&eastern.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap())
+        );
+        assert_eq!(None, calendar.next_open(&next_day));
+        Ok(())
+    }
+
+    //------------------------------------------------------------------------------------------------
+    #[test]
+    fn test_trading_calendar_formats() -> Result<(), IBKRApiLibError> {
+        // Older format, an overnight session and a session ending at midnight, without liquid
+        // hours
+        let calendar = TradingCalendar::parse(
+            "20240311:1700-0800;20240312:0930-1200,1300-2400",
+            "",
+            "CST (Central Standard Time)",
+        )?;
+        let sessions: Vec<_> = calendar.sessions().collect();
+        assert_eq!(3, sessions.len());
+        assert!(sessions.iter().all(|s| s.kind == SessionKind::Regular));
+        // Central daylight time, which started on 2024-03-10
+        let central = FixedOffset::west_opt(5 * 3600).unwrap();
+        assert_eq!(
+            central.with_ymd_and_hms(2024, 3, 12, 8, 0, 0).unwrap(),
+            sessions[0].end
+        );
+        assert_eq!(
+            central.with_ymd_and_hms(2024, 3, 13, 0, 0, 0).unwrap(),
+            sessions[2].end
+        );
+        assert!(!calendar.is_open(&central.with_ymd_and_hms(2024, 3, 12, 12, 30, 0).unwrap()));
+
+        // LSE, ASX and TSX contracts, in summer and winter
+        let open_at = |hours, time_zone_id| -> Result<_, IBKRApiLibError> {
+            let calendar = TradingCalendar::parse(hours, "", time_zone_id)?;
+            Ok(calendar.days[0].sessions[0].start)
+        };
+        assert_eq!(
+            Utc.with_ymd_and_hms(2024, 7, 1, 7, 0, 0).unwrap(),
+            open_at("20240701:0800-1630", "GB-Eire")?
+        );
+        assert_eq!(
+            Utc.with_ymd_and_hms(2024, 1, 2, 8, 0, 0).unwrap(),
+            open_at("20240102:0800-1630", "GB-Eire")?
+        );
+        assert_eq!(
+            Utc.with_ymd_and_hms(2024, 1, 1, 23, 0, 0).unwrap(),
+            open_at("20240102:1000-1600", "Australia/NSW")?
+        );
+        assert_eq!(
+            Utc.with_ymd_and_hms(2024, 7, 1, 0, 0, 0).unwrap(),
+            open_at("20240701:1000-1600", "Australia/NSW")?
+        );
+        assert_eq!(
+            Utc.with_ymd_and_hms(2024, 7, 2, 13, 30, 0).unwrap(),
+            open_at("20240702:0930-1600", "America/Toronto")?
+        );
+
+        assert!(TradingCalendar::parse("20240102:0930-1600", "", "Mars/Olympus").is_err());
+        assert!(TradingCalendar::parse("20240102:0930", "", "US/Eastern").is_err());
+        assert!(TradingCalendar::parse("2024-01-02:CLOSED", "", "US/Eastern").is_err());
+        Ok(())
+    }
+}